@@ -0,0 +1,43 @@
+//! Times the decoding of large time axes and counts its allocations, to compare the storage of the
+//! datetimes on the heap with the `inline-datetimes` feature.
+//!
+//! Run with `cargo bench --bench decode_allocations [--features inline-datetimes]`
+
+use cftime_rs::calendars::Calendar;
+use cftime_rs::decoder::VecCFDecoder;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+/// System allocator counting the allocations, the benchmark having a single thread
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn main() {
+    for len in [100_000, 1_000_000, 10_000_000] {
+        let values: Vec<i64> = (0..len).collect();
+        let before = ALLOCATIONS.load(Ordering::Relaxed);
+        let start = Instant::now();
+        let datetimes = values
+            .decode_cf("hours since 2000-01-01", Calendar::NoLeap)
+            .unwrap();
+        let elapsed = start.elapsed();
+        let allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+        assert_eq!(datetimes.len(), values.len());
+        println!("{len} values: {allocations} allocations in {elapsed:?}");
+    }
+}
@@ -0,0 +1,38 @@
+//! Times the conversion of the timestamps of a sorted time axis to dates, carrying the days from
+//! one date to the next, against converting every timestamp from 1970.
+//!
+//! Run with `cargo bench --bench ymd_hms`
+
+use cftime_rs::calendars::Calendar;
+use cftime_rs::raw::{ymd_hms_from_timestamp, ymd_hms_from_timestamps};
+use std::time::Instant;
+
+const CALENDARS: [Calendar; 6] = [
+    Calendar::Standard,
+    Calendar::ProlepticGregorian,
+    Calendar::Julian,
+    Calendar::NoLeap,
+    Calendar::AllLeap,
+    Calendar::Day360,
+];
+
+fn main() {
+    // Hourly values of a thousand years
+    let timestamps: Vec<i64> = (0..8_760_000).map(|hour| hour * 3_600).collect();
+    for calendar in CALENDARS {
+        let start = Instant::now();
+        let sorted = ymd_hms_from_timestamps(&timestamps, calendar);
+        let sorted_elapsed = start.elapsed();
+        let start = Instant::now();
+        let single: Vec<_> = timestamps
+            .iter()
+            .map(|&timestamp| ymd_hms_from_timestamp(timestamp, calendar))
+            .collect();
+        let single_elapsed = start.elapsed();
+        assert_eq!(sorted, single);
+        println!(
+            "{calendar}: {} timestamps in {sorted_elapsed:?} sorted, {single_elapsed:?} one by one",
+            timestamps.len()
+        );
+    }
+}
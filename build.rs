@@ -0,0 +1,29 @@
+//! Build script exposing build information to the python module
+use std::process::Command;
+
+fn main() {
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let rustc_version = Command::new(rustc)
+        .arg("--version")
+        .output()
+        .ok()
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|version| version.trim().to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+    // Cargo sets a CARGO_FEATURE_<NAME> environment variable for each enabled feature
+    let mut features: Vec<String> = std::env::vars()
+        .filter_map(|(key, _)| {
+            key.strip_prefix("CARGO_FEATURE_")
+                .map(|feature| feature.to_lowercase().replace('_', "-"))
+        })
+        .collect();
+    features.sort();
+    let target = std::env::var("TARGET").unwrap_or_else(|_| "unknown".to_string());
+    let profile = std::env::var("PROFILE").unwrap_or_else(|_| "unknown".to_string());
+
+    println!("cargo:rustc-env=CFTIME_RS_RUSTC_VERSION={rustc_version}");
+    println!("cargo:rustc-env=CFTIME_RS_FEATURES={}", features.join(","));
+    println!("cargo:rustc-env=CFTIME_RS_TARGET={target}");
+    println!("cargo:rustc-env=CFTIME_RS_PROFILE={profile}");
+    println!("cargo:rerun-if-changed=build.rs");
+}
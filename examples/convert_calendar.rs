@@ -0,0 +1,69 @@
+//! Converts a daily time axis from the `standard` calendar to the `noleap` and `360_day`
+//! calendars, and rebases its values on new units.
+//!
+//! Run with `cargo run --example convert_calendar`, `cargo test --examples` checks it too.
+
+use cftime_rs::calendars::Calendar;
+use cftime_rs::converter::{rebase_cf, CFConverter};
+use cftime_rs::datetime::{CFDatetime, CalendarChangeAction, InvalidDatePolicy};
+use cftime_rs::duration::CFDuration;
+
+fn main() {
+    // Every day of February and March 2000, a leap year of the standard calendar
+    let calendar = Calendar::Standard;
+    let start = CFDatetime::from_ymd(2000, 2, 1, calendar).unwrap();
+    let end = CFDatetime::from_ymd(2000, 4, 1, calendar).unwrap();
+    let step = CFDuration::from_days(1, calendar);
+    let daily: Vec<CFDatetime> = start.iter_forward(step).unwrap().until(end).collect();
+    assert_eq!(daily.len(), 29 + 31);
+
+    // 2000-02-29 does not exist in the noleap calendar
+    let (noleap, report) =
+        CFDatetime::change_calendar_many(&daily, Calendar::NoLeap, InvalidDatePolicy::Drop)
+            .unwrap();
+    assert_eq!(noleap.len(), daily.len() - 1);
+    assert_eq!(report.dropped().collect::<Vec<_>>(), vec![28]);
+    for issue in &report.issues {
+        println!("noleap: {issue}");
+    }
+
+    // 2000-03-31 does not exist in the 360_day calendar, clamping keeps one date per day
+    let (day_360, report) =
+        CFDatetime::change_calendar_many(&daily, Calendar::Day360, InvalidDatePolicy::Clamp)
+            .unwrap();
+    assert_eq!(day_360.len(), daily.len());
+    assert_eq!(report.issues.len(), 1);
+    assert_eq!(report.issues[0].index, 59);
+    assert_eq!(
+        report.issues[0].action,
+        CalendarChangeAction::Clamped { day: 30 }
+    );
+    assert!(day_360[59] == CFDatetime::from_ymd(2000, 3, 30, Calendar::Day360).unwrap());
+    for issue in &report.issues {
+        println!("360_day: {issue}");
+    }
+
+    // Encode the noleap axis and rebase it on hours since the start of the year
+    let days: Vec<i64> = CFConverter::new("days since 2000-01-01", Calendar::NoLeap)
+        .unwrap()
+        .encode(&noleap)
+        .unwrap();
+    assert_eq!(days[0], 31);
+    assert_eq!(days[28], 59);
+    let hours: Vec<i64> = rebase_cf(
+        &days,
+        "days since 2000-01-01",
+        "hours since 1999-12-31 00:00:00",
+        Calendar::NoLeap,
+    )
+    .unwrap();
+    assert!(hours
+        .iter()
+        .zip(&days)
+        .all(|(hours, days)| *hours == (days + 1) * 24));
+}
+
+#[test]
+fn run() {
+    main()
+}
@@ -0,0 +1,85 @@
+//! Decodes the time axis of a netCDF variable, subsets it and computes yearly means.
+//!
+//! The values and attributes are the ones of a monthly variable of a climate model in the
+//! `noleap` calendar, as read from the `time` variable of a netCDF file.
+//!
+//! Run with `cargo run --example decode_netcdf_axis`, `cargo test --examples` checks it too.
+
+use cftime_rs::array::CFTimeArray;
+use cftime_rs::calendars::Calendar;
+use cftime_rs::converter::CFConverter;
+use cftime_rs::datetime::CFDatetime;
+use cftime_rs::frequency::{infer_freq, Step};
+use cftime_rs::resample::{Period, TimeUnitsConverter};
+use core::str::FromStr;
+
+/// Middle of every month of 2000 and 2001, in days since 2000-01-01
+fn monthly_axis() -> Vec<f64> {
+    let days_in_month = [31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
+    let mut values = Vec::new();
+    let mut start = 0.0;
+    for _ in 0..2 {
+        for days in days_in_month {
+            values.push(start + days as f64 / 2.0);
+            start += days as f64;
+        }
+    }
+    values
+}
+
+fn main() {
+    let units = "days since 2000-01-01 00:00:00";
+    let calendar = Calendar::from_str("noleap").unwrap();
+    let values = monthly_axis();
+
+    // Decode the whole axis at once, the units are parsed a single time
+    let converter = CFConverter::new(units, calendar).unwrap();
+    let datetimes = converter.decode(&values).unwrap();
+    assert_eq!(datetimes.len(), 24);
+    assert_eq!(datetimes[1].ymd_hms().unwrap(), (2000, 2, 15, 0, 0, 0));
+    assert_eq!(datetimes[2].ymd_hms().unwrap(), (2000, 3, 16, 12, 0, 0));
+    println!("{} to {}", datetimes[0], datetimes[23]);
+
+    // The mid-month values are not a whole number of months apart, only February is shorter
+    let frequency = infer_freq(&datetimes).unwrap().unwrap();
+    assert!(matches!(frequency.step, Step::Nanoseconds(_)));
+    assert!(!frequency.anomalies.is_empty());
+
+    // Subset the summer of 2001 with a binary search
+    let axis = CFTimeArray::new(datetimes, calendar).unwrap();
+    assert!(axis.is_sorted());
+    let start = CFDatetime::from_ymd(2001, 6, 1, calendar).unwrap();
+    let end = CFDatetime::from_ymd(2001, 8, 31, calendar).unwrap();
+    let summer = axis.index_range(&start, &end).unwrap();
+    assert_eq!(summer, 17..20);
+    assert_eq!(axis.slice_by_time(&start, &end).unwrap().len(), 3);
+
+    // Yearly means weighted by the length of the months
+    let temperatures: Vec<f64> = (0..24).map(|month| (month % 12) as f64).collect();
+    let months = TimeUnitsConverter::new(Period::Month, calendar);
+    let weights = months.weights(axis.datetimes()).unwrap();
+    let years = TimeUnitsConverter::new(Period::Year, calendar)
+        .bins(axis.datetimes())
+        .unwrap();
+    assert_eq!(years.len(), 2);
+    for year in &years {
+        let total: i64 = weights[year.indices.clone()].iter().sum();
+        assert_eq!(total, year.seconds);
+        let mean = year
+            .indices
+            .clone()
+            .map(|i| temperatures[i] * weights[i] as f64)
+            .sum::<f64>()
+            / total as f64;
+        println!("{}: {mean:.3}", year.start);
+    }
+
+    // Encoding back with the same units gives the original values
+    let encoded: Vec<f64> = converter.encode(axis.datetimes()).unwrap();
+    assert_eq!(encoded, values);
+}
+
+#[test]
+fn run() {
+    main()
+}
@@ -0,0 +1,27 @@
+//! Parses arbitrary units with every parser of the crate, which must return an error instead of
+//! panicking on invalid units.
+#![no_main]
+
+use cftime_rs::calendars::Calendar;
+use cftime_rs::parser::{
+    parse_absolute_time, parse_cf_time, parse_cf_time_with_mode, parse_lead_time_units, ParseMode,
+    Unit,
+};
+use cftime_rs::utils::get_datetime_and_unit_from_units;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|units: &str| {
+    let strict = parse_cf_time(units);
+    let lenient = parse_cf_time_with_mode(units, ParseMode::Lenient);
+    // The lenient mode only adds repairs to the strict one
+    if strict.is_ok() {
+        assert!(lenient.is_ok(), "{units:?} is valid in strict mode only");
+    }
+    let _ = parse_cf_time_with_mode(units, ParseMode::Strict);
+    let _ = parse_lead_time_units(units);
+    let _ = parse_absolute_time(units);
+    let _ = units.parse::<Unit>();
+    for calendar in [Calendar::Standard, Calendar::NoLeap, Calendar::Day360] {
+        let _ = get_datetime_and_unit_from_units(units, calendar);
+    }
+});
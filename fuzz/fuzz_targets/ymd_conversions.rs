@@ -0,0 +1,38 @@
+//! Converts arbitrary timestamps of every calendar to year, month and day and back, which must
+//! give the same number of days.
+#![no_main]
+
+use cftime_rs::calendars::Calendar;
+use cftime_rs::datetime::CFDatetime;
+use libfuzzer_sys::fuzz_target;
+
+const CALENDARS: [Calendar; 6] = [
+    Calendar::Standard,
+    Calendar::ProlepticGregorian,
+    Calendar::NoLeap,
+    Calendar::AllLeap,
+    Calendar::Julian,
+    Calendar::Day360,
+];
+
+fuzz_target!(|input: (u8, i64, u32)| {
+    let (calendar, timestamp, nanoseconds) = input;
+    // More nanoseconds are carried into the timestamp, and so into another day
+    let nanoseconds = nanoseconds % 1_000_000_000;
+    let calendar = CALENDARS[calendar as usize % CALENDARS.len()];
+    let days = timestamp.div_euclid(86_400);
+    let (year, month, day) = calendar.days_to_ymd(days);
+    assert!(
+        calendar.is_valid_date(year, month, day),
+        "{year}-{month}-{day} from {days} days is not valid in {calendar}"
+    );
+    assert_eq!(calendar.ymd_to_days(year, month, day).ok(), Some(days));
+
+    let Ok(datetime) = CFDatetime::from_timestamp(timestamp, nanoseconds, calendar) else {
+        return;
+    };
+    if let Ok((y, m, d, _, _, _)) = datetime.ymd_hms() {
+        assert_eq!((y, m, d), (year, month, day));
+    }
+    let _ = datetime.to_string();
+});
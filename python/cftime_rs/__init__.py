@@ -1 +1,3 @@
 from cftime_rs.cftime_rs import *
+from cftime_rs.cftime_rs import __all__, __version__
+from cftime_rs.cftime_rs import calendars, convert, units
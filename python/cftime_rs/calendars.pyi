@@ -0,0 +1,21 @@
+"""Calendar aware datetimes and durations, also exported by ``cftime_rs``."""
+
+from cftime_rs.cftime_rs import (
+    PyCFCalendar as PyCFCalendar,
+    PyCFDatetime as PyCFDatetime,
+    PyCFDuration as PyCFDuration,
+    TimeUnitsConverter as TimeUnitsConverter,
+    datetime_components as datetime_components,
+    infer_freq as infer_freq,
+    merge_time_axes as merge_time_axes,
+)
+
+__all__ = [
+    "PyCFCalendar",
+    "PyCFDuration",
+    "PyCFDatetime",
+    "TimeUnitsConverter",
+    "infer_freq",
+    "datetime_components",
+    "merge_time_axes",
+]
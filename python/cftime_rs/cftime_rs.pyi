@@ -1,10 +1,61 @@
 # flake8: noqa
 
-from typing import Iterable, Union, List, Tuple
+from typing import Any, Dict, Iterable, Union, List, Optional, Tuple
 import datetime as dt
 
+from cftime_rs import calendars as calendars, convert as convert, units as units
+
+__all__ = [
+    "num2date",
+    "date2num",
+    "num2pydate",
+    "num2bounds",
+    "pydate2num",
+    "date2index",
+    "forecast_valid_times",
+    "infer_freq",
+    "datetime_components",
+    "merge_time_axes",
+    "parse_units",
+    "validate_time_coordinate",
+    "encode_for_netcdf",
+    "rebase_units",
+    "build_info",
+    "is_accelerated",
+    "__version__",
+    "UNIT_YEAR",
+    "UNIT_MONTH",
+    "UNIT_WEEK",
+    "UNIT_DAY",
+    "UNIT_HOUR",
+    "UNIT_MINUTE",
+    "UNIT_SECOND",
+    "UNIT_MILLISECOND",
+    "UNIT_MICROSECOND",
+    "UNIT_NANOSECOND",
+    "PyCFCalendar",
+    "PyCFDuration",
+    "PyCFDatetime",
+    "Converter",
+    "TimeUnitsConverter",
+]
+
+__version__: str
+
+# Names of the units as returned by parse_units
+UNIT_YEAR: str  # "common_years"
+UNIT_MONTH: str  # "months"
+UNIT_WEEK: str  # "weeks"
+UNIT_DAY: str  # "days"
+UNIT_HOUR: str  # "hours"
+UNIT_MINUTE: str  # "minutes"
+UNIT_SECOND: str  # "seconds"
+UNIT_MILLISECOND: str  # "milliseconds"
+UNIT_MICROSECOND: str  # "microseconds"
+UNIT_NANOSECOND: str  # "nanoseconds"
+
 class PyCFCalendar:
-    """PyCFCalendar represents a calendar object."""
+    """PyCFCalendar represents a calendar object. PyCFCalendar objects can be pickled."""
 
     @staticmethod
     def from_str(s: str) -> "PyCFCalendar":
@@ -20,6 +71,80 @@ class PyCFCalendar:
             ValueError: If the calendar string cannot be parsed.
         """
         ...
+    @property
+    def name(self) -> str:
+        """The canonical name of the calendar as written in the CF conventions,
+        e.g. "standard", "proleptic_gregorian", "noleap", "all_leap", "julian" or "360_day".
+        """
+        ...
+    def units_seconds_per_year(self) -> float:
+        """Returns the number of seconds in a year as defined in the udunits package.
+
+        Returns:
+            float: Number of seconds in a year, e.g. 31556925.9747 for the standard calendar.
+        """
+        ...
+    def is_real_world(self) -> bool:
+        """Returns True if the dates of the calendar are dates of the real world, i.e.
+        "standard", "proleptic_gregorian" and "julian", as opposed to model calendars.
+
+        Returns:
+            bool: Whether the calendar is a real world calendar.
+        """
+        ...
+    def has_leap_years(self) -> bool:
+        """Returns True if the calendar has both leap and common years.
+
+        Returns:
+            bool: Whether the calendar has leap years.
+        """
+        ...
+    def fixed_year_length(self) -> Optional[int]:
+        """Returns the number of days of every year for "noleap", "all_leap" and "360_day".
+
+        Returns:
+            Optional[int]: Number of days of every year, None if the years have different lengths.
+        """
+        ...
+    def convertible_to_unix(self) -> bool:
+        """Returns True if the timestamps of the calendar are unix timestamps, i.e.
+        "standard" and "proleptic_gregorian", so the datetimes can be converted to python or
+        numpy datetimes as is.
+
+        Returns:
+            bool: Whether the calendar is convertible to unix timestamps.
+        """
+        ...
+    def is_valid_date(self, year: int, month: int, day: int) -> bool:
+        """Returns True if the date exists in the calendar, e.g. False for 2001-02-29, or for
+        1582-10-10 in the "standard" calendar, without making a datetime.
+
+        Args:
+            year (int): The year, with a year zero.
+            month (int): The month from 1 to 12.
+            day (int): The day of the month.
+
+        Returns:
+            bool: Whether the date exists.
+        """
+        ...
+    def are_valid_dates(self, years: List[int], months: List[int], days: List[int]) -> List[bool]:
+        """Returns `is_valid_date` for the dates given as one list per component.
+
+        Args:
+            years (List[int]): The years.
+            months (List[int]): The months.
+            days (List[int]): The days.
+
+        Returns:
+            List[bool]: Whether each date exists.
+
+        Raises:
+            ValueError: If the lists have different lengths.
+        """
+        ...
+    def __eq__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
 
 class PyCFDuration:
     """PyCFDuration is a wrapper around Rust CFDuration.
@@ -29,8 +154,48 @@ class PyCFDuration:
 
     This duration can be added to a PyCFDatetime.
     The result of the subtraction between two PyCFDatetime objects gives a PyCFDuration.
+    PyCFDuration objects can be pickled.
     """
 
+    def __init__(
+        self, seconds: int, nanoseconds: int, calendar: PyCFCalendar, months: int = 0
+    ) -> None:
+        """
+        Makes a new PyCFDuration with the given number of seconds, nanoseconds and
+        calendar months. The months are applied with the calendar arithmetic when
+        added to a PyCFDatetime.
+        """
+        ...
+    @property
+    def months(self) -> int:
+        """The number of calendar months of the duration."""
+        ...
+    @property
+    def seconds(self) -> int:
+        """
+        The number of seconds of the duration rounded toward negative infinity, without the
+        months, e.g. -2 for -1.5 seconds.
+        """
+        ...
+    @property
+    def nanoseconds(self) -> int:
+        """The nanoseconds to add to the seconds, between 0 and 999999999."""
+        ...
+    @property
+    def calendar(self) -> PyCFCalendar:
+        """The calendar of the duration."""
+        ...
+    def exact_components(self) -> Tuple[int, int, int]:
+        """
+        Returns the exact components of the duration, without the float rounding of the
+        num_* methods.
+
+        Returns:
+            Tuple[int, int, int]: The months, seconds and nanoseconds, such that
+                PyCFDuration(seconds, nanoseconds, calendar, months) is the same duration.
+        """
+        ...
+
     @classmethod
     def from_years(cls, years: int, calendar: PyCFCalendar) -> "PyCFDuration":
         """
@@ -42,15 +207,18 @@ class PyCFDuration:
 
         Returns:
             PyCFDuration: A new PyCFDuration object.
+
+        Raises:
+            ValueError: If the number of months does not fit in 64 bits.
         """
         ...
     @classmethod
-    def from_month(cls, month: int, calendar: PyCFCalendar) -> "PyCFDuration":
+    def from_months(cls, months: int, calendar: PyCFCalendar) -> "PyCFDuration":
         """
         Makes a new PyCFDuration with the given number of month and specific calendar.
 
         Args:
-            month (int): Number of month.
+            months (int): Number of month.
             calendar (PyCFCalendar): The calendar for the duration.
 
         Returns:
@@ -68,6 +236,9 @@ class PyCFDuration:
 
         Returns:
             PyCFDuration: A new PyCFDuration object.
+
+        Raises:
+            ValueError: If the duration does not fit in 64-bit seconds.
         """
         ...
     @classmethod
@@ -81,6 +252,9 @@ class PyCFDuration:
 
         Returns:
             PyCFDuration: A new PyCFDuration object.
+
+        Raises:
+            ValueError: If the duration does not fit in 64-bit seconds.
         """
         ...
     @classmethod
@@ -94,6 +268,9 @@ class PyCFDuration:
 
         Returns:
             PyCFDuration: A new PyCFDuration object.
+
+        Raises:
+            ValueError: If the duration does not fit in 64-bit seconds.
         """
         ...
     @classmethod
@@ -107,6 +284,9 @@ class PyCFDuration:
 
         Returns:
             PyCFDuration: A new PyCFDuration object.
+
+        Raises:
+            ValueError: If the duration does not fit in 64-bit seconds.
         """
         ...
     @classmethod
@@ -163,6 +343,35 @@ class PyCFDuration:
             nanoseconds (int): Number of nanoseconds.
             calendar (PyCFCalendar): The calendar for the duration.
 
+        Returns:
+            PyCFDuration: A new PyCFDuration object.
+        """
+        ...
+    @classmethod
+    def from_iso8601(cls, duration: str, calendar: PyCFCalendar) -> "PyCFDuration":
+        """
+        Parses an ISO 8601 duration such as P1Y2M3DT4H5M6S or -P1D in a specific calendar.
+
+        Args:
+            duration (str): The ISO 8601 duration, optionally signed.
+            calendar (PyCFCalendar): The calendar for the duration.
+
+        Returns:
+            PyCFDuration: A new PyCFDuration object.
+
+        Raises:
+            ValueError: If the string is not a valid ISO 8601 duration.
+        """
+        ...
+    def to_approximate_seconds(self) -> "PyCFDuration":
+        """
+        Returns a new PyCFDuration where the months are converted to seconds.
+
+        Months and years are stored as calendar months and are applied with the
+        calendar arithmetic when added to a PyCFDatetime. This method uses the
+        udunits definitions instead, e.g. one year is 365.242198781 days in the
+        standard calendar.
+
         Returns:
             PyCFDuration: A new PyCFDuration object.
         """
@@ -247,6 +456,53 @@ class PyCFDuration:
             float: Number of nanoseconds.
         """
         ...
+    def whole_seconds(self) -> int:
+        """
+        Returns the number of whole seconds truncated toward zero, without the months,
+        e.g. -1 for -1.5 seconds.
+
+        Returns:
+            int: Number of whole seconds.
+        """
+        ...
+    def subsec_nanos(self) -> int:
+        """
+        Returns the fraction of second in nanoseconds with the sign of the duration,
+        e.g. -500000000 for -1.5 seconds.
+
+        Returns:
+            int: Number of nanoseconds.
+        """
+        ...
+    def signum(self) -> int:
+        """
+        Returns -1 if the duration is negative, 0 if it is zero and 1 if it is positive.
+        Months are converted to seconds with the udunits definitions to get the sign.
+
+        Returns:
+            int: Sign of the duration.
+        """
+        ...
+    def is_negative(self) -> bool:
+        """
+        Returns True if the duration is strictly negative.
+
+        Returns:
+            bool: Whether the duration is negative.
+        """
+        ...
+    def is_zero(self) -> bool:
+        """
+        Returns True if the duration is zero.
+
+        Returns:
+            bool: Whether the duration is zero.
+        """
+        ...
+    def __add__(self, other: "PyCFDuration") -> "PyCFDuration": ...
+    def __sub__(self, other: "PyCFDuration") -> "PyCFDuration": ...
+    def __neg__(self) -> "PyCFDuration": ...
+    def __abs__(self) -> "PyCFDuration": ...
 
 class PyCFDatetime:
     """
@@ -256,11 +512,12 @@ class PyCFDatetime:
 
     All the methods depend on the Calendar definitions found in
     [udunits package](https://github.com/nco/nco/blob/master/data/udunits.dat).
+
+    PyCFDatetime objects can be pickled, the nanoseconds are preserved.
     """
 
-    @classmethod
-    def new(
-        cls,
+    def __init__(
+        self,
         year: int,
         month: int,
         day: int,
@@ -268,7 +525,7 @@ class PyCFDatetime:
         minute: int,
         second: float,
         calendar: PyCFCalendar,
-    ) -> "PyCFDatetime":
+    ) -> None:
         """
         Makes a new PyCFDatetime with given year, month, day, hour, minute, second, and specific calendar.
 
@@ -280,9 +537,6 @@ class PyCFDatetime:
             minute (int): The minute.
             second (float): The second.
             calendar (PyCFCalendar): The calendar for the datetime.
-
-        Returns:
-            PyCFDatetime: A new PyCFDatetime object.
         """
         ...
     def ymd(self) -> Tuple[int, int, int]:
@@ -301,6 +555,18 @@ class PyCFDatetime:
             Tuple[int, int, int]: A tuple of (hour, minute, second).
         """
         ...
+    def hours(self) -> int:
+        """Returns the hours of the date."""
+        ...
+    def minutes(self) -> int:
+        """Returns the minutes of the date."""
+        ...
+    def seconds(self) -> int:
+        """Returns the seconds of the date."""
+        ...
+    def nanoseconds(self) -> int:
+        """Returns the nanoseconds of the date."""
+        ...
     def ymd_hms(self) -> Tuple[int, int, int, int, int, int]:
         """
         Returns the year, month, day, hour, minute, and second of the datetime.
@@ -309,193 +575,925 @@ class PyCFDatetime:
             Tuple[int, int, int, int, int, int]: A tuple of (year, month, day, hour, minute, second).
         """
         ...
-    @classmethod
-    def from_ymd_hms(
-        cls,
-        year: int,
-        month: int,
-        day: int,
-        hour: int,
-        minute: int,
-        second: float,
-        calendar: PyCFCalendar,
-    ) -> "PyCFDatetime":
+    def date(self) -> "PyCFDatetime":
         """
-        Makes a new PyCFDatetime with given year, month, day, hour, minute, second, and specific calendar.
-
-        Args:
-            year (int): The year.
-            month (int): The month.
-            day (int): The day.
-            hour (int): The hour.
-            minute (int): The minute.
-            second (float): The second.
-            calendar (PyCFCalendar): The calendar for the datetime.
+        Returns the datetime at midnight, with the hour, minute, second and nanoseconds set to zero.
 
         Returns:
             PyCFDatetime: A new PyCFDatetime object.
         """
         ...
-    @classmethod
-    def from_hms(
-        cls,
-        hour: int,
-        minute: int,
-        second: float,
-        calendar: PyCFCalendar,
-    ) -> "PyCFDatetime":
+    def succ_day(self) -> "PyCFDatetime":
         """
-        Makes a new PyCFDatetime with given hour, minute, second, and specific calendar.
-        The year, month, and day are set to 1970-01-01.
-
-        Args:
-            hour (int): The hour.
-            minute (int): The minute.
-            second (float): The second.
-            calendar (PyCFCalendar): The calendar for the datetime.
+        Returns the datetime of the next day at the same time of the day. The day after
+        1582-10-04 is 1582-10-15 in the "standard" calendar.
 
         Returns:
             PyCFDatetime: A new PyCFDatetime object.
+
+        Raises:
+            ValueError: If the datetime is out of the range of the calendar.
         """
         ...
-    @classmethod
-    def from_ymd(
-        cls,
-        year: int,
-        month: int,
-        day: int,
-        calendar: PyCFCalendar,
-    ) -> "PyCFDatetime":
+    def pred_day(self) -> "PyCFDatetime":
         """
-        Makes a new PyCFDatetime with given year, month, day, and specific calendar.
-        The hour, minute, and second are set to 0.
-
-        Args:
-            year (int): The year.
-            month (int): The month.
-            day (int): The day.
-            calendar (PyCFCalendar): The calendar for the datetime.
+        Returns the datetime of the previous day at the same time of the day.
 
         Returns:
             PyCFDatetime: A new PyCFDatetime object.
+
+        Raises:
+            ValueError: If the datetime is out of the range of the calendar.
         """
         ...
-    @classmethod
-    def from_timestamp(
-        cls,
-        timestamp: int,
-        nanoseconds: int,
-        calendar: PyCFCalendar,
-    ) -> "PyCFDatetime":
+    def start_of_month(self) -> "PyCFDatetime":
         """
-        Makes a new PyCFDatetime with given timestamp, nanoseconds, and specific calendar.
-
-        Args:
-            timestamp (int): The timestamp.
-            nanoseconds (int): The nanoseconds.
-            calendar (PyCFCalendar): The calendar for the datetime.
+        Returns the first day of the month at midnight.
 
         Returns:
             PyCFDatetime: A new PyCFDatetime object.
+
+        Raises:
+            ValueError: If the datetime is out of the range of the calendar.
         """
         ...
-    def change_calendar(self, calendar: PyCFCalendar) -> "PyCFDatetime":
-        """Change the calendar of the PyCFDatetime.
-
-        This can be considered as safe as this method try to recreate the datetime with the same year, month,
-        day, hour, minute, second and nanoseconds.
-
-        Args:
-            calendar (PyCFCalendar): The calendar for the datetime.
+    def end_of_month(self) -> "PyCFDatetime":
+        """
+        Returns the last day of the month at midnight, e.g. 2000-02-30 in the "360_day"
+        calendar.
 
         Returns:
             PyCFDatetime: A new PyCFDatetime object.
 
         Raises:
-            ValueError: If the date is not possible in the target calendar.
+            ValueError: If the datetime is out of the range of the calendar.
         """
         ...
-    def change_calendar_from_timestamp(
-        self,
-        calendar: PyCFCalendar,
-    ) -> "PyCFDatetime":
-        """Change the calendar of the CFDatetime using the timestamp
-
-        Be aware that there is highly chance that the two dates do not correspond.
-        However their distances from epoch are the same.
-
-        Args:
-            calendar (PyCFCalendar): The calendar for the datetime.
+    def start_of_year(self) -> "PyCFDatetime":
+        """
+        Returns the first of January of the year at midnight.
 
         Returns:
             PyCFDatetime: A new PyCFDatetime object.
 
         Raises:
-            ValueError: If the date is not possible in the target calendar.
+            ValueError: If the datetime is out of the range of the calendar.
         """
         ...
-    def to_pydatetime(self) -> dt.datetime:
+    def end_of_year(self) -> "PyCFDatetime":
         """
-        Converts the object to a Python datetime object using year, month, day, hour, minute,
-        and second
+        Returns the last day of the year at midnight, e.g. 2000-12-30 in the "360_day"
+        calendar.
 
         Returns:
-            A datetime object representing the same date and time as the object.
+            PyCFDatetime: A new PyCFDatetime object.
 
         Raises:
-            ValueError: If the date cannot be converted to a datetime
+            ValueError: If the datetime is out of the range of the calendar.
         """
         ...
-    def to_pydatetime_from_timestamp(self) -> dt.datetime:
+    def time_of_day(self) -> PyCFDuration:
         """
-        Converts the object to a Python datetime object using the timestamp
+        Returns the duration elapsed since midnight.
 
         Returns:
-            A datetime object representing the same underlying timestamp
+            PyCFDuration: The duration since midnight.
+        """
+        ...
+    def seconds_since_midnight(self) -> float:
+        """
+        Returns the number of seconds elapsed since midnight, including the fraction of seconds.
 
-        Raises:
-            ValueError: If the date cannot be converted to a datetime
+        Returns:
+            float: Number of seconds since midnight.
         """
         ...
+    def toordinal(self, fractional: bool = False) -> Union[int, float]:
+        """
+        Returns the ordinal day number of the datetime in its calendar, as cftime.
 
-def num2date(
-    arr: Iterable[Union[int, float]],
-    units: str,
-    calendar: str,
-) -> List[PyCFDatetime]:
-    """Convert a list of numbers to PyCFDatetime objects based on the specified calendar.
+        Day 0 starts at noon on January 1 of the year -4713 in the julian calendar for the
+        standard, julian and proleptic_gregorian calendars, i.e. the Julian Day Number.
+        Day 0 starts at noon on January 1 of the year 0 for the noleap, all_leap and
+        360_day calendars.
 
-    Args:
-        arr : Iterable[Union[int, float]]
-            Array of numbers to convert to PyCFDatetime
-        units : str
-            Valid CF units
-        calendar : str
-            CF calendar name. Should be one of "standard", "gregorian",
-            "proleptic_gregorian", "julian", "all_leap", "no_leap", "360_day", "365_day", "366_day".
-            If the calendar is not recognized, "standard" will be used
+        Args:
+            fractional (bool): Include the fraction of the day, the day starting at noon.
 
-    Raises:
-        ValueError
-            If the date is not valid in the calendar
+        Returns:
+            Union[int, float]: The ordinal day number.
+        """
+        ...
+    @staticmethod
+    def j2000(calendar: PyCFCalendar) -> PyCFDatetime:
+        """
+        Returns the J2000.0 epoch of the calendar, 2000-01-01 12:00:00, i.e. the Julian Day
+        2451545.0 in the standard calendar. The difference between Terrestrial Time and UTC is
+        ignored.
 
-    Returns:
-        List[PyCFDatetime]
-            List of PyCFDatetime objects
+        Args:
+            calendar (PyCFCalendar): The calendar of the epoch.
 
-    """
-    ...
+        Returns:
+            PyCFDatetime: The epoch.
+        """
+        ...
+    def days_since(self, epoch: PyCFDatetime) -> float:
+        """
+        Returns the number of days elapsed since the epoch, including the fraction of the day,
+        negative if the datetime is before the epoch.
+
+        Args:
+            epoch (PyCFDatetime): The epoch, in the calendar of the datetime.
+
+        Returns:
+            float: Number of days since the epoch.
+
+        Raises:
+            ValueError: If the epoch is not in the calendar of the datetime.
+        """
+        ...
+    def julian_centuries_since(self, epoch: PyCFDatetime) -> float:
+        """
+        Returns the number of centuries elapsed since the epoch. A century is the Julian century
+        of 36525 days in the standard, julian and proleptic_gregorian calendars, and a hundred
+        years of the calendar in the noleap, all_leap and 360_day calendars.
+
+        Args:
+            epoch (PyCFDatetime): The epoch, in the calendar of the datetime.
+
+        Returns:
+            float: Number of centuries since the epoch.
+
+        Raises:
+            ValueError: If the epoch is not in the calendar of the datetime.
+        """
+        ...
+    def julian_century(self) -> float:
+        """
+        Returns the number of Julian centuries since the J2000.0 epoch of the calendar, the
+        time variable T of the solar position formulas, see julian_centuries_since.
+
+        Returns:
+            float: Number of centuries since 2000-01-01 12:00:00.
+        """
+        ...
+    def elapsed_since(self, origin: PyCFDatetime, unit: str) -> float:
+        """
+        Returns the time elapsed since the origin as a number of units, the value that
+        date2num gives with the units "<unit> since <origin>" without formatting and parsing
+        them. Months and years have the fixed lengths of the encoding.
+
+        Args:
+            origin (PyCFDatetime): The origin, in the calendar of the datetime.
+            unit (str): The unit e.g. "days", "hours" or "seconds".
+
+        Returns:
+            float: Number of units since the origin.
+
+        Raises:
+            ValueError: If the unit is unknown or the origin is not in the calendar of the
+                datetime.
+        """
+        ...
+    def is_leap_year(self) -> bool:
+        """
+        Returns True if the year of the datetime is a leap year in its calendar.
+
+        Returns:
+            bool: True for a leap year.
+        """
+        ...
+    def days_in_month(self) -> int:
+        """
+        Returns the number of days of the month of the datetime in its calendar.
+
+        Returns:
+            int: Number of days of the month.
+        """
+        ...
+    def days_in_year(self) -> int:
+        """
+        Returns the number of days of the year of the datetime in its calendar.
+
+        Returns:
+            int: Number of days of the year.
+        """
+        ...
+    def weekday(self) -> int:
+        """
+        Returns the day of the week, as `dayofwk` of python-cftime.
+
+        Returns:
+            int: Day of the week from 0 for Monday to 6 for Sunday.
+        """
+        ...
+    def weekday_name(self, names: Optional[List[str]] = None) -> str:
+        """
+        Returns the name of the day of the week.
+
+        Args:
+            names (Optional[List[str]], optional): 7 names of the days starting on Monday, e.g. to
+                localize the names. Defaults to the english names.
+
+        Returns:
+            str: Name of the day of the week.
+        """
+        ...
+    def month_name(self, names: Optional[List[str]] = None) -> str:
+        """
+        Returns the name of the month.
+
+        Args:
+            names (Optional[List[str]], optional): 12 names of the months starting with January,
+                e.g. to localize the names. Defaults to the english names.
+
+        Returns:
+            str: Name of the month.
+
+        Raises:
+            ValueError: If there are not 12 names.
+        """
+        ...
+    @classmethod
+    def from_ymd_hms(
+        cls,
+        year: int,
+        month: int,
+        day: int,
+        hour: int,
+        minute: int,
+        second: float,
+        calendar: PyCFCalendar,
+    ) -> "PyCFDatetime":
+        """
+        Makes a new PyCFDatetime with given year, month, day, hour, minute, second, and specific calendar.
+
+        Args:
+            year (int): The year.
+            month (int): The month.
+            day (int): The day.
+            hour (int): The hour.
+            minute (int): The minute.
+            second (float): The second.
+            calendar (PyCFCalendar): The calendar for the datetime.
+
+        Returns:
+            PyCFDatetime: A new PyCFDatetime object.
+        """
+        ...
+    @classmethod
+    def from_hms(
+        cls,
+        hour: int,
+        minute: int,
+        second: float,
+        calendar: PyCFCalendar,
+    ) -> "PyCFDatetime":
+        """
+        Makes a new PyCFDatetime with given hour, minute, second, and specific calendar.
+        The year, month, and day are set to 1970-01-01.
+
+        Args:
+            hour (int): The hour.
+            minute (int): The minute.
+            second (float): The second.
+            calendar (PyCFCalendar): The calendar for the datetime.
+
+        Returns:
+            PyCFDatetime: A new PyCFDatetime object.
+        """
+        ...
+    @classmethod
+    def from_ymd(
+        cls,
+        year: int,
+        month: int,
+        day: int,
+        calendar: PyCFCalendar,
+    ) -> "PyCFDatetime":
+        """
+        Makes a new PyCFDatetime with given year, month, day, and specific calendar.
+        The hour, minute, and second are set to 0.
+
+        Args:
+            year (int): The year.
+            month (int): The month.
+            day (int): The day.
+            calendar (PyCFCalendar): The calendar for the datetime.
+
+        Returns:
+            PyCFDatetime: A new PyCFDatetime object.
+        """
+        ...
+    @classmethod
+    def from_timestamp(
+        cls,
+        timestamp: int,
+        nanoseconds: int,
+        calendar: PyCFCalendar,
+    ) -> "PyCFDatetime":
+        """
+        Makes a new PyCFDatetime with given timestamp, nanoseconds, and specific calendar.
+
+        Args:
+            timestamp (int): The timestamp.
+            nanoseconds (int): The nanoseconds.
+            calendar (PyCFCalendar): The calendar for the datetime.
+
+        Returns:
+            PyCFDatetime: A new PyCFDatetime object.
+        """
+        ...
+    @classmethod
+    def from_timestamp_nanos(
+        cls,
+        timestamp_nanos: int,
+        calendar: PyCFCalendar,
+    ) -> "PyCFDatetime":
+        """
+        Makes a new PyCFDatetime with given number of nanoseconds since epoch and specific calendar.
+
+        Args:
+            timestamp_nanos (int): The number of nanoseconds since epoch.
+            calendar (PyCFCalendar): The calendar for the datetime.
+
+        Returns:
+            PyCFDatetime: A new PyCFDatetime object.
+
+        Raises:
+            ValueError: If the number of seconds does not fit in a 64 bit integer.
+        """
+        ...
+    @staticmethod
+    def fromtimestamp(timestamp: float, calendar: PyCFCalendar) -> "PyCFDatetime":
+        """
+        Makes a new PyCFDatetime from a number of seconds since 1970-01-01 00:00:00 of the
+        calendar, as `datetime.fromtimestamp`. The fractional seconds are rounded to the nanosecond.
+
+        Args:
+            timestamp (float): The number of seconds since epoch.
+            calendar (PyCFCalendar): The calendar for the datetime.
+
+        Returns:
+            PyCFDatetime: A new PyCFDatetime object.
+
+        Raises:
+            ValueError: If the number of seconds is not finite or does not fit in a 64 bit integer.
+        """
+        ...
+    @staticmethod
+    def fromisoformat(
+        date_string: str, calendar: PyCFCalendar, force: bool = False
+    ) -> "PyCFDatetime":
+        """
+        Makes a new PyCFDatetime from a RFC 3339 string such as "2000-01-01T12:00:00.5+01:00",
+        as `datetime.fromisoformat`. The offset is subtracted to get the datetime in UTC. A space
+        separator, a date without time and a time without offset, taken as UTC, are accepted.
+
+        Args:
+            date_string (str): The RFC 3339 string.
+            calendar (PyCFCalendar): The calendar for the datetime.
+            force (bool): Parse the date in a calendar that is not a real world calendar,
+                e.g. "360_day", whose dates are not RFC 3339 dates.
+
+        Returns:
+            PyCFDatetime: A new PyCFDatetime object.
+
+        Raises:
+            ValueError: If the string is not a RFC 3339 datetime, if the date does not exist in
+                the calendar or if the calendar is not a real world calendar and force is False.
+        """
+        ...
+    @staticmethod
+    def fromordinal(jday: float, calendar: PyCFCalendar) -> "PyCFDatetime":
+        """
+        Makes a new PyCFDatetime from an ordinal day number, as cftime. This is the inverse
+        of toordinal(fractional=True), so an integer ordinal gives the datetime at noon.
+
+        Args:
+            jday (float): The ordinal day number.
+            calendar (PyCFCalendar): The calendar for the datetime.
+
+        Returns:
+            PyCFDatetime: A new PyCFDatetime object.
+
+        Raises:
+            ValueError: If the datetime is out of the range of the calendar.
+        """
+        ...
+    @staticmethod
+    def min(calendar: PyCFCalendar) -> "PyCFDatetime":
+        """
+        Returns the earliest PyCFDatetime representable in the calendar.
+
+        Args:
+            calendar (PyCFCalendar): The calendar for the datetime.
+
+        Returns:
+            PyCFDatetime: The datetime at the smallest 64 bit number of seconds since epoch.
+        """
+        ...
+    @staticmethod
+    def max(calendar: PyCFCalendar) -> "PyCFDatetime":
+        """
+        Returns the latest PyCFDatetime representable in the calendar.
+
+        Args:
+            calendar (PyCFCalendar): The calendar for the datetime.
+
+        Returns:
+            PyCFDatetime: The datetime at the largest 64 bit number of seconds since epoch.
+        """
+        ...
+    @staticmethod
+    def clamp_to_valid_range(
+        timestamp_nanos: int, calendar: PyCFCalendar
+    ) -> "PyCFDatetime":
+        """
+        Makes a new PyCFDatetime from nanoseconds since epoch, clamped between
+        PyCFDatetime.min and PyCFDatetime.max of the calendar.
+
+        Args:
+            timestamp_nanos (int): The number of nanoseconds since epoch.
+            calendar (PyCFCalendar): The calendar for the datetime.
+
+        Returns:
+            PyCFDatetime: A new PyCFDatetime object.
+        """
+        ...
+    @staticmethod
+    def from_timestamps(
+        timestamps: List[int], calendar: PyCFCalendar
+    ) -> List["PyCFDatetime"]:
+        """
+        Makes a list of PyCFDatetime from seconds since epoch, without units string.
+
+        Args:
+            timestamps (List[int]): Seconds since epoch.
+            calendar (PyCFCalendar): The calendar for the dates.
+
+        Returns:
+            List[PyCFDatetime]: List of PyCFDatetime objects.
+        """
+        ...
+    @staticmethod
+    def from_components(
+        years: List[int],
+        months: List[int],
+        days: List[int],
+        hours: List[int],
+        minutes: List[int],
+        seconds: List[float],
+        calendar: PyCFCalendar,
+    ) -> List["PyCFDatetime"]:
+        """
+        Makes a list of PyCFDatetime from lists of components, e.g. the columns of a table.
+        The datetime i is made of the element i of every list.
+
+        Args:
+            years (List[int]): The years.
+            months (List[int]): The months.
+            days (List[int]): The days.
+            hours (List[int]): The hours.
+            minutes (List[int]): The minutes.
+            seconds (List[float]): The seconds.
+            calendar (PyCFCalendar): The calendar for the dates.
+
+        Returns:
+            List[PyCFDatetime]: List of PyCFDatetime objects.
+
+        Raises:
+            ValueError: If the lists do not have the same length or if a datetime is invalid,
+                with the index of the first invalid datetime in the message.
+        """
+        ...
+    @staticmethod
+    def from_timestamps_nanos(
+        timestamps_nanos: List[int], calendar: PyCFCalendar
+    ) -> List["PyCFDatetime"]:
+        """
+        Makes a list of PyCFDatetime from nanoseconds since epoch, without units string.
+
+        Args:
+            timestamps_nanos (List[int]): Nanoseconds since epoch.
+            calendar (PyCFCalendar): The calendar for the dates.
+
+        Returns:
+            List[PyCFDatetime]: List of PyCFDatetime objects.
+        """
+        ...
+    @staticmethod
+    def to_timestamps(datetimes: List["PyCFDatetime"]) -> List[int]:
+        """
+        Returns the seconds since epoch of the dates, the fractions of seconds are dropped.
+
+        Returns:
+            List[int]: Seconds since epoch.
+        """
+        ...
+    @staticmethod
+    def to_timestamps_nanos(datetimes: List["PyCFDatetime"]) -> List[int]:
+        """
+        Returns the nanoseconds since epoch of the dates.
+
+        Returns:
+            List[int]: Nanoseconds since epoch.
+        """
+        ...
+    def isoformat(self, sep: str = "T", force: bool = False) -> str:
+        """
+        Returns the RFC 3339 string of the datetime, as `datetime.isoformat`, e.g.
+        "2000-01-01T12:00:00.5+00:00".
+
+        Args:
+            sep (str): The character between the date and the time.
+            force (bool): Format the date of a calendar that is not a real world calendar,
+                e.g. "360_day", whose dates are not RFC 3339 dates.
+
+        Returns:
+            str: The RFC 3339 string.
+
+        Raises:
+            ValueError: If the calendar is not a real world calendar and force is False, or if
+                the year is not between 0 and 9999.
+        """
+        ...
+    def timestamp(self) -> float:
+        """
+        Returns the number of seconds since 1970-01-01 00:00:00 of the calendar, as `datetime.timestamp`.
+
+        Returns:
+            float: The number of seconds since epoch, fractional seconds included.
+        """
+        ...
+    def timestamp_nanos(self) -> int:
+        """
+        Returns the number of nanoseconds since epoch, as used by `numpy.datetime64[ns]`.
+
+        Returns:
+            int: The number of nanoseconds since epoch.
+        """
+        ...
+    def replace(
+        self,
+        year: Optional[int] = None,
+        month: Optional[int] = None,
+        day: Optional[int] = None,
+        hour: Optional[int] = None,
+        minute: Optional[int] = None,
+        second: Optional[int] = None,
+        nanosecond: Optional[int] = None,
+    ) -> "PyCFDatetime":
+        """Return a new PyCFDatetime with the given fields replaced.
+
+        The fields that are not given are kept from the current datetime and the
+        calendar is not changed.
+
+        Args:
+            year (Optional[int]): The year.
+            month (Optional[int]): The month.
+            day (Optional[int]): The day.
+            hour (Optional[int]): The hour.
+            minute (Optional[int]): The minute.
+            second (Optional[int]): The second.
+            nanosecond (Optional[int]): The nanosecond.
+
+        Returns:
+            PyCFDatetime: A new PyCFDatetime object.
+
+        Raises:
+            ValueError: If the resulting date is not valid in the calendar.
+        """
+        ...
+    def change_calendar(self, calendar: PyCFCalendar) -> "PyCFDatetime":
+        """Change the calendar of the PyCFDatetime.
+
+        This can be considered as safe as this method try to recreate the datetime with the same year, month,
+        day, hour, minute, second and nanoseconds.
+
+        Args:
+            calendar (PyCFCalendar): The calendar for the datetime.
+
+        Returns:
+            PyCFDatetime: A new PyCFDatetime object.
+
+        Raises:
+            ValueError: If the date is not possible in the target calendar.
+        """
+        ...
+    @staticmethod
+    def change_calendar_many(
+        datetimes: List["PyCFDatetime"],
+        calendar: PyCFCalendar,
+        policy: str = "drop",
+    ) -> Tuple[List["PyCFDatetime"], List[Dict[str, Any]]]:
+        """Change the calendar of many PyCFDatetime and report the dates that do not exist in the new calendar.
+
+        Args:
+            datetimes (List[PyCFDatetime]): The datetimes, all in the same calendar.
+            calendar (PyCFCalendar): The new calendar.
+            policy (str, optional): `drop` to drop the invalid dates or `clamp` to clamp them
+                to the last day of their month. Defaults to "drop".
+
+        Returns:
+            Tuple[List[PyCFDatetime], List[Dict[str, Any]]]: The converted datetimes and one dict per
+                invalid date with the keys `index`, `original` (year, month, day, hour, minute, second),
+                `action` (`dropped` or `clamped`) and `message`.
+
+        Raises:
+            ValueError: If the policy is not valid or the datetimes are not in the same calendar.
+        """
+        ...
+    def change_calendar_from_timestamp(
+        self,
+        calendar: PyCFCalendar,
+    ) -> "PyCFDatetime":
+        """Change the calendar of the CFDatetime using the timestamp
+
+        Be aware that there is highly chance that the two dates do not correspond.
+        However their distances from epoch are the same.
+
+        Args:
+            calendar (PyCFCalendar): The calendar for the datetime.
+
+        Returns:
+            PyCFDatetime: A new PyCFDatetime object.
+
+        Raises:
+            ValueError: If the date is not possible in the target calendar.
+        """
+        ...
+    def interp_calendar(
+        self,
+        calendar: PyCFCalendar,
+    ) -> "PyCFDatetime":
+        """Change the calendar of the PyCFDatetime by keeping its relative position within the year
+
+        The elapsed fraction of the year in the current calendar is applied to the same year
+        of the target calendar, like xarray `interp_calendar`.
+
+        Args:
+            calendar (PyCFCalendar): The calendar for the datetime.
+
+        Returns:
+            PyCFDatetime: A new PyCFDatetime object.
+
+        Raises:
+            ValueError: If the bounds of the year cannot be computed in one of the calendars.
+        """
+        ...
+    def to_pydatetime(self, tz: Optional[str] = None) -> dt.datetime:
+        """
+        Converts the object to a Python datetime object using year, month, day, hour, minute,
+        and second
+
+        Args:
+            tz (Optional[str]): If given, e.g. "UTC" or "+05:30", the date is converted to this
+                fixed offset and returned as an aware datetime with this offset as tzinfo.
+                Otherwise a naive datetime is returned.
+
+        Returns:
+            A datetime object representing the same date and time as the object.
+
+        Raises:
+            ValueError: If the date cannot be converted to a datetime
+        """
+        ...
+    def to_pydatetime_from_timestamp(self) -> dt.datetime:
+        """
+        Converts the object to a Python datetime object using the timestamp
+
+        Returns:
+            A datetime object representing the same underlying timestamp
+
+        Raises:
+            ValueError: If the date cannot be converted to a datetime
+        """
+        ...
+    def __add__(self, other: PyCFDuration) -> "PyCFDatetime": ...
+    def __sub__(self, other: "PyCFDatetime") -> PyCFDuration: ...
+    def __eq__(self, other: object) -> bool: ...
+
+class Converter:
+    """
+    Converter parses the units once to convert many batches of numbers or datetimes with the
+    same units, e.g. one batch per file of a dataset, without parsing the units for every batch.
+    """
+
+    def __init__(self, units: str, calendar: str, has_year_zero: bool = True) -> None:
+        """
+        Makes a new Converter with given units and calendar.
+
+        Args:
+            units (str): Valid CF units, e.g. "days since 2000-01-01".
+            calendar (str): CF calendar name, see num2date.
+            has_year_zero (bool, optional): Whether the year of the reference date of the units is
+                counted with a year zero, otherwise the year -1 is the year before the year 1.
+                The decoded PyCFDatetime always count the years with a year zero. Defaults to True.
+
+        Raises:
+            ValueError: If the units or the calendar are not valid, or if the year of the
+                reference date is 0 without a year zero.
+        """
+        ...
+    @property
+    def units(self) -> str:
+        """The units of the converter."""
+        ...
+    @property
+    def calendar(self) -> PyCFCalendar:
+        """The calendar of the converter."""
+        ...
+    def num2date(self, numbers: Iterable[Union[int, float]]) -> List[PyCFDatetime]:
+        """
+        Converts numbers to PyCFDatetime objects, as num2date with the units of the converter.
+
+        Args:
+            numbers (Iterable[Union[int, float]]): Numbers to convert.
+
+        Returns:
+            List[PyCFDatetime]: The decoded datetimes.
+
+        Raises:
+            ValueError: If a date is not valid in the calendar.
+        """
+        ...
+    def date2num(
+        self,
+        datetimes: Iterable[Union[PyCFDatetime, dt.datetime, dt.date]],
+        dtype: str,
+    ) -> Union[int, float]:
+        """
+        Converts PyCFDatetime objects to numbers, as date2num with the units of the converter.
+
+        Args:
+            datetimes (Iterable[Union[PyCFDatetime, dt.datetime, dt.date]]): Datetimes to
+                convert, any iterable of PyCFDatetime objects, python datetimes or python dates.
+            dtype (str): Type of the numbers, see date2num.
+
+        Returns:
+            Union[int, float]: List of numbers of the given dtype.
+
+        Raises:
+            TypeError: If an element is not a PyCFDatetime, a datetime or a date.
+            ValueError: If an element can not be converted, the dtype is not recognized or a
+                number does not fit in the dtype.
+        """
+        ...
+
+class TimeUnitsConverter:
+    """
+    TimeUnitsConverter gives the exact length in seconds of the day, month or year containing a
+    datetime in its calendar, e.g. 30 days for February in the 360_day calendar, to weight the
+    means of a time axis aggregated by period.
+    """
+
+    def __init__(self, period: str, calendar: str) -> None:
+        """
+        Makes a new TimeUnitsConverter for the periods of the calendar.
+
+        Args:
+            period (str): One of "day", "month" or "year".
+            calendar (str): CF calendar name, see num2date.
+
+        Raises:
+            ValueError: If the period is not valid.
+        """
+        ...
+    @property
+    def period(self) -> str:
+        """The period of the converter, one of "day", "month" or "year"."""
+        ...
+    @property
+    def calendar(self) -> PyCFCalendar:
+        """The calendar of the converter."""
+        ...
+    def period_start(self, datetime: PyCFDatetime) -> PyCFDatetime:
+        """
+        Returns the start of the period containing the datetime, at midnight.
+
+        Raises:
+            ValueError: If the datetime is not in the calendar of the converter.
+        """
+        ...
+    def period_seconds(self, datetime: PyCFDatetime) -> int:
+        """
+        Returns the length in seconds of the period containing the datetime.
+
+        Raises:
+            ValueError: If the datetime is not in the calendar of the converter.
+        """
+        ...
+    def weights(self, datetimes: List[PyCFDatetime]) -> List[int]:
+        """
+        Returns the length in seconds of the period containing every datetime.
+
+        Args:
+            datetimes (List[PyCFDatetime]): Datetimes of the time axis.
+
+        Returns:
+            List[int]: One weight per datetime.
+
+        Raises:
+            ValueError: If a datetime is not in the calendar of the converter.
+        """
+        ...
+    def bins(
+        self, datetimes: List[PyCFDatetime]
+    ) -> List[Tuple[PyCFDatetime, PyCFDatetime, int, int, int]]:
+        """
+        Splits a sorted time axis into the periods containing at least one datetime.
+
+        Args:
+            datetimes (List[PyCFDatetime]): Sorted datetimes of the time axis.
+
+        Returns:
+            List[Tuple[PyCFDatetime, PyCFDatetime, int, int, int]]: For every period, its start,
+                the start of the next period, the start and stop indices of the slice of the
+                datetimes within it and its length in seconds.
+
+        Raises:
+            ValueError: If the datetimes are not sorted or not in the calendar of the converter.
+        """
+        ...
+
+def num2date(
+    numbers: Iterable[Any],
+    units: str,
+    calendar: str,
+    absolute_time: bool = False,
+) -> List[Any]:
+    """Convert a list of numbers to PyCFDatetime objects based on the specified calendar.
+
+    Args:
+        numbers : Iterable[Any]
+            Array of numbers to convert to PyCFDatetime. Nested lists and tuples, e.g. 2-D
+            time bounds, and arrays of more than one dimension are decoded into nested lists.
+            Objects exposing the buffer protocol, e.g. `array.array("d")` or a `memoryview`,
+            are read in one copy except with the stable ABI wheels
+        units : str
+            Valid CF units
+        calendar : str
+            CF calendar name. Should be one of "standard", "gregorian",
+            "proleptic_gregorian", "julian", "all_leap", "no_leap", "360_day", "365_day", "366_day".
+            If the calendar is not recognized, "standard" will be used
+        absolute_time : bool
+            If True, the units are the non CF absolute time units "day as %Y%m%d.%f" or
+            "month as %Y%m.%f" written by some tools such as cdo, where the numbers are the
+            dates written as numbers e.g. 20000115.5 for 2000-01-15 12:00:00.
+            Default value is False.
+
+    Raises:
+        ValueError
+            If the date is not valid in the calendar
+
+    Returns:
+        List[Any]
+            List of PyCFDatetime objects, nested as the numbers
+
+    """
+    ...
+
+def num2bounds(
+    numbers: Iterable[Iterable[Union[int, float]]],
+    units: str,
+    calendar: str,
+) -> List[Tuple[PyCFDatetime, PyCFDatetime]]:
+    """Convert pairs of numbers such as the CF "time_bnds" variable to (start, end) PyCFDatetime tuples.
+
+    Args:
+        numbers : Iterable[Iterable[Union[int, float]]]
+            Two-dimensional array of shape (n, 2) of numbers to convert
+        units : str
+            Valid CF units
+        calendar : str
+            CF calendar name. Should be one of "standard", "gregorian",
+            "proleptic_gregorian", "julian", "all_leap", "no_leap", "360_day", "365_day", "366_day".
+
+    Raises:
+        ValueError
+            If the array is not of shape (n, 2) or if a date is not valid in the calendar
+
+    Returns:
+        List[Tuple[PyCFDatetime, PyCFDatetime]]
+            List of (start, end) PyCFDatetime tuples
+
+    """
+    ...
 
 def num2pydate(
-    arr: Iterable[Union[int, float]],
+    numbers: Iterable[Union[int, float]],
     units: str,
     calendar: str,
     from_timestamp: bool = False,
+    absolute_time: bool = False,
+    tz: Optional[str] = None,
 ) -> List[dt.datetime]:
     """Convert a list of numbers to datetime objects based on the specified calendar.
 
     Args:
-        arr : Iterable[Union[int, float]]
+        numbers : Iterable[Union[int, float]]
             Array of numbers to convert to datetime
         units : str
             Valid CF units
@@ -509,6 +1507,16 @@ def num2pydate(
             overflow occurs. While the distance from epoch is the same, the date are likely to be different.
             If False, the date will be converted using python datetime constructor method : the year, month, day, hour, minute, second and nanoseconds will be extyracted from PyCFDatetime object and given back to python datetime constructor. There is no guarantee that the date is valid in the calendar. This method is also considerably slower than using the timestamp.
             Default value is False.
+        absolute_time : bool
+            If True, the units are the non CF absolute time units "day as %Y%m%d.%f" or
+            "month as %Y%m.%f" written by some tools such as cdo, where the numbers are the
+            dates written as numbers e.g. 20000115.5 for 2000-01-15 12:00:00.
+            Default value is False.
+        tz : Optional[str]
+            If given, the datetimes are aware datetimes with a fixed offset as tzinfo. The dates,
+            which are in UTC as the offset of the units is applied to the reference date, are
+            converted to this offset e.g. "UTC" or "+05:30". "units" converts them to the timezone
+            of the units. Default value is None, which returns naive datetimes in UTC.
 
     Raises:
         ValueError
@@ -522,7 +1530,7 @@ def num2pydate(
     ...
 
 def date2num(
-    datetimes: List[PyCFDatetime],
+    datetimes: Iterable[Union[PyCFDatetime, dt.datetime, dt.date]],
     units: str,
     calendar: str,
     dtype: str,
@@ -531,8 +1539,10 @@ def date2num(
 
 
     Args:
-        datetimes : List[PyCFDatetime]
-            List of PyCFDatetime objects
+        datetimes : Iterable[Union[PyCFDatetime, dt.datetime, dt.date]]
+            Any iterable, e.g. a list, a tuple, a generator or a numpy array of objects, of
+            PyCFDatetime objects, python datetimes or python dates. The python datetimes and
+            dates are converted as in pydate2num
         units : str
             Valid CF units
         calendar : str
@@ -540,14 +1550,26 @@ def date2num(
         dtype : str
             32 bit integer : "i32"
             64 bit integer : "i64", "i", "integer", "int"
+            32 bit unsigned integer : "u32", "u4", "uint32"
+            64 bit unsigned integer : "u64", "u8", "uint64"
             32 bit float   : "f32"
             64 bit float   :  "f64", "f", "float"
+            Integers are rounded toward negative infinity, e.g. a date 1.5 days before the
+            reference date of "days since ..." units is encoded to -2.
 
     Raises:
+        TypeError
+            If an element is not a PyCFDatetime, a datetime or a date, with its index
         ValueError
-            If the date is not valid in the calendar
+            If a date is not valid in the calendar, with its index
+        ValueError
+            If a PyCFDatetime is not in the calendar, with its index
         ValueError
             If the dtype is not recognized
+        ValueError
+            If the dtype is unsigned and a date is before the reference date of the units
+        ValueError
+            If an integer does not fit in the dtype
 
     Returns:
         Union[int, float]
@@ -556,7 +1578,7 @@ def date2num(
     ...
 
 def pydate2num(
-    datetimes: List[dt.datetime],
+    datetimes: List[Union[dt.datetime, dt.date]],
     units: str,
     calendar: str,
     dtype: str,
@@ -568,8 +1590,9 @@ def pydate2num(
     lower performance thant using num2date.
 
     Args:
-        datetimes : List[datetime.datetime]
-            List of python datetime.datetime objects
+        datetimes : List[Union[datetime.datetime, datetime.date]]
+            List of python datetime.datetime or datetime.date objects. Dates are assumed
+            to be at midnight and timezone aware datetimes are converted to UTC.
         units : str
             Valid CF units
         calendar : str
@@ -577,17 +1600,324 @@ def pydate2num(
         dtype : str
             32 bit integer : "i32"
             64 bit integer : "i64", "i", "integer", "int"
+            32 bit unsigned integer : "u32", "u4", "uint32"
+            64 bit unsigned integer : "u64", "u8", "uint64"
             32 bit float   : "f32"
             64 bit float   :  "f64", "f", "float"
+            Integers are rounded toward negative infinity, e.g. a date 1.5 days before the
+            reference date of "days since ..." units is encoded to -2.
 
     Raises:
         ValueError
             If the date is not valid in the calendar
         ValueError
             If the dtype is not recognized
+        ValueError
+            If the dtype is unsigned and a date is before the reference date of the units
+        ValueError
+            If an integer does not fit in the dtype
 
     Returns:
         Union[int, float]
             List of numbers based on calendar, units, and dtype
     """
     ...
+
+def date2index(
+    dates: Union[PyCFDatetime, List[PyCFDatetime]],
+    numbers: Iterable[Union[int, float]],
+    units: str,
+    calendar: str,
+    select: str = "exact",
+) -> Union[int, List[int]]:
+    """Return the indices of the datetimes in a time axis, like netCDF4 `date2index`.
+
+    If the numbers are monotonically increasing a binary search is used and only
+    the numbers needed are decoded.
+
+    Args:
+        dates : Union[PyCFDatetime, List[PyCFDatetime]]
+            Datetime or list of datetimes to look for
+        numbers : Iterable[Union[int, float]]
+            Numbers of the time axis
+        units : str
+            Valid CF units of the time axis
+        calendar : str
+            CF calendar name of the time axis
+        select : str
+            "exact"   : index of the exact match
+            "before"  : index of the match or of the last datetime before
+            "after"   : index of the match or of the first datetime after
+            "nearest" : index of the nearest datetime
+            Default value is "exact".
+
+    Raises:
+        ValueError
+            If no index can be found with the given select
+        ValueError
+            If select is not "exact" and the time axis is not monotonically increasing
+
+    Returns:
+        Union[int, List[int]]
+            Index or list of indices
+    """
+    ...
+
+def forecast_valid_times(
+    reference_times: Iterable[Union[int, float]],
+    reference_units: str,
+    lead_times: Iterable[Union[int, float]],
+    lead_units: str,
+    calendar: str,
+) -> List[List[PyCFDatetime]]:
+    """Compute the valid times of forecasts, i.e. their reference time plus their lead time.
+
+    Args:
+        reference_times : Iterable[Union[int, float]]
+            Values of the forecast_reference_time coordinate
+        reference_units : str
+            Units of the reference times, e.g. "hours since 2000-01-01"
+        lead_times : Iterable[Union[int, float]]
+            Values of the forecast_period coordinate
+        lead_units : str
+            Units of the lead times, e.g. "hours" or "hours since forecast_reference_time"
+        calendar : str
+            CF calendar name. Should be one of "standard", "gregorian",
+            "proleptic_gregorian", "julian", "all_leap", "no_leap", "360_day", "365_day", "366_day".
+
+    Raises:
+        ValueError
+            If the units are not valid or a valid time is out of the range of the calendar
+
+    Returns:
+        List[List[PyCFDatetime]]
+            Valid times with one row per reference time and one column per lead time
+    """
+    ...
+
+def parse_units(
+    units: str,
+    calendar: str = "standard",
+) -> Tuple[str, PyCFDatetime, Optional[str]]:
+    """Parse CF units into their parts, to inspect or rewrite units without regexes.
+
+    Args:
+        units : str
+            Valid CF units, e.g. "3 hours since 2000-01-01 06:00:00 +01:00"
+        calendar : str
+            CF calendar name of the reference date, see num2date. Default value is "standard".
+
+    Raises:
+        ValueError
+            If the units are not valid or the reference date is not valid in the calendar
+
+    Returns:
+        Tuple[str, PyCFDatetime, Optional[str]]
+            The name of the unit, one of the UNIT_* constants e.g. "hours", the reference date in
+            UTC and the timezone e.g. "+01:00" or None if the units have no timezone.
+    """
+    ...
+
+def infer_freq(
+    datetimes: List[PyCFDatetime],
+) -> Optional[Tuple[PyCFDuration, List[int]]]:
+    """Infer the sampling frequency of a time axis.
+
+    The step is the most common step between consecutive datetimes. It is a number of calendar
+    months if the datetimes are at the same time of the day and on the same day of the month or
+    both on the last day of their month, the exact duration between the datetimes otherwise.
+
+    Args:
+        datetimes : List[PyCFDatetime]
+            Datetimes of the time axis
+
+    Raises:
+        ValueError
+            If the datetimes are not all in the same calendar
+
+    Returns:
+        Optional[Tuple[PyCFDuration, List[int]]]
+            The step and the indices i of the datetimes that are not one step after the datetime
+            i - 1, empty if the time axis is regular. None if there are less than two datetimes.
+    """
+    ...
+
+def datetime_components(
+    datetimes: List[PyCFDatetime],
+) -> Dict[str, List[int]]:
+    """Split datetimes into one list per component, e.g. the columns of a dataframe.
+
+    The dates of a sorted time axis are computed incrementally, which is much faster than
+    calling ``ymd_hms`` on each datetime.
+
+    Args:
+        datetimes : List[PyCFDatetime]
+            Datetimes of the same calendar
+
+    Raises:
+        ValueError
+            If the datetimes are not all in the same calendar
+
+    Returns:
+        Dict[str, List[int]]
+            The lists of the ``year``, ``month``, ``day``, ``hour``, ``minute``, ``second`` and
+            ``nanosecond`` of the datetimes
+    """
+    ...
+
+def merge_time_axes(
+    axes: List[List[PyCFDatetime]],
+    duplicates: str = "first",
+    require_monotonic: bool = False,
+) -> Dict[str, Any]:
+    """Merge the time axes of several files into a sorted axis without duplicates.
+
+    Each axis has to be sorted in increasing order. The datetimes found more than once are
+    duplicates, only one of them is kept in the merged axis.
+
+    Args:
+        axes : List[List[PyCFDatetime]]
+            Time axes of the files, in the same calendar
+        duplicates : str
+            Datetime kept among the duplicates: ``first`` for the one of the first axis,
+            ``last`` for the one of the last axis, ``error`` to raise an error
+        require_monotonic : bool
+            Require the axes to follow each other, i.e. their concatenation without the
+            duplicates to be strictly increasing. Otherwise the axes are interleaved.
+
+    Raises:
+        ValueError
+            If an axis is not sorted, the axes are not in the same calendar, a datetime is a
+            duplicate with the ``error`` policy, or the axes do not follow each other with
+            ``require_monotonic``
+
+    Returns:
+        Dict[str, Any]
+            ``datetimes`` the merged axis, ``sources`` the (axis, index) of every merged
+            datetime in the axes, ``duplicates`` the ((axis, index), (axis, index)) of the kept
+            and dropped datetimes, and ``overlaps`` the (i, j) pairs of axes whose ranges of
+            datetimes intersect
+    """
+    ...
+
+def validate_time_coordinate(
+    numbers: Iterable[Union[int, float]],
+    units: str,
+    calendar: str,
+) -> List[Dict[str, Any]]:
+    """Check a time coordinate against the CF conventions.
+
+    Args:
+        numbers : Iterable[Union[int, float]]
+            Values of the time coordinate
+        units : str
+            Units of the time coordinate
+        calendar : str
+            CF calendar name. Should be one of "standard", "gregorian",
+            "proleptic_gregorian", "julian", "all_leap", "no_leap", "360_day", "365_day", "366_day".
+
+    Raises:
+        ValueError
+            If the calendar is not valid or the values are not numbers
+
+    Returns:
+        List[Dict[str, Any]]
+            List of issues, empty if the time coordinate is valid. Each issue has the keys :
+            "kind"    : one of "invalid units", "nonstandard units", "invalid value",
+                        "non monotonic", "duplicate time", "before calendar range"
+            "index"   : index of the value in error or None if the issue is about the units
+            "message" : description of the issue
+    """
+    ...
+
+def encode_for_netcdf(
+    datetimes: List[PyCFDatetime],
+    policy: str = "integer",
+) -> Dict[str, Any]:
+    """Encode datetimes with the units and type recommended to write them in a netCDF file.
+
+    The reference date of the units is the midnight before the earliest datetime.
+
+    Args:
+        datetimes : List[PyCFDatetime]
+            Datetimes of the same calendar
+        policy : str, optional
+            "integer" for int64 seconds, or the coarsest of milliseconds, microseconds and
+            nanoseconds encoding every datetime exactly, or "double_days" for float64 days.
+            Defaults to "integer".
+
+    Raises:
+        ValueError
+            If the policy is not valid or the datetimes are not all of the same calendar
+
+    Returns:
+        Dict[str, Any]
+            A dictionary with the keys :
+            "values"   : list of the encoded int or float values
+            "units"    : the units attribute, e.g. "seconds since 2000-01-01 00:00:00"
+            "calendar" : the calendar attribute
+            "dtype"    : "int64" or "float64"
+    """
+    ...
+
+def rebase_units(
+    numbers: Iterable[Union[int, float]],
+    from_units: str,
+    to_units: str,
+    calendar: str,
+) -> Union[List[int], List[float]]:
+    """Convert numbers from some units to others, e.g. from "hours since 1900-01-01" to
+    "days since 2000-01-01", without making the datetimes.
+
+    The result is the same as decoding the numbers with `num2date` and encoding them with
+    `date2num`, but much faster.
+
+    Args:
+        numbers : Iterable[Union[int, float]]
+            Numbers in the units `from_units`
+        from_units : str
+            Units of the numbers
+        to_units : str
+            Units of the result
+        calendar : str
+            Calendar of both units
+
+    Raises:
+        ValueError
+            If the units or the calendar are not valid, a number is NaN or infinite or a date
+            is out of the range of the calendar
+
+    Returns:
+        Union[List[int], List[float]]
+            Integers rounded toward negative infinity for integer numbers, floats otherwise
+    """
+    ...
+
+def build_info() -> Dict[str, Any]:
+    """Return the information about how the module has been built.
+
+    Useful to include in bug reports.
+
+    Returns:
+        Dict[str, Any]
+            Dictionary with the following keys :
+            "version"       : version of cftime-rs
+            "rustc_version" : version of the rust compiler
+            "target"        : target triple
+            "profile"       : build profile, "release" or "debug"
+            "abi3"          : whether the module uses the stable ABI of CPython 3.7+
+            "features"      : list of enabled cargo features
+    """
+    ...
+
+def is_accelerated() -> bool:
+    """Return whether the functions are implemented in Rust.
+
+    Always True for cftime_rs, so packages that fall back to a pure python implementation can
+    check which one they imported the same way.
+
+    Returns:
+        bool
+            True
+    """
+    ...
@@ -0,0 +1,97 @@
+"""Functions with the signatures and semantics of python-cftime.
+
+Projects migrating from python-cftime can replace ``from cftime import num2date`` with
+``from cftime_rs.compat import num2date``.
+
+The year of the reference date of the units follows ``has_year_zero``, but PyCFDatetime always
+numbers the years astronomically, i.e. the year 0 is the year before the year 1, which is what
+python-cftime calls ``has_year_zero=True``. With ``has_year_zero=False`` both numberings agree from
+the year 1, so the decoded dates before the year 1 are rejected instead of being renumbered.
+"""
+
+import datetime as dt
+from typing import Any, List, Optional, Union
+
+from cftime_rs.cftime_rs import Converter, PyCFDatetime
+
+__all__ = ["num2date"]
+
+# Calendars for which python-cftime has no year zero by default
+_REAL_WORLD_CALENDARS = {"standard", "gregorian", "proleptic_gregorian", "julian"}
+# First date of the gregorian calendar in the standard calendar
+_GREGORIAN_BEGIN = (1582, 10, 15)
+
+
+def _python_datetimes_allowed(calendar: str, dates: List[PyCFDatetime]) -> bool:
+    """Return ``True`` if the dates can be python datetimes, as python-cftime does."""
+    if calendar == "proleptic_gregorian":
+        return all(date.ymd()[0] >= dt.MINYEAR for date in dates)
+    if calendar in ("standard", "gregorian"):
+        return all(date.ymd() >= _GREGORIAN_BEGIN for date in dates)
+    return False
+
+
+def num2date(
+    times: Any,
+    units: str,
+    calendar: str = "standard",
+    only_use_cftime_datetimes: bool = True,
+    only_use_python_datetimes: bool = False,
+    has_year_zero: Optional[bool] = None,
+) -> Union[PyCFDatetime, dt.datetime, List[Union[PyCFDatetime, dt.datetime]]]:
+    """Decode numbers into datetimes, as ``cftime.num2date``.
+
+    Args:
+        times (Any): A number or a sequence of numbers.
+        units (str): The units of the numbers, e.g. "days since 2000-01-01".
+        calendar (str, optional): The calendar. Defaults to "standard".
+        only_use_cftime_datetimes (bool, optional): Always return PyCFDatetime. Defaults to True.
+        only_use_python_datetimes (bool, optional): Always return python datetimes, raising if a
+            date is not in the gregorian calendar. Defaults to False.
+        has_year_zero (Optional[bool], optional): Whether the calendar has a year zero, by
+            default only the idealized calendars have one.
+
+    Returns:
+        The datetimes, a single datetime if ``times`` is a number. They are python datetimes if
+        ``only_use_python_datetimes`` is True, or if ``only_use_cftime_datetimes`` is False and
+        every date is in the gregorian calendar, and PyCFDatetime otherwise.
+
+    Raises:
+        ValueError: If both ``only_use_cftime_datetimes`` and ``only_use_python_datetimes`` are
+            True, if python datetimes are required but not possible, or if a decoded date is
+            before the year 1 while the calendar has no year zero.
+    """
+    if only_use_cftime_datetimes and only_use_python_datetimes:
+        raise ValueError(
+            "only_use_cftime_datetimes must be False when only_use_python_datetimes is True"
+        )
+    calendar = calendar.lower()
+    if has_year_zero is None:
+        has_year_zero = calendar not in _REAL_WORLD_CALENDARS
+    try:
+        numbers = list(times)
+        is_scalar = False
+    except TypeError:
+        numbers = [times]
+        is_scalar = True
+    dates = Converter(units, calendar, has_year_zero).num2date(numbers)
+    if not has_year_zero:
+        for date in dates:
+            if date.ymd()[0] < 1:
+                raise ValueError(
+                    f"Date {date} is before the year 1, which cftime_rs only supports "
+                    "with has_year_zero=True"
+                )
+    use_python_datetimes = only_use_python_datetimes or not only_use_cftime_datetimes
+    if use_python_datetimes and _python_datetimes_allowed(calendar, dates):
+        result: List[Union[PyCFDatetime, dt.datetime]] = [
+            date.to_pydatetime() for date in dates
+        ]
+    elif only_use_python_datetimes:
+        raise ValueError(
+            f"Dates in calendar {calendar} can not be converted to python datetimes, "
+            "use only_use_python_datetimes=False"
+        )
+    else:
+        result = list(dates)
+    return result[0] if is_scalar else result
@@ -0,0 +1,29 @@
+"""Decoding and encoding of numbers, also exported by ``cftime_rs``."""
+
+from cftime_rs.cftime_rs import (
+    Converter as Converter,
+    date2index as date2index,
+    date2num as date2num,
+    encode_for_netcdf as encode_for_netcdf,
+    forecast_valid_times as forecast_valid_times,
+    num2bounds as num2bounds,
+    num2date as num2date,
+    num2pydate as num2pydate,
+    pydate2num as pydate2num,
+    rebase_units as rebase_units,
+    validate_time_coordinate as validate_time_coordinate,
+)
+
+__all__ = [
+    "num2date",
+    "date2num",
+    "num2pydate",
+    "num2bounds",
+    "pydate2num",
+    "date2index",
+    "forecast_valid_times",
+    "validate_time_coordinate",
+    "encode_for_netcdf",
+    "rebase_units",
+    "Converter",
+]
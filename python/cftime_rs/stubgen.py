@@ -0,0 +1,107 @@
+"""Generate a stub of the ``cftime_rs`` extension module from the bindings.
+
+The parameter names and defaults come from the ``__text_signature__`` that pyo3 generates
+for every function and method, and the exported names from the ``__all__`` populated by the
+Rust module. The signatures carry no types, so the generated stub is a starting point for
+``cftime_rs.pyi`` and is used by the tests to check that the typed stub does not drift from
+the bindings.
+
+Usage::
+
+    python -m cftime_rs.stubgen > cftime_rs.pyi
+"""
+
+import inspect
+import sys
+from types import ModuleType
+from typing import Iterator, List, Optional, Tuple
+
+# Attributes of the classes that are not part of the API
+_IGNORED_ATTRIBUTES = {"__doc__", "__module__", "__new__", "__init__", "__dict__", "__weakref__"}
+
+
+def _signature(obj: object) -> Optional[inspect.Signature]:
+    try:
+        return inspect.signature(obj)  # type: ignore[arg-type]
+    except (TypeError, ValueError):
+        return None
+
+
+def iter_class_members(cls: type) -> Iterator[Tuple[str, str, Optional[inspect.Signature]]]:
+    """Yield ``(name, kind, signature)`` of the members of a class.
+
+    ``kind`` is one of "method", "staticmethod", "classmethod" or "property".
+    The constructor is yielded as ``__init__`` if it has a signature.
+    """
+    constructor = _signature(cls)
+    # Classes without constructor only have the generic `(*args, **kwargs)` signature
+    if constructor is not None and not any(
+        parameter.kind in (parameter.VAR_POSITIONAL, parameter.VAR_KEYWORD)
+        for parameter in constructor.parameters.values()
+    ):
+        yield "__init__", "method", constructor
+    for name, attribute in vars(cls).items():
+        if name in _IGNORED_ATTRIBUTES:
+            continue
+        if isinstance(attribute, staticmethod):
+            yield name, "staticmethod", _signature(getattr(cls, name))
+        elif isinstance(attribute, classmethod):
+            yield name, "classmethod", _signature(getattr(cls, name))
+        elif inspect.isgetsetdescriptor(attribute) or isinstance(attribute, property):
+            yield name, "property", None
+        elif callable(attribute):
+            yield name, "method", _signature(attribute)
+
+
+def iter_members(module: ModuleType) -> Iterator[Tuple[str, object]]:
+    """Yield the ``(name, object)`` exported in ``__all__`` by the module."""
+    for name in getattr(module, "__all__", []):
+        yield name, getattr(module, name)
+
+
+def _format_parameters(signature: Optional[inspect.Signature], first: Optional[str]) -> str:
+    if signature is None:
+        parameters = "*args, **kwargs"
+        return f"{first}, {parameters}" if first else parameters
+    parameters = [str(parameter) for parameter in signature.parameters.values()]
+    if first and (not parameters or parameters[0] != first):
+        parameters.insert(0, first)
+    return ", ".join(parameters)
+
+
+def generate_stubs(module: Optional[ModuleType] = None) -> str:
+    """Return the stub of the module, ``cftime_rs.cftime_rs`` by default."""
+    if module is None:
+        from cftime_rs import cftime_rs as module
+    lines: List[str] = [
+        "# Generated by cftime_rs.stubgen",
+        "",
+        "from typing import Any",
+        "",
+        f"__all__ = {list(getattr(module, '__all__', []))!r}",
+        "",
+    ]
+    for name, member in iter_members(module):
+        if inspect.isclass(member):
+            lines.extend(["", f"class {name}:"])
+            for member_name, kind, signature in iter_class_members(member):
+                if kind == "property":
+                    lines.append("    @property")
+                    lines.append(f"    def {member_name}(self) -> Any: ...")
+                    continue
+                first = {"method": "self", "classmethod": "cls"}.get(kind)
+                if kind != "method":
+                    lines.append(f"    @{kind}")
+                parameters = _format_parameters(signature, first)
+                lines.append(f"    def {member_name}({parameters}) -> Any: ...")
+            lines.append("")
+        elif callable(member):
+            parameters = _format_parameters(_signature(member), None)
+            lines.append(f"def {name}({parameters}) -> Any: ...")
+        else:
+            lines.append(f"{name}: {type(member).__name__}")
+    return "\n".join(lines) + "\n"
+
+
+if __name__ == "__main__":
+    sys.stdout.write(generate_stubs())
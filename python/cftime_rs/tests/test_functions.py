@@ -1,5 +1,11 @@
+import array
+import ast
+import inspect
+import pathlib
+import pickle
 import pytest
 import cftime_rs
+import cftime_rs.compat
 import datetime as dt
 
 
@@ -33,6 +39,67 @@ def test_date2num():
     assert result == expected
 
 
+def test_date2num_iterables_and_python_datetimes():
+    units = "hours since 2000-01-01"
+    calendar = cftime_rs.PyCFCalendar.from_str("standard")
+    dates = [
+        cftime_rs.PyCFDatetime.from_ymd(2000, 1, 2, calendar),
+        dt.datetime(2000, 1, 1, 6),
+        dt.date(2000, 1, 3),
+    ]
+    expected = [24, 6, 48]
+    assert cftime_rs.date2num(dates, units, "standard", dtype="i64") == expected
+    assert cftime_rs.date2num(tuple(dates), units, "standard", dtype="i64") == expected
+    assert cftime_rs.date2num((d for d in dates), units, "standard", dtype="i64") == expected
+    assert cftime_rs.date2num([], units, "standard", dtype="i64") == []
+    converter = cftime_rs.Converter(units, "standard")
+    assert converter.date2num(iter(dates), "i64") == expected
+    with pytest.raises(TypeError, match="At index 1"):
+        cftime_rs.date2num([dates[0], "2000-01-01"], units, "standard", dtype="i64")
+    # 1582-10-10 is in the days removed from the standard calendar
+    with pytest.raises(ValueError, match="At index 2"):
+        cftime_rs.date2num(dates[:2] + [dt.date(1582, 10, 10)], units, "standard", dtype="i64")
+    noleap = cftime_rs.PyCFCalendar.from_str("noleap")
+    with pytest.raises(ValueError, match="At index 1"):
+        cftime_rs.date2num(
+            [dates[0], cftime_rs.PyCFDatetime.from_ymd(2000, 1, 1, noleap)],
+            units,
+            "standard",
+            dtype="i64",
+        )
+
+
+def test_date2num_before_reference_date():
+    units = "days since 2000-01-01"
+    calendar = "standard"
+    cf_calendar = cftime_rs.PyCFCalendar.from_str(calendar)
+    dates = [cftime_rs.PyCFDatetime.from_ymd_hms(1999, 12, 30, 12, 0, 0, cf_calendar)]
+    assert cftime_rs.date2num(dates, units, calendar, dtype="f64") == [-1.5]
+    assert cftime_rs.date2num(dates, units, calendar, dtype="f32") == [-1.5]
+    assert cftime_rs.date2num(dates, units, calendar, dtype="i64") == [-2]
+    assert cftime_rs.date2num(dates, units, calendar, dtype="i32") == [-2]
+    dates = [cftime_rs.PyCFDatetime.from_ymd(2100, 1, 1, cf_calendar)]
+    with pytest.raises(ValueError):
+        cftime_rs.date2num(dates, "seconds since 1970-01-01", calendar, dtype="i32")
+
+
+def test_date2num_unsigned():
+    units = "days since 1970-01-02"
+    calendar = "standard"
+    cf_calendar = cftime_rs.PyCFCalendar.from_str(calendar)
+    dates = [
+        cftime_rs.PyCFDatetime.from_ymd(1970, 1, 2, cf_calendar),
+        cftime_rs.PyCFDatetime.from_ymd(1970, 1, 4, cf_calendar),
+    ]
+    assert cftime_rs.date2num(dates, units, calendar, dtype="u4") == [0, 2]
+    assert cftime_rs.date2num(dates, units, calendar, dtype="u8") == [0, 2]
+    dates.append(cftime_rs.PyCFDatetime.from_ymd(1970, 1, 1, cf_calendar))
+    with pytest.raises(ValueError, match="negative"):
+        cftime_rs.date2num(dates, units, calendar, dtype="u32")
+    with pytest.raises(ValueError, match="negative"):
+        cftime_rs.pydate2num([dt.date(1970, 1, 1)], units, calendar, dtype="u64")
+
+
 def test_pydate2num():
     units = "days since 1970-01-01"
     calendar = "standard"
@@ -46,6 +113,50 @@ def test_pydate2num():
     assert result == expected
 
 
+def test_pydate2num_microseconds_and_timezone():
+    units = "microseconds since 1970-01-01"
+    calendar = "proleptic_gregorian"
+    tz = dt.timezone(dt.timedelta(hours=-6))
+    dates = [
+        dt.datetime(1970, 1, 1, 0, 0, 0, 500_000),
+        dt.datetime(1970, 1, 1, 0, 0, 1, 123_456),
+        dt.datetime(1969, 12, 31, 18, 0, 0, 1, tzinfo=tz),
+        dt.datetime(1970, 1, 1, 0, 0, 0, 1, tzinfo=dt.timezone.utc),
+    ]
+    expected = [500_000, 1_123_456, 1, 1]
+    result = cftime_rs.pydate2num(dates, units, calendar, dtype="i64")
+    assert result == expected
+
+
+def test_pydate2num_dates_and_mixed_list():
+    units = "days since 1970-01-01"
+    calendar = "standard"
+    dates = [
+        dt.date(1970, 1, 2),
+        dt.datetime(1970, 1, 3, 12),
+        dt.date(1970, 1, 4),
+    ]
+    result = cftime_rs.pydate2num(dates, units, calendar, dtype="f64")
+    assert result == [1.0, 2.5, 3.0]
+
+
+def test_num2bounds():
+    calendar = cftime_rs.PyCFCalendar.from_str("standard")
+    bounds = cftime_rs.num2bounds([[0, 1], [1, 2]], "days since 1970-01-01", "standard")
+    assert bounds == [
+        (
+            cftime_rs.PyCFDatetime.from_ymd(1970, 1, 1, calendar),
+            cftime_rs.PyCFDatetime.from_ymd(1970, 1, 2, calendar),
+        ),
+        (
+            cftime_rs.PyCFDatetime.from_ymd(1970, 1, 2, calendar),
+            cftime_rs.PyCFDatetime.from_ymd(1970, 1, 3, calendar),
+        ),
+    ]
+    with pytest.raises(ValueError):
+        cftime_rs.num2bounds([[0, 1, 2]], "days since 1970-01-01", "standard")
+
+
 def test_num2pydate():
     arr = [1, 2, 3]
     units = "days since 1970-01-01"
@@ -59,6 +170,37 @@ def test_num2pydate():
         dt.datetime(1970, 1, 4),
     ]
     assert result == expected
+    # The year does not wrap around to 2000
+    with pytest.raises(ValueError):
+        cftime_rs.num2pydate([2**32], "common_years since 2000-01-01", "365_day")
+    calendar = cftime_rs.PyCFCalendar.from_str("365_day")
+    with pytest.raises(ValueError):
+        cftime_rs.PyCFDatetime.from_ymd(2**32 + 2000, 1, 1, calendar).to_pydatetime()
+
+
+def test_num2pydate_tz():
+    utc = dt.timezone.utc
+    offset = dt.timezone(dt.timedelta(hours=-6))
+    result = cftime_rs.num2pydate([1], "hours since 2000-01-01", "standard", tz="UTC")
+    assert result == [dt.datetime(2000, 1, 1, 1, tzinfo=utc)]
+    assert result[0].tzinfo == utc
+    units = "hours since 2000-01-01 00:00:00 -06:00"
+    result = cftime_rs.num2pydate([1], units, "standard", tz="units")
+    assert result[0] == dt.datetime(2000, 1, 1, 1, tzinfo=offset)
+    assert result[0].tzinfo == offset
+    result = cftime_rs.num2pydate([1], units, "standard", tz="UTC")
+    assert result[0] == dt.datetime(2000, 1, 1, 7, tzinfo=utc)
+    assert (result[0].hour, result[0].tzinfo) == (7, utc)
+    result = cftime_rs.num2pydate([1], units, "standard", from_timestamp=True, tz="UTC")
+    assert (result[0].hour, result[0].tzinfo) == (7, utc)
+    result = cftime_rs.num2pydate([1], units, "standard")
+    assert result[0] == dt.datetime(2000, 1, 1, 7)
+    with pytest.raises(ValueError):
+        cftime_rs.num2pydate([1], units, "standard", tz="nowhere")
+    calendar = cftime_rs.PyCFCalendar.from_str("standard")
+    datetime = cftime_rs.PyCFDatetime.from_ymd(2000, 1, 1, calendar)
+    assert datetime.to_pydatetime(tz="+05:30").hour == 5
+    assert datetime.to_pydatetime().tzinfo is None
 
 
 def test_num2date_for_float():
@@ -73,6 +215,14 @@ def test_num2date_for_float():
     assert [i.ymd_hms() for i in result] == [i.ymd_hms() for i in expected]
 
 
+def test_num2date_non_finite():
+    units = "days since 1970-01-01"
+    with pytest.raises(ValueError, match="index 1 : NaN"):
+        cftime_rs.num2date([0.0, float("nan")], units, "standard")
+    with pytest.raises(ValueError, match="index 0 : inf"):
+        cftime_rs.Converter(units, "noleap").num2date([float("inf")])
+
+
 def test_idempotence_of_num2pydate_then_pydate2num_for_float():
     dts = [
         dt.datetime(2000, 1, 1, 0, 0, 0),
@@ -120,3 +270,732 @@ def test_360_day_issue_impl_xarray():
 
     decoded_time = cftime_rs.num2pydate(encoded_time, units, calendar="360_day")
     assert decoded_time == times
+
+
+def test_interp_calendar():
+    day_360 = cftime_rs.PyCFCalendar.from_str("360_day")
+    standard = cftime_rs.PyCFCalendar.from_str("standard")
+    datetime = cftime_rs.PyCFDatetime.from_ymd(2000, 7, 1, day_360)
+    result = datetime.interp_calendar(standard)
+    assert result.ymd_hms() == (2000, 7, 2, 0, 0, 0)
+
+
+def test_replace():
+    cf_calendar = cftime_rs.PyCFCalendar.from_str("standard")
+    datetime = cftime_rs.PyCFDatetime.from_ymd_hms(2000, 1, 31, 12, 30, 15, cf_calendar)
+    assert datetime.replace(hour=6).ymd_hms() == (2000, 1, 31, 6, 30, 15)
+    assert datetime.replace(year=1999, month=12).ymd() == (1999, 12, 31)
+    with pytest.raises(ValueError):
+        datetime.replace(month=2)
+
+
+def test_timestamp_nanos():
+    cf_calendar = cftime_rs.PyCFCalendar.from_str("proleptic_gregorian")
+    datetime = cftime_rs.PyCFDatetime.from_timestamp_nanos(-2_500_000_000, cf_calendar)
+    assert datetime.ymd_hms() == (1969, 12, 31, 23, 59, 57)
+    assert datetime.nanoseconds() == 500_000_000
+    assert datetime.timestamp_nanos() == -2_500_000_000
+
+
+def test_timestamp_float():
+    cf_calendar = cftime_rs.PyCFCalendar.from_str("standard")
+    datetime = cftime_rs.PyCFDatetime.fromtimestamp(-2.5, cf_calendar)
+    assert datetime.ymd_hms() == (1969, 12, 31, 23, 59, 57)
+    assert datetime.nanoseconds() == 500_000_000
+    assert datetime.timestamp() == -2.5
+    expected = dt.datetime(2000, 1, 1, 0, 0, 1, 250000, tzinfo=dt.timezone.utc).timestamp()
+    datetime = cftime_rs.PyCFDatetime.fromtimestamp(expected, cf_calendar)
+    assert datetime.ymd_hms() == (2000, 1, 1, 0, 0, 1)
+    assert datetime.timestamp() == expected
+    with pytest.raises(ValueError):
+        cftime_rs.PyCFDatetime.fromtimestamp(float("nan"), cf_calendar)
+
+
+def test_isoformat():
+    cf_calendar = cftime_rs.PyCFCalendar.from_str("standard")
+    datetime = cftime_rs.PyCFDatetime.fromisoformat("2000-01-01T12:30:00.5+01:00", cf_calendar)
+    assert datetime.ymd_hms() == (2000, 1, 1, 11, 30, 0)
+    assert datetime.isoformat() == "2000-01-01T11:30:00.5+00:00"
+    assert datetime.isoformat(" ") == "2000-01-01 11:30:00.5+00:00"
+    expected = dt.datetime(2000, 1, 1, 11, 30, 0, 500000, tzinfo=dt.timezone.utc)
+    assert dt.datetime.fromisoformat(datetime.isoformat()) == expected
+    with pytest.raises(ValueError):
+        cftime_rs.PyCFDatetime.fromisoformat("2001-02-29", cf_calendar)
+    day_360 = cftime_rs.PyCFCalendar.from_str("360_day")
+    with pytest.raises(ValueError):
+        cftime_rs.PyCFDatetime.fromisoformat("2000-02-30", day_360)
+    datetime = cftime_rs.PyCFDatetime.fromisoformat("2000-02-30", day_360, force=True)
+    with pytest.raises(ValueError):
+        datetime.isoformat()
+    assert datetime.isoformat(force=True) == "2000-02-30T00:00:00+00:00"
+
+
+def test_day_month_year_navigation():
+    calendar = cftime_rs.PyCFCalendar.from_str("360_day")
+    datetime = cftime_rs.PyCFDatetime.from_ymd_hms(2000, 2, 15, 6, 30, 0, calendar)
+    assert datetime.start_of_month().ymd_hms() == (2000, 2, 1, 0, 0, 0)
+    assert datetime.end_of_month().ymd_hms() == (2000, 2, 30, 0, 0, 0)
+    assert datetime.start_of_year().ymd() == (2000, 1, 1)
+    assert datetime.end_of_year().ymd() == (2000, 12, 30)
+    assert datetime.succ_day().ymd_hms() == (2000, 2, 16, 6, 30, 0)
+    assert datetime.pred_day().ymd_hms() == (2000, 2, 14, 6, 30, 0)
+    standard = cftime_rs.PyCFCalendar.from_str("standard")
+    datetime = cftime_rs.PyCFDatetime.from_ymd(1582, 10, 4, standard)
+    assert datetime.succ_day().ymd() == (1582, 10, 15)
+
+
+def test_date2index():
+    units = "days since 1970-01-01"
+    calendar = "standard"
+    cf_calendar = cftime_rs.PyCFCalendar.from_str(calendar)
+    numbers = [0, 1, 2, 4, 8]
+    date = cftime_rs.PyCFDatetime.from_ymd(1970, 1, 3, cf_calendar)
+    assert cftime_rs.date2index(date, numbers, units, calendar) == 2
+
+    date = cftime_rs.PyCFDatetime.from_ymd_hms(1970, 1, 6, 12, 0, 0, cf_calendar)
+    assert cftime_rs.date2index([date], numbers, units, calendar, select="before") == [3]
+    assert cftime_rs.date2index(date, numbers, units, calendar, select="after") == 4
+    assert cftime_rs.date2index(date, numbers, units, calendar, select="nearest") == 3
+    with pytest.raises(ValueError):
+        cftime_rs.date2index(date, numbers, units, calendar)
+
+
+def test_build_info():
+    info = cftime_rs.build_info()
+    assert info["version"] == cftime_rs.__version__
+    assert info["rustc_version"].startswith("rustc")
+    assert isinstance(info["features"], list)
+    assert info["abi3"] == ("abi3" in info["features"])
+    assert cftime_rs.is_accelerated() is True
+
+
+def test_add_months():
+    calendar = cftime_rs.PyCFCalendar.from_str("standard")
+    datetime = cftime_rs.PyCFDatetime.from_ymd(2000, 1, 31, calendar)
+    one_month = cftime_rs.PyCFDuration.from_months(1, calendar)
+    assert (datetime + one_month).ymd() == (2000, 2, 29)
+    # A month is exactly a twelfth of the year of udunits
+    assert abs(one_month.to_approximate_seconds().num_seconds() - 31556925.9747 / 12) < 1e-6
+
+
+def test_num2date_absolute_time():
+    datetimes = cftime_rs.num2date(
+        [20000115.5, 20000116.0], "day as %Y%m%d.%f", "standard", absolute_time=True
+    )
+    assert datetimes[0].ymd_hms() == (2000, 1, 15, 12, 0, 0)
+    assert datetimes[1].ymd_hms() == (2000, 1, 16, 0, 0, 0)
+    with pytest.raises(ValueError):
+        cftime_rs.num2date([20000115.5], "day as %Y%m%d.%f", "standard")
+
+
+def test_pickle():
+    calendar = cftime_rs.PyCFCalendar.from_str("360_day")
+    datetime = cftime_rs.PyCFDatetime.from_timestamp_nanos(1_000_000_001, calendar)
+    assert pickle.loads(pickle.dumps(datetime)) == datetime
+    duration = cftime_rs.PyCFDuration(86400, 5, calendar, months=2)
+    unpickled = pickle.loads(pickle.dumps(duration))
+    assert unpickled.num_seconds() == duration.num_seconds()
+    assert (datetime + unpickled) == (datetime + duration)
+    unpickled = pickle.loads(pickle.dumps(calendar))
+    assert cftime_rs.PyCFDatetime.from_ymd(2000, 2, 30, unpickled).ymd() == (2000, 2, 30)
+
+
+def test_duration_sign():
+    calendar = cftime_rs.PyCFCalendar.from_str("standard")
+    duration = cftime_rs.PyCFDuration.from_milliseconds(-1_500, calendar)
+    assert duration.whole_seconds() == -1
+    assert duration.subsec_nanos() == -500_000_000
+    assert duration.signum() == -1
+    assert duration.is_negative()
+    assert abs(duration).whole_seconds() == 1
+    assert cftime_rs.PyCFDuration.from_seconds(0, calendar).is_zero()
+
+
+def test_duration_iso8601():
+    calendar = cftime_rs.PyCFCalendar.from_str("standard")
+    duration = -cftime_rs.PyCFDuration.from_days(1, calendar)
+    assert str(duration) == "-P0Y0M1DT0H0M0S"
+    parsed = cftime_rs.PyCFDuration.from_iso8601(str(duration), calendar)
+    assert parsed.num_days() == -1
+    with pytest.raises(ValueError):
+        cftime_rs.PyCFDuration.from_iso8601("P1H", calendar)
+
+
+def test_duration_exact_components():
+    calendar = cftime_rs.PyCFCalendar.from_str("360_day")
+    duration = cftime_rs.PyCFDuration(-2, 500_000_000, calendar, months=3)
+    assert (duration.months, duration.seconds, duration.nanoseconds) == (3, -2, 500_000_000)
+    assert duration.calendar == calendar
+    assert duration.exact_components() == (3, -2, 500_000_000)
+    months, seconds, nanoseconds = duration.exact_components()
+    rebuilt = cftime_rs.PyCFDuration(seconds, nanoseconds, duration.calendar, months)
+    assert rebuilt.exact_components() == duration.exact_components()
+    # Exact beyond the precision of the floats
+    duration = cftime_rs.PyCFDuration.from_nanoseconds(2**62 + 1, calendar)
+    seconds, nanoseconds = duration.seconds, duration.nanoseconds
+    assert seconds * 1_000_000_000 + nanoseconds == 2**62 + 1
+    assert cftime_rs.PyCFDuration.from_milliseconds(-1500, calendar).exact_components() == (
+        0,
+        -2,
+        500_000_000,
+    )
+
+
+def test_duration_overflow():
+    calendar = cftime_rs.PyCFCalendar.from_str("standard")
+    days = (2**63 - 1) // 86400
+    assert cftime_rs.PyCFDuration.from_days(days, calendar).whole_seconds() == days * 86400
+    with pytest.raises(ValueError):
+        cftime_rs.PyCFDuration.from_days(days + 1, calendar)
+    with pytest.raises(ValueError):
+        cftime_rs.PyCFDuration.from_years(2**62, calendar)
+    with pytest.raises(ValueError):
+        cftime_rs.PyCFDuration.from_hours(-(2**63), calendar)
+
+
+def test_units_multiplier_overflow():
+    units = f"{2**63 - 1} days since 2000-01-01"
+    calendar = cftime_rs.PyCFCalendar.from_str("standard")
+    date = cftime_rs.PyCFDatetime.from_ymd(2000, 1, 1, calendar)
+    with pytest.raises(ValueError):
+        cftime_rs.num2date([1], units, "standard")
+    with pytest.raises(ValueError):
+        cftime_rs.date2num([date], units, "standard", dtype="i64")
+    with pytest.raises(ValueError):
+        cftime_rs.rebase_units([1], units, "days since 2000-01-01", "standard")
+    with pytest.raises(ValueError):
+        cftime_rs.date2index(date, [0, 1], units, "standard")
+    with pytest.raises(ValueError):
+        cftime_rs.Converter(units, "standard")
+
+
+def test_calendar():
+    calendar = cftime_rs.PyCFCalendar.from_str("365_day")
+    assert calendar.name == "noleap"
+    assert calendar == cftime_rs.PyCFCalendar.from_str("noleap")
+    assert calendar != cftime_rs.PyCFCalendar.from_str("standard")
+    assert repr(calendar) == "PyCFCalendar(noleap)"
+    assert calendar.units_seconds_per_year() == 365 * 86400
+    assert len({calendar, cftime_rs.PyCFCalendar.from_str("noleap")}) == 1
+
+
+def test_calendar_capabilities():
+    julian = cftime_rs.PyCFCalendar.from_str("julian")
+    assert julian.is_real_world()
+    assert julian.has_leap_years()
+    assert julian.fixed_year_length() is None
+    assert not julian.convertible_to_unix()
+    day360 = cftime_rs.PyCFCalendar.from_str("360_day")
+    assert not day360.is_real_world()
+    assert day360.fixed_year_length() == 360
+    assert cftime_rs.PyCFCalendar.from_str("standard").convertible_to_unix()
+
+
+def test_from_timestamps():
+    calendar = cftime_rs.PyCFCalendar.from_str("noleap")
+    datetimes = cftime_rs.PyCFDatetime.from_timestamps([0, 86400], calendar)
+    assert datetimes[1].ymd() == (1970, 1, 2)
+    assert cftime_rs.PyCFDatetime.to_timestamps(datetimes) == [0, 86400]
+    datetimes = cftime_rs.PyCFDatetime.from_timestamps_nanos([1_500_000_000], calendar)
+    assert cftime_rs.PyCFDatetime.to_timestamps_nanos(datetimes) == [1_500_000_000]
+
+
+def test_from_components():
+    calendar = cftime_rs.PyCFCalendar.from_str("360_day")
+    datetimes = cftime_rs.PyCFDatetime.from_components(
+        [2000, 2000], [2, 2], [29, 30], [0, 6], [0, 30], [0, 1.5], calendar
+    )
+    assert [d.ymd_hms() for d in datetimes] == [(2000, 2, 29, 0, 0, 0), (2000, 2, 30, 6, 30, 1)]
+    assert datetimes[1].nanoseconds() == 500_000_000
+    with pytest.raises(ValueError, match="At index 1"):
+        cftime_rs.PyCFDatetime.from_components(
+            [2000, 2000], [1, 13], [1, 1], [0, 0], [0, 0], [0, 0], calendar
+        )
+    with pytest.raises(ValueError, match="Different lengths"):
+        cftime_rs.PyCFDatetime.from_components([2000], [1], [1, 2], [0], [0], [0], calendar)
+
+
+def test_forecast_valid_times():
+    valid_times = cftime_rs.forecast_valid_times(
+        [0, 12], "hours since 2000-01-01", [0, 6.5], "hours", "standard"
+    )
+    assert len(valid_times) == 2
+    assert [len(row) for row in valid_times] == [2, 2]
+    assert valid_times[1][1].ymd_hms() == (2000, 1, 1, 18, 30, 0)
+
+
+def test_converter():
+    converter = cftime_rs.Converter("hours since 2000-01-01", "noleap")
+    assert converter.units == "hours since 2000-01-01"
+    assert converter.calendar.name == "noleap"
+    for numbers in ([0, 6, 12], [1.5, -24]):
+        datetimes = converter.num2date(numbers)
+        assert datetimes == cftime_rs.num2date(numbers, "hours since 2000-01-01", "noleap")
+        assert converter.date2num(datetimes, "f64") == numbers
+    datetimes = converter.num2date([1.5, -24])
+    assert converter.date2num(datetimes, "i64") == [1, -24]
+    with pytest.raises(ValueError):
+        converter.date2num(datetimes, "u32")
+    with pytest.raises(ValueError):
+        converter.date2num(datetimes, "complex")
+    with pytest.raises(ValueError):
+        cftime_rs.Converter("hours after 2000-01-01", "noleap")
+    assert repr(converter) == "Converter(hours since 2000-01-01, noleap)"
+    # 1 BC is the year 0 of the decoded datetimes
+    converter = cftime_rs.Converter("days since -1-12-31", "julian", has_year_zero=False)
+    assert [d.ymd() for d in converter.num2date([0, 1])] == [(0, 12, 31), (1, 1, 1)]
+    with pytest.raises(ValueError):
+        cftime_rs.Converter("days since 0000-12-31", "julian", has_year_zero=False)
+
+
+def test_time_units_converter():
+    day = 86400
+    months = cftime_rs.TimeUnitsConverter("month", "360_day")
+    assert months.period == "month"
+    assert months.calendar.name == "360_day"
+    assert repr(months) == "TimeUnitsConverter(month, 360_day)"
+    axis = cftime_rs.num2date(list(range(29, 61)), "days since 2000-01-01", "360_day")
+    assert months.period_start(axis[5]).ymd_hms() == (2000, 2, 1, 0, 0, 0)
+    assert months.period_seconds(axis[5]) == 30 * day
+    assert months.weights(axis) == [30 * day] * len(axis)
+    bins = months.bins(axis)
+    assert [(start.ymd(), end.ymd(), first, stop) for start, end, first, stop, _ in bins] == [
+        ((2000, 1, 1), (2000, 2, 1), 0, 1),
+        ((2000, 2, 1), (2000, 3, 1), 1, 31),
+        ((2000, 3, 1), (2000, 4, 1), 31, 32),
+    ]
+    noleap = cftime_rs.TimeUnitsConverter("year", "noleap")
+    monthly = cftime_rs.PyCFDatetime.from_components(
+        [2000 + m // 12 for m in range(24)],
+        [m % 12 + 1 for m in range(24)],
+        [15] * 24,
+        [0] * 24,
+        [0] * 24,
+        [0.0] * 24,
+        cftime_rs.PyCFCalendar.from_str("noleap"),
+    )
+    weights = cftime_rs.TimeUnitsConverter("month", "noleap").weights(monthly)
+    assert weights[1] == 28 * day
+    for _, _, first, stop, seconds in noleap.bins(monthly):
+        assert sum(weights[first:stop]) == seconds == 365 * day
+    with pytest.raises(ValueError):
+        months.bins(axis[::-1])
+    with pytest.raises(ValueError):
+        noleap.weights(axis)
+    with pytest.raises(ValueError):
+        cftime_rs.TimeUnitsConverter("fortnight", "noleap")
+
+
+def test_parse_units():
+    unit, origin, tz = cftime_rs.parse_units("days since 2000-01-01")
+    assert unit == cftime_rs.UNIT_DAY == "days"
+    assert origin.ymd_hms() == (2000, 1, 1, 0, 0, 0)
+    assert tz is None
+    unit, origin, tz = cftime_rs.parse_units(
+        "3 hrs since 2000-02-30 06:30:00 -06:00", "360_day"
+    )
+    assert unit == cftime_rs.UNIT_HOUR
+    # The reference date is in UTC
+    assert origin.ymd_hms() == (2000, 2, 30, 12, 30, 0)
+    assert tz == "-06:00"
+    assert cftime_rs.UNIT_YEAR == "common_years"
+    with pytest.raises(ValueError):
+        cftime_rs.parse_units("days after 2000-01-01")
+
+
+def test_infer_freq():
+    datetimes = cftime_rs.num2date([0, 3, 6, 12, 15], "hours since 2000-01-01", "standard")
+    step, anomalies = cftime_rs.infer_freq(datetimes)
+    assert step.num_hours() == 3
+    assert anomalies == [3]
+    datetimes = cftime_rs.num2date([0, 1, 2], "months since 2000-01-01", "360_day")
+    step, anomalies = cftime_rs.infer_freq(datetimes)
+    assert step.num_months() == 1
+    assert anomalies == []
+    assert cftime_rs.infer_freq(datetimes[:1]) is None
+
+
+def test_is_valid_date():
+    calendar = cftime_rs.PyCFCalendar.from_str("standard")
+    assert calendar.is_valid_date(2000, 2, 29)
+    assert not calendar.is_valid_date(2001, 2, 29)
+    assert not calendar.is_valid_date(1582, 10, 10)
+    noleap = cftime_rs.PyCFCalendar.from_str("noleap")
+    assert noleap.are_valid_dates([2000, 2000], [2, 12], [29, 31]) == [False, True]
+    with pytest.raises(ValueError):
+        noleap.are_valid_dates([2000], [1, 2], [1])
+
+
+def test_num2date_reference_in_gregorian_gap():
+    units = "days since 1582-10-10"
+    with pytest.raises(ValueError, match="proleptic_gregorian"):
+        cftime_rs.num2date([0, 1], units, "standard")
+    dates = cftime_rs.num2date([0, 1], units, "proleptic_gregorian")
+    assert [d.ymd() for d in dates] == [(1582, 10, 10), (1582, 10, 11)]
+
+
+def test_num2date_buffer():
+    units = "hours since 2000-01-01"
+    expected = cftime_rs.num2date([0, 1.5, 36], units, "noleap")
+    for numbers in (
+        array.array("d", [0, 1.5, 36]),
+        array.array("f", [0, 1.5, 36]),
+        memoryview(array.array("d", [0, 1.5, 36])),
+    ):
+        assert cftime_rs.num2date(numbers, units, "noleap") == expected
+    expected = cftime_rs.num2date([0, -1, 2**40], units, "noleap")
+    for typecode in ("q", "l"):
+        numbers = array.array(typecode, [0, -1, 2**40])
+        assert cftime_rs.num2date(numbers, units, "noleap") == expected
+    numbers = array.array("i", [0, -1, 7])
+    assert cftime_rs.num2date(numbers, units, "noleap") == cftime_rs.num2date(
+        [0, -1, 7], units, "noleap"
+    )
+    absolute = cftime_rs.num2date(
+        array.array("d", [20000115.5]), "day as %Y%m%d.%f", "standard", absolute_time=True
+    )
+    assert absolute[0].ymd_hms() == (2000, 1, 15, 12, 0, 0)
+    with pytest.raises(ValueError):
+        cftime_rs.num2date(array.array("d", [float("nan")]), units, "noleap")
+
+
+class BufferOnly(array.array):
+    """An array whose numbers can only be read through the buffer protocol"""
+
+    def __len__(self):
+        raise TypeError("BufferOnly is not a sequence")
+
+    def __iter__(self):
+        raise TypeError("BufferOnly is not a sequence")
+
+
+@pytest.mark.skipif(
+    cftime_rs.build_info()["abi3"],
+    reason="The buffer protocol is not part of the stable ABI before python 3.11",
+)
+def test_num2date_buffer_is_read_in_one_copy():
+    units = "hours since 2000-01-01"
+    numbers = BufferOnly("d", [0, 1.5, 36])
+    with pytest.raises(TypeError):
+        list(numbers)
+    dates = cftime_rs.num2date(numbers, units, "noleap")
+    assert dates == cftime_rs.num2date([0, 1.5, 36], units, "noleap")
+
+
+def test_num2date_nested():
+    units = "days since 2000-01-01"
+    nested = cftime_rs.num2date([[0, 1], (2, 3.5), [[4]]], units, "standard")
+    assert [len(row) for row in nested] == [2, 2, 1]
+    assert isinstance(nested[1], list)
+    assert nested[1][1] == cftime_rs.num2date([3.5], units, "standard")[0]
+    assert nested[2][0][0].ymd() == (2000, 1, 5)
+    assert cftime_rs.num2date([[]], units, "standard") == [[]]
+    with pytest.raises(ValueError):
+        cftime_rs.num2date([[0, "a"]], units, "standard")
+
+
+def test_rebase_units():
+    numbers = [876_576, 876_600, 876_612]
+    rebased = cftime_rs.rebase_units(
+        numbers, "hours since 1900-01-01", "days since 2000-01-01", "standard"
+    )
+    assert rebased == [0, 1, 1]
+    assert all(isinstance(number, int) for number in rebased)
+    rebased = cftime_rs.convert.rebase_units(
+        [876_612.0], "hours since 1900-01-01", "days since 2000-01-01", "standard"
+    )
+    assert rebased == [1.5]
+    datetimes = cftime_rs.num2date(numbers, "hours since 1900-01-01", "noleap")
+    assert cftime_rs.rebase_units(
+        numbers, "hours since 1900-01-01", "days since 2000-01-01", "noleap"
+    ) == cftime_rs.date2num(datetimes, "days since 2000-01-01", "noleap", dtype="int")
+    with pytest.raises(ValueError):
+        cftime_rs.rebase_units([float("nan")], "days since 2000-01-01", "days since 1900-01-01", "standard")
+
+
+def test_datetime_components():
+    datetimes = cftime_rs.num2date([0, 25.5, 24 * 366], "hours since 2000-01-01", "standard")
+    components = cftime_rs.datetime_components(datetimes)
+    assert components == {
+        "year": [2000, 2000, 2001],
+        "month": [1, 1, 1],
+        "day": [1, 2, 1],
+        "hour": [0, 1, 0],
+        "minute": [0, 30, 0],
+        "second": [0, 0, 0],
+        "nanosecond": [0, 0, 0],
+    }
+    assert cftime_rs.calendars.datetime_components([])["year"] == []
+    noleap = cftime_rs.num2date([0], "days since 2000-01-01", "noleap")
+    with pytest.raises(ValueError):
+        cftime_rs.datetime_components(datetimes + noleap)
+
+
+def test_ordinal():
+    calendar = cftime_rs.PyCFCalendar.from_str("standard")
+    datetime = cftime_rs.PyCFDatetime.from_ymd_hms(2000, 1, 1, 18, 0, 0, calendar)
+    assert datetime.toordinal() == 2451545
+    assert datetime.toordinal(fractional=True) == 2451545.25
+    assert cftime_rs.PyCFDatetime.fromordinal(2451545.25, calendar) == datetime
+
+
+def test_julian_century():
+    calendar = cftime_rs.PyCFCalendar.from_str("standard")
+    epoch = cftime_rs.PyCFDatetime.j2000(calendar)
+    assert epoch.toordinal(fractional=True) == 2451545.0
+    assert epoch.julian_century() == 0.0
+    datetime = cftime_rs.PyCFDatetime.from_ymd(2025, 1, 1, calendar)
+    assert datetime.days_since(epoch) == 9131.5
+    assert datetime.julian_century() == 9131.5 / 36525.0
+    day_360 = cftime_rs.PyCFCalendar.from_str("360_day")
+    datetime = cftime_rs.PyCFDatetime.from_ymd_hms(2050, 1, 1, 12, 0, 0, day_360)
+    assert datetime.julian_century() == 0.5
+    origin = cftime_rs.PyCFDatetime.from_ymd(2000, 1, 1, day_360)
+    assert datetime.julian_centuries_since(origin) == (50 * 360 + 0.5) / 36000
+    with pytest.raises(ValueError):
+        datetime.days_since(epoch)
+
+
+def test_elapsed_since():
+    calendar = cftime_rs.PyCFCalendar.from_str("noleap")
+    origin = cftime_rs.PyCFDatetime.from_ymd(2000, 1, 1, calendar)
+    datetime = cftime_rs.PyCFDatetime.from_ymd_hms(2000, 1, 2, 12, 0, 0, calendar)
+    assert datetime.elapsed_since(origin, "hours") == 36.0
+    assert datetime.elapsed_since(origin, "days") == 1.5
+    assert origin.elapsed_since(datetime, "minutes") == -2160.0
+    assert datetime.elapsed_since(origin, "seconds") == cftime_rs.date2num(
+        [datetime], "seconds since 2000-01-01", "noleap", dtype="f64"
+    )[0]
+    with pytest.raises(ValueError):
+        datetime.elapsed_since(origin, "fortnights")
+    standard = cftime_rs.PyCFCalendar.from_str("standard")
+    with pytest.raises(ValueError):
+        datetime.elapsed_since(cftime_rs.PyCFDatetime.from_ymd(2000, 1, 1, standard), "days")
+    with pytest.raises(ValueError):
+        cftime_rs.PyCFDatetime.max(calendar).elapsed_since(
+            cftime_rs.PyCFDatetime.min(calendar), "days"
+        )
+
+
+def test_min_max():
+    calendar = cftime_rs.PyCFCalendar.from_str("proleptic_gregorian")
+    earliest = cftime_rs.PyCFDatetime.min(calendar)
+    latest = cftime_rs.PyCFDatetime.max(calendar)
+    assert earliest.ymd()[0] < -291_000_000_000
+    assert latest.ymd()[0] > 291_000_000_000
+    assert cftime_rs.PyCFDatetime.clamp_to_valid_range(2**100, calendar) == latest
+    assert cftime_rs.PyCFDatetime.clamp_to_valid_range(-(2**100), calendar) == earliest
+    clamped = cftime_rs.PyCFDatetime.clamp_to_valid_range(1_500_000_000, calendar)
+    assert clamped.timestamp_nanos() == 1_500_000_000
+
+
+def test_days_in_month_and_year():
+    calendar = cftime_rs.PyCFCalendar.from_str("julian")
+    datetime = cftime_rs.PyCFDatetime.from_ymd(1900, 2, 1, calendar)
+    assert datetime.is_leap_year()
+    assert datetime.days_in_month() == 29
+    assert datetime.days_in_year() == 366
+
+
+def test_weekday_and_month_names():
+    calendar = cftime_rs.PyCFCalendar.from_str("standard")
+    datetime = cftime_rs.PyCFDatetime.from_ymd(2000, 1, 1, calendar)
+    assert datetime.weekday() == dt.date(2000, 1, 1).weekday() == 5
+    assert datetime.weekday_name() == "Saturday"
+    assert datetime.month_name() == "January"
+    weekdays = ["lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi", "dimanche"]
+    assert datetime.weekday_name(weekdays) == "samedi"
+    months = [str(month) for month in range(1, 13)]
+    assert datetime.month_name(months) == "1"
+    with pytest.raises(ValueError):
+        datetime.month_name(months[:11])
+
+
+def test_date_and_time_of_day():
+    calendar = cftime_rs.PyCFCalendar.from_str("360_day")
+    datetime = cftime_rs.PyCFDatetime.from_ymd_hms(2000, 2, 30, 6, 30, 15, calendar)
+    assert datetime.date().ymd_hms() == (2000, 2, 30, 0, 0, 0)
+    assert datetime.time_of_day().num_seconds() == 23415
+    assert datetime.seconds_since_midnight() == 23415
+    with pytest.raises(ValueError):
+        cftime_rs.PyCFDatetime.min(calendar).date()
+
+
+def test_validate_time_coordinate():
+    assert cftime_rs.validate_time_coordinate([0, 1, 2], "days since 2000-01-01", "standard") == []
+    issues = cftime_rs.validate_time_coordinate(
+        [0.0, 2.0, 1.0, 1.0], "days since 2000-01-01", "standard"
+    )
+    assert [(issue["kind"], issue["index"]) for issue in issues] == [
+        ("non monotonic", 2),
+        ("duplicate time", 3),
+    ]
+
+
+def test_change_calendar_many():
+    standard = cftime_rs.PyCFCalendar.from_str("standard")
+    datetimes = [
+        cftime_rs.PyCFDatetime(2000, 2, 28, 0, 0, 0, standard),
+        cftime_rs.PyCFDatetime(2000, 2, 29, 1, 0, 0, standard),
+    ]
+    noleap = cftime_rs.PyCFCalendar.from_str("noleap")
+    converted, issues = cftime_rs.PyCFDatetime.change_calendar_many(datetimes, noleap)
+    assert [d.ymd() for d in converted] == [(2000, 2, 28)]
+    assert [(i["index"], i["original"], i["action"]) for i in issues] == [
+        (1, (2000, 2, 29, 1, 0, 0), "dropped")
+    ]
+    converted, issues = cftime_rs.PyCFDatetime.change_calendar_many(datetimes, noleap, "clamp")
+    assert [d.ymd_hms() for d in converted] == [(2000, 2, 28, 0, 0, 0), (2000, 2, 28, 1, 0, 0)]
+    assert issues[0]["action"] == "clamped"
+    with pytest.raises(ValueError):
+        cftime_rs.PyCFDatetime.change_calendar_many(datetimes, noleap, "nearest")
+
+
+def test_compat_num2date():
+    num2date = cftime_rs.compat.num2date
+    units = "days since 2000-01-01"
+    result = num2date([0, 1.5], units)
+    assert all(isinstance(date, cftime_rs.PyCFDatetime) for date in result)
+    assert [date.ymd_hms() for date in result] == [(2000, 1, 1, 0, 0, 0), (2000, 1, 2, 12, 0, 0)]
+    assert num2date(1, units, only_use_cftime_datetimes=False) == dt.datetime(2000, 1, 2)
+    # Python datetimes only when every date is in the gregorian calendar
+    assert isinstance(
+        num2date([0], units, "noleap", only_use_cftime_datetimes=False)[0], cftime_rs.PyCFDatetime
+    )
+    assert isinstance(
+        num2date([0], "days since 1582-10-04", "standard", only_use_cftime_datetimes=False)[0],
+        cftime_rs.PyCFDatetime,
+    )
+    python_only = {"only_use_cftime_datetimes": False, "only_use_python_datetimes": True}
+    assert num2date([0], units, "proleptic_gregorian", **python_only) == [dt.datetime(2000, 1, 1)]
+    with pytest.raises(ValueError):
+        num2date([0], units, "360_day", **python_only)
+    with pytest.raises(ValueError):
+        num2date([0], units, only_use_python_datetimes=True)
+    # The reference year follows has_year_zero but the decoded years are astronomical
+    assert num2date(1, "days since -1-12-31", "julian").ymd() == (1, 1, 1)
+    with pytest.raises(ValueError):
+        num2date(0, "days since -1-12-31", "julian")
+    with pytest.raises(ValueError):
+        num2date([0], "days since 0000-06-01", "julian")
+    assert num2date(0, "days since 0000-06-01", "julian", has_year_zero=True).ymd() == (0, 6, 1)
+    assert num2date(0, "days since 0000-06-01", "noleap").ymd() == (0, 6, 1)
+
+
+def _assert_same_parameters(qualname, node, signature):
+    if signature is None or any(
+        p.kind in (p.VAR_POSITIONAL, p.VAR_KEYWORD) for p in signature.parameters.values()
+    ):
+        return
+    runtime = [p for p in signature.parameters.values() if p.name not in ("self", "cls")]
+    arguments = node.args.posonlyargs + node.args.args
+    defaults = dict(
+        zip([a.arg for a in arguments[len(arguments) - len(node.args.defaults) :]], node.args.defaults)
+    )
+    stub = [a.arg for a in arguments + node.args.kwonlyargs if a.arg not in ("self", "cls")]
+    assert [p.name for p in runtime] == stub, qualname
+    for parameter in runtime:
+        # pyo3 writes `...` for the defaults it can not render
+        if parameter.default not in (parameter.empty, Ellipsis):
+            assert parameter.name in defaults, f"{qualname}({parameter.name})"
+            assert ast.literal_eval(defaults[parameter.name]) == parameter.default, qualname
+
+
+def test_stub_matches_bindings():
+    from cftime_rs import cftime_rs as module
+    from cftime_rs.stubgen import generate_stubs, iter_class_members, iter_members
+
+    path = pathlib.Path(cftime_rs.__file__).parent / "cftime_rs.pyi"
+    tree = ast.parse(path.read_text())
+    definitions = {
+        node.name: node for node in tree.body if isinstance(node, (ast.FunctionDef, ast.ClassDef))
+    }
+    attributes = {
+        node.target.id
+        for node in tree.body
+        if isinstance(node, ast.AnnAssign) and isinstance(node.target, ast.Name)
+    }
+    assert set(cftime_rs.__all__) == set(module.__all__)
+    for name, member in iter_members(module):
+        if inspect.isclass(member):
+            assert name in definitions, name
+            methods = {
+                node.name: node
+                for node in definitions[name].body
+                if isinstance(node, ast.FunctionDef)
+            }
+            for member_name, _, signature in iter_class_members(member):
+                # Slots such as __richcmp__ expand to several dunder methods
+                if member_name.startswith("__") and member_name != "__init__":
+                    continue
+                qualname = f"{name}.{member_name}"
+                assert member_name in methods, qualname
+                _assert_same_parameters(qualname, methods[member_name], signature)
+        elif callable(member):
+            assert name in definitions, name
+            _assert_same_parameters(name, definitions[name], inspect.signature(member))
+        else:
+            assert name in attributes, name
+    # The generated stub is valid python
+    ast.parse(generate_stubs())
+
+
+def test_encode_for_netcdf():
+    calendar = cftime_rs.PyCFCalendar.from_str("noleap")
+    datetimes = [
+        cftime_rs.PyCFDatetime.from_ymd_hms(2000, 1, 2, 12, 0, 0, calendar),
+        cftime_rs.PyCFDatetime.from_ymd_hms(2000, 1, 1, 6, 0, 0, calendar),
+    ]
+    assert cftime_rs.encode_for_netcdf(datetimes) == {
+        "values": [129600, 21600],
+        "units": "seconds since 2000-01-01 00:00:00",
+        "calendar": "noleap",
+        "dtype": "int64",
+    }
+    encoded = cftime_rs.convert.encode_for_netcdf(datetimes, "double_days")
+    assert encoded["values"] == [1.5, 0.25]
+    assert encoded["dtype"] == "float64"
+    with pytest.raises(ValueError, match="policy"):
+        cftime_rs.encode_for_netcdf(datetimes, "float")
+
+
+def test_submodules():
+    import cftime_rs.units
+    from cftime_rs.convert import num2date
+
+    assert num2date is cftime_rs.num2date
+    assert cftime_rs.units.parse_units is cftime_rs.parse_units
+    directory = pathlib.Path(cftime_rs.__file__).parent
+    exported = set()
+    for submodule in (cftime_rs.calendars, cftime_rs.convert, cftime_rs.units):
+        name = submodule.__name__.split(".")[-1]
+        tree = ast.parse((directory / f"{name}.pyi").read_text())
+        (stub_all,) = [
+            ast.literal_eval(node.value)
+            for node in tree.body
+            if isinstance(node, ast.Assign) and node.targets[0].id == "__all__"
+        ]
+        assert stub_all == submodule.__all__, name
+        for member in submodule.__all__:
+            assert getattr(submodule, member) is getattr(cftime_rs, member), member
+        exported.update(submodule.__all__)
+    # Only the build information is not in a submodule
+    assert set(cftime_rs.__all__) - exported == {"build_info", "is_accelerated", "__version__"}
+
+
+def test_merge_time_axes():
+    first = cftime_rs.num2date([0, 1, 2], "days since 2000-01-01", "noleap")
+    second = cftime_rs.num2date([0, 1], "days since 2000-01-03", "noleap")
+    merged = cftime_rs.calendars.merge_time_axes([first, second], require_monotonic=True)
+    assert merged["datetimes"] == first + second[1:]
+    assert merged["sources"] == [(0, 0), (0, 1), (0, 2), (1, 1)]
+    assert merged["duplicates"] == [((0, 2), (1, 0))]
+    assert merged["overlaps"] == [(0, 1)]
+    merged = cftime_rs.merge_time_axes([first, second], duplicates="last")
+    assert merged["sources"][2] == (1, 0)
+    with pytest.raises(ValueError, match="already at index 2 of axis 0"):
+        cftime_rs.merge_time_axes([first, second], duplicates="error")
+    with pytest.raises(ValueError, match="not sorted"):
+        cftime_rs.merge_time_axes([second, first], require_monotonic=True)
+    with pytest.raises(ValueError):
+        cftime_rs.merge_time_axes([first], duplicates="newest")
@@ -0,0 +1,29 @@
+"""Parsing of the CF units, also exported by ``cftime_rs``."""
+
+from cftime_rs.cftime_rs import (
+    UNIT_DAY as UNIT_DAY,
+    UNIT_HOUR as UNIT_HOUR,
+    UNIT_MICROSECOND as UNIT_MICROSECOND,
+    UNIT_MILLISECOND as UNIT_MILLISECOND,
+    UNIT_MINUTE as UNIT_MINUTE,
+    UNIT_MONTH as UNIT_MONTH,
+    UNIT_NANOSECOND as UNIT_NANOSECOND,
+    UNIT_SECOND as UNIT_SECOND,
+    UNIT_WEEK as UNIT_WEEK,
+    UNIT_YEAR as UNIT_YEAR,
+    parse_units as parse_units,
+)
+
+__all__ = [
+    "parse_units",
+    "UNIT_YEAR",
+    "UNIT_MONTH",
+    "UNIT_WEEK",
+    "UNIT_DAY",
+    "UNIT_HOUR",
+    "UNIT_MINUTE",
+    "UNIT_SECOND",
+    "UNIT_MILLISECOND",
+    "UNIT_MICROSECOND",
+    "UNIT_NANOSECOND",
+]
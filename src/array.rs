@@ -0,0 +1,344 @@
+//! Module that looks up dates in a decoded time axis.
+//!
+//! [CFTimeArray] keeps the datetimes of a time coordinate, e.g. decoded with
+//! [CFTimeArray::decode], and finds indices with a binary search such as `numpy.searchsorted`,
+//! so subsetting a variable by a range of dates does not scan the whole axis.
+//!
+//! ```rust
+//! use cftime_rs::array::CFTimeArray;
+//! use cftime_rs::calendars::Calendar;
+//! use cftime_rs::datetime::CFDatetime;
+//!
+//! let calendar = Calendar::NoLeap;
+//! let values: Vec<i64> = (0..365).collect();
+//! let axis = CFTimeArray::decode(&values, "days since 2001-01-01", calendar).unwrap();
+//! let start = CFDatetime::from_ymd(2001, 3, 1, calendar).unwrap();
+//! let end = CFDatetime::from_ymd(2001, 3, 31, calendar).unwrap();
+//! assert_eq!(axis.index_range(&start, &end).unwrap(), 59..90);
+//! ```
+
+use crate::calendars::Calendar;
+use crate::converter::CFConverter;
+use crate::datetime::CFDatetime;
+use crate::decoder::ScaleDuration;
+use alloc::{boxed::Box, format, string::ToString, vec::Vec};
+use core::ops::Range;
+
+/// Side of the equal datetimes returned by [CFTimeArray::search_sorted]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum Side {
+    /// Index of the first datetime greater or equal than the target
+    #[default]
+    Left,
+    /// Index of the first datetime strictly greater than the target
+    Right,
+}
+
+/// Datetimes of a time axis of a single calendar
+pub struct CFTimeArray {
+    datetimes: Vec<CFDatetime>,
+    calendar: Calendar,
+    /// Nanoseconds since epoch of the datetimes, compared by the lookups
+    timestamps_nanos: Vec<i128>,
+    /// Index of the first datetime before its predecessor, if any
+    unsorted_at: Option<usize>,
+}
+
+impl CFTimeArray {
+    /// Makes an array of the datetimes of the given calendar. The datetimes may be unsorted, but
+    /// only the arrays sorted in increasing order support the lookups.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the array or an error of type
+    /// `crate::errors::Error::DifferentCalendars` if a datetime is not of the calendar
+    pub fn new(datetimes: Vec<CFDatetime>, calendar: Calendar) -> crate::errors::Result<Self> {
+        if let Some(datetime) = datetimes.iter().find(|dt| dt.calendar() != calendar) {
+            return Err(crate::errors::Error::DifferentCalendars(
+                calendar.to_string(),
+                datetime.calendar().to_string(),
+            ));
+        }
+        let timestamps_nanos = CFDatetime::to_timestamps_nanos(&datetimes);
+        let unsorted_at = timestamps_nanos
+            .windows(2)
+            .position(|pair| pair[1] < pair[0])
+            .map(|index| index + 1);
+        Ok(Self {
+            datetimes,
+            calendar,
+            timestamps_nanos,
+            unsorted_at,
+        })
+    }
+    /// Decodes the values with the units and calendar into an array
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the array or an error if decoding fails, see
+    /// [crate::converter::CFConverter::decode]
+    pub fn decode<T: ScaleDuration>(
+        values: &[T],
+        units: &str,
+        calendar: Calendar,
+    ) -> crate::errors::Result<Self> {
+        let datetimes = CFConverter::new(units, calendar)?.decode(values)?;
+        Self::new(datetimes, calendar)
+    }
+    /// Builds an array from the components of the datetimes, e.g. the columns of a table of
+    /// station data. The element `i` of the array is made of the element `i` of every slice.
+    ///
+    /// Unlike [CFDatetime::from_ymd_hms], the days that do not exist in the calendar, such as
+    /// `2001-02-29`, are rejected instead of carried over to the next month.
+    ///
+    /// ```rust
+    /// # use cftime_rs::{array::CFTimeArray, calendars::Calendar};
+    /// let axis = CFTimeArray::from_components(
+    ///     &[2000, 2000],
+    ///     &[1, 2],
+    ///     &[31, 30],
+    ///     &[0, 12],
+    ///     &[0, 0],
+    ///     &[0.0, 0.0],
+    ///     Calendar::NoLeap,
+    /// );
+    /// let error = axis.err().unwrap();
+    /// assert_eq!(error.index(), Some(1));
+    /// assert!(error.is_invalid_datetime());
+    /// ```
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the array or an error of type
+    /// `crate::errors::Error::DifferentLengths` if the slices do not have the same length, or
+    /// `crate::errors::Error::AtIndex` with the index of the first invalid datetime and an
+    /// error of type `crate::errors::Error::InvalidDate` or the error of [CFDatetime::from_ymd_hms]
+    pub fn from_components(
+        years: &[i64],
+        months: &[u8],
+        days: &[u8],
+        hours: &[u8],
+        minutes: &[u8],
+        seconds: &[f32],
+        calendar: Calendar,
+    ) -> crate::errors::Result<Self> {
+        let len = years.len();
+        for other in [
+            months.len(),
+            days.len(),
+            hours.len(),
+            minutes.len(),
+            seconds.len(),
+        ] {
+            if other != len {
+                return Err(crate::errors::Error::DifferentLengths(len, other));
+            }
+        }
+        let datetimes = (0..len)
+            .map(|i| {
+                let (year, month, day) = (years[i], months[i], days[i]);
+                let datetime = if calendar.is_valid_date(year, month, day) {
+                    CFDatetime::from_ymd_hms(
+                        year, month, day, hours[i], minutes[i], seconds[i], calendar,
+                    )
+                } else {
+                    Err(crate::errors::Error::InvalidDate(format!(
+                        "{year:04}-{month:02}-{day:02} does not exist in the {calendar} calendar"
+                    )))
+                };
+                datetime.map_err(|error| crate::errors::Error::AtIndex(i, Box::new(error)))
+            })
+            .collect::<crate::errors::Result<Vec<_>>>()?;
+        Self::new(datetimes, calendar)
+    }
+    /// Returns the calendar of the datetimes
+    pub fn calendar(&self) -> Calendar {
+        self.calendar
+    }
+    /// Returns the datetimes in their order
+    pub fn datetimes(&self) -> &[CFDatetime] {
+        &self.datetimes
+    }
+    /// Returns the datetimes in their order
+    pub fn into_datetimes(self) -> Vec<CFDatetime> {
+        self.datetimes
+    }
+    /// Returns the number of datetimes
+    pub fn len(&self) -> usize {
+        self.datetimes.len()
+    }
+    /// Returns `true` if the array has no datetime
+    pub fn is_empty(&self) -> bool {
+        self.datetimes.is_empty()
+    }
+    /// Returns `true` if the datetimes are sorted in increasing order, equal datetimes allowed
+    pub fn is_sorted(&self) -> bool {
+        self.unsorted_at.is_none()
+    }
+    fn check_lookup(&self, datetime: &CFDatetime) -> crate::errors::Result<()> {
+        if let Some(index) = self.unsorted_at {
+            return Err(crate::errors::Error::UnsortedAxis(index));
+        }
+        if datetime.calendar() != self.calendar {
+            return Err(crate::errors::Error::DifferentCalendars(
+                self.calendar.to_string(),
+                datetime.calendar().to_string(),
+            ));
+        }
+        Ok(())
+    }
+    /// Finds the index where the datetime would be inserted to keep the array sorted, as
+    /// `numpy.searchsorted`.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the index, between 0 and the length of the array, or an error of type
+    /// `crate::errors::Error::UnsortedAxis` if the array is not sorted or
+    /// `crate::errors::Error::DifferentCalendars` if the datetime is not of the calendar of the array
+    pub fn search_sorted(&self, datetime: &CFDatetime, side: Side) -> crate::errors::Result<usize> {
+        self.check_lookup(datetime)?;
+        let target = datetime.timestamp_nanos();
+        Ok(match side {
+            Side::Left => self.timestamps_nanos.partition_point(|&t| t < target),
+            Side::Right => self.timestamps_nanos.partition_point(|&t| t <= target),
+        })
+    }
+    /// Finds the index of the datetime, the first one if it is repeated
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the index or `None` if the datetime is not in the array, or an error as
+    /// [Self::search_sorted]
+    pub fn index_of(&self, datetime: &CFDatetime) -> crate::errors::Result<Option<usize>> {
+        let index = self.search_sorted(datetime, Side::Left)?;
+        let target = datetime.timestamp_nanos();
+        Ok((self.timestamps_nanos.get(index) == Some(&target)).then_some(index))
+    }
+    /// Finds the indices of the datetimes between `start` and `end`, both included, e.g. to
+    /// subset the variables along the time axis.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the range of indices, empty if no datetime is in the window or if
+    /// `end` is before `start`, or an error as [Self::search_sorted]
+    pub fn index_range(
+        &self,
+        start: &CFDatetime,
+        end: &CFDatetime,
+    ) -> crate::errors::Result<Range<usize>> {
+        let first = self.search_sorted(start, Side::Left)?;
+        let last = self.search_sorted(end, Side::Right)?;
+        Ok(first..last.max(first))
+    }
+    /// Returns the datetimes between `start` and `end`, both included, see [Self::index_range]
+    pub fn slice_by_time(
+        &self,
+        start: &CFDatetime,
+        end: &CFDatetime,
+    ) -> crate::errors::Result<&[CFDatetime]> {
+        Ok(&self.datetimes[self.index_range(start, end)?])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_sorted_and_slice_by_time() {
+        let calendar = Calendar::Day360;
+        let values = [0.0, 0.5, 1.0, 1.0, 3.0];
+        let axis = CFTimeArray::decode(&values, "days since 2000-02-29", calendar).unwrap();
+        assert!(axis.is_sorted());
+        assert_eq!(axis.len(), 5);
+        let date = |day: u8, hour: u8| {
+            CFDatetime::from_ymd_hms(2000, 2, day, hour, 0, 0.0, calendar).unwrap()
+        };
+        assert_eq!(axis.search_sorted(&date(30, 0), Side::Left).unwrap(), 2);
+        assert_eq!(axis.search_sorted(&date(30, 0), Side::Right).unwrap(), 4);
+        assert_eq!(axis.search_sorted(&date(1, 0), Side::Left).unwrap(), 0);
+        let after = CFDatetime::from_ymd(2000, 3, 3, calendar).unwrap();
+        assert_eq!(axis.search_sorted(&after, Side::Left).unwrap(), 5);
+        assert_eq!(axis.index_of(&date(30, 0)).unwrap(), Some(2));
+        assert_eq!(axis.index_of(&date(30, 6)).unwrap(), None);
+
+        assert_eq!(axis.index_range(&date(29, 6), &date(30, 0)).unwrap(), 1..4);
+        let window = axis.slice_by_time(&date(29, 12), &after).unwrap();
+        assert!(window
+            .iter()
+            .zip(&axis.datetimes()[1..])
+            .all(|(a, b)| a == b));
+        assert_eq!(window.len(), 4);
+        assert!(axis.slice_by_time(&after, &date(29, 0)).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_lookup_errors() {
+        let calendar = Calendar::Standard;
+        let axis = CFTimeArray::decode(&[0, 2, 1], "days since 2000-01-01", calendar).unwrap();
+        assert!(!axis.is_sorted());
+        let date = CFDatetime::from_ymd(2000, 1, 2, calendar).unwrap();
+        assert!(matches!(
+            axis.search_sorted(&date, Side::Left),
+            Err(crate::errors::Error::UnsortedAxis(2))
+        ));
+        let axis = CFTimeArray::decode(&[0, 1], "days since 2000-01-01", calendar).unwrap();
+        let date = CFDatetime::from_ymd(2000, 1, 2, Calendar::NoLeap).unwrap();
+        assert!(axis
+            .index_range(&date, &date)
+            .is_err_and(|e| e.is_calendar_error()));
+        assert!(CFTimeArray::new(vec![date], calendar).is_err_and(|e| e.is_calendar_error()));
+    }
+
+    #[test]
+    fn test_from_components() {
+        let calendar = Calendar::Day360;
+        let axis = CFTimeArray::from_components(
+            &[2000, 2000, 2001],
+            &[2, 2, 1],
+            &[29, 30, 1],
+            &[0, 6, 0],
+            &[0, 30, 0],
+            &[0.0, 1.5, 0.0],
+            calendar,
+        )
+        .unwrap();
+        assert!(axis.is_sorted());
+        assert_eq!(axis.calendar(), calendar);
+        let expected = CFDatetime::from_ymd_hms(2000, 2, 30, 6, 30, 1.5, calendar).unwrap();
+        assert!(axis.datetimes()[1] == expected);
+
+        let error =
+            CFTimeArray::from_components(&[2000], &[1, 2], &[1], &[0], &[0], &[0.0], calendar);
+        assert!(matches!(
+            error,
+            Err(crate::errors::Error::DifferentLengths(1, 2))
+        ));
+        let error = CFTimeArray::from_components(
+            &[2000, 2000],
+            &[1, 1],
+            &[1, 1],
+            &[0, 24],
+            &[0, 0],
+            &[0.0, 0.0],
+            calendar,
+        )
+        .err()
+        .unwrap();
+        assert_eq!(error.index(), Some(1));
+        assert!(error.is_invalid_datetime());
+        let error = CFTimeArray::from_components(
+            &[2001, 2001],
+            &[2, 2],
+            &[28, 29],
+            &[0, 0],
+            &[0, 0],
+            &[0.0, 0.0],
+            Calendar::Standard,
+        )
+        .err()
+        .unwrap();
+        assert_eq!(error.index(), Some(1));
+        assert!(error.is_invalid_datetime());
+    }
+}
@@ -1,8 +1,22 @@
 //! Module defining the calendars and their methods
 
+use crate::constants;
+use crate::datetimes::{
+    all_leap::AllLeapDatetime, day_360::Day360Datetime, julian::JulianDatetime,
+    no_leap::NoLeapDatetime, proleptic_gregorian::ProlepticGregorianDatetime,
+    standard::StandardDatetime, traits::CalendarOps,
+};
+use alloc::{string::ToString, vec::Vec};
+
 /// Represents the different types of calendars based on the
 /// CF Conventions.
+/// Later versions of the conventions add calendars, e.g. `utc` and `tai` in CF 1.11, which is why
+/// the enum is non exhaustive.
+///
+/// The hash of a calendar is the hash of its [CF name](Calendar::cf_name), so it does not depend
+/// on the order of the variants and stays the same across versions.
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Default)]
+#[non_exhaustive]
 pub enum Calendar {
     // alias of Standard
     #[default]
@@ -15,8 +29,8 @@ pub enum Calendar {
 }
 
 /// Convert the calendar to a good formatted string
-impl std::fmt::Display for Calendar {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+impl core::fmt::Display for Calendar {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         let name = match *self {
             Calendar::Standard => "Standard",
             Calendar::ProlepticGregorian => "Proleptic Gregorian",
@@ -29,19 +43,471 @@ impl std::fmt::Display for Calendar {
     }
 }
 
+impl core::hash::Hash for Calendar {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.cf_name().hash(state);
+    }
+}
+
+impl Calendar {
+    /// Returns the canonical name of the calendar as written in the CF conventions
+    pub const fn cf_name(&self) -> &'static str {
+        match *self {
+            Calendar::Standard => "standard",
+            Calendar::ProlepticGregorian => "proleptic_gregorian",
+            Calendar::NoLeap => "noleap",
+            Calendar::AllLeap => "all_leap",
+            Calendar::Julian => "julian",
+            Calendar::Day360 => "360_day",
+        }
+    }
+    /// Returns `true` for the calendars following the gregorian rules of the real world after 1582,
+    /// i.e. standard and proleptic gregorian
+    pub const fn is_gregorian(&self) -> bool {
+        matches!(self, Calendar::Standard | Calendar::ProlepticGregorian)
+    }
+    /// Returns `true` if the calendar has both leap and common years
+    pub const fn has_leap_years(&self) -> bool {
+        matches!(
+            self,
+            Calendar::Standard | Calendar::ProlepticGregorian | Calendar::Julian
+        )
+    }
+    /// Returns `true` if every year of the calendar has the same number of days
+    pub const fn has_fixed_year_length(&self) -> bool {
+        self.fixed_year_length().is_some()
+    }
+    /// Returns the number of days of every year for the calendars whose years have the same
+    /// length, i.e. noleap, all_leap and 360_day, and `None` otherwise
+    pub const fn fixed_year_length(&self) -> Option<u16> {
+        match *self {
+            Calendar::NoLeap => Some(365),
+            Calendar::AllLeap => Some(366),
+            Calendar::Day360 => Some(360),
+            Calendar::Standard | Calendar::ProlepticGregorian | Calendar::Julian => None,
+        }
+    }
+    /// Returns `true` if the dates of the calendar are dates of the real world, i.e. standard,
+    /// proleptic gregorian and julian, as opposed to the calendars used only by models
+    pub const fn is_real_world(&self) -> bool {
+        matches!(
+            self,
+            Calendar::Standard | Calendar::ProlepticGregorian | Calendar::Julian
+        )
+    }
+    /// Returns `true` if the timestamps of the calendar are unix timestamps, so the datetimes can
+    /// be converted to the datetimes of chrono or the `datetime64` of numpy as is, i.e. standard
+    /// and proleptic gregorian.
+    ///
+    /// The julian calendar is a real world calendar but its timestamps count from 1970-01-01 in
+    /// the julian calendar, use [crate::datetime::CFDatetime::change_calendar] first.
+    pub const fn convertible_to_unix(&self) -> bool {
+        matches!(self, Calendar::Standard | Calendar::ProlepticGregorian)
+    }
+    /// Returns the number of days of each month of a leap year if `leap_year` is `true`, or of a
+    /// common year otherwise. The calendars without leap years ignore `leap_year`.
+    ///
+    /// The table does not know about October 1582 in the standard calendar, which has 21 days,
+    /// see [Self::days_in_month].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cftime_rs::calendars::Calendar;
+    /// const FEBRUARY: u8 = Calendar::AllLeap.month_lengths(false)[1];
+    /// assert_eq!(FEBRUARY, 29);
+    /// ```
+    pub const fn month_lengths(&self, leap_year: bool) -> &'static [u8; 12] {
+        match *self {
+            Calendar::Day360 => &constants::DAYS_PER_MONTH_360,
+            Calendar::AllLeap => &constants::DAYS_PER_MONTH_LEAP,
+            Calendar::NoLeap => &constants::DAYS_PER_MONTH,
+            Calendar::Standard | Calendar::ProlepticGregorian | Calendar::Julian => {
+                if leap_year {
+                    &constants::DAYS_PER_MONTH_LEAP
+                } else {
+                    &constants::DAYS_PER_MONTH
+                }
+            }
+        }
+    }
+    /// Returns the number of days before each month, and of the whole year as last element,
+    /// following [Self::month_lengths], e.g. to size or index a lookup table in a const context
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cftime_rs::calendars::Calendar;
+    /// const DAYS: usize = Calendar::NoLeap.cumulative_days(false)[12] as usize;
+    /// let day_of_year_weights = [0.0_f64; DAYS];
+    /// assert_eq!(day_of_year_weights.len(), 365);
+    /// ```
+    pub const fn cumulative_days(&self, leap_year: bool) -> &'static [u32; 13] {
+        match *self {
+            Calendar::Day360 => &constants::CUM_DAYS_PER_MONTH_360,
+            Calendar::AllLeap => &constants::CUM_DAYS_PER_MONTH_LEAP,
+            Calendar::NoLeap => &constants::CUM_DAYS_PER_MONTH,
+            Calendar::Standard | Calendar::ProlepticGregorian | Calendar::Julian => {
+                if leap_year {
+                    &constants::CUM_DAYS_PER_MONTH_LEAP
+                } else {
+                    &constants::CUM_DAYS_PER_MONTH
+                }
+            }
+        }
+    }
+    /// Returns the number of seconds in a year as defined in the
+    /// [udunits package](https://github.com/nco/nco/blob/master/data/udunits.dat)
+    pub fn seconds_per_year(&self) -> f64 {
+        match *self {
+            Calendar::ProlepticGregorian | Calendar::Standard => 3.15569259747e7,
+            Calendar::NoLeap => 365.0 * constants::SECS_PER_DAY as f64,
+            Calendar::AllLeap => 366.0 * constants::SECS_PER_DAY as f64,
+            Calendar::Julian => 365.25 * constants::SECS_PER_DAY as f64,
+            Calendar::Day360 => 360.0 * constants::SECS_PER_DAY as f64,
+        }
+    }
+    /// Returns [Self::seconds_per_year] as an exact number of nanoseconds, with which the years
+    /// and months of the units are both decoded and encoded
+    pub(crate) fn nanoseconds_per_year(&self) -> i128 {
+        match *self {
+            // 365.242198781 days
+            Calendar::ProlepticGregorian | Calendar::Standard => 31_556_925_974_700_000,
+            calendar => calendar.seconds_per_year() as i128 * constants::MAX_NS as i128,
+        }
+    }
+    /// Returns the number of days in a century used by the astronomical formulas, see
+    /// [crate::datetime::CFDatetime::julian_century].
+    ///
+    /// The real-world calendars use the Julian century of 36525 days. The model calendars use a
+    /// hundred of their own years, so the seasons computed from the century stay at the same dates.
+    pub fn days_per_century(&self) -> f64 {
+        match *self {
+            Calendar::Standard | Calendar::ProlepticGregorian | Calendar::Julian => 36525.0,
+            Calendar::NoLeap => 100.0 * constants::DAYS_PER_YEAR_NON_LEAP as f64,
+            Calendar::AllLeap => 100.0 * constants::DAYS_PER_YEAR_LEAP as f64,
+            Calendar::Day360 => 36000.0,
+        }
+    }
+    /// Returns `true` if the year is a leap year in the calendar.
+    ///
+    /// The standard calendar uses the julian rules before 1582 and the gregorian rules after.
+    pub fn is_leap_year(&self, year: i64) -> bool {
+        match *self {
+            Calendar::Standard => StandardDatetime::is_leap(year),
+            Calendar::ProlepticGregorian => ProlepticGregorianDatetime::is_leap(year),
+            Calendar::Julian => JulianDatetime::is_leap(year),
+            Calendar::AllLeap => AllLeapDatetime::is_leap(year),
+            Calendar::NoLeap => NoLeapDatetime::is_leap(year),
+            Calendar::Day360 => Day360Datetime::is_leap(year),
+        }
+    }
+    /// Returns the number of days of the month in the calendar.
+    ///
+    /// October 1582 has 21 days in the standard calendar because of the switch from the julian
+    /// to the gregorian calendar.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the number of days or an error of type `crate::errors::Error::InvalidDate`
+    /// if the month is not between 1 and 12
+    pub fn days_in_month(&self, year: i64, month: u8) -> crate::errors::Result<u8> {
+        match *self {
+            Calendar::Standard => StandardDatetime::days_in_month(year, month),
+            Calendar::ProlepticGregorian => ProlepticGregorianDatetime::days_in_month(year, month),
+            Calendar::Julian => JulianDatetime::days_in_month(year, month),
+            Calendar::AllLeap => AllLeapDatetime::days_in_month(year, month),
+            Calendar::NoLeap => NoLeapDatetime::days_in_month(year, month),
+            Calendar::Day360 => Day360Datetime::days_in_month(year, month),
+        }
+    }
+    /// Returns the number of days of the year in the calendar.
+    ///
+    /// 1582 has 355 days in the standard calendar because of the switch from the julian
+    /// to the gregorian calendar.
+    pub fn days_in_year(&self, year: i64) -> u16 {
+        match *self {
+            Calendar::Day360 => 360,
+            Calendar::Standard if year == 1582 => 355,
+            _ if self.is_leap_year(year) => 366,
+            _ => 365,
+        }
+    }
+    /// Returns the number of days between 1970-01-01 and the date in the calendar, see
+    /// [CalendarOps::ymd_to_days]
+    pub fn ymd_to_days(&self, year: i64, month: u8, day: u8) -> crate::errors::Result<i64> {
+        match *self {
+            Calendar::Standard => StandardDatetime::ymd_to_days(year, month, day),
+            Calendar::ProlepticGregorian => {
+                ProlepticGregorianDatetime::ymd_to_days(year, month, day)
+            }
+            Calendar::Julian => JulianDatetime::ymd_to_days(year, month, day),
+            Calendar::AllLeap => AllLeapDatetime::ymd_to_days(year, month, day),
+            Calendar::NoLeap => NoLeapDatetime::ymd_to_days(year, month, day),
+            Calendar::Day360 => Day360Datetime::ymd_to_days(year, month, day),
+        }
+    }
+    /// Returns the year, month and day of the given number of days since 1970-01-01 in the
+    /// calendar, see [CalendarOps::days_to_ymd]
+    pub fn days_to_ymd(&self, days: i64) -> (i64, u8, u8) {
+        match *self {
+            Calendar::Standard => StandardDatetime::days_to_ymd(days),
+            Calendar::ProlepticGregorian => ProlepticGregorianDatetime::days_to_ymd(days),
+            Calendar::Julian => JulianDatetime::days_to_ymd(days),
+            Calendar::AllLeap => AllLeapDatetime::days_to_ymd(days),
+            Calendar::NoLeap => NoLeapDatetime::days_to_ymd(days),
+            Calendar::Day360 => Day360Datetime::days_to_ymd(days),
+        }
+    }
+    /// Returns `true` if the date exists in the calendar, i.e. the month is between 1 and 12 and
+    /// the day between 1 and the number of days of the month.
+    ///
+    /// Unlike [crate::datetime::CFDatetime::from_ymd], which carries a day past the end of the
+    /// month to the next month, `2001-02-29` is not valid. The days from 1582-10-05 to 1582-10-14
+    /// do not exist in the standard calendar.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cftime_rs::calendars::Calendar;
+    /// assert!(Calendar::Day360.is_valid_date(2001, 2, 30));
+    /// assert!(!Calendar::NoLeap.is_valid_date(2000, 2, 29));
+    /// assert!(!Calendar::Standard.is_valid_date(1582, 10, 10));
+    /// ```
+    pub fn is_valid_date(&self, year: i64, month: u8, day: u8) -> bool {
+        match (*self, year, month) {
+            (Calendar::Standard, 1582, 10) => (1..=4).contains(&day) || (15..=31).contains(&day),
+            _ => self
+                .days_in_month(year, month)
+                .is_ok_and(|days| (1..=days).contains(&day)),
+        }
+    }
+    /// Returns [Self::is_valid_date] for the dates given as one array per component, e.g. the
+    /// columns of a table.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the validity of each date or an error of type
+    /// `crate::errors::Error::DifferentLengths` if the arrays do not have the same length
+    pub fn are_valid_dates(
+        &self,
+        years: &[i64],
+        months: &[u8],
+        days: &[u8],
+    ) -> crate::errors::Result<Vec<bool>> {
+        for other in [months.len(), days.len()] {
+            if other != years.len() {
+                return Err(crate::errors::Error::DifferentLengths(years.len(), other));
+            }
+        }
+        Ok(years
+            .iter()
+            .zip(months)
+            .zip(days)
+            .map(|((&year, &month), &day)| self.is_valid_date(year, month, day))
+            .collect())
+    }
+    /// Converts a `calendar` attribute into a Calendar by accepting only the values
+    /// sanctioned by the CF conventions.
+    ///
+    /// `none` is accepted and normalized to the standard calendar.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the canonical Calendar and `true` if the attribute was an alias
+    /// that has been normalized (e.g. `gregorian` or `365_day`), or an error of type
+    /// `crate::errors::Error::InvalidCalendar` if the attribute is not a CF calendar
+    pub fn from_cf_attribute(attribute: &str) -> crate::errors::Result<(Calendar, bool)> {
+        let normalized = attribute.trim().to_lowercase();
+        let calendar = match normalized.as_str() {
+            "standard" | "gregorian" | "none" => Calendar::Standard,
+            "proleptic_gregorian" => Calendar::ProlepticGregorian,
+            "noleap" | "365_day" => Calendar::NoLeap,
+            "all_leap" | "366_day" => Calendar::AllLeap,
+            "julian" => Calendar::Julian,
+            "360_day" => Calendar::Day360,
+            _ => return Err(crate::errors::Error::InvalidCalendar(attribute.to_string())),
+        };
+        let is_alias = normalized != calendar.cf_name();
+        Ok((calendar, is_alias))
+    }
+}
+
 /// Convert a valid cf unit calendar string to a Calendar
 /// If no valid string is provided, Standard is returned
-impl std::str::FromStr for Calendar {
+impl core::str::FromStr for Calendar {
     type Err = crate::errors::Error;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s.trim().to_lowercase().as_str() {
             "standard" | "gregorian" => Ok(Calendar::Standard),
             "proleptic_gregorian" => Ok(Calendar::ProlepticGregorian),
-            "no_leap" | "day365" => Ok(Calendar::NoLeap),
-            "all_leap" | "day366" => Ok(Calendar::AllLeap),
+            "noleap" | "no_leap" | "365_day" | "day365" => Ok(Calendar::NoLeap),
+            "all_leap" | "366_day" | "day366" => Ok(Calendar::AllLeap),
             "julian" => Ok(Calendar::Julian),
             "360_day" => Ok(Calendar::Day360),
             _ => Ok(Calendar::Standard),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::str::FromStr;
+
+    #[test]
+    fn test_calendar_predicates() {
+        assert!(Calendar::Standard.is_gregorian());
+        assert!(!Calendar::Julian.is_gregorian());
+        assert!(Calendar::Julian.has_leap_years());
+        assert!(!Calendar::AllLeap.has_leap_years());
+        assert!(Calendar::Day360.has_fixed_year_length());
+        assert!(!Calendar::ProlepticGregorian.has_fixed_year_length());
+        assert_eq!(Calendar::AllLeap.fixed_year_length(), Some(366));
+        assert_eq!(Calendar::Julian.fixed_year_length(), None);
+        assert!(Calendar::Julian.is_real_world());
+        assert!(!Calendar::NoLeap.is_real_world());
+        assert!(Calendar::Standard.convertible_to_unix());
+        assert!(!Calendar::Julian.convertible_to_unix());
+        assert!(!Calendar::Day360.convertible_to_unix());
+    }
+
+    #[test]
+    fn test_days_in_month_and_year() {
+        let cases = vec![
+            (Calendar::Standard, 2000, true, 29, 366),
+            (Calendar::Standard, 1900, false, 28, 365),
+            (Calendar::Standard, 1500, true, 29, 366),
+            (Calendar::ProlepticGregorian, 1500, false, 28, 365),
+            (Calendar::Julian, 1900, true, 29, 366),
+            (Calendar::NoLeap, 2000, false, 28, 365),
+            (Calendar::AllLeap, 2001, true, 29, 366),
+            (Calendar::Day360, 2000, false, 30, 360),
+        ];
+        for (calendar, year, is_leap, february, days) in cases {
+            assert_eq!(calendar.is_leap_year(year), is_leap);
+            assert_eq!(calendar.days_in_month(year, 2).unwrap(), february);
+            assert_eq!(calendar.days_in_year(year), days);
+            let sum: u16 = (1..=12)
+                .map(|month| calendar.days_in_month(year, month).unwrap() as u16)
+                .sum();
+            assert_eq!(sum, days);
+            let month_lengths = calendar.month_lengths(is_leap);
+            assert_eq!(month_lengths[1], february);
+            assert_eq!(calendar.cumulative_days(is_leap)[12], days as u32);
+        }
+        assert_eq!(Calendar::Standard.days_in_month(1582, 10).unwrap(), 21);
+        assert_eq!(Calendar::Standard.days_in_year(1582), 355);
+        assert!(Calendar::Standard.days_in_month(2000, 13).is_err());
+    }
+
+    #[test]
+    fn test_hash_is_hash_of_cf_name() {
+        use core::hash::{Hash, Hasher};
+        use std::collections::hash_map::DefaultHasher;
+        let hash = |value: &dyn Fn(&mut DefaultHasher)| {
+            let mut hasher = DefaultHasher::new();
+            value(&mut hasher);
+            hasher.finish()
+        };
+        for calendar in [Calendar::Standard, Calendar::NoLeap, Calendar::Day360] {
+            assert_eq!(
+                hash(&|hasher| calendar.hash(hasher)),
+                hash(&|hasher| calendar.cf_name().hash(hasher))
+            );
+        }
+    }
+
+    #[test]
+    fn test_is_valid_date() {
+        let cases = [
+            (Calendar::Standard, (2000, 2, 29), true),
+            (Calendar::Standard, (1900, 2, 29), false),
+            (Calendar::Standard, (1500, 2, 29), true),
+            (Calendar::Standard, (1582, 10, 4), true),
+            (Calendar::Standard, (1582, 10, 5), false),
+            (Calendar::Standard, (1582, 10, 14), false),
+            (Calendar::Standard, (1582, 10, 15), true),
+            (Calendar::Standard, (1582, 10, 31), true),
+            (Calendar::ProlepticGregorian, (1582, 10, 10), true),
+            (Calendar::ProlepticGregorian, (1500, 2, 29), false),
+            (Calendar::Julian, (1900, 2, 29), true),
+            (Calendar::NoLeap, (2000, 2, 29), false),
+            (Calendar::AllLeap, (2001, 2, 29), true),
+            (Calendar::Day360, (2001, 2, 30), true),
+            (Calendar::Day360, (2001, 1, 31), false),
+            (Calendar::Standard, (2000, 0, 1), false),
+            (Calendar::Standard, (2000, 13, 1), false),
+            (Calendar::Standard, (2000, 1, 0), false),
+            (Calendar::Standard, (-4_000_000, 2, 29), true),
+        ];
+        for (calendar, (year, month, day), valid) in cases {
+            assert_eq!(
+                calendar.is_valid_date(year, month, day),
+                valid,
+                "{calendar} {year}-{month}-{day}"
+            );
+        }
+        // Every valid date makes a datetime with the same date
+        for calendar in [Calendar::Standard, Calendar::Julian, Calendar::Day360] {
+            for (month, day) in (1..=12).flat_map(|month| (1..=31).map(move |day| (month, day))) {
+                if calendar.is_valid_date(2000, month, day) {
+                    let datetime =
+                        crate::datetime::CFDatetime::from_ymd(2000, month, day, calendar).unwrap();
+                    assert_eq!(datetime.ymd().unwrap(), (2000, month, day));
+                }
+            }
+        }
+        assert_eq!(
+            Calendar::NoLeap
+                .are_valid_dates(&[2000, 2000, 2001], &[2, 3, 13], &[29, 31, 1])
+                .unwrap(),
+            vec![false, true, false]
+        );
+        assert!(Calendar::NoLeap
+            .are_valid_dates(&[], &[], &[])
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_are_valid_dates_lengths() {
+        assert!(matches!(
+            Calendar::NoLeap.are_valid_dates(&[2000], &[1, 2], &[1]),
+            Err(crate::errors::Error::DifferentLengths(1, 2))
+        ));
+        assert!(matches!(
+            Calendar::NoLeap.are_valid_dates(&[2000], &[1], &[]),
+            Err(crate::errors::Error::DifferentLengths(1, 0))
+        ));
+    }
+
+    #[test]
+    fn test_from_cf_attribute() {
+        let attributes = vec![
+            ("standard", (Calendar::Standard, false)),
+            ("gregorian", (Calendar::Standard, true)),
+            ("none", (Calendar::Standard, true)),
+            ("proleptic_gregorian", (Calendar::ProlepticGregorian, false)),
+            ("noleap", (Calendar::NoLeap, false)),
+            ("365_day", (Calendar::NoLeap, true)),
+            ("all_leap", (Calendar::AllLeap, false)),
+            ("366_day", (Calendar::AllLeap, true)),
+            ("360_day", (Calendar::Day360, false)),
+            ("julian", (Calendar::Julian, false)),
+            (" Julian ", (Calendar::Julian, false)),
+        ];
+        for (attribute, expected) in attributes {
+            assert_eq!(Calendar::from_cf_attribute(attribute).unwrap(), expected);
+        }
+        for attribute in ["no_leap", "day365", "", "unknown"] {
+            assert!(Calendar::from_cf_attribute(attribute).is_err());
+        }
+    }
+    #[test]
+    fn test_from_str_cf_names() {
+        assert_eq!(Calendar::from_str("noleap").unwrap(), Calendar::NoLeap);
+        assert_eq!(Calendar::from_str("365_day").unwrap(), Calendar::NoLeap);
+        assert_eq!(Calendar::from_str("366_day").unwrap(), Calendar::AllLeap);
+    }
+}
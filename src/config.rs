@@ -0,0 +1,376 @@
+//! Module that decodes and encodes arrays from instructions written in configuration files.
+//!
+//! A [TimeDecodingSpec] gathers the units, the calendar, the type of the encoded values and the
+//! fill value of a time coordinate. With the `serde` feature it is deserialized from the table of
+//! any configuration format, e.g.
+//!
+//! ```toml
+//! [time]
+//! units = "hours since 2000-01-01"
+//! calendar = "noleap"
+//! dtype = "i64"
+//! fill_value = -9999
+//! ```
+//!
+//! Without it, [TimeDecodingSpec::from_fields] reads the fields from strings.
+
+use crate::calendars::Calendar;
+use crate::converter::CFConverter;
+use crate::datetime::CFDatetime;
+use crate::decoder::{DType, Decoding, ScaleDuration};
+use crate::float;
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::borrow::Borrow;
+use core::str::FromStr;
+
+/// Instructions to decode and encode the values of a time coordinate
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize),
+    serde(deny_unknown_fields)
+)]
+pub struct TimeDecodingSpec {
+    /// CF units, e.g. `days since 2000-01-01`
+    pub units: String,
+    /// Calendar of the time coordinate
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, deserialize_with = "deserialize_from_str")
+    )]
+    pub calendar: Calendar,
+    /// Type of the encoded values
+    #[cfg_attr(
+        feature = "serde",
+        serde(default = "default_dtype", deserialize_with = "deserialize_from_str")
+    )]
+    pub dtype: DType,
+    /// Value marking a missing time, NaN values are always missing
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub fill_value: Option<f64>,
+}
+
+/// Type of the encoded values when a spec does not give one
+#[cfg(feature = "serde")]
+fn default_dtype() -> DType {
+    DType::Float64
+}
+
+/// Reads a field from its name, e.g. `noleap` or `i64`
+#[cfg(feature = "serde")]
+fn deserialize_from_str<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: FromStr<Err = crate::errors::Error>,
+{
+    let name = <String as serde::Deserialize>::deserialize(deserializer)?;
+    T::from_str(&name).map_err(serde::de::Error::custom)
+}
+
+/// Converts a value of an integer dtype, read as a float, to its type
+fn to_integer<T: TryFrom<i128>>(value: f64, dtype: DType) -> crate::errors::Result<T> {
+    if value.is_finite() && float::fract(value) == 0.0 {
+        // Saturates beyond the `i128` so the conversion fails
+        if let Ok(value) = T::try_from(value as i128) {
+            return Ok(value);
+        }
+    }
+    Err(crate::errors::Error::InvalidConfig(format!(
+        "Value {value} is not an integer of dtype {dtype}"
+    )))
+}
+
+impl TimeDecodingSpec {
+    /// Makes a spec for the units and calendar, with `f64` values and no fill value
+    pub fn new(units: &str, calendar: Calendar) -> Self {
+        Self {
+            units: units.to_string(),
+            calendar,
+            dtype: DType::Float64,
+            fill_value: None,
+        }
+    }
+    /// Makes a spec from the `units`, `calendar`, `dtype` and `fill_value` fields of a
+    /// configuration. Only `units` is required, the calendar defaults to `standard` and the dtype
+    /// to `f64`.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the spec or an error if a field is unknown, missing or not valid, or if
+    /// the fill value is not a value of the dtype
+    pub fn from_fields<'a>(
+        fields: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) -> crate::errors::Result<Self> {
+        let mut units = None;
+        let mut spec = Self::new("", Calendar::default());
+        for (key, value) in fields {
+            match key {
+                "units" => units = Some(value),
+                "calendar" => spec.calendar = Calendar::from_str(value)?,
+                "dtype" => spec.dtype = DType::from_str(value)?,
+                "fill_value" => {
+                    spec.fill_value = Some(value.trim().parse().map_err(|_| {
+                        crate::errors::Error::InvalidConfig(format!("Invalid fill_value `{value}`"))
+                    })?)
+                }
+                _ => {
+                    return Err(crate::errors::Error::InvalidConfig(format!(
+                        "Unknown field `{key}`"
+                    )))
+                }
+            }
+        }
+        spec.units = units
+            .ok_or_else(|| crate::errors::Error::InvalidConfig("Missing units".to_string()))?
+            .to_string();
+        spec.check_fill_value()?;
+        Ok(spec)
+    }
+    /// Checks that the fill value is a value of the dtype
+    fn check_fill_value(&self) -> crate::errors::Result<()> {
+        let Some(fill_value) = self.fill_value else {
+            return Ok(());
+        };
+        match self.dtype {
+            DType::Int32 => to_integer::<i32>(fill_value, self.dtype).map(drop),
+            DType::Int64 => to_integer::<i64>(fill_value, self.dtype).map(drop),
+            DType::UInt32 => to_integer::<u32>(fill_value, self.dtype).map(drop),
+            DType::UInt64 => to_integer::<u64>(fill_value, self.dtype).map(drop),
+            DType::Float32 if fill_value.is_finite() && (fill_value as f32).is_infinite() => {
+                Err(crate::errors::Error::InvalidConfig(format!(
+                    "Fill value {fill_value} does not fit in dtype {}",
+                    self.dtype
+                )))
+            }
+            DType::Float32 | DType::Float64 => Ok(()),
+        }
+    }
+    /// Returns `true` if the value marks a missing time. The values of `f32` are compared as
+    /// `f32`, as the fill value of a configuration is rarely exact in this type.
+    fn is_missing(&self, value: f64) -> bool {
+        match (self.dtype, self.fill_value) {
+            _ if value.is_nan() => true,
+            (DType::Float32, Some(fill_value)) => value as f32 == fill_value as f32,
+            (_, fill_value) => fill_value == Some(value),
+        }
+    }
+    /// Decodes the values, the missing ones into `None`.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the datetimes or an error if the units are not valid, if the fill value
+    /// or a value is infinite or not of the dtype or if a datetime is out of the bounds of the
+    /// calendar
+    pub fn decode(&self, values: &[f64]) -> crate::errors::Result<Vec<Option<CFDatetime>>> {
+        self.check_fill_value()?;
+        let decoding = Decoding::from_units(&self.units, self.calendar)?;
+        values
+            .iter()
+            .enumerate()
+            .map(|(index, &value)| {
+                if self.is_missing(value) {
+                    return Ok(None);
+                }
+                let value = value.check_finite(index)?;
+                let datetime = match self.dtype {
+                    DType::Int32 => decoding.decode(to_integer::<i32>(value, self.dtype)?, index),
+                    DType::Int64 => decoding.decode(to_integer::<i64>(value, self.dtype)?, index),
+                    DType::UInt32 => decoding.decode(to_integer::<u32>(value, self.dtype)?, index),
+                    DType::UInt64 => decoding.decode(to_integer::<u64>(value, self.dtype)?, index),
+                    DType::Float32 => decoding.decode(value as f32, index),
+                    DType::Float64 => decoding.decode(value, index),
+                }?;
+                Ok(Some(datetime))
+            })
+            .collect()
+    }
+    /// Encodes the datetimes into values of the dtype, the missing ones into the fill value or NaN
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the values or an error if the units or the fill value are not valid, if
+    /// a datetime is not in the calendar of the spec or does not fit in the dtype
+    pub fn encode<D: Borrow<CFDatetime>>(
+        &self,
+        datetimes: &[Option<D>],
+    ) -> crate::errors::Result<Vec<f64>> {
+        self.check_fill_value()?;
+        let converter = CFConverter::new(&self.units, self.calendar)?;
+        let present: Vec<&CFDatetime> = datetimes.iter().flatten().map(Borrow::borrow).collect();
+        let encoded: Vec<f64> = match self.dtype {
+            DType::Int32 => converter
+                .encode::<i32, _>(&present)?
+                .into_iter()
+                .map(|value| value as f64)
+                .collect(),
+            DType::Int64 => converter
+                .encode::<i64, _>(&present)?
+                .into_iter()
+                .map(|value| value as f64)
+                .collect(),
+            DType::UInt32 => converter
+                .encode::<u32, _>(&present)?
+                .into_iter()
+                .map(|value| value as f64)
+                .collect(),
+            DType::UInt64 => converter
+                .encode::<u64, _>(&present)?
+                .into_iter()
+                .map(|value| value as f64)
+                .collect(),
+            DType::Float32 => converter
+                .encode::<f32, _>(&present)?
+                .into_iter()
+                .map(|value| value as f64)
+                .collect(),
+            DType::Float64 => converter.encode::<f64, _>(&present)?,
+        };
+        let missing = self.fill_value.unwrap_or(f64::NAN);
+        let mut encoded = encoded.into_iter();
+        Ok(datetimes
+            .iter()
+            .map(|datetime| match datetime {
+                Some(_) => encoded.next().unwrap_or(missing),
+                None => missing,
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spec_from_fields() {
+        let spec = TimeDecodingSpec::from_fields([
+            ("units", "hours since 2000-01-01"),
+            ("calendar", "noleap"),
+            ("dtype", "int64"),
+            ("fill_value", "-9999"),
+        ])
+        .unwrap();
+        assert_eq!(
+            spec,
+            TimeDecodingSpec {
+                units: "hours since 2000-01-01".to_string(),
+                calendar: Calendar::NoLeap,
+                dtype: DType::Int64,
+                fill_value: Some(-9999.0),
+            }
+        );
+        let spec = TimeDecodingSpec::from_fields([("units", "days since 2000-01-01")]).unwrap();
+        assert_eq!(
+            spec,
+            TimeDecodingSpec::new("days since 2000-01-01", Calendar::Standard)
+        );
+        for fields in [
+            vec![("calendar", "noleap")],
+            vec![("units", "days since 2000-01-01"), ("dtype", "u16")],
+            vec![
+                ("units", "days since 2000-01-01"),
+                ("dtype", "i32"),
+                ("fill_value", "1e10"),
+            ],
+            vec![("units", "days since 2000-01-01"), ("fill_value", "none")],
+            vec![("units", "days since 2000-01-01"), ("offset", "1")],
+        ] {
+            assert!(TimeDecodingSpec::from_fields(fields).is_err_and(|e| e.is_parse_error()));
+        }
+    }
+
+    #[test]
+    fn test_spec_decode_and_encode() {
+        let mut spec = TimeDecodingSpec::new("hours since 2000-01-01", Calendar::NoLeap);
+        spec.dtype = DType::Int32;
+        spec.fill_value = Some(-9999.0);
+        let datetimes = spec.decode(&[0.0, -9999.0, 25.0, f64::NAN]).unwrap();
+        let ymd_hms: Vec<_> = datetimes
+            .iter()
+            .map(|datetime| datetime.as_ref().map(|d| d.ymd_hms().unwrap()))
+            .collect();
+        assert_eq!(
+            ymd_hms,
+            vec![
+                Some((2000, 1, 1, 0, 0, 0)),
+                None,
+                Some((2000, 1, 2, 1, 0, 0)),
+                None
+            ]
+        );
+        assert_eq!(
+            spec.encode(&datetimes).unwrap(),
+            vec![0.0, -9999.0, 25.0, -9999.0]
+        );
+        assert!(spec.decode(&[1.5]).is_err_and(|e| e.is_parse_error()));
+        assert!(spec.decode(&[1e10]).is_err_and(|e| e.is_parse_error()));
+        assert!(matches!(
+            spec.decode(&[0.0, f64::NAN, f64::INFINITY]),
+            Err(crate::errors::Error::InvalidValue(2, value)) if value == f64::INFINITY
+        ));
+        spec.dtype = DType::Float64;
+        spec.fill_value = None;
+        let datetimes = spec.decode(&[1.5]).unwrap();
+        assert_eq!(spec.encode(&datetimes).unwrap(), vec![1.5]);
+        assert!(spec.encode::<CFDatetime>(&[None]).unwrap()[0].is_nan());
+    }
+
+    #[test]
+    fn test_spec_unsigned_and_fill_value() {
+        let mut spec = TimeDecodingSpec::new("days since 2000-01-01", Calendar::NoLeap);
+        spec.dtype = DType::UInt32;
+        spec.fill_value = Some(-1.0);
+        // A negative fill value does not fit in an unsigned dtype
+        assert!(spec.decode(&[0.0]).is_err_and(|e| e.is_parse_error()));
+        spec.fill_value = Some(u32::MAX as f64);
+        let datetimes = spec.decode(&[1.0, u32::MAX as f64]).unwrap();
+        assert!(datetimes[1].is_none());
+        assert!(spec.decode(&[-1.0]).is_err_and(|e| e.is_parse_error()));
+        assert_eq!(spec.encode(&datetimes).unwrap(), vec![1.0, u32::MAX as f64]);
+        let before = CFDatetime::from_ymd(1999, 12, 31, Calendar::NoLeap).unwrap();
+        assert!(matches!(
+            spec.encode(&[Some(before)]),
+            Err(crate::errors::Error::OutOfBoundsEncoding(_))
+        ));
+        // The fill value of a configuration is not exact in `f32`
+        spec.dtype = DType::Float32;
+        spec.fill_value = Some(9.96921e36);
+        assert!(spec.decode(&[9.96921e36_f32 as f64]).unwrap()[0].is_none());
+        spec.fill_value = Some(1e300);
+        assert!(spec.decode(&[0.0]).is_err_and(|e| e.is_parse_error()));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_spec_deserialize() {
+        let spec: TimeDecodingSpec = serde_json::from_str(
+            r#"{"units": "hours since 2000-01-01", "calendar": "noleap", "dtype": "i64", "fill_value": -9999}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            spec,
+            TimeDecodingSpec {
+                units: "hours since 2000-01-01".to_string(),
+                calendar: Calendar::NoLeap,
+                dtype: DType::Int64,
+                fill_value: Some(-9999.0),
+            }
+        );
+        let spec: TimeDecodingSpec =
+            serde_json::from_str(r#"{"units": "days since 2000-01-01"}"#).unwrap();
+        assert_eq!(
+            spec,
+            TimeDecodingSpec::new("days since 2000-01-01", Calendar::Standard)
+        );
+        for json in [
+            r#"{"calendar": "noleap"}"#,
+            r#"{"units": "days since 2000-01-01", "dtype": "u16"}"#,
+            r#"{"units": "days since 2000-01-01", "offset": 1}"#,
+        ] {
+            assert!(serde_json::from_str::<TimeDecodingSpec>(json).is_err());
+        }
+    }
+}
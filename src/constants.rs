@@ -49,4 +49,27 @@ pub const MONTHS: [&str; 12] = [
     "December",
 ];
 
+pub const WEEKDAYS: [&str; 7] = [
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+    "Sunday",
+];
+
 pub const MAX_NS: i64 = 1_000_000_000;
+
+// REPRESENTABLE RANGE
+/// Smallest number of seconds since epoch of a datetime, which is the same for every calendar
+pub const MIN_TIMESTAMP: i64 = i64::MIN;
+/// Largest number of seconds since epoch of a datetime, which is the same for every calendar
+pub const MAX_TIMESTAMP: i64 = i64::MAX;
+/// Largest number of nanoseconds of a datetime in addition to its seconds
+pub const MAX_NANOSECONDS: u32 = MAX_NS as u32 - 1;
+/// Smallest number of nanoseconds since epoch of a datetime
+pub const MIN_TIMESTAMP_NANOS: i128 = MIN_TIMESTAMP as i128 * MAX_NS as i128;
+/// Largest number of nanoseconds since epoch of a datetime
+pub const MAX_TIMESTAMP_NANOS: i128 =
+    MAX_TIMESTAMP as i128 * MAX_NS as i128 + MAX_NANOSECONDS as i128;
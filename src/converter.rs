@@ -0,0 +1,401 @@
+//! Module that converts many batches of values with the same units.
+//!
+//! [CFConverter] parses the units once and keeps the reference date, the unit and the calendar, so
+//! converting thousands of small batches, e.g. one per file of a dataset, does not parse the units
+//! for every batch as [crate::decoder::VecCFDecoder] and [crate::encoder::CFEncoder] do.
+//!
+//! [rebase_cf] converts values from some units to others, e.g. from "hours since 1900-01-01" to
+//! "days since 2000-01-01", without making the datetimes.
+
+use crate::calendars::Calendar;
+use crate::constants;
+use crate::datetime::CFDatetime;
+use crate::decoder::{Decoding, ScaleDuration};
+use crate::duration::CFDuration;
+use crate::encoder::{EncodeDuration, LossyEncoding};
+use crate::instrument::trace_span;
+use crate::parser::{parse_cf_time_with_options, ParseOptions, Unit};
+use crate::utils::{
+    get_datetime_and_unit_from_units, get_datetime_and_unit_from_units_with_year_zero,
+    get_reference_datetime,
+};
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::borrow::Borrow;
+
+/// Decoder and encoder of the values of a time coordinate with already parsed units
+pub struct CFConverter {
+    units: String,
+    calendar: Calendar,
+    unit: Unit,
+    multiplier: i64,
+    /// Reference date and duration of one increment of the units
+    decoding: Decoding,
+}
+
+impl CFConverter {
+    /// Parses the units once for all the conversions.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the converter or an error if the units are not valid or the reference
+    /// date is not valid in the calendar
+    pub fn new(units: &str, calendar: Calendar) -> crate::errors::Result<Self> {
+        let (origin, unit, multiplier) = get_datetime_and_unit_from_units(units, calendar)?;
+        Self::from_parts(units, calendar, origin, unit, multiplier)
+    }
+    /// Same as [Self::new] with the year of the reference date counted with or without a year
+    /// zero, see [crate::utils::to_astronomical_year]. The decoded datetimes count the years with a
+    /// year zero, use [CFDatetime::ymd_hms_with_year_zero] to read them without.
+    pub fn new_with_year_zero(
+        units: &str,
+        calendar: Calendar,
+        has_year_zero: bool,
+    ) -> crate::errors::Result<Self> {
+        let (origin, unit, multiplier) =
+            get_datetime_and_unit_from_units_with_year_zero(units, calendar, has_year_zero)?;
+        Self::from_parts(units, calendar, origin, unit, multiplier)
+    }
+    /// Same as [Self::new] with the units parsed with the given [ParseOptions], e.g. to decode
+    /// units such as `Days since 2000-01` repaired by [crate::parser::ParseMode::Lenient]. The
+    /// repairs are not kept, use [parse_cf_time_with_options] to report them.
+    pub fn new_with_options(
+        units: &str,
+        calendar: Calendar,
+        options: ParseOptions,
+    ) -> crate::errors::Result<Self> {
+        let parsed = parse_cf_time_with_options(units, options)?.parsed;
+        let origin = get_reference_datetime(units, &parsed, calendar)?;
+        Self::from_parts(units, calendar, origin, parsed.unit, parsed.multiplier)
+    }
+    fn from_parts(
+        units: &str,
+        calendar: Calendar,
+        origin: CFDatetime,
+        unit: Unit,
+        multiplier: i64,
+    ) -> crate::errors::Result<Self> {
+        Ok(Self {
+            units: units.to_string(),
+            calendar,
+            decoding: Decoding::new(origin, &unit, multiplier)?,
+            unit,
+            multiplier,
+        })
+    }
+    /// Returns the units given to [Self::new]
+    pub fn units(&self) -> &str {
+        &self.units
+    }
+    /// Returns the calendar given to [Self::new]
+    pub fn calendar(&self) -> Calendar {
+        self.calendar
+    }
+    /// Returns the reference date of the units
+    pub fn origin(&self) -> &CFDatetime {
+        self.decoding.origin()
+    }
+    /// Decodes the values into datetimes, as [crate::decoder::VecCFDecoder::decode_cf]
+    pub fn decode<T: ScaleDuration>(&self, values: &[T]) -> crate::errors::Result<Vec<CFDatetime>> {
+        trace_span!(DEBUG, "CFConverter::decode", calendar = %self.calendar, values = values.len());
+        self.decoding.decode_many(values)
+    }
+    /// Encodes the datetimes into values, as [crate::encoder::CFEncoder::encode_cf]
+    pub fn encode<T: EncodeDuration, D: Borrow<CFDatetime>>(
+        &self,
+        datetimes: &[D],
+    ) -> crate::errors::Result<Vec<T>> {
+        trace_span!(DEBUG, "CFConverter::encode", calendar = %self.calendar, values = datetimes.len());
+        datetimes
+            .iter()
+            .map(|datetime| {
+                let duration = (datetime.borrow() - self.origin())?;
+                T::encode_duration(&self.unit, &duration, self.multiplier)
+            })
+            .collect()
+    }
+    /// Encodes the datetimes as [Self::encode] without stopping at the first error, so a large
+    /// batch is encoded in full and its problems reported at the end.
+    ///
+    /// The values of the datetimes that can not be encoded, e.g. of another calendar or that do
+    /// not fit in the type, are `fill_value`.
+    ///
+    /// # Returns
+    ///
+    /// The values, aligned with the datetimes, and the index and the error of every datetime
+    /// that could not be encoded, in increasing order of index
+    pub fn encode_lossy<T: EncodeDuration + Copy, D: Borrow<CFDatetime>>(
+        &self,
+        datetimes: &[D],
+        fill_value: T,
+    ) -> LossyEncoding<T> {
+        trace_span!(DEBUG, "CFConverter::encode_lossy", calendar = %self.calendar, values = datetimes.len());
+        let mut errors = Vec::new();
+        let values = datetimes
+            .iter()
+            .enumerate()
+            .map(|(index, datetime)| {
+                (datetime.borrow() - self.origin())
+                    .and_then(|duration| T::encode_duration(&self.unit, &duration, self.multiplier))
+                    .unwrap_or_else(|error| {
+                        errors.push((index, error));
+                        fill_value
+                    })
+            })
+            .collect();
+        (values, errors)
+    }
+    /// Converts values of the units of this converter into values of the units of `to`, as
+    /// decoding then encoding them with `to` but without making the datetimes.
+    ///
+    /// Each value is scaled into a duration since the reference date, shifted by the duration
+    /// between the two reference dates and encoded in the units of `to` with the integer
+    /// arithmetic and the rounding of [Self::encode].
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the values or an error of type
+    /// `crate::errors::Error::DifferentCalendars` if the calendars of the converters differ,
+    /// `crate::errors::Error::InvalidValue` for a NaN or infinite value or
+    /// `crate::errors::Error::OutOfBoundsCalendar` if a duration overflows
+    pub fn rebase<T: ScaleDuration, U: EncodeDuration>(
+        &self,
+        values: &[T],
+        to: &CFConverter,
+    ) -> crate::errors::Result<Vec<U>> {
+        trace_span!(DEBUG, "CFConverter::rebase", calendar = %self.calendar, values = values.len());
+        let offset = (self.origin() - to.origin())?;
+        values
+            .iter()
+            .enumerate()
+            .map(|(index, value)| {
+                let duration = value.check_finite(index)?.scale(self.decoding.duration())?;
+                U::encode_duration(&to.unit, &shift(&duration, &offset)?, to.multiplier)
+            })
+            .collect()
+    }
+}
+
+/// Adds two durations of seconds, returning an error of type
+/// `crate::errors::Error::OutOfBoundsCalendar` instead of overflowing
+fn shift(duration: &CFDuration, offset: &CFDuration) -> crate::errors::Result<CFDuration> {
+    let nanoseconds = (duration.seconds as i128 + offset.seconds as i128)
+        * constants::MAX_NS as i128
+        + duration.nanoseconds as i128
+        + offset.nanoseconds as i128;
+    let seconds =
+        i64::try_from(nanoseconds.div_euclid(constants::MAX_NS as i128)).map_err(|_| {
+            crate::errors::Error::OutOfBoundsCalendar(
+                duration.calendar.to_string(),
+                format!("{} plus {} overflows", duration, offset),
+            )
+        })?;
+    Ok(CFDuration::new(
+        seconds,
+        nanoseconds.rem_euclid(constants::MAX_NS as i128) as i64,
+        duration.calendar,
+    ))
+}
+
+/// Converts values from the units `from_units` to the units `to_units` of the same calendar, see
+/// [CFConverter::rebase].
+///
+/// The integer values stay integers, the values that do not fall on a whole number of the new
+/// units are rounded toward negative infinity as [crate::encoder::CFEncoder::encode_cf] does.
+///
+/// # Example
+///
+/// ```
+/// # use cftime_rs::{calendars::Calendar, converter::rebase_cf};
+/// let values: Vec<i64> = rebase_cf(
+///     &[876_576_i64, 876_600],
+///     "hours since 1900-01-01",
+///     "days since 2000-01-01",
+///     Calendar::Standard,
+/// )
+/// .unwrap();
+/// assert_eq!(values, vec![0, 1]);
+/// ```
+pub fn rebase_cf<T: ScaleDuration, U: EncodeDuration>(
+    values: &[T],
+    from_units: &str,
+    to_units: &str,
+    calendar: Calendar,
+) -> crate::errors::Result<Vec<U>> {
+    CFConverter::new(from_units, calendar)?.rebase(values, &CFConverter::new(to_units, calendar)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::VecCFDecoder;
+    use crate::encoder::CFEncoder;
+
+    #[test]
+    fn test_converter_matches_decoder_and_encoder() {
+        let units = "3 hours since 2000-01-01 06:00:00";
+        let converter = CFConverter::new(units, Calendar::NoLeap).unwrap();
+        assert_eq!(converter.units(), units);
+        assert_eq!(converter.calendar(), Calendar::NoLeap);
+        assert_eq!(converter.origin().ymd_hms().unwrap(), (2000, 1, 1, 6, 0, 0));
+        for batch in [vec![0.0, 1.5, -2.0], vec![1_000.25]] {
+            let datetimes = converter.decode(&batch).unwrap();
+            let expected = batch.decode_cf(units, Calendar::NoLeap).unwrap();
+            assert!(datetimes == expected);
+            let values: Vec<f64> = converter.encode(&datetimes).unwrap();
+            assert_eq!(values, batch);
+            let values: Vec<i64> = converter.encode(&datetimes).unwrap();
+            let expected: Vec<i64> = datetimes.encode_cf(units, Calendar::NoLeap).unwrap();
+            assert_eq!(values, expected);
+        }
+        let references: Vec<&CFDatetime> = vec![converter.origin()];
+        let values: Vec<i32> = converter.encode(&references).unwrap();
+        assert_eq!(values, vec![0]);
+    }
+
+    #[test]
+    fn test_converter_year_zero() {
+        // One day after 1 BC-12-31 is 1 AD-01-01 without a year zero
+        let converter =
+            CFConverter::new_with_year_zero("days since -1-12-31", Calendar::Julian, false)
+                .unwrap();
+        let datetimes = converter.decode(&[0, 1]).unwrap();
+        let dates: Vec<_> = datetimes
+            .iter()
+            .map(|datetime| datetime.ymd_hms_with_year_zero(false).unwrap())
+            .collect();
+        assert_eq!(dates, vec![(-1, 12, 31, 0, 0, 0), (1, 1, 1, 0, 0, 0)]);
+        let converter =
+            CFConverter::new_with_year_zero("days since -1-12-31", Calendar::Julian, true).unwrap();
+        assert_eq!(converter.decode(&[1]).unwrap()[0].ymd().unwrap(), (0, 1, 1));
+    }
+
+    #[test]
+    fn test_converter_with_options() {
+        use crate::parser::ParseMode;
+        let units = "Dayss  after 2000-01";
+        let lenient = ParseOptions::default().with_mode(ParseMode::Lenient);
+        let converter = CFConverter::new_with_options(units, Calendar::NoLeap, lenient).unwrap();
+        assert_eq!(converter.units(), units);
+        assert_eq!(
+            converter.decode(&[1]).unwrap()[0].ymd().unwrap(),
+            (2000, 1, 2)
+        );
+        assert!(CFConverter::new(units, Calendar::NoLeap).is_err());
+        // The strict mode also rejects the double spaces accepted by [CFConverter::new]
+        let units = "days  since 2000-01-01";
+        assert!(CFConverter::new(units, Calendar::NoLeap).is_ok());
+        assert!(
+            CFConverter::new_with_options(units, Calendar::NoLeap, ParseOptions::default())
+                .is_err_and(|e| e.is_parse_error())
+        );
+    }
+
+    #[test]
+    fn test_rebase_matches_decode_and_encode() {
+        let from = "hours since 1900-01-01 00:00:00";
+        for (to, calendar) in [
+            ("days since 2000-01-01", Calendar::Standard),
+            ("3 hours since 1850-06-15 12:00:00", Calendar::NoLeap),
+            ("months since 1990-01-01", Calendar::Day360),
+            ("seconds since 1900-01-01 00:00:00.5", Calendar::Julian),
+        ] {
+            let values = vec![0_i64, 1, -25, 876_599, 1_000_001, -3_000_000];
+            let datetimes = values.decode_cf(from, calendar).unwrap();
+            let expected: Vec<i64> = datetimes.encode_cf(to, calendar).unwrap();
+            let rebased: Vec<i64> = rebase_cf(&values, from, to, calendar).unwrap();
+            assert_eq!(rebased, expected, "{to} {calendar}");
+            let expected: Vec<f64> = datetimes.encode_cf(to, calendar).unwrap();
+            let rebased: Vec<f64> = rebase_cf(&values, from, to, calendar).unwrap();
+            assert_eq!(rebased, expected, "{to} {calendar}");
+            let values = vec![0.25, -1.5, 876_599.75, 1e6 / 3.0];
+            let datetimes = values.decode_cf(from, calendar).unwrap();
+            let expected: Vec<f64> = datetimes.encode_cf(to, calendar).unwrap();
+            let rebased: Vec<f64> = rebase_cf(&values, from, to, calendar).unwrap();
+            assert_eq!(rebased, expected, "{to} {calendar}");
+        }
+        // Back and forth with the same converters
+        let hours = CFConverter::new(from, Calendar::Standard).unwrap();
+        let days = CFConverter::new("days since 2000-01-01", Calendar::Standard).unwrap();
+        let rebased: Vec<i64> = hours.rebase(&[24_i64, 48], &days).unwrap();
+        assert_eq!(rebased, vec![-36_523, -36_522]);
+        let back: Vec<i64> = days.rebase(&rebased, &hours).unwrap();
+        assert_eq!(back, vec![24, 48]);
+    }
+
+    #[test]
+    fn test_rebase_errors() {
+        let from = "days since 2000-01-01";
+        let result: Result<Vec<i64>, _> = rebase_cf(
+            &[f64::NAN],
+            from,
+            "hours since 2000-01-01",
+            Calendar::Standard,
+        );
+        assert!(result.is_err_and(|e| matches!(e, crate::errors::Error::InvalidValue(0, _))));
+        let result: Result<Vec<i64>, _> = rebase_cf(
+            &[i64::MAX],
+            from,
+            "days since 1900-01-01",
+            Calendar::Standard,
+        );
+        assert!(result.is_err_and(|e| e.is_out_of_bounds()));
+        let result: Result<Vec<i32>, _> = rebase_cf(
+            &[i64::MAX / 86_400],
+            from,
+            "days since 1900-01-01",
+            Calendar::Standard,
+        );
+        assert!(result.is_err());
+        let standard = CFConverter::new(from, Calendar::Standard).unwrap();
+        let noleap = CFConverter::new(from, Calendar::NoLeap).unwrap();
+        let result: Result<Vec<i64>, _> = standard.rebase(&[0_i64], &noleap);
+        assert!(result.is_err_and(|e| e.is_calendar_error()));
+        let result: Result<Vec<i64>, _> =
+            rebase_cf(&[0_i64], "days after 2000", from, Calendar::Standard);
+        assert!(result.is_err_and(|e| e.is_parse_error()));
+    }
+
+    #[test]
+    fn test_converter_errors() {
+        assert!(
+            CFConverter::new("days after 2000-01-01", Calendar::Standard)
+                .is_err_and(|e| e.is_parse_error())
+        );
+        let converter = CFConverter::new("days since 2000-01-01", Calendar::Standard).unwrap();
+        assert!(converter
+            .decode(&[i64::MAX])
+            .is_err_and(|e| e.is_out_of_bounds()));
+        let datetime = CFDatetime::from_ymd(2000, 1, 2, Calendar::NoLeap).unwrap();
+        let result: Result<Vec<f64>, _> = converter.encode(&[datetime]);
+        assert!(result.is_err_and(|e| e.is_calendar_error()));
+    }
+
+    #[test]
+    fn test_encode_lossy() {
+        let converter = CFConverter::new("seconds since 2000-01-01", Calendar::Standard).unwrap();
+        let datetimes = [
+            CFDatetime::from_ymd(2000, 1, 2, Calendar::Standard).unwrap(),
+            CFDatetime::from_ymd(2100, 1, 1, Calendar::Standard).unwrap(),
+            CFDatetime::from_ymd(2000, 1, 2, Calendar::NoLeap).unwrap(),
+            CFDatetime::from_ymd(1999, 12, 31, Calendar::Standard).unwrap(),
+        ];
+        // 2100 does not fit in i32 seconds since 2000
+        let (values, errors) = converter.encode_lossy(&datetimes, i32::MIN);
+        assert_eq!(values, vec![86_400, i32::MIN, i32::MIN, -86_400]);
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].0, 1);
+        assert!(errors[0].1.is_out_of_bounds());
+        assert_eq!(errors[1].0, 2);
+        assert!(errors[1].1.is_calendar_error());
+        // The valid values are the ones of the strict encoding
+        let (values, errors) = converter.encode_lossy(&datetimes[..1], f64::NAN);
+        assert!(errors.is_empty());
+        assert_eq!(values, converter.encode::<f64, _>(&datetimes[..1]).unwrap());
+        let (values, errors) = converter.encode_lossy(&datetimes, f64::NAN);
+        assert!(values[2].is_nan());
+        assert_eq!(errors.len(), 1);
+    }
+}
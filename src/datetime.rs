@@ -1,17 +1,70 @@
-/// Wrapper for all the different datetime and calendars
 use crate::datetimes::all_leap::AllLeapDatetime;
 use crate::datetimes::day_360::Day360Datetime;
 use crate::datetimes::julian::JulianDatetime;
 use crate::datetimes::no_leap::NoLeapDatetime;
 use crate::datetimes::proleptic_gregorian::ProlepticGregorianDatetime;
 use crate::datetimes::standard::StandardDatetime;
+use crate::datetimes::storage::{DatetimeStorage, IntoStorage};
+#[cfg(feature = "inline-datetimes")]
 use crate::datetimes::traits::CalendarDatetime;
 use crate::datetimes::traits::CalendarDatetimeCreator;
 use crate::duration::CFDuration;
+use crate::instrument::trace_span;
+use crate::raw::YmdCursor;
+use crate::timezone::Tz;
+/// Wrapper for all the different datetime and calendars
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::borrow::Borrow;
 
-use crate::utils::normalize_nanoseconds;
+use crate::parser::Unit;
+use crate::utils::{
+    encode_duration, from_astronomical_year, get_hms_from_timestamp, normalize_nanoseconds,
+    to_astronomical_year,
+};
 use crate::{calendars::Calendar, constants};
 
+/// Year, month, day, hour, minute and second of a date
+pub type YmdHms = (i64, u8, u8, u8, u8, u8);
+
+/// Components of many datetimes as one array per component, e.g. the columns of a dataframe,
+/// see [CFDatetime::components_many]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DatetimeComponents {
+    pub years: Vec<i64>,
+    pub months: Vec<u8>,
+    pub days: Vec<u8>,
+    pub hours: Vec<u8>,
+    pub minutes: Vec<u8>,
+    pub seconds: Vec<u8>,
+    pub nanoseconds: Vec<u32>,
+}
+
+impl DatetimeComponents {
+    /// Returns empty arrays with room for `capacity` datetimes
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            years: Vec::with_capacity(capacity),
+            months: Vec::with_capacity(capacity),
+            days: Vec::with_capacity(capacity),
+            hours: Vec::with_capacity(capacity),
+            minutes: Vec::with_capacity(capacity),
+            seconds: Vec::with_capacity(capacity),
+            nanoseconds: Vec::with_capacity(capacity),
+        }
+    }
+    /// Returns the number of datetimes
+    pub fn len(&self) -> usize {
+        self.years.len()
+    }
+    /// Returns `true` if there are no datetimes
+    pub fn is_empty(&self) -> bool {
+        self.years.is_empty()
+    }
+}
+
 /// Represents a calendar CF datetime.
 ///
 /// Internally it uses the timestamp in seconds representation
@@ -25,7 +78,8 @@ use crate::{calendars::Calendar, constants};
 /// ## Creating a datetime
 ///
 /// ```rust
-/// let cf_datetime = CFDatetime::from_ymd_hms(1970, 1, 1, 0, 0, 0, Calendar::Standard).unwrap();
+/// # use cftime_rs::{calendars::Calendar, datetime::CFDatetime};
+/// let cf_datetime = CFDatetime::from_ymd_hms(1970, 1, 1, 0, 0, 0.0, Calendar::Standard).unwrap();
 /// // Computation of the timestamp
 /// assert_eq!(cf_datetime.timestamp(), 0);
 /// // Idempotence
@@ -33,14 +87,15 @@ use crate::{calendars::Calendar, constants};
 /// ```
 /// ## Duration between two datetimes
 /// ```rust
-/// let cf_datetime_1 = CFDatetime::from_ymd_hms(1970, 1, 1, 0, 0, 0, Calendar::Standard).unwrap();
-/// let cf_datetime_2 = CFDatetime::from_ymd_hms(1970, 1, 2, 0, 0, 0, Calendar::Standard).unwrap();
-/// let duration = cf_datetime_2 - cf_datetime_1;
-/// assert_eq!(duration.num_days, 1);
+/// # use cftime_rs::{calendars::Calendar, datetime::CFDatetime};
+/// let cf_datetime_1 = CFDatetime::from_ymd_hms(1970, 1, 1, 0, 0, 0.0, Calendar::Standard).unwrap();
+/// let cf_datetime_2 = CFDatetime::from_ymd_hms(1970, 1, 2, 0, 0, 0.0, Calendar::Standard).unwrap();
+/// let duration = (cf_datetime_2 - cf_datetime_1).unwrap();
+/// assert_eq!(duration.num_days(), 1.0);
 /// ```
 ///
 pub struct CFDatetime {
-    inner: Box<dyn CalendarDatetime + Send + Sync>,
+    inner: DatetimeStorage,
 }
 
 /// Immplementation of the CF convention specifications :
@@ -50,6 +105,10 @@ impl CFDatetime {
     pub fn calendar(&self) -> Calendar {
         self.inner.calendar()
     }
+    /// Returns the timezone
+    pub fn timezone(&self) -> Tz {
+        self.inner.timezone()
+    }
     /// Returns the timestamp
     pub fn timestamp(&self) -> i64 {
         self.inner.timestamp()
@@ -61,7 +120,7 @@ impl CFDatetime {
     ///
     /// A Result containing a tuple containing the hour, minute, second as `(i64, u8, u8)`.
     /// or an error of type `crate::errors::Error::InvalidDate` if the date cannot be computed from the timestamp.
-    pub fn ymd(&self) -> Result<(i64, u8, u8), crate::errors::Error> {
+    pub fn ymd(&self) -> crate::errors::Result<(i64, u8, u8)> {
         let (year, month, day, _, _, _) = self.ymd_hms()?;
 
         Ok((year, month, day))
@@ -74,7 +133,7 @@ impl CFDatetime {
     /// or an error of type `crate::errors::Error::InvalidDate` if the date cannot be computed from the timestamp.
     ///
     /// hms needs to first compute the date to see if the date is impossible
-    pub fn hms(&self) -> Result<(u8, u8, u8), crate::errors::Error> {
+    pub fn hms(&self) -> crate::errors::Result<(u8, u8, u8)> {
         let (_, _, _, hour, min, sec) = self.ymd_hms()?;
         Ok((hour, min, sec))
     }
@@ -85,9 +144,27 @@ impl CFDatetime {
     /// A Result containing a tuple containing the year, month,  day, hour, minute, second  as
     /// `(i64, u8, u8, u8, u8, u8)` or an error of type `crate::errors::Error::InvalidDate` if
     /// the date cannot be computed from the timestamp.
-    pub fn ymd_hms(&self) -> Result<(i64, u8, u8, u8, u8, u8), crate::errors::Error> {
+    pub fn ymd_hms(&self) -> crate::errors::Result<(i64, u8, u8, u8, u8, u8)> {
         self.inner.ymd_hms()
     }
+    /// Returns the year, month, day, hour, minute, second of the date with the year counted with
+    /// or without a year zero, see [crate::utils::to_astronomical_year].
+    ///
+    /// [Self::ymd_hms] counts the years with a year zero.
+    pub fn ymd_hms_with_year_zero(
+        &self,
+        has_year_zero: bool,
+    ) -> crate::errors::Result<(i64, u8, u8, u8, u8, u8)> {
+        let (year, month, day, hour, minute, second) = self.ymd_hms()?;
+        Ok((
+            from_astronomical_year(year, has_year_zero),
+            month,
+            day,
+            hour,
+            minute,
+            second,
+        ))
+    }
     /// Creates a new CFDatetime from the given year, month, day, hour, minute, second, and calendar.
     ///
     /// # Returns
@@ -102,41 +179,61 @@ impl CFDatetime {
         minute: u8,
         second: f32,
         calendar: Calendar,
-    ) -> Result<Self, crate::errors::Error> {
+    ) -> crate::errors::Result<Self> {
         match calendar {
             Calendar::ProlepticGregorian => Ok(Self {
-                inner: Box::new(ProlepticGregorianDatetime::from_ymd_hms(
+                inner: ProlepticGregorianDatetime::from_ymd_hms(
                     year, month, day, hour, minute, second,
-                )?),
+                )?
+                .into_storage(),
             }),
             Calendar::Standard => Ok(Self {
-                inner: Box::new(StandardDatetime::from_ymd_hms(
-                    year, month, day, hour, minute, second,
-                )?),
+                inner: StandardDatetime::from_ymd_hms(year, month, day, hour, minute, second)?
+                    .into_storage(),
             }),
             Calendar::Day360 => Ok(Self {
-                inner: Box::new(Day360Datetime::from_ymd_hms(
-                    year, month, day, hour, minute, second,
-                )?),
+                inner: Day360Datetime::from_ymd_hms(year, month, day, hour, minute, second)?
+                    .into_storage(),
             }),
             Calendar::Julian => Ok(Self {
-                inner: Box::new(JulianDatetime::from_ymd_hms(
-                    year, month, day, hour, minute, second,
-                )?),
+                inner: JulianDatetime::from_ymd_hms(year, month, day, hour, minute, second)?
+                    .into_storage(),
             }),
             Calendar::NoLeap => Ok(Self {
-                inner: Box::new(NoLeapDatetime::from_ymd_hms(
-                    year, month, day, hour, minute, second,
-                )?),
+                inner: NoLeapDatetime::from_ymd_hms(year, month, day, hour, minute, second)?
+                    .into_storage(),
             }),
             Calendar::AllLeap => Ok(Self {
-                inner: Box::new(AllLeapDatetime::from_ymd_hms(
-                    year, month, day, hour, minute, second,
-                )?),
+                inner: AllLeapDatetime::from_ymd_hms(year, month, day, hour, minute, second)?
+                    .into_storage(),
             }),
         }
     }
 
+    /// Creates a new CFDatetime from the given year, month, day, hour, minute, second, and calendar
+    /// with the year counted with or without a year zero, see [crate::utils::to_astronomical_year].
+    ///
+    /// [Self::from_ymd_hms] counts the years with a year zero.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing a new CFDatetime or an error of type `crate::errors::Error::InvalidDate` if
+    /// the date is not valid in the calendar or if the year is 0 without a year zero
+    #[allow(clippy::too_many_arguments)]
+    pub fn from_ymd_hms_with_year_zero(
+        year: i64,
+        month: u8,
+        day: u8,
+        hour: u8,
+        minute: u8,
+        second: f32,
+        calendar: Calendar,
+        has_year_zero: bool,
+    ) -> crate::errors::Result<Self> {
+        let year = to_astronomical_year(year, has_year_zero)?;
+        Self::from_ymd_hms(year, month, day, hour, minute, second, calendar)
+    }
+
     /// Creates a new CFDatetime from the given hour, minute, second, and calendar.
     /// It sets the year, month, day to 1970, 1, 1
     ///
@@ -149,7 +246,7 @@ impl CFDatetime {
         minute: u8,
         second: f32,
         calendar: Calendar,
-    ) -> Result<Self, crate::errors::Error> {
+    ) -> crate::errors::Result<Self> {
         Self::from_ymd_hms(
             constants::UNIX_DEFAULT_YEAR,
             constants::UNIX_DEFAULT_MONTH,
@@ -172,57 +269,278 @@ impl CFDatetime {
         month: u8,
         day: u8,
         calendar: Calendar,
-    ) -> Result<Self, crate::errors::Error> {
+    ) -> crate::errors::Result<Self> {
         Self::from_ymd_hms(year, month, day, 0, 0, 0.0, calendar)
     }
     /// Creates a new CFDatetime from a given timestamp and calendar atrting from the epoch
     ///
+    /// Nanoseconds above one second are carried into the timestamp, e.g. 1 second and
+    /// 1_500_000_000 nanoseconds is 2.5 seconds after the epoch.
+    ///
     /// # Returns
     ///
-    /// A Result containing a new CFDatetime or an error of type `crate::errors::Error::InvalidDate` if
-    /// the date is not valid in the calendar
+    /// A Result containing a new CFDatetime or an error of type `crate::errors::Error::OutOfBoundsCalendar`
+    /// if the carried timestamp does not fit in an `i64`
     pub fn from_timestamp(
         timestamp: i64,
         nanoseconds: u32,
         calendar: Calendar,
-    ) -> Result<Self, crate::errors::Error> {
+    ) -> crate::errors::Result<Self> {
+        let (carry, nanoseconds) = normalize_nanoseconds(nanoseconds as i64);
+        let timestamp = timestamp.checked_add(carry).ok_or_else(|| {
+            crate::errors::Error::OutOfBoundsCalendar(
+                calendar.to_string(),
+                format!("{timestamp} seconds and {nanoseconds} nanoseconds since epoch"),
+            )
+        })?;
         match calendar {
             Calendar::ProlepticGregorian => Ok(Self {
-                inner: Box::new(ProlepticGregorianDatetime::from_timestamp(
-                    timestamp,
-                    nanoseconds,
-                )),
+                inner: ProlepticGregorianDatetime::from_timestamp(timestamp, nanoseconds)
+                    .into_storage(),
             }),
             Calendar::Standard => Ok(Self {
-                inner: Box::new(StandardDatetime::from_timestamp(timestamp, nanoseconds)),
+                inner: StandardDatetime::from_timestamp(timestamp, nanoseconds).into_storage(),
             }),
             Calendar::Day360 => Ok(Self {
-                inner: Box::new(Day360Datetime::from_timestamp(timestamp, nanoseconds)),
+                inner: Day360Datetime::from_timestamp(timestamp, nanoseconds).into_storage(),
             }),
             Calendar::Julian => Ok(Self {
-                inner: Box::new(JulianDatetime::from_timestamp(timestamp, nanoseconds)),
+                inner: JulianDatetime::from_timestamp(timestamp, nanoseconds).into_storage(),
             }),
             Calendar::NoLeap => Ok(Self {
-                inner: Box::new(NoLeapDatetime::from_timestamp(timestamp, nanoseconds)),
+                inner: NoLeapDatetime::from_timestamp(timestamp, nanoseconds).into_storage(),
             }),
             Calendar::AllLeap => Ok(Self {
-                inner: Box::new(AllLeapDatetime::from_timestamp(timestamp, nanoseconds)),
+                inner: AllLeapDatetime::from_timestamp(timestamp, nanoseconds).into_storage(),
             }),
         }
     }
 
+    /// Returns the number of nanoseconds since the epoch
+    ///
+    /// This is the representation used by arrow, polars or numpy `datetime64[ns]`.
+    pub fn timestamp_nanos(&self) -> i128 {
+        self.timestamp() as i128 * constants::MAX_NS as i128 + self.nanoseconds() as i128
+    }
+    /// Creates a new CFDatetime from a given number of nanoseconds since the epoch and calendar
+    ///
+    /// # Returns
+    ///
+    /// A Result containing a new CFDatetime or an error of type `crate::errors::Error::OutOfBoundsCalendar` if
+    /// the number of seconds does not fit in an `i64`
+    pub fn from_timestamp_nanos(
+        timestamp_nanos: i128,
+        calendar: Calendar,
+    ) -> crate::errors::Result<Self> {
+        let ns_per_second = constants::MAX_NS as i128;
+        let timestamp = i64::try_from(timestamp_nanos.div_euclid(ns_per_second)).map_err(|_| {
+            crate::errors::Error::OutOfBoundsCalendar(
+                calendar.to_string(),
+                format!("{timestamp_nanos} nanoseconds since epoch"),
+            )
+        })?;
+        let nanoseconds = timestamp_nanos.rem_euclid(ns_per_second) as u32;
+        Self::from_timestamp(timestamp, nanoseconds, calendar)
+    }
+    /// Returns the number of seconds since the epoch as a float, fractional seconds included
+    ///
+    /// This is the representation of Python `datetime.timestamp()`, precise to the microsecond
+    /// for dates within a few centuries of the epoch.
+    pub fn timestamp_f64(&self) -> f64 {
+        self.timestamp() as f64 + self.nanoseconds() as f64 / constants::MAX_NS as f64
+    }
+    /// Creates a new CFDatetime from a float number of seconds since the epoch and calendar
+    ///
+    /// The fractional seconds are rounded to the nearest nanosecond.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing a new CFDatetime or an error of type `crate::errors::Error::OutOfBoundsCalendar` if
+    /// the number of seconds is not finite or does not fit in an `i64`
+    pub fn from_timestamp_f64(seconds: f64, calendar: Calendar) -> crate::errors::Result<Self> {
+        let out_of_bounds = || {
+            crate::errors::Error::OutOfBoundsCalendar(
+                calendar.to_string(),
+                format!("{seconds} seconds since epoch"),
+            )
+        };
+        // i64::MAX as f64 rounds up to 2^63, which is already out of range
+        if !seconds.is_finite() || seconds < i64::MIN as f64 || seconds >= i64::MAX as f64 {
+            return Err(out_of_bounds());
+        }
+        let whole_seconds = crate::float::floor(seconds);
+        let mut timestamp = whole_seconds as i64;
+        let mut nanoseconds =
+            crate::float::round((seconds - whole_seconds) * constants::MAX_NS as f64) as u32;
+        if nanoseconds >= constants::MAX_NS as u32 {
+            timestamp = timestamp.checked_add(1).ok_or_else(out_of_bounds)?;
+            nanoseconds -= constants::MAX_NS as u32;
+        }
+        Self::from_timestamp(timestamp, nanoseconds, calendar)
+    }
+    /// Returns the earliest datetime representable in the calendar, at
+    /// [constants::MIN_TIMESTAMP] seconds since the epoch
+    pub fn min(calendar: Calendar) -> Self {
+        Self::from_timestamp(constants::MIN_TIMESTAMP, 0, calendar)
+            .expect("The minimum timestamp is valid in every calendar")
+    }
+    /// Returns the latest datetime representable in the calendar, at
+    /// [constants::MAX_TIMESTAMP] seconds and [constants::MAX_NANOSECONDS] nanoseconds since the epoch
+    pub fn max(calendar: Calendar) -> Self {
+        Self::from_timestamp(
+            constants::MAX_TIMESTAMP,
+            constants::MAX_NANOSECONDS,
+            calendar,
+        )
+        .expect("The maximum timestamp is valid in every calendar")
+    }
+    /// Returns `true` if the number of nanoseconds since the epoch is between
+    /// [CFDatetime::min] and [CFDatetime::max]
+    pub fn is_in_valid_range(timestamp_nanos: i128) -> bool {
+        (constants::MIN_TIMESTAMP_NANOS..=constants::MAX_TIMESTAMP_NANOS).contains(&timestamp_nanos)
+    }
+    /// Creates a new CFDatetime from a given number of nanoseconds since the epoch, clamped
+    /// to the range between [CFDatetime::min] and [CFDatetime::max] of the calendar
+    pub fn clamp_to_valid_range(timestamp_nanos: i128, calendar: Calendar) -> Self {
+        let timestamp_nanos = timestamp_nanos.clamp(
+            constants::MIN_TIMESTAMP_NANOS,
+            constants::MAX_TIMESTAMP_NANOS,
+        );
+        Self::from_timestamp_nanos(timestamp_nanos, calendar)
+            .expect("A clamped timestamp is valid in every calendar")
+    }
+    /// Creates CFDatetimes from seconds since the epoch, e.g. an epoch column of a database,
+    /// without going through a units string
+    pub fn from_timestamps(
+        timestamps: &[i64],
+        calendar: Calendar,
+    ) -> crate::errors::Result<Vec<Self>> {
+        timestamps
+            .iter()
+            .map(|timestamp| Self::from_timestamp(*timestamp, 0, calendar))
+            .collect()
+    }
+    /// Creates CFDatetimes from nanoseconds since the epoch
+    ///
+    /// See [Self::from_timestamp_nanos]
+    pub fn from_timestamps_nanos(
+        timestamps_nanos: &[i128],
+        calendar: Calendar,
+    ) -> crate::errors::Result<Vec<Self>> {
+        timestamps_nanos
+            .iter()
+            .map(|timestamp_nanos| Self::from_timestamp_nanos(*timestamp_nanos, calendar))
+            .collect()
+    }
+    /// Returns the seconds since the epoch of the datetimes, the fractions of seconds are dropped
+    pub fn to_timestamps(datetimes: &[Self]) -> Vec<i64> {
+        datetimes
+            .iter()
+            .map(|datetime| datetime.timestamp())
+            .collect()
+    }
+    /// Returns the nanoseconds since the epoch of the datetimes
+    pub fn to_timestamps_nanos(datetimes: &[Self]) -> Vec<i128> {
+        datetimes
+            .iter()
+            .map(|datetime| datetime.timestamp_nanos())
+            .collect()
+    }
+
+    /// Returns the year, month, day, hour, minute and second of many CFDatetimes of the same calendar.
+    ///
+    /// Time axes are almost always sorted, so the date of each datetime is found by carrying the days
+    /// and months from the date of the previous one instead of scanning the years from 1970. An
+    /// unsorted datetime or a gap of more than a century falls back to the full conversion of [Self::ymd_hms].
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the dates in the same order or an error of type
+    /// `crate::errors::Error::DifferentCalendars` if the datetimes are not all of the same calendar
+    pub fn ymd_hms_many<D: Borrow<Self>>(datetimes: &[D]) -> crate::errors::Result<Vec<YmdHms>> {
+        trace_span!(DEBUG, "ymd_hms_many", values = datetimes.len());
+        let mut dates = Vec::with_capacity(datetimes.len());
+        Self::for_each_ymd_hms(datetimes, |_, date| dates.push(date))?;
+        Ok(dates)
+    }
+
+    /// Returns the components of many CFDatetimes of the same calendar as one array per
+    /// component, with the incremental conversion of [Self::ymd_hms_many].
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the components in the same order or an error of type
+    /// `crate::errors::Error::DifferentCalendars` if the datetimes are not all of the same calendar
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cftime_rs::{calendars::Calendar, datetime::CFDatetime};
+    /// let datetimes = CFDatetime::from_timestamps(&[0, 90_061], Calendar::NoLeap).unwrap();
+    /// let components = CFDatetime::components_many(&datetimes).unwrap();
+    /// assert_eq!(components.days, vec![1, 2]);
+    /// assert_eq!(components.hours, vec![0, 1]);
+    /// ```
+    pub fn components_many<D: Borrow<Self>>(
+        datetimes: &[D],
+    ) -> crate::errors::Result<DatetimeComponents> {
+        trace_span!(DEBUG, "components_many", values = datetimes.len());
+        let mut components = DatetimeComponents::with_capacity(datetimes.len());
+        Self::for_each_ymd_hms(
+            datetimes,
+            |datetime, (year, month, day, hour, minute, second)| {
+                components.years.push(year);
+                components.months.push(month);
+                components.days.push(day);
+                components.hours.push(hour);
+                components.minutes.push(minute);
+                components.seconds.push(second);
+                components.nanoseconds.push(datetime.nanoseconds());
+            },
+        )?;
+        Ok(components)
+    }
+
+    /// Calls `f` with each datetime and its date, carrying the date of a sorted time axis from
+    /// one datetime to the next
+    fn for_each_ymd_hms<D: Borrow<Self>>(
+        datetimes: &[D],
+        mut f: impl FnMut(&Self, YmdHms),
+    ) -> crate::errors::Result<()> {
+        let Some(first) = datetimes.first() else {
+            return Ok(());
+        };
+        let calendar = first.borrow().calendar();
+        let mut cursor = YmdCursor::new(calendar);
+        for datetime in datetimes {
+            let datetime = datetime.borrow();
+            if datetime.calendar() != calendar {
+                return Err(crate::errors::Error::DifferentCalendars(
+                    calendar.to_string(),
+                    datetime.calendar().to_string(),
+                ));
+            }
+            let timestamp = datetime.timestamp();
+            let (year, month, day) =
+                cursor.ymd(timestamp.div_euclid(constants::SECS_PER_DAY as i64));
+            let (hour, minute, second) = get_hms_from_timestamp(timestamp);
+            f(datetime, (year, month, day, hour, minute, second));
+        }
+        Ok(())
+    }
+
     /// Returns the hours of the date.
-    pub fn hours(&self) -> Result<u8, crate::errors::Error> {
+    pub fn hours(&self) -> crate::errors::Result<u8> {
         let (hour, _, _) = self.hms()?;
         Ok(hour)
     }
     /// Returns the minutes of the date.
-    pub fn minutes(&self) -> Result<u8, crate::errors::Error> {
+    pub fn minutes(&self) -> crate::errors::Result<u8> {
         let (_, min, _) = self.hms()?;
         Ok(min)
     }
     /// Returns the seconds of the date.
-    pub fn seconds(&self) -> Result<u8, crate::errors::Error> {
+    pub fn seconds(&self) -> crate::errors::Result<u8> {
         let (_, _, sec) = self.hms()?;
         Ok(sec)
     }
@@ -230,6 +548,330 @@ impl CFDatetime {
     pub fn nanoseconds(&self) -> u32 {
         self.inner.nanoseconds()
     }
+    /// Returns the date at midnight, i.e. with the hour, minute, second and nanoseconds set to zero.
+    ///
+    /// Fails with `crate::errors::Error::OutOfBoundsCalendar` if the midnight is before the
+    /// minimum of the calendar, see [Self::min]
+    pub fn date(&self) -> crate::errors::Result<Self> {
+        let seconds = self.timestamp().rem_euclid(constants::SECS_PER_DAY as i64);
+        let timestamp = self.timestamp().checked_sub(seconds).ok_or_else(|| {
+            crate::errors::Error::OutOfBoundsCalendar(
+                self.calendar().to_string(),
+                format!(
+                    "midnight of the day of {} seconds since epoch",
+                    self.timestamp()
+                ),
+            )
+        })?;
+        Self::from_timestamp(timestamp, 0, self.calendar())
+    }
+    fn shift_days(&self, days: i64) -> crate::errors::Result<Self> {
+        let timestamp = self
+            .timestamp()
+            .checked_add(days * constants::SECS_PER_DAY as i64)
+            .ok_or_else(|| {
+                crate::errors::Error::OutOfBoundsCalendar(
+                    self.calendar().to_string(),
+                    format!("{days} days from {}", self.timestamp()),
+                )
+            })?;
+        Self::from_timestamp(timestamp, self.nanoseconds(), self.calendar())
+    }
+    /// Returns the datetime of the next day at the same time of the day.
+    ///
+    /// The day after 1582-10-04 is 1582-10-15 in the standard calendar.
+    pub fn succ_day(&self) -> crate::errors::Result<Self> {
+        self.shift_days(1)
+    }
+    /// Returns the datetime of the previous day at the same time of the day.
+    pub fn pred_day(&self) -> crate::errors::Result<Self> {
+        self.shift_days(-1)
+    }
+    /// Returns the first day of the month at midnight.
+    pub fn start_of_month(&self) -> crate::errors::Result<Self> {
+        let (year, month, _) = self.ymd()?;
+        Self::from_ymd(year, month, 1, self.calendar())
+    }
+    /// Returns the last day of the month at midnight, e.g. `2000-02-30` in the 360_day calendar.
+    ///
+    /// Bins of whole months are better written as `[start_of_month, start_of_month + 1 month)`,
+    /// see [Self::add_months], so the times of the last day fall in the bin.
+    pub fn end_of_month(&self) -> crate::errors::Result<Self> {
+        self.start_of_month()?.add_months(1)?.pred_day()
+    }
+    /// Returns the first of January of the year at midnight.
+    pub fn start_of_year(&self) -> crate::errors::Result<Self> {
+        let (year, _, _) = self.ymd()?;
+        Self::from_ymd(year, 1, 1, self.calendar())
+    }
+    /// Returns the last day of the year at midnight, e.g. `2000-12-30` in the 360_day calendar.
+    pub fn end_of_year(&self) -> crate::errors::Result<Self> {
+        self.start_of_year()?.add_months(12)?.pred_day()
+    }
+    /// Returns the duration elapsed since midnight.
+    pub fn time_of_day(&self) -> CFDuration {
+        let seconds = self.timestamp().rem_euclid(constants::SECS_PER_DAY as i64);
+        CFDuration::new(seconds, self.nanoseconds() as i64, self.calendar())
+    }
+    /// Returns the number of seconds elapsed since midnight, including the fraction of seconds.
+    pub fn seconds_since_midnight(&self) -> f64 {
+        self.time_of_day().num_seconds()
+    }
+    /// Returns the ordinal day number of the date, as `toordinal` of python-cftime.
+    ///
+    /// Day 0 starts at noon on January 1 of the year -4713 in the julian calendar for the
+    /// standard, julian and proleptic gregorian calendars, i.e. the ordinal is the Julian Day Number.
+    /// Day 0 starts at noon on January 1 of the year 0 for the noleap, all_leap and 360_day calendars.
+    pub fn to_ordinal(&self) -> i64 {
+        self.timestamp().div_euclid(constants::SECS_PER_DAY as i64)
+            + ordinal_of_epoch(self.calendar())
+    }
+    /// Returns the ordinal day number of the date including the fraction of the day, as
+    /// `toordinal(fractional=True)` of python-cftime. The date at midnight has a fraction of -0.5
+    /// since the day starts at noon, see [CFDatetime::to_ordinal].
+    pub fn to_ordinal_fractional(&self) -> f64 {
+        self.to_ordinal() as f64 - 0.5
+            + self.seconds_since_midnight() / constants::SECS_PER_DAY as f64
+    }
+    /// Creates a new CFDatetime from an ordinal day number, as `fromordinal` of python-cftime.
+    /// It is the inverse of [CFDatetime::to_ordinal_fractional], so an integer ordinal gives the
+    /// date at noon.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing a new CFDatetime or an error of type `crate::errors::Error::OutOfBoundsCalendar`
+    /// if the ordinal is not finite or the date is out of the range of the calendar
+    pub fn from_ordinal(ordinal: f64, calendar: Calendar) -> crate::errors::Result<Self> {
+        let out_of_bounds = || {
+            crate::errors::Error::OutOfBoundsCalendar(
+                calendar.to_string(),
+                format!("ordinal {ordinal}"),
+            )
+        };
+        // i64::MAX as f64 rounds up to 2^63 which is out of bounds
+        if !ordinal.is_finite() || crate::float::abs(ordinal) >= i64::MAX as f64 {
+            return Err(out_of_bounds());
+        }
+        let days = crate::float::floor(ordinal);
+        let nanoseconds_in_day = crate::float::round(
+            (ordinal - days) * (constants::SECS_PER_DAY as i64 * constants::MAX_NS) as f64,
+        );
+        let days = (days as i64)
+            .checked_sub(ordinal_of_epoch(calendar))
+            .ok_or_else(out_of_bounds)?;
+        // The ordinal days start at noon
+        let timestamp_nanos = (days as i128 * constants::SECS_PER_DAY as i128
+            + constants::SECS_PER_DAY as i128 / 2)
+            * constants::MAX_NS as i128
+            + nanoseconds_in_day as i128;
+        Self::from_timestamp_nanos(timestamp_nanos, calendar)
+    }
+    /// Returns the J2000.0 epoch of the calendar, `2000-01-01 12:00:00`, whose ordinal is the
+    /// Julian Day 2451545.0 in the standard calendar.
+    ///
+    /// The epoch is in UTC, the 64 seconds between Terrestrial Time and UTC in 2000 are ignored.
+    pub fn j2000(calendar: Calendar) -> Self {
+        Self::from_ymd_hms(2000, 1, 1, 12, 0, 0.0, calendar)
+            .expect("2000-01-01 12:00:00 exists in every calendar")
+    }
+    /// Returns the number of days elapsed since `epoch`, including the fraction of the day,
+    /// negative if the datetime is before `epoch`.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the number of days or an error of type
+    /// `crate::errors::Error::DifferentCalendars` if `epoch` is not in the calendar of the datetime
+    pub fn days_since(&self, epoch: &CFDatetime) -> crate::errors::Result<f64> {
+        if self.calendar() != epoch.calendar() {
+            return Err(crate::errors::Error::DifferentCalendars(
+                self.calendar().to_string(),
+                epoch.calendar().to_string(),
+            ));
+        }
+        let nanoseconds = self.timestamp_nanos() - epoch.timestamp_nanos();
+        Ok(nanoseconds as f64 / (constants::SECS_PER_DAY as i64 * constants::MAX_NS) as f64)
+    }
+    /// Returns the number of centuries elapsed since `epoch`, a century being
+    /// [Calendar::days_per_century] days of the calendar.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the number of centuries or an error as [Self::days_since]
+    pub fn julian_centuries_since(&self, epoch: &CFDatetime) -> crate::errors::Result<f64> {
+        Ok(self.days_since(epoch)? / self.calendar().days_per_century())
+    }
+    /// Returns the number of Julian centuries since the J2000.0 epoch of the calendar, the time
+    /// variable `T` of the solar position formulas, see [Self::j2000] and [Self::julian_centuries_since].
+    ///
+    /// ```
+    /// # use cftime_rs::{calendars::Calendar, datetime::CFDatetime};
+    /// let datetime = CFDatetime::from_ymd_hms(2050, 1, 1, 12, 0, 0.0, Calendar::Day360).unwrap();
+    /// assert_eq!(datetime.julian_century(), 0.5);
+    /// ```
+    pub fn julian_century(&self) -> f64 {
+        let epoch = Self::j2000(self.calendar());
+        self.julian_centuries_since(&epoch)
+            .expect("The epoch is in the calendar of the datetime")
+    }
+    /// Returns the time elapsed since `origin` as a number of `unit`, the value that
+    /// [crate::encoder::CFEncoder::encode_cf] gives with the units `<unit> since <origin>` without
+    /// formatting and parsing them. Months and years have the fixed lengths of the encoding.
+    ///
+    /// ```
+    /// # use cftime_rs::{calendars::Calendar, datetime::CFDatetime, parser::Unit};
+    /// let origin = CFDatetime::from_ymd(2000, 1, 1, Calendar::NoLeap).unwrap();
+    /// let datetime = CFDatetime::from_ymd_hms(2000, 1, 2, 12, 0, 0.0, Calendar::NoLeap).unwrap();
+    /// assert_eq!(datetime.elapsed_since(&origin, Unit::Hour).unwrap(), 36.0);
+    /// ```
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the number of units or an error of type
+    /// `crate::errors::Error::DifferentCalendars` if `origin` is not in the calendar of the datetime
+    pub fn elapsed_since(&self, origin: &CFDatetime, unit: Unit) -> crate::errors::Result<f64> {
+        let duration = (self - origin)?;
+        Ok(encode_duration(&unit, &duration, 1))
+    }
+    /// Returns `true` if the year of the date is a leap year in its calendar.
+    pub fn is_leap_year(&self) -> crate::errors::Result<bool> {
+        let (year, _, _) = self.ymd()?;
+        Ok(self.calendar().is_leap_year(year))
+    }
+    /// Returns the number of days of the month of the date in its calendar.
+    pub fn days_in_month(&self) -> crate::errors::Result<u8> {
+        let (year, month, _) = self.ymd()?;
+        self.calendar().days_in_month(year, month)
+    }
+    /// Returns the number of days of the year of the date in its calendar.
+    pub fn days_in_year(&self) -> crate::errors::Result<u16> {
+        let (year, _, _) = self.ymd()?;
+        Ok(self.calendar().days_in_year(year))
+    }
+    /// Returns the day of the week from 0 for Monday to 6 for Sunday, as `dayofwk` of
+    /// python-cftime. The days of the week follow the ordinal day numbers, see [CFDatetime::to_ordinal].
+    pub fn weekday(&self) -> u8 {
+        self.to_ordinal().rem_euclid(7) as u8
+    }
+    /// Returns the english name of the day of the week, see [constants::WEEKDAYS]
+    pub fn weekday_name(&self) -> &'static str {
+        self.weekday_name_in(&constants::WEEKDAYS)
+    }
+    /// Returns the name of the day of the week in a table of names starting on Monday, e.g. to
+    /// localize the names
+    pub fn weekday_name_in<'a>(&self, names: &[&'a str; 7]) -> &'a str {
+        names[self.weekday() as usize]
+    }
+    /// Returns the english name of the month, see [constants::MONTHS]
+    pub fn month_name(&self) -> crate::errors::Result<&'static str> {
+        self.month_name_in(&constants::MONTHS)
+    }
+    /// Returns the name of the month in a table of names starting with January, e.g. to localize
+    /// the names
+    pub fn month_name_in<'a>(&self, names: &[&'a str; 12]) -> crate::errors::Result<&'a str> {
+        let (_, month, _) = self.ymd()?;
+        Ok(names[month as usize - 1])
+    }
+    /// Returns a new CFDatetime with the given fields replaced.
+    ///
+    /// The fields set to `None` are kept from the current datetime and the calendar is
+    /// not changed.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing a new CFDatetime or an error of type `crate::errors::Error::InvalidDate`
+    /// or `crate::errors::Error::InvalidTime` if the resulting date is not valid in the calendar
+    #[allow(clippy::too_many_arguments)]
+    pub fn replace(
+        &self,
+        year: Option<i64>,
+        month: Option<u8>,
+        day: Option<u8>,
+        hour: Option<u8>,
+        minute: Option<u8>,
+        second: Option<u8>,
+        nanosecond: Option<u32>,
+    ) -> crate::errors::Result<Self> {
+        let (
+            current_year,
+            current_month,
+            current_day,
+            current_hour,
+            current_minute,
+            current_second,
+        ) = self.ymd_hms()?;
+        let year = year.unwrap_or(current_year);
+        let month = month.unwrap_or(current_month);
+        let day = day.unwrap_or(current_day);
+        let hour = hour.unwrap_or(current_hour);
+        let minute = minute.unwrap_or(current_minute);
+        let second = second.unwrap_or(current_second);
+        let nanosecond = nanosecond.unwrap_or(self.nanoseconds());
+
+        if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+            return Err(crate::errors::Error::InvalidDate(format!(
+                "{year}-{month:02}-{day:02} is out of bounds"
+            )));
+        }
+        if nanosecond as i64 >= constants::MAX_NS {
+            return Err(crate::errors::Error::InvalidTime(format!(
+                "Nanosecond {nanosecond} is out of bounds"
+            )));
+        }
+        let datetime = Self::from_ymd_hms(
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second as f32,
+            self.calendar(),
+        )?;
+        // Days that do not exist in the calendar overflow on the next month
+        if datetime.ymd()? != (year, month, day) {
+            return Err(crate::errors::Error::InvalidDate(format!(
+                "{year}-{month:02}-{day:02} is not defined in the {} calendar",
+                self.calendar()
+            )));
+        }
+        Self::from_timestamp(datetime.timestamp(), nanosecond, self.calendar())
+    }
+    /// Returns a new CFDatetime shifted by the given number of calendar months.
+    ///
+    /// The time of the day is kept and the day is clamped to the last day of the
+    /// resulting month, i.e. `2000-01-31` plus one month gives `2000-02-29` in the standard calendar.
+    /// Fails with `crate::errors::Error::OutOfBoundsCalendar` if the year of the result overflows.
+    pub fn add_months(&self, months: i64) -> crate::errors::Result<Self> {
+        if months == 0 {
+            return Self::from_timestamp(self.timestamp(), self.nanoseconds(), self.calendar());
+        }
+        let (year, month, mut day) = self.ymd()?;
+        let total_months = year
+            .checked_mul(12)
+            .and_then(|total_months| total_months.checked_add(month as i64 - 1))
+            .and_then(|total_months| total_months.checked_add(months))
+            .ok_or_else(|| {
+                crate::errors::Error::OutOfBoundsCalendar(
+                    self.calendar().to_string(),
+                    format!("{months} months from {year}-{month:02}-{day:02}"),
+                )
+            })?;
+        let new_year = total_months.div_euclid(12);
+        let new_month = (total_months.rem_euclid(12) + 1) as u8;
+        loop {
+            match self.replace(
+                Some(new_year),
+                Some(new_month),
+                Some(day),
+                None,
+                None,
+                None,
+                None,
+            ) {
+                Err(crate::errors::Error::InvalidDate(_)) if day > 28 => day -= 1,
+                result => return result,
+            }
+        }
+    }
     /// Change the calendar of the CFDatetime.
     ///
     /// It get the year, month, day, hour, minute, second and nanoseconds by calling the [Self::ymd_hms]
@@ -239,7 +881,7 @@ impl CFDatetime {
     /// # Returns
     /// A Result containing a new CFDatetime or an error of type `crate::errors::Error::InvalidDate` if
     /// the date is not valid in the calendar
-    pub fn change_calendar(&self, calendar: Calendar) -> Result<Self, crate::errors::Error> {
+    pub fn change_calendar(&self, calendar: Calendar) -> crate::errors::Result<Self> {
         let (year, month, day, hour, minute, second) = self.ymd_hms()?;
         let ns = self.nanoseconds();
         let f_second = second as f32 + ns as f32 / 1e9;
@@ -259,11 +901,192 @@ impl CFDatetime {
     pub fn change_calendar_from_timestamp(
         &self,
         calendar: Calendar,
-    ) -> Result<Self, crate::errors::Error> {
+    ) -> crate::errors::Result<Self> {
         let timestamp = self.timestamp();
         let nanoseconds = self.nanoseconds();
         Self::from_timestamp(timestamp, nanoseconds, calendar)
     }
+    /// Change the calendar of the CFDatetime by keeping its relative position within the year
+    ///
+    /// The elapsed fraction of the year is computed in the current calendar and the same
+    /// fraction is applied to the same year of the new calendar. This is the behaviour of
+    /// xarray `interp_calendar` and is useful to compare e.g. a `360_day` model output with
+    /// observations in the `standard` calendar.
+    ///
+    /// # Returns
+    /// A Result containing a new CFDatetime or an error of type `crate::errors::Error::InvalidDate` if
+    /// the bounds of the year cannot be computed in one of the calendars
+    pub fn interp_calendar(&self, calendar: Calendar) -> crate::errors::Result<Self> {
+        let (year, _, _) = self.ymd()?;
+        let source_start = Self::from_ymd(year, 1, 1, self.calendar())?;
+        let source_end = Self::from_ymd(year + 1, 1, 1, self.calendar())?;
+        let target_start = Self::from_ymd(year, 1, 1, calendar)?;
+        let target_end = Self::from_ymd(year + 1, 1, 1, calendar)?;
+
+        let ns_per_second = constants::MAX_NS as i128;
+        let elapsed = (self.timestamp() - source_start.timestamp()) as i128 * ns_per_second
+            + self.nanoseconds() as i128;
+        let source_length = (source_end.timestamp() - source_start.timestamp()) as i128;
+        let target_length = (target_end.timestamp() - target_start.timestamp()) as i128;
+        let new_elapsed = elapsed * target_length / source_length;
+
+        Self::from_timestamp(
+            target_start.timestamp() + new_elapsed.div_euclid(ns_per_second) as i64,
+            new_elapsed.rem_euclid(ns_per_second) as u32,
+            calendar,
+        )
+    }
+}
+
+impl CFDatetime {
+    /// Changes the calendar of many CFDatetimes as [Self::change_calendar] and reports the dates that
+    /// do not exist in the new calendar, e.g. `2000-02-29` in the `noleap` calendar, instead of
+    /// silently moving them to the next month.
+    ///
+    /// The nanoseconds are kept exactly. The dates that do not exist in the new calendar are
+    /// dropped or clamped to the last valid day of their month depending on `policy`.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the converted datetimes and a [CalendarChangeReport] listing the dropped
+    /// and clamped dates, or an error of type `crate::errors::Error::DifferentCalendars` if the
+    /// datetimes are not all of the same calendar
+    pub fn change_calendar_many(
+        datetimes: &[Self],
+        calendar: Calendar,
+        policy: InvalidDatePolicy,
+    ) -> crate::errors::Result<(Vec<Self>, CalendarChangeReport)> {
+        let mut converted = Vec::with_capacity(datetimes.len());
+        let mut report = CalendarChangeReport::default();
+        let dates = Self::ymd_hms_many(datetimes)?;
+        for (index, (datetime, original)) in datetimes.iter().zip(dates).enumerate() {
+            let (year, month, day, hour, minute, second) = original;
+            let mut new_day = day;
+            let new_datetime = loop {
+                match Self::from_ymd_hms(
+                    year,
+                    month,
+                    new_day,
+                    hour,
+                    minute,
+                    second as f32,
+                    calendar,
+                ) {
+                    // Some calendars accept a day after the end of the month and move it to the next month
+                    Ok(candidate) if candidate.ymd()? == (year, month, new_day) => {
+                        break Some(candidate)
+                    }
+                    Ok(_) | Err(crate::errors::Error::InvalidDate(_)) => {}
+                    Err(e) => return Err(e),
+                }
+                if policy == InvalidDatePolicy::Drop || new_day == 1 {
+                    break None;
+                }
+                new_day -= 1;
+            };
+            let action = match new_datetime {
+                Some(new_datetime) => {
+                    converted.push(Self::from_timestamp(
+                        new_datetime.timestamp(),
+                        datetime.nanoseconds(),
+                        calendar,
+                    )?);
+                    if new_day == day {
+                        continue;
+                    }
+                    CalendarChangeAction::Clamped { day: new_day }
+                }
+                None => CalendarChangeAction::Dropped,
+            };
+            report.issues.push(CalendarChangeIssue {
+                index,
+                original,
+                action,
+            });
+        }
+        Ok((converted, report))
+    }
+}
+
+/// What [CFDatetime::change_calendar_many] does with a date that does not exist in the new
+/// calendar, e.g. `2000-02-29` in the `noleap` calendar
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum InvalidDatePolicy {
+    /// Removes the date from the converted datetimes
+    #[default]
+    Drop,
+    /// Replaces the day with the last valid day of the month, e.g. `2000-02-28`
+    Clamp,
+}
+
+/// Action taken by [CFDatetime::change_calendar_many] on a date that does not exist in the new calendar
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum CalendarChangeAction {
+    /// The date is not part of the converted datetimes
+    Dropped,
+    /// The date is converted to the given day of the same month
+    Clamped { day: u8 },
+}
+
+/// A date that does not exist in the new calendar
+#[derive(Debug, PartialEq, Clone)]
+pub struct CalendarChangeIssue {
+    /// Index of the date in the datetimes given to [CFDatetime::change_calendar_many]
+    pub index: usize,
+    /// Date in the original calendar
+    pub original: YmdHms,
+    pub action: CalendarChangeAction,
+}
+
+impl core::fmt::Display for CalendarChangeIssue {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let (year, month, day, hour, minute, second) = self.original;
+        write!(
+            f,
+            "{year:04}-{month:02}-{day:02} {hour:02}:{minute:02}:{second:02} at index {} ",
+            self.index
+        )?;
+        match self.action {
+            CalendarChangeAction::Dropped => write!(f, "dropped"),
+            CalendarChangeAction::Clamped { day } => {
+                write!(f, "clamped to {year:04}-{month:02}-{day:02}")
+            }
+        }
+    }
+}
+
+/// Result of [CFDatetime::change_calendar_many]
+#[derive(Debug, Default, PartialEq, Clone)]
+pub struct CalendarChangeReport {
+    pub issues: Vec<CalendarChangeIssue>,
+}
+
+impl CalendarChangeReport {
+    /// Returns `true` if every date exists in the new calendar
+    pub fn is_lossless(&self) -> bool {
+        self.issues.is_empty()
+    }
+    /// Returns the indices of the dropped dates
+    pub fn dropped(&self) -> impl Iterator<Item = usize> + '_ {
+        self.issues
+            .iter()
+            .filter(|issue| issue.action == CalendarChangeAction::Dropped)
+            .map(|issue| issue.index)
+    }
+}
+
+/// Returns the ordinal day number of 1970-01-01 in the calendar, see [CFDatetime::to_ordinal]
+fn ordinal_of_epoch(calendar: Calendar) -> i64 {
+    match calendar {
+        // Julian Day Number of 1970-01-01 in the gregorian calendar
+        Calendar::Standard | Calendar::ProlepticGregorian => 2_440_588,
+        // 1970-01-01 in the julian calendar is 13 days after 1970-01-01 in the gregorian calendar
+        Calendar::Julian => 2_440_601,
+        // Number of days from the year 0
+        Calendar::NoLeap => 1970 * constants::DAYS_PER_YEAR_NON_LEAP,
+        Calendar::AllLeap => 1970 * constants::DAYS_PER_YEAR_LEAP,
+        Calendar::Day360 => 1970 * 360,
+    }
 }
 
 impl PartialEq for CFDatetime {
@@ -273,29 +1096,355 @@ impl PartialEq for CFDatetime {
             && self.nanoseconds() == other.nanoseconds()
     }
 }
-/// Display a CFDatetime with the following format : `YYYY-MM-DD HH:MM:SS.SSS`
-impl std::fmt::Display for CFDatetime {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        let nanoseconds = self.nanoseconds() as f64 / 1_000_000_000.;
-        match self.ymd_hms() {
-            Ok((year, month, day, hour, minute, second)) => {
-                write!(
-                    f,
-                    "{:04}-{:02}-{:02} {:02}:{:02}:{:02}.{:03}",
-                    year, month, day, hour, minute, second, nanoseconds,
-                )
+/// Number of digits used to display the fraction of seconds of a CFDatetime
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Default)]
+#[non_exhaustive]
+pub enum Precision {
+    /// Display the fraction of seconds only if it is not zero and without trailing zeros
+    #[default]
+    Auto,
+    /// Display exactly the given number of digits, up to 9 (nanoseconds). The digits after are
+    /// truncated.
+    Fixed(u8),
+}
+
+impl Precision {
+    /// Display the milliseconds, i.e. 3 digits
+    pub const MILLISECONDS: Precision = Precision::Fixed(3);
+    /// Display the microseconds, i.e. 6 digits
+    pub const MICROSECONDS: Precision = Precision::Fixed(6);
+    /// Display the nanoseconds, i.e. 9 digits
+    pub const NANOSECONDS: Precision = Precision::Fixed(9);
+}
+
+/// Options used to display a CFDatetime
+///
+/// The default options give the same output as the `Display` implementation of [CFDatetime],
+/// which can be parsed back within units e.g. `days since 2000-01-01 12:00:00.5`.
+/// New options may be added in the future, so the options are built from the default ones with
+/// the `with_*` methods.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Default)]
+#[non_exhaustive]
+pub struct DisplayOptions {
+    /// Number of digits of the fraction of seconds
+    pub precision: Precision,
+    /// Use the ISO 8601 `T` separator between the date and the time instead of a space
+    pub iso_separator: bool,
+    /// Prefix the years greater than 9999 with a `+` sign as in the ISO 8601 expanded representation
+    pub expanded_year: bool,
+    /// Append the CF name of the calendar, e.g. `2000-02-30 00:00:00 [360_day]`, as the alternate
+    /// format `{:#}` does
+    pub calendar: bool,
+}
+
+impl DisplayOptions {
+    /// Sets the number of digits of the fraction of seconds
+    pub fn with_precision(mut self, precision: Precision) -> Self {
+        self.precision = precision;
+        self
+    }
+    /// Sets whether the ISO 8601 `T` separator is used between the date and the time
+    pub fn with_iso_separator(mut self, iso_separator: bool) -> Self {
+        self.iso_separator = iso_separator;
+        self
+    }
+    /// Sets whether the years greater than 9999 are prefixed with a `+` sign
+    pub fn with_expanded_year(mut self, expanded_year: bool) -> Self {
+        self.expanded_year = expanded_year;
+        self
+    }
+    /// Sets whether the CF name of the calendar is appended between brackets
+    pub fn with_calendar(mut self, calendar: bool) -> Self {
+        self.calendar = calendar;
+        self
+    }
+}
+
+/// Helper returned by [CFDatetime::display_with] to display a CFDatetime with [DisplayOptions]
+pub struct CFDatetimeDisplay<'a> {
+    datetime: &'a CFDatetime,
+    options: DisplayOptions,
+}
+
+impl CFDatetime {
+    /// Returns an object implementing `Display` that formats the datetime with the given options
+    ///
+    /// # Example
+    /// ```
+    /// # use cftime_rs::{calendars::Calendar, datetime::*};
+    /// let datetime = CFDatetime::from_ymd_hms(2000, 1, 1, 12, 0, 0.5, Calendar::Standard).unwrap();
+    /// let options = DisplayOptions::default()
+    ///     .with_precision(Precision::Fixed(3))
+    ///     .with_iso_separator(true);
+    /// assert_eq!(datetime.display_with(options).to_string(), "2000-01-01T12:00:00.500");
+    /// ```
+    pub fn display_with(&self, options: DisplayOptions) -> CFDatetimeDisplay<'_> {
+        CFDatetimeDisplay {
+            datetime: self,
+            options,
+        }
+    }
+}
+
+impl core::fmt::Display for CFDatetimeDisplay<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let (year, month, day, hour, minute, second) = match self.datetime.ymd_hms() {
+            Ok(ymd_hms) => ymd_hms,
+            Err(err) => return write!(f, "{:?}", err),
+        };
+        if year < 0 {
+            write!(f, "-{:04}", year.unsigned_abs())?;
+        } else if self.options.expanded_year && year > 9999 {
+            write!(f, "+{:04}", year)?;
+        } else {
+            write!(f, "{:04}", year)?;
+        }
+        let separator = if self.options.iso_separator { 'T' } else { ' ' };
+        write!(
+            f,
+            "-{:02}-{:02}{}{:02}:{:02}:{:02}",
+            month, day, separator, hour, minute, second
+        )?;
+
+        let fraction = format!("{:09}", self.datetime.nanoseconds());
+        let fraction = match self.options.precision {
+            Precision::Auto => fraction.trim_end_matches('0'),
+            Precision::Fixed(digits) => &fraction[..(digits as usize).min(fraction.len())],
+        };
+        if !fraction.is_empty() {
+            write!(f, ".{}", fraction)?;
+        }
+        if self.options.calendar || f.alternate() {
+            write!(f, " [{}]", self.datetime.calendar().cf_name())?;
+        }
+        Ok(())
+    }
+}
+
+/// Display a CFDatetime with the following format : `YYYY-MM-DD HH:MM:SS` followed by the
+/// fraction of seconds if it is not zero, e.g. `YYYY-MM-DD HH:MM:SS.5`
+///
+/// The alternate format `{:#}` appends the CF name of the calendar, e.g.
+/// `2000-02-30 00:00:00 [360_day]`, to tell apart the datetimes of different calendars.
+///
+/// See [CFDatetime::display_with] to configure the output.
+impl core::fmt::Display for CFDatetime {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        self.display_with(DisplayOptions::default()).fmt(f)
+    }
+}
+
+/// Returns an error if the dates of the calendar can not be written as RFC 3339 dates, i.e. the
+/// calendar is not a real world calendar and `force` is `false`
+fn check_rfc3339_calendar(calendar: Calendar, force: bool) -> crate::errors::Result<()> {
+    if force || calendar.is_real_world() {
+        return Ok(());
+    }
+    Err(crate::errors::Error::InvalidCalendar(format!(
+        "RFC 3339 dates are real world dates, which the {calendar} calendar does not have"
+    )))
+}
+
+/// Parses the digits of `s` as a number, without sign
+fn parse_digits<T: core::str::FromStr>(s: &str) -> Option<T> {
+    if s.is_empty() || !s.bytes().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    s.parse().ok()
+}
+
+impl CFDatetime {
+    /// Formats the datetime as [RFC 3339](https://www.rfc-editor.org/rfc/rfc3339) e.g.
+    /// `2000-01-01T12:00:00.5+00:00`, which `datetime.fromisoformat` of python parses.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the string or an error of type `crate::errors::Error::InvalidCalendar`
+    /// if the calendar is not a [real world calendar](Calendar::is_real_world) and `force` is
+    /// `false`, or `crate::errors::Error::InvalidDate` if the year is not between 0 and 9999
+    pub fn to_rfc3339(&self, force: bool) -> crate::errors::Result<String> {
+        check_rfc3339_calendar(self.calendar(), force)?;
+        let year = self.ymd()?.0;
+        if !(0..=9999).contains(&year) {
+            return Err(crate::errors::Error::InvalidDate(format!(
+                "The year {year} does not fit in the 4 digits of RFC 3339"
+            )));
+        }
+        let options = DisplayOptions::default().with_iso_separator(true);
+        Ok(format!("{}{}", self.display_with(options), self.timezone()))
+    }
+    /// Parses an [RFC 3339](https://www.rfc-editor.org/rfc/rfc3339) datetime such as
+    /// `2000-01-01T12:00:00.5+01:00`, whose offset is subtracted to get the datetime in UTC.
+    ///
+    /// As `datetime.fromisoformat` of python, a space separator, a date without time and a time
+    /// without offset, taken as UTC, are accepted. The fraction of seconds is truncated to the
+    /// nanosecond.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cftime_rs::{calendars::Calendar, datetime::CFDatetime};
+    /// let datetime =
+    ///     CFDatetime::parse_rfc3339("2000-01-01T12:00:00.5+01:00", Calendar::Standard, false).unwrap();
+    /// assert_eq!(datetime.to_rfc3339(false).unwrap(), "2000-01-01T11:00:00.5+00:00");
+    /// assert!(CFDatetime::parse_rfc3339("2000-02-30", Calendar::Day360, false).is_err());
+    /// assert!(CFDatetime::parse_rfc3339("2000-02-30", Calendar::Day360, true).is_ok());
+    /// ```
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the datetime or an error of type
+    /// `crate::errors::Error::InvalidCalendar` as [Self::to_rfc3339],
+    /// `crate::errors::Error::InvalidDate` if the string is not a RFC 3339 datetime or the date does
+    /// not exist in the calendar, or the error of [Self::from_ymd_hms]
+    pub fn parse_rfc3339(s: &str, calendar: Calendar, force: bool) -> crate::errors::Result<Self> {
+        check_rfc3339_calendar(calendar, force)?;
+        let invalid =
+            || crate::errors::Error::InvalidDate(format!("Invalid RFC 3339 datetime '{s}'"));
+        if !s.is_ascii() || s.len() < 10 || &s[4..5] != "-" || &s[7..8] != "-" {
+            return Err(invalid());
+        }
+        let year: i64 = parse_digits(&s[..4]).ok_or_else(invalid)?;
+        let month: u8 = parse_digits(&s[5..7]).ok_or_else(invalid)?;
+        let day: u8 = parse_digits(&s[8..10]).ok_or_else(invalid)?;
+        if !calendar.is_valid_date(year, month, day) {
+            return Err(crate::errors::Error::InvalidDate(format!(
+                "{year:04}-{month:02}-{day:02} does not exist in the {calendar} calendar"
+            )));
+        }
+        let (mut hour, mut minute, mut second, mut nanoseconds, mut offset) = (0, 0, 0, 0, 0);
+        if s.len() > 10 {
+            let time = &s[11..];
+            if !matches!(&s[10..11], "T" | "t" | " ")
+                || time.len() < 8
+                || &time[2..3] != ":"
+                || &time[5..6] != ":"
+            {
+                return Err(invalid());
             }
-            Err(err) => {
-                write!(f, "{:?}", err)
+            hour = parse_digits(&time[..2]).ok_or_else(invalid)?;
+            minute = parse_digits(&time[3..5]).ok_or_else(invalid)?;
+            second = parse_digits(&time[6..8]).ok_or_else(invalid)?;
+            let mut rest = &time[8..];
+            if let Some(fraction) = rest.strip_prefix('.') {
+                let digits = fraction
+                    .find(|c: char| !c.is_ascii_digit())
+                    .unwrap_or(fraction.len());
+                if digits == 0 {
+                    return Err(invalid());
+                }
+                let padded = format!("{:0<9}", &fraction[..digits.min(9)]);
+                nanoseconds = parse_digits(&padded).ok_or_else(invalid)?;
+                rest = &fraction[digits..];
+            }
+            offset = match rest {
+                "" | "Z" | "z" => 0,
+                _ if rest.len() == 6 && matches!(&rest[..1], "+" | "-") && &rest[3..4] == ":" => {
+                    let hours: i64 = parse_digits(&rest[1..3]).ok_or_else(invalid)?;
+                    let minutes: i64 = parse_digits(&rest[4..6]).ok_or_else(invalid)?;
+                    if hours > 23 || minutes > 59 {
+                        return Err(invalid());
+                    }
+                    let sign = if rest.starts_with('-') { -1 } else { 1 };
+                    sign * (hours * 60 + minutes)
+                }
+                _ => return Err(invalid()),
+            };
+        }
+        let wall_clock =
+            Self::from_ymd_hms(year, month, day, hour, minute, second as f32, calendar)?;
+        let timestamp = wall_clock
+            .timestamp()
+            .checked_sub(offset * 60)
+            .ok_or_else(invalid)?;
+        Self::from_timestamp(timestamp, nanoseconds, calendar)
+    }
+}
+
+impl CFDatetime {
+    /// Returns an infinite iterator over `self`, `self + step`, `self + step * 2`, ...
+    ///
+    /// See [CFDatetimeIter::until] to bound the iterator.
+    ///
+    /// # Example
+    /// ```
+    /// # use cftime_rs::{calendars::Calendar, datetime::CFDatetime, duration::CFDuration};
+    /// let start = CFDatetime::from_ymd(2000, 1, 1, Calendar::Day360).unwrap();
+    /// let end = CFDatetime::from_ymd(2000, 2, 1, Calendar::Day360).unwrap();
+    /// let step = CFDuration::from_days(1, Calendar::Day360);
+    /// assert_eq!(start.iter_forward(step).unwrap().until(end).count(), 30);
+    /// ```
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the iterator or an error of type `crate::errors::Error::DifferentCalendars`
+    /// if the step is not in the calendar of the datetime, or `crate::errors::Error::InvalidDuration`
+    /// if the step is zero
+    pub fn iter_forward(&self, step: CFDuration) -> crate::errors::Result<CFDatetimeIter> {
+        if self.calendar() != step.calendar() {
+            return Err(crate::errors::Error::DifferentCalendars(
+                self.calendar().to_string(),
+                step.calendar().to_string(),
+            ));
+        }
+        if step.months == 0 && step.seconds == 0 && step.nanoseconds == 0 {
+            return Err(crate::errors::Error::InvalidDuration(
+                "The step of the iterator can not be zero".to_string(),
+            ));
+        }
+        Ok(CFDatetimeIter {
+            start: Self::from_timestamp(self.timestamp(), self.nanoseconds(), self.calendar())?,
+            step,
+            index: 0,
+            end: None,
+        })
+    }
+}
+
+/// Iterator returned by [CFDatetime::iter_forward]
+///
+/// Each datetime is computed from the start, i.e. `start + step * n`, so the day clamped by the
+/// months of the step does not accumulate : stepping by one month from `2000-01-31` gives `2000-02-29`
+/// and then `2000-03-31`. The iteration ends if a datetime is out of bounds of the calendar.
+pub struct CFDatetimeIter {
+    start: CFDatetime,
+    step: CFDuration,
+    index: i64,
+    end: Option<CFDatetime>,
+}
+
+impl CFDatetimeIter {
+    /// Bounds the iterator to the datetimes before `end`, excluded.
+    /// With a negative step the iterator yields the datetimes after `end`.
+    pub fn until(self, end: CFDatetime) -> Self {
+        Self {
+            end: Some(end),
+            ..self
+        }
+    }
+}
+
+impl Iterator for CFDatetimeIter {
+    type Item = CFDatetime;
+    fn next(&mut self) -> Option<Self::Item> {
+        let datetime = (&self.start + self.step.checked_mul(self.index)?).ok()?;
+        if let Some(end) = &self.end {
+            let current = datetime.timestamp_nanos();
+            let end = end.timestamp_nanos();
+            let forward = self.step.num_seconds() >= 0.0;
+            if (forward && current >= end) || (!forward && current <= end) {
+                return None;
             }
         }
+        self.index += 1;
+        Some(datetime)
     }
 }
 
 macro_rules! impl_add_duration {
     ($rhs:ty, $for:ty) => {
-        impl std::ops::Add<$rhs> for $for {
-            type Output = Result<CFDatetime, crate::errors::Error>;
+        impl core::ops::Add<$rhs> for $for {
+            type Output = crate::errors::Result<CFDatetime>;
             fn add(self, rhs: $rhs) -> Self::Output {
                 if self.calendar() != rhs.calendar() {
                     return Err(crate::errors::Error::DifferentCalendars(
@@ -303,10 +1452,19 @@ macro_rules! impl_add_duration {
                         rhs.calendar().to_string(),
                     ));
                 }
-                let nanoseconds = self.nanoseconds() as i64 + rhs.nanoseconds as i64;
-                let (_remaining_seconds, remaining_nanoseconds) =
-                    normalize_nanoseconds(nanoseconds);
-                let new_timestamp = self.timestamp() + rhs.seconds;
+                let datetime = self.add_months(rhs.months)?;
+                let nanoseconds = datetime.nanoseconds() as i64 + rhs.nanoseconds as i64;
+                let (remaining_seconds, remaining_nanoseconds) = normalize_nanoseconds(nanoseconds);
+                let new_timestamp = datetime
+                    .timestamp()
+                    .checked_add(rhs.seconds)
+                    .and_then(|timestamp| timestamp.checked_add(remaining_seconds))
+                    .ok_or_else(|| {
+                        crate::errors::Error::OutOfBoundsCalendar(
+                            self.calendar().to_string(),
+                            format!("{} + {}", datetime, rhs),
+                        )
+                    })?;
                 CFDatetime::from_timestamp(new_timestamp, remaining_nanoseconds, self.calendar())
             }
         }
@@ -319,8 +1477,8 @@ impl_add_duration!(&CFDuration, &CFDatetime);
 
 macro_rules! impl_sub_duration {
     ($rhs:ty, $for:ty) => {
-        impl std::ops::Sub<$rhs> for $for {
-            type Output = Result<CFDatetime, crate::errors::Error>;
+        impl core::ops::Sub<$rhs> for $for {
+            type Output = crate::errors::Result<CFDatetime>;
             fn sub(self, rhs: $rhs) -> Self::Output {
                 if self.calendar() != rhs.calendar() {
                     return Err(crate::errors::Error::DifferentCalendars(
@@ -328,9 +1486,25 @@ macro_rules! impl_sub_duration {
                         rhs.calendar().to_string(),
                     ));
                 }
-                let nanoseconds = self.nanoseconds() as i64 - rhs.nanoseconds as i64;
+                let months = rhs.months.checked_neg().ok_or_else(|| {
+                    crate::errors::Error::OutOfBoundsCalendar(
+                        self.calendar().to_string(),
+                        format!("{} - {}", self, rhs),
+                    )
+                })?;
+                let datetime = self.add_months(months)?;
+                let nanoseconds = datetime.nanoseconds() as i64 - rhs.nanoseconds as i64;
                 let (remaining_seconds, remaining_nanoseconds) = normalize_nanoseconds(nanoseconds);
-                let new_timestamp = (self.timestamp() - rhs.seconds) + remaining_seconds;
+                let new_timestamp = datetime
+                    .timestamp()
+                    .checked_sub(rhs.seconds)
+                    .and_then(|timestamp| timestamp.checked_add(remaining_seconds))
+                    .ok_or_else(|| {
+                        crate::errors::Error::OutOfBoundsCalendar(
+                            self.calendar().to_string(),
+                            format!("{} - {}", datetime, rhs),
+                        )
+                    })?;
                 CFDatetime::from_timestamp(new_timestamp, remaining_nanoseconds, self.calendar())
             }
         }
@@ -341,10 +1515,33 @@ impl_sub_duration!(&CFDuration, CFDatetime);
 impl_sub_duration!(CFDuration, &CFDatetime);
 impl_sub_duration!(&CFDuration, &CFDatetime);
 
+/// Adds or subtracts a `std::time::Duration`, which is an exact number of seconds and nanoseconds
+/// in any calendar
+macro_rules! impl_std_duration {
+    ($for:ty) => {
+        impl core::ops::Add<core::time::Duration> for $for {
+            type Output = crate::errors::Result<CFDatetime>;
+            fn add(self, rhs: core::time::Duration) -> Self::Output {
+                let timestamp_nanos = self.timestamp_nanos() + rhs.as_nanos() as i128;
+                CFDatetime::from_timestamp_nanos(timestamp_nanos, self.calendar())
+            }
+        }
+        impl core::ops::Sub<core::time::Duration> for $for {
+            type Output = crate::errors::Result<CFDatetime>;
+            fn sub(self, rhs: core::time::Duration) -> Self::Output {
+                let timestamp_nanos = self.timestamp_nanos() - rhs.as_nanos() as i128;
+                CFDatetime::from_timestamp_nanos(timestamp_nanos, self.calendar())
+            }
+        }
+    };
+}
+impl_std_duration!(CFDatetime);
+impl_std_duration!(&CFDatetime);
+
 macro_rules! impl_sub_datetime {
     ($rhs:ty, $for:ty) => {
-        impl std::ops::Sub<$rhs> for $for {
-            type Output = Result<CFDuration, crate::errors::Error>;
+        impl core::ops::Sub<$rhs> for $for {
+            type Output = crate::errors::Result<CFDuration>;
             fn sub(self, rhs: $rhs) -> Self::Output {
                 if self.calendar() != rhs.calendar() {
                     return Err(crate::errors::Error::DifferentCalendars(
@@ -352,9 +1549,14 @@ macro_rules! impl_sub_datetime {
                         rhs.calendar().to_string(),
                     ));
                 }
-                let nanoseconds = self.nanoseconds() as i64 - rhs.nanoseconds() as i64;
-                let new_timestamp = self.timestamp() - rhs.timestamp();
-                Ok(CFDuration::new(new_timestamp, nanoseconds, self.calendar()))
+                // The difference of two timestamps can overflow an i64
+                let nanoseconds = self.timestamp_nanos() - rhs.timestamp_nanos();
+                CFDuration::checked_with_months(0, nanoseconds, self.calendar()).ok_or_else(|| {
+                    crate::errors::Error::InvalidDuration(format!(
+                        "Overflow when subtracting {} and {}",
+                        self, rhs
+                    ))
+                })
             }
         }
     };
@@ -365,6 +1567,59 @@ impl_sub_datetime!(&CFDatetime, CFDatetime);
 impl_sub_datetime!(CFDatetime, &CFDatetime);
 impl_sub_datetime!(&CFDatetime, &CFDatetime);
 
+/// Converts a `SystemTime` into a datetime of the standard calendar
+#[cfg(feature = "std")]
+impl TryFrom<std::time::SystemTime> for CFDatetime {
+    type Error = crate::errors::Error;
+    fn try_from(time: std::time::SystemTime) -> Result<Self, Self::Error> {
+        let timestamp_nanos = match time.duration_since(std::time::UNIX_EPOCH) {
+            Ok(duration) => duration.as_nanos() as i128,
+            Err(before_epoch) => -(before_epoch.duration().as_nanos() as i128),
+        };
+        Self::from_timestamp_nanos(timestamp_nanos, Calendar::Standard)
+    }
+}
+
+/// Converts a datetime of the standard or proleptic gregorian calendar into a `SystemTime`
+#[cfg(feature = "std")]
+impl TryFrom<&CFDatetime> for std::time::SystemTime {
+    type Error = crate::errors::Error;
+    fn try_from(datetime: &CFDatetime) -> Result<Self, Self::Error> {
+        let calendar = datetime.calendar();
+        if !calendar.convertible_to_unix() {
+            return Err(crate::errors::Error::InvalidCalendar(format!(
+                "Datetimes of the {calendar} calendar can not be converted to SystemTime"
+            )));
+        }
+        let timestamp_nanos = datetime.timestamp_nanos();
+        let (seconds, nanoseconds) = (
+            timestamp_nanos.unsigned_abs() / constants::MAX_NS as u128,
+            timestamp_nanos.unsigned_abs() % constants::MAX_NS as u128,
+        );
+        // The seconds of an i64 timestamp fit in a u64
+        let duration = core::time::Duration::new(seconds as u64, nanoseconds as u32);
+        let time = if timestamp_nanos >= 0 {
+            std::time::UNIX_EPOCH.checked_add(duration)
+        } else {
+            std::time::UNIX_EPOCH.checked_sub(duration)
+        };
+        time.ok_or_else(|| {
+            crate::errors::Error::OutOfBoundsCalendar(
+                calendar.to_string(),
+                format!("{datetime} is out of the range of SystemTime"),
+            )
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl TryFrom<CFDatetime> for std::time::SystemTime {
+    type Error = crate::errors::Error;
+    fn try_from(datetime: CFDatetime) -> Result<Self, Self::Error> {
+        Self::try_from(&datetime)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::calendars;
@@ -477,6 +1732,68 @@ mod tests {
         }
     }
     #[test]
+    fn test_add_std_duration() {
+        // December has 30 days in the 360_day calendar
+        for (calendar, last_day) in [
+            (Calendar::Day360, 30),
+            (Calendar::Julian, 31),
+            (Calendar::NoLeap, 31),
+        ] {
+            let datetime = CFDatetime::from_ymd(1970, 1, 1, calendar).unwrap();
+            let duration = core::time::Duration::new(86_400 + 61, 500_000_000);
+            let shifted = (&datetime + duration).unwrap();
+            assert_eq!(shifted.ymd_hms().unwrap(), (1970, 1, 2, 0, 1, 1));
+            assert_eq!(shifted.nanoseconds(), 500_000_000);
+            assert_eq!(shifted.calendar(), calendar);
+            let shifted = (datetime - duration).unwrap();
+            assert_eq!(
+                shifted.ymd_hms().unwrap(),
+                (1969, 12, last_day - 1, 23, 58, 58)
+            );
+            assert_eq!(shifted.nanoseconds(), 500_000_000);
+        }
+        let max = CFDatetime::max(Calendar::Standard);
+        assert!((max + core::time::Duration::from_nanos(1)).is_err_and(|e| e.is_out_of_bounds()));
+        let min = CFDatetime::min(Calendar::Standard);
+        assert!((min - core::time::Duration::MAX).is_err_and(|e| e.is_out_of_bounds()));
+    }
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_system_time() {
+        use std::time::{Duration, SystemTime, UNIX_EPOCH};
+        let time = UNIX_EPOCH + Duration::new(946_684_800, 250);
+        let datetime = CFDatetime::try_from(time).unwrap();
+        assert_eq!(datetime.calendar(), Calendar::Standard);
+        assert_eq!(datetime.ymd_hms().unwrap(), (2000, 1, 1, 0, 0, 0));
+        assert_eq!(datetime.nanoseconds(), 250);
+        assert_eq!(SystemTime::try_from(&datetime).unwrap(), time);
+        let time = UNIX_EPOCH - Duration::new(1, 500_000_000);
+        let datetime = CFDatetime::try_from(time).unwrap();
+        assert_eq!(datetime.ymd_hms().unwrap(), (1969, 12, 31, 23, 59, 58));
+        assert_eq!(datetime.nanoseconds(), 500_000_000);
+        assert_eq!(SystemTime::try_from(datetime).unwrap(), time);
+        let datetime = CFDatetime::from_ymd(2000, 1, 1, Calendar::ProlepticGregorian).unwrap();
+        assert_eq!(
+            SystemTime::try_from(&datetime).unwrap(),
+            UNIX_EPOCH + Duration::from_secs(946_684_800)
+        );
+        let datetime = CFDatetime::from_ymd(2000, 1, 1, Calendar::NoLeap).unwrap();
+        assert!(SystemTime::try_from(&datetime).is_err_and(|e| e.is_calendar_error()));
+        // Far dates beyond the 584 years of a u64 of nanoseconds
+        let datetime = CFDatetime::from_ymd(3000, 1, 1, Calendar::Standard).unwrap();
+        let time = SystemTime::try_from(&datetime).unwrap();
+        assert!(CFDatetime::try_from(time).unwrap() == datetime);
+        // SystemTime covers the range of the datetimes on unix
+        for datetime in [
+            CFDatetime::min(Calendar::Standard),
+            CFDatetime::max(Calendar::Standard),
+        ] {
+            if let Ok(time) = SystemTime::try_from(&datetime) {
+                assert!(CFDatetime::try_from(time).unwrap() == datetime);
+            }
+        }
+    }
+    #[test]
     fn test_timestamp() {
         let timestamp_expected = vec![
             (0, (1970, 1, 1, 0, 0, 0)),
@@ -510,4 +1827,920 @@ mod tests {
             assert_eq!(datetime.unwrap().ymd().unwrap(), expected);
         }
     }
+    #[test]
+    fn test_weekday_and_month_names() {
+        let datetime = CFDatetime::from_ymd(1970, 1, 1, Calendar::Standard).unwrap();
+        assert_eq!(datetime.weekday(), 3);
+        assert_eq!(datetime.weekday_name(), "Thursday");
+        assert_eq!(datetime.month_name().unwrap(), "January");
+        let datetime = CFDatetime::from_ymd(2000, 1, 1, Calendar::ProlepticGregorian).unwrap();
+        assert_eq!(datetime.weekday_name(), "Saturday");
+        // The day after 1582-10-04 is 1582-10-15 in the standard calendar
+        let datetime = CFDatetime::from_ymd(1582, 10, 4, Calendar::Standard).unwrap();
+        assert_eq!(datetime.weekday_name(), "Thursday");
+        let datetime = CFDatetime::from_ymd(1582, 10, 15, Calendar::Standard).unwrap();
+        assert_eq!(datetime.weekday_name(), "Friday");
+        // 1970-01-01 in the julian calendar is 1970-01-14 in the gregorian calendar
+        let datetime = CFDatetime::from_ymd(1970, 1, 1, Calendar::Julian).unwrap();
+        assert_eq!(datetime.weekday_name(), "Wednesday");
+        // The week continues across the idealized months
+        let first = CFDatetime::from_ymd(2000, 2, 30, Calendar::Day360).unwrap();
+        let next = CFDatetime::from_ymd(2000, 3, 1, Calendar::Day360).unwrap();
+        assert_eq!((first.weekday() + 1) % 7, next.weekday());
+        assert_eq!(next.month_name().unwrap(), "March");
+        let months = [
+            "janvier",
+            "février",
+            "mars",
+            "avril",
+            "mai",
+            "juin",
+            "juillet",
+            "août",
+            "septembre",
+            "octobre",
+            "novembre",
+            "décembre",
+        ];
+        let weekdays = [
+            "lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi", "dimanche",
+        ];
+        assert_eq!(next.month_name_in(&months).unwrap(), "mars");
+        assert_eq!(
+            datetime.weekday_name_in(&weekdays),
+            weekdays[datetime.weekday() as usize]
+        );
+    }
+
+    #[test]
+    fn test_year_zero() {
+        // 1 BC is the year 0 of the crate and a leap year in the julian calendar
+        let datetime =
+            CFDatetime::from_ymd_hms_with_year_zero(-1, 2, 29, 12, 0, 0.0, Calendar::Julian, false)
+                .unwrap();
+        assert_eq!(datetime.ymd_hms().unwrap(), (0, 2, 29, 12, 0, 0));
+        assert_eq!(
+            datetime.ymd_hms_with_year_zero(false).unwrap(),
+            (-1, 2, 29, 12, 0, 0)
+        );
+        let next_year =
+            CFDatetime::from_ymd_hms_with_year_zero(1, 1, 1, 0, 0, 0.0, Calendar::Julian, false);
+        let new_year = CFDatetime::from_ymd_hms(0, 12, 31, 0, 0, 0.0, Calendar::Julian).unwrap();
+        assert_eq!((&next_year.unwrap() - &new_year).unwrap().num_days(), 1.0);
+        assert!(CFDatetime::from_ymd_hms_with_year_zero(
+            0,
+            1,
+            1,
+            0,
+            0,
+            0.0,
+            Calendar::Standard,
+            false
+        )
+        .is_err_and(|e| e.is_invalid_datetime()));
+        // Astronomical leap years before the year 1
+        for (year, days) in [(0, 29), (-1, 28), (-4, 29), (-100, 28), (-400, 29)] {
+            let datetime = CFDatetime::from_ymd(year, 2, 1, Calendar::ProlepticGregorian).unwrap();
+            assert_eq!(datetime.days_in_month().unwrap(), days, "{year}");
+            let march = CFDatetime::from_ymd(year, 3, 1, Calendar::ProlepticGregorian).unwrap();
+            assert_eq!(
+                (&march - &datetime).unwrap().num_days(),
+                days as f64,
+                "{year}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_from_timestamp_carries_nanoseconds() {
+        for calendar in [
+            Calendar::ProlepticGregorian,
+            Calendar::Standard,
+            Calendar::Day360,
+            Calendar::Julian,
+            Calendar::NoLeap,
+            Calendar::AllLeap,
+        ] {
+            let datetime = CFDatetime::from_timestamp(1, 1_500_000_000, calendar).unwrap();
+            assert_eq!(datetime.timestamp(), 2);
+            assert_eq!(datetime.nanoseconds(), 500_000_000);
+            assert_eq!(datetime.calendar(), calendar);
+            let datetime = CFDatetime::from_timestamp(-1, u32::MAX, calendar).unwrap();
+            assert_eq!(datetime.timestamp(), 3);
+            assert_eq!(datetime.nanoseconds(), 294_967_295);
+            let datetime = CFDatetime::from_timestamp(0, 999_999_999, calendar).unwrap();
+            assert_eq!(
+                (datetime.timestamp(), datetime.nanoseconds()),
+                (0, 999_999_999)
+            );
+            assert!(
+                CFDatetime::from_timestamp(i64::MAX, 1_000_000_000, calendar)
+                    .is_err_and(|e| e.is_out_of_bounds())
+            );
+        }
+    }
+
+    #[test]
+    fn test_timestamp_nanos() {
+        let timestamp_nanos_expected: Vec<(i128, (i64, u32))> = vec![
+            (0, (0, 0)),
+            (1_500_000_000, (1, 500_000_000)),
+            (-1, (-1, 999_999_999)),
+            (-2_500_000_000, (-3, 500_000_000)),
+        ];
+        for calendar in [
+            Calendar::ProlepticGregorian,
+            Calendar::Day360,
+            Calendar::NoLeap,
+        ] {
+            for (timestamp_nanos, (timestamp, nanoseconds)) in &timestamp_nanos_expected {
+                let datetime =
+                    CFDatetime::from_timestamp_nanos(*timestamp_nanos, calendar).unwrap();
+                assert_eq!(datetime.timestamp(), *timestamp);
+                assert_eq!(datetime.nanoseconds(), *nanoseconds);
+                assert_eq!(datetime.timestamp_nanos(), *timestamp_nanos);
+            }
+        }
+        assert!(CFDatetime::from_timestamp_nanos(i128::MAX, Calendar::Standard).is_err());
+        assert!(CFDatetime::from_timestamp_nanos(i128::MIN, Calendar::Standard).is_err());
+    }
+    #[test]
+    fn test_timestamp_f64() {
+        let seconds_expected: Vec<(f64, (i64, u32))> = vec![
+            (0.0, (0, 0)),
+            (1.5, (1, 500_000_000)),
+            (-0.25, (-1, 750_000_000)),
+            (946684800.123456, (946684800, 123_456_001)),
+            (0.9999999999, (1, 0)),
+        ];
+        for calendar in [Calendar::Standard, Calendar::Day360, Calendar::NoLeap] {
+            for (seconds, (timestamp, nanoseconds)) in &seconds_expected {
+                let datetime = CFDatetime::from_timestamp_f64(*seconds, calendar).unwrap();
+                assert_eq!(datetime.timestamp(), *timestamp);
+                assert_eq!(datetime.nanoseconds(), *nanoseconds);
+                assert!((datetime.timestamp_f64() - seconds).abs() < 1e-6);
+            }
+        }
+        for seconds in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY, 1e19, -1e19] {
+            assert!(CFDatetime::from_timestamp_f64(seconds, Calendar::Standard).is_err());
+        }
+    }
+    #[test]
+    fn test_day_month_year_navigation() {
+        let cases = [
+            (
+                Calendar::Standard,
+                (2000, 2, 15),
+                (2000, 2, 29),
+                (2000, 12, 31),
+            ),
+            (
+                Calendar::NoLeap,
+                (2000, 2, 15),
+                (2000, 2, 28),
+                (2000, 12, 31),
+            ),
+            (
+                Calendar::Day360,
+                (2000, 2, 15),
+                (2000, 2, 30),
+                (2000, 12, 30),
+            ),
+            (
+                Calendar::Julian,
+                (1900, 2, 1),
+                (1900, 2, 29),
+                (1900, 12, 31),
+            ),
+            (
+                Calendar::Standard,
+                (1582, 10, 3),
+                (1582, 10, 31),
+                (1582, 12, 31),
+            ),
+        ];
+        for (calendar, (year, month, day), end_of_month, end_of_year) in cases {
+            let datetime =
+                CFDatetime::from_ymd_hms(year, month, day, 6, 30, 0.0, calendar).unwrap();
+            assert_eq!(
+                datetime.start_of_month().unwrap().ymd_hms().unwrap(),
+                (year, month, 1, 0, 0, 0)
+            );
+            assert_eq!(
+                datetime.end_of_month().unwrap().ymd().unwrap(),
+                end_of_month
+            );
+            assert_eq!(
+                datetime.start_of_year().unwrap().ymd_hms().unwrap(),
+                (year, 1, 1, 0, 0, 0)
+            );
+            assert_eq!(datetime.end_of_year().unwrap().ymd().unwrap(), end_of_year);
+            let next = datetime.succ_day().unwrap();
+            assert_eq!(next.hms().unwrap(), (6, 30, 0));
+            assert!(next.pred_day().unwrap() == datetime);
+        }
+        let datetime = CFDatetime::from_ymd(1582, 10, 4, Calendar::Standard).unwrap();
+        assert_eq!(datetime.succ_day().unwrap().ymd().unwrap(), (1582, 10, 15));
+        let datetime = CFDatetime::from_ymd(2000, 12, 31, Calendar::Standard).unwrap();
+        assert_eq!(datetime.succ_day().unwrap().ymd().unwrap(), (2001, 1, 1));
+        assert!(CFDatetime::max(Calendar::NoLeap).succ_day().is_err());
+        assert!(CFDatetime::min(Calendar::NoLeap).pred_day().is_err());
+    }
+    #[test]
+    fn test_rfc3339() {
+        let calendar = Calendar::Standard;
+        let datetime = CFDatetime::from_timestamp(946684800, 123_456_789, calendar).unwrap();
+        assert_eq!(
+            datetime.to_rfc3339(false).unwrap(),
+            "2000-01-01T00:00:00.123456789+00:00"
+        );
+        let parsed = [
+            ("2000-01-01T00:00:00.123456789Z", 946684800, 123_456_789),
+            ("2000-01-01t00:00:00.1234567891z", 946684800, 123_456_789),
+            ("2000-01-01 01:30:00+01:30", 946684800, 0),
+            ("1999-12-31T23:00:00-01:00", 946684800, 0),
+            ("2000-01-01T00:00:00", 946684800, 0),
+            ("2000-01-01", 946684800, 0),
+            ("0000-03-01T00:00:00.5Z", -62162208000, 500_000_000),
+        ];
+        for (input, timestamp, nanoseconds) in parsed {
+            let datetime = CFDatetime::parse_rfc3339(input, calendar, false).unwrap();
+            assert_eq!(datetime.timestamp(), timestamp, "{input}");
+            assert_eq!(datetime.nanoseconds(), nanoseconds, "{input}");
+            let round_trip =
+                CFDatetime::parse_rfc3339(&datetime.to_rfc3339(false).unwrap(), calendar, false)
+                    .unwrap();
+            assert!(round_trip == datetime, "{input}");
+        }
+        for input in [
+            "2000-1-01",
+            "2000-01-01T",
+            "2000-01-01T00:00",
+            "2000-01-01T00:00:00.",
+            "2000-01-01T00:00:00+0100",
+            "2000-01-01T00:00:00+24:00",
+            "2000-01-01T24:00:00",
+            "2000-01-01T00:00:60",
+            "2001-02-29",
+            "+2000-01-01",
+            "2000-01-01T00:00:00 UTC",
+            "2000-01-01T00:00:00Zé",
+        ] {
+            assert!(
+                CFDatetime::parse_rfc3339(input, calendar, false).is_err(),
+                "{input}"
+            );
+        }
+        let datetime = CFDatetime::from_ymd(2000, 2, 30, Calendar::Day360).unwrap();
+        assert!(datetime
+            .to_rfc3339(false)
+            .is_err_and(|e| e.is_calendar_error()));
+        assert_eq!(
+            datetime.to_rfc3339(true).unwrap(),
+            "2000-02-30T00:00:00+00:00"
+        );
+        let datetime = CFDatetime::from_ymd(10000, 1, 1, calendar).unwrap();
+        assert!(datetime.to_rfc3339(false).is_err());
+    }
+    #[test]
+    fn test_display() {
+        let datetime = CFDatetime::from_ymd(2000, 1, 1, Calendar::Standard).unwrap();
+        assert_eq!(datetime.to_string(), "2000-01-01 00:00:00");
+        let datetime =
+            CFDatetime::from_timestamp(946684800, 500_000_000, Calendar::ProlepticGregorian)
+                .unwrap();
+        assert_eq!(datetime.to_string(), "2000-01-01 00:00:00.5");
+        let options = DisplayOptions::default()
+            .with_precision(Precision::Fixed(3))
+            .with_iso_separator(true);
+        assert_eq!(
+            datetime.display_with(options).to_string(),
+            "2000-01-01T00:00:00.500"
+        );
+        let options = DisplayOptions {
+            precision: Precision::Fixed(0),
+            ..Default::default()
+        };
+        assert_eq!(
+            datetime.display_with(options).to_string(),
+            "2000-01-01 00:00:00"
+        );
+
+        // Fractions smaller than a millisecond are not lost
+        let datetime =
+            CFDatetime::from_timestamp(946684800, 123_456_789, Calendar::Standard).unwrap();
+        assert_eq!(datetime.to_string(), "2000-01-01 00:00:00.123456789");
+        let datetime = CFDatetime::from_timestamp(946684800, 1_500, Calendar::Standard).unwrap();
+        assert_eq!(datetime.to_string(), "2000-01-01 00:00:00.0000015");
+        for (precision, expected) in [
+            (Precision::MILLISECONDS, "2000-01-01 00:00:00.000"),
+            (Precision::MICROSECONDS, "2000-01-01 00:00:00.000001"),
+            (Precision::NANOSECONDS, "2000-01-01 00:00:00.000001500"),
+            (Precision::Fixed(12), "2000-01-01 00:00:00.000001500"),
+        ] {
+            let options = DisplayOptions::default().with_precision(precision);
+            assert_eq!(datetime.display_with(options).to_string(), expected);
+        }
+
+        let datetime = CFDatetime::from_ymd(-100, 1, 1, Calendar::ProlepticGregorian).unwrap();
+        assert_eq!(datetime.to_string(), "-0100-01-01 00:00:00");
+        let datetime = CFDatetime::from_ymd(10000, 1, 1, Calendar::ProlepticGregorian).unwrap();
+        assert_eq!(datetime.to_string(), "10000-01-01 00:00:00");
+        let options = DisplayOptions {
+            expanded_year: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            datetime.display_with(options).to_string(),
+            "+10000-01-01 00:00:00"
+        );
+    }
+    #[test]
+    fn test_display_calendar() {
+        let datetime = CFDatetime::from_ymd(2000, 2, 30, Calendar::Day360).unwrap();
+        assert_eq!(format!("{datetime:#}"), "2000-02-30 00:00:00 [360_day]");
+        assert_eq!(datetime.to_string(), "2000-02-30 00:00:00");
+        let datetime =
+            CFDatetime::from_timestamp(946684800, 500_000_000, Calendar::ProlepticGregorian)
+                .unwrap();
+        let options = DisplayOptions::default()
+            .with_iso_separator(true)
+            .with_calendar(true);
+        assert_eq!(
+            datetime.display_with(options).to_string(),
+            "2000-01-01T00:00:00.5 [proleptic_gregorian]"
+        );
+        let options = DisplayOptions::default().with_precision(Precision::MILLISECONDS);
+        assert_eq!(
+            format!("{:#}", datetime.display_with(options)),
+            "2000-01-01 00:00:00.500 [proleptic_gregorian]"
+        );
+    }
+    #[test]
+    fn test_display_round_trip() {
+        let datetimes = vec![
+            CFDatetime::from_ymd_hms(2000, 1, 1, 12, 30, 15.25, Calendar::ProlepticGregorian)
+                .unwrap(),
+            CFDatetime::from_ymd(-100, 3, 1, Calendar::ProlepticGregorian).unwrap(),
+            CFDatetime::from_ymd(12345, 6, 30, Calendar::Day360).unwrap(),
+        ];
+        for datetime in datetimes {
+            let units = format!("seconds since {}", datetime);
+            let (parsed, _, _) =
+                crate::utils::get_datetime_and_unit_from_units(&units, datetime.calendar())
+                    .unwrap();
+            assert!(parsed == datetime, "{} != {}", parsed, datetime);
+        }
+        // The fixed precisions parse back to the datetime truncated to their digits
+        let datetime =
+            CFDatetime::from_timestamp(-946684800, 987_654_321, Calendar::NoLeap).unwrap();
+        for (precision, nanoseconds) in [
+            (Precision::Auto, 987_654_321),
+            (Precision::MILLISECONDS, 987_000_000),
+            (Precision::MICROSECONDS, 987_654_000),
+            (Precision::NANOSECONDS, 987_654_321),
+        ] {
+            let options = DisplayOptions::default().with_precision(precision);
+            let units = format!("seconds since {}", datetime.display_with(options));
+            let (parsed, _, _) =
+                crate::utils::get_datetime_and_unit_from_units(&units, Calendar::NoLeap).unwrap();
+            assert_eq!(parsed.timestamp(), datetime.timestamp(), "{units}");
+            assert_eq!(parsed.nanoseconds(), nanoseconds, "{units}");
+        }
+    }
+    #[test]
+    fn test_replace() {
+        let datetime =
+            CFDatetime::from_ymd_hms(2000, 1, 31, 12, 30, 15.0, Calendar::Standard).unwrap();
+        let new_datetime = datetime
+            .replace(None, None, None, Some(6), None, None, None)
+            .unwrap();
+        assert_eq!(new_datetime.ymd_hms().unwrap(), (2000, 1, 31, 6, 30, 15));
+        let new_datetime = datetime
+            .replace(Some(1999), Some(12), None, None, None, None, Some(500))
+            .unwrap();
+        assert_eq!(new_datetime.ymd_hms().unwrap(), (1999, 12, 31, 12, 30, 15));
+        assert_eq!(new_datetime.calendar(), Calendar::Standard);
+        let new_datetime = CFDatetime::from_ymd(2000, 1, 1, Calendar::ProlepticGregorian)
+            .unwrap()
+            .replace(None, None, None, None, None, None, Some(500))
+            .unwrap();
+        assert_eq!(new_datetime.nanoseconds(), 500);
+
+        // 2000-02-31 does not exist
+        assert!(datetime
+            .replace(None, Some(2), None, None, None, None, None)
+            .is_err());
+        assert!(datetime
+            .replace(None, Some(13), None, None, None, None, None)
+            .is_err());
+        assert!(datetime
+            .replace(None, None, None, None, None, None, Some(1_000_000_000))
+            .is_err());
+
+        // 29th of February only exists in leap years of the calendar
+        let datetime = CFDatetime::from_ymd(2001, 2, 1, Calendar::NoLeap).unwrap();
+        assert!(datetime
+            .replace(None, None, Some(29), None, None, None, None)
+            .is_err());
+        let datetime = CFDatetime::from_ymd(2001, 2, 1, Calendar::Day360).unwrap();
+        let new_datetime = datetime
+            .replace(None, None, Some(30), None, None, None, None)
+            .unwrap();
+        assert_eq!(new_datetime.ymd().unwrap(), (2001, 2, 30));
+    }
+    #[test]
+    fn test_interp_calendar() {
+        let datetime_expected = vec![
+            // Middle of the year in a leap year of 366 days
+            (
+                CFDatetime::from_ymd(2000, 7, 1, Calendar::Day360).unwrap(),
+                Calendar::Standard,
+                (2000, 7, 2, 0, 0, 0),
+            ),
+            // 359.5 / 360 of a 365 days year
+            (
+                CFDatetime::from_ymd_hms(2000, 12, 30, 12, 0, 0.0, Calendar::Day360).unwrap(),
+                Calendar::NoLeap,
+                (2000, 12, 31, 11, 50, 0),
+            ),
+            // Same length of year keeps the date
+            (
+                CFDatetime::from_ymd(2001, 3, 1, Calendar::Standard).unwrap(),
+                Calendar::NoLeap,
+                (2001, 3, 1, 0, 0, 0),
+            ),
+            (
+                CFDatetime::from_ymd(2000, 1, 1, Calendar::AllLeap).unwrap(),
+                Calendar::Day360,
+                (2000, 1, 1, 0, 0, 0),
+            ),
+        ];
+        for (datetime, calendar, expected) in datetime_expected {
+            let new_datetime = datetime.interp_calendar(calendar).unwrap();
+            assert_eq!(new_datetime.calendar(), calendar);
+            assert_eq!(new_datetime.ymd_hms().unwrap(), expected);
+        }
+    }
+    #[test]
+    fn test_change_calendar_many() {
+        let datetimes = vec![
+            CFDatetime::from_ymd(2000, 2, 28, Calendar::ProlepticGregorian).unwrap(),
+            CFDatetime::from_timestamp(951_782_400 + 3_600, 1, Calendar::ProlepticGregorian)
+                .unwrap(),
+            CFDatetime::from_ymd(2000, 3, 1, Calendar::ProlepticGregorian).unwrap(),
+        ];
+        assert_eq!(datetimes[1].ymd_hms().unwrap(), (2000, 2, 29, 1, 0, 0));
+        let (converted, report) =
+            CFDatetime::change_calendar_many(&datetimes, Calendar::NoLeap, InvalidDatePolicy::Drop)
+                .unwrap();
+        let dates: Vec<_> = converted.iter().map(|d| d.ymd().unwrap()).collect();
+        assert_eq!(dates, vec![(2000, 2, 28), (2000, 3, 1)]);
+        assert_eq!(
+            report.issues,
+            vec![CalendarChangeIssue {
+                index: 1,
+                original: (2000, 2, 29, 1, 0, 0),
+                action: CalendarChangeAction::Dropped
+            }]
+        );
+        assert_eq!(report.dropped().collect::<Vec<_>>(), vec![1]);
+        assert_eq!(
+            report.issues[0].to_string(),
+            "2000-02-29 01:00:00 at index 1 dropped"
+        );
+        let (converted, report) = CFDatetime::change_calendar_many(
+            &datetimes,
+            Calendar::NoLeap,
+            InvalidDatePolicy::Clamp,
+        )
+        .unwrap();
+        assert_eq!(converted.len(), 3);
+        assert_eq!(converted[1].ymd_hms().unwrap(), (2000, 2, 28, 1, 0, 0));
+        assert_eq!(converted[1].nanoseconds(), 1);
+        assert_eq!(
+            report.issues[0].action,
+            CalendarChangeAction::Clamped { day: 28 }
+        );
+        assert_eq!(
+            report.issues[0].to_string(),
+            "2000-02-29 01:00:00 at index 1 clamped to 2000-02-28"
+        );
+        assert!(report.dropped().next().is_none());
+        // The 31st of a month does not exist in the 360_day calendar
+        let datetimes = vec![CFDatetime::from_ymd(2000, 1, 31, Calendar::NoLeap).unwrap()];
+        let (converted, report) = CFDatetime::change_calendar_many(
+            &datetimes,
+            Calendar::Day360,
+            InvalidDatePolicy::Clamp,
+        )
+        .unwrap();
+        assert_eq!(converted[0].ymd().unwrap(), (2000, 1, 30));
+        assert!(!report.is_lossless());
+        // Every date exists in a calendar with more days
+        let (converted, report) = CFDatetime::change_calendar_many(
+            &datetimes,
+            Calendar::AllLeap,
+            InvalidDatePolicy::Drop,
+        )
+        .unwrap();
+        assert_eq!(converted[0].ymd().unwrap(), (2000, 1, 31));
+        assert!(report.is_lossless());
+        let mixed = vec![
+            CFDatetime::from_ymd(2000, 1, 1, Calendar::NoLeap).unwrap(),
+            CFDatetime::from_ymd(2000, 1, 1, Calendar::Standard).unwrap(),
+        ];
+        assert!(CFDatetime::change_calendar_many(
+            &mixed,
+            Calendar::Day360,
+            InvalidDatePolicy::Drop
+        )
+        .is_err_and(|e| e.is_calendar_error()));
+    }
+
+    #[test]
+    fn test_add_months() {
+        let cases = [
+            (Calendar::Standard, (2000, 1, 15), 1, (2000, 2, 15)),
+            (Calendar::Standard, (2000, 1, 31), 1, (2000, 2, 29)),
+            (Calendar::Standard, (2001, 1, 31), 1, (2001, 2, 28)),
+            (Calendar::Standard, (2000, 3, 31), -1, (2000, 2, 29)),
+            (Calendar::NoLeap, (2000, 1, 31), 1, (2000, 2, 28)),
+            (Calendar::AllLeap, (2001, 1, 31), 1, (2001, 2, 29)),
+            (Calendar::Day360, (2000, 1, 30), 1, (2000, 2, 30)),
+            (Calendar::Julian, (1900, 1, 31), 1, (1900, 2, 29)),
+            (
+                Calendar::ProlepticGregorian,
+                (2000, 11, 15),
+                14,
+                (2002, 1, 15),
+            ),
+            (Calendar::ProlepticGregorian, (0, 1, 15), -1, (-1, 12, 15)),
+        ];
+        for (calendar, (year, month, day), months, expected) in cases {
+            let datetime =
+                CFDatetime::from_ymd_hms(year, month, day, 6, 30, 0.0, calendar).unwrap();
+            let shifted = (&datetime + CFDuration::from_months(months, calendar)).unwrap();
+            assert_eq!(shifted.ymd().unwrap(), expected);
+            assert_eq!(shifted.hms().unwrap(), (6, 30, 0));
+        }
+        let datetime = CFDatetime::from_ymd(2000, 3, 31, Calendar::ProlepticGregorian).unwrap();
+        let shifted =
+            (&datetime - CFDuration::from_years(1, Calendar::ProlepticGregorian)).unwrap();
+        assert_eq!(shifted.ymd().unwrap(), (1999, 3, 31));
+        let duration = CFDuration::with_months(1, 86400, 500_000_000, Calendar::ProlepticGregorian);
+        let shifted = ((&datetime + &duration).unwrap() + &duration).unwrap();
+        assert_eq!(shifted.ymd_hms().unwrap(), (2000, 6, 2, 0, 0, 1));
+        let duration = CFDuration::from_months(i64::MAX, Calendar::ProlepticGregorian);
+        assert!(matches!(
+            &datetime + duration,
+            Err(crate::errors::Error::OutOfBoundsCalendar(_, _))
+        ));
+        let duration = CFDuration::from_months(i64::MIN, Calendar::ProlepticGregorian);
+        assert!((&datetime + &duration).is_err());
+        assert!(matches!(
+            &datetime - &duration,
+            Err(crate::errors::Error::OutOfBoundsCalendar(_, _))
+        ));
+        let duration = CFDuration::from_months(i64::MAX, Calendar::ProlepticGregorian);
+        assert!((&datetime - duration).is_err());
+        let duration = CFDuration::from_years(i64::MAX, Calendar::ProlepticGregorian);
+        assert!((&datetime + duration).is_err());
+    }
+
+    #[test]
+    fn test_iter_forward() {
+        let start = CFDatetime::from_ymd(2000, 1, 31, Calendar::Standard).unwrap();
+        let step = CFDuration::from_months(1, Calendar::Standard);
+        let dates: Vec<(i64, u8, u8)> = start
+            .iter_forward(step)
+            .unwrap()
+            .take(3)
+            .map(|datetime| datetime.ymd().unwrap())
+            .collect();
+        assert_eq!(dates, vec![(2000, 1, 31), (2000, 2, 29), (2000, 3, 31)]);
+
+        let start = CFDatetime::from_ymd(2000, 1, 1, Calendar::Day360).unwrap();
+        let end = CFDatetime::from_ymd(2000, 2, 1, Calendar::Day360).unwrap();
+        let step = CFDuration::from_hours(6, Calendar::Day360);
+        assert_eq!(start.iter_forward(step).unwrap().until(end).count(), 120);
+
+        let start = CFDatetime::from_ymd(2000, 1, 3, Calendar::NoLeap).unwrap();
+        let end = CFDatetime::from_ymd(2000, 1, 1, Calendar::NoLeap).unwrap();
+        let step = -CFDuration::from_days(1, Calendar::NoLeap);
+        let days: Vec<u8> = start
+            .iter_forward(step)
+            .unwrap()
+            .until(end)
+            .map(|datetime| datetime.ymd().unwrap().2)
+            .collect();
+        assert_eq!(days, vec![3, 2]);
+
+        assert!(start
+            .iter_forward(CFDuration::from_days(1, Calendar::Standard))
+            .is_err());
+        assert!(matches!(
+            start.iter_forward(CFDuration::from_days(0, Calendar::NoLeap)),
+            Err(crate::errors::Error::InvalidDuration(_))
+        ));
+        assert!(start
+            .iter_forward(CFDuration::from_months(0, Calendar::NoLeap))
+            .is_err());
+    }
+
+    #[test]
+    fn test_from_timestamps() {
+        let datetimes = CFDatetime::from_timestamps(&[0, 86400, -1], Calendar::Day360).unwrap();
+        assert_eq!(datetimes[1].ymd().unwrap(), (1970, 1, 2));
+        assert_eq!(datetimes[2].ymd_hms().unwrap(), (1969, 12, 30, 23, 59, 59));
+        assert_eq!(CFDatetime::to_timestamps(&datetimes), vec![0, 86400, -1]);
+        let datetimes =
+            CFDatetime::from_timestamps_nanos(&[1_500_000_000, -1], Calendar::ProlepticGregorian)
+                .unwrap();
+        assert_eq!(CFDatetime::to_timestamps(&datetimes), vec![1, -1]);
+        assert_eq!(
+            CFDatetime::to_timestamps_nanos(&datetimes),
+            vec![1_500_000_000, -1]
+        );
+        assert!(CFDatetime::from_timestamps_nanos(&[i128::MAX], Calendar::Standard).is_err());
+    }
+
+    #[test]
+    fn test_days_in_month_and_year() {
+        let datetime = CFDatetime::from_ymd(2000, 2, 10, Calendar::Standard).unwrap();
+        assert!(datetime.is_leap_year().unwrap());
+        assert_eq!(datetime.days_in_month().unwrap(), 29);
+        assert_eq!(datetime.days_in_year().unwrap(), 366);
+        let datetime = CFDatetime::from_ymd(2000, 2, 10, Calendar::Day360).unwrap();
+        assert!(!datetime.is_leap_year().unwrap());
+        assert_eq!(datetime.days_in_month().unwrap(), 30);
+        assert_eq!(datetime.days_in_year().unwrap(), 360);
+    }
+
+    #[test]
+    fn test_date_and_time_of_day() {
+        for calendar in [
+            Calendar::ProlepticGregorian,
+            Calendar::Day360,
+            Calendar::Julian,
+        ] {
+            let datetime = CFDatetime::from_ymd_hms(1500, 3, 4, 6, 30, 15.5, calendar).unwrap();
+            let date = datetime.date().unwrap();
+            assert_eq!(date.ymd_hms().unwrap(), (1500, 3, 4, 0, 0, 0));
+            assert_eq!(date.nanoseconds(), 0);
+            let time_of_day = datetime.time_of_day();
+            assert_eq!(time_of_day.seconds, 6 * 3600 + 30 * 60 + 15);
+            assert_eq!(time_of_day.nanoseconds, 500_000_000);
+            assert_eq!(datetime.seconds_since_midnight(), 23415.5);
+            assert!((&date + &time_of_day).unwrap() == datetime);
+            assert!(matches!(
+                CFDatetime::min(calendar).date(),
+                Err(crate::errors::Error::OutOfBoundsCalendar(_, _))
+            ));
+            assert!(CFDatetime::max(calendar).date().is_ok());
+        }
+    }
+
+    #[test]
+    fn test_ymd_hms_many() {
+        let calendars = [
+            Calendar::Standard,
+            Calendar::ProlepticGregorian,
+            Calendar::Julian,
+            Calendar::NoLeap,
+            Calendar::AllLeap,
+            Calendar::Day360,
+        ];
+        for calendar in calendars {
+            // Sorted axis crossing months, years and the gregorian break, then unsorted values
+            let mut timestamps: Vec<i64> = (0..3_000)
+                .map(|i| -12_219_292_800 - 40 * 86_400 + i * 3 * 3_600 + i * i * 977)
+                .collect();
+            timestamps.extend([0, -86_401, 10_000_000_000, 9_999_999_999, -90_000_000_000]);
+            let datetimes = CFDatetime::from_timestamps(&timestamps, calendar).unwrap();
+            let expected: Vec<_> = datetimes
+                .iter()
+                .map(|datetime| datetime.ymd_hms().unwrap())
+                .collect();
+            assert_eq!(
+                CFDatetime::ymd_hms_many(&datetimes).unwrap(),
+                expected,
+                "{calendar}"
+            );
+        }
+        let datetimes = vec![
+            CFDatetime::from_ymd(2000, 1, 1, Calendar::Standard).unwrap(),
+            CFDatetime::from_ymd(2000, 1, 1, Calendar::NoLeap).unwrap(),
+        ];
+        assert!(CFDatetime::ymd_hms_many(&datetimes).is_err());
+        assert!(CFDatetime::ymd_hms_many::<CFDatetime>(&[])
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_components_many() {
+        let calendar = Calendar::Standard;
+        let mut datetimes: Vec<CFDatetime> = (0..500)
+            .map(|i| {
+                CFDatetime::from_timestamp(-12_219_292_800 + i * 7_777, i as u32 * 13, calendar)
+            })
+            .collect::<Result<_, _>>()
+            .unwrap();
+        datetimes.push(CFDatetime::from_ymd_hms(1, 12, 31, 23, 59, 58.25, calendar).unwrap());
+        let components = CFDatetime::components_many(&datetimes).unwrap();
+        assert_eq!(components.len(), datetimes.len());
+        let dates = CFDatetime::ymd_hms_many(&datetimes).unwrap();
+        for (i, ((year, month, day, hour, minute, second), datetime)) in
+            dates.into_iter().zip(&datetimes).enumerate()
+        {
+            assert_eq!(components.years[i], year);
+            assert_eq!(components.months[i], month);
+            assert_eq!(components.days[i], day);
+            assert_eq!(components.hours[i], hour);
+            assert_eq!(components.minutes[i], minute);
+            assert_eq!(components.seconds[i], second);
+            assert_eq!(components.nanoseconds[i], datetime.nanoseconds());
+        }
+        assert_eq!(components.nanoseconds[500], 250_000_000);
+        // References work as well, e.g. the datetimes of the python bindings
+        let references: Vec<&CFDatetime> = datetimes.iter().collect();
+        assert_eq!(
+            CFDatetime::components_many(&references).unwrap(),
+            components
+        );
+        datetimes.push(CFDatetime::from_ymd(2000, 1, 1, Calendar::NoLeap).unwrap());
+        assert!(CFDatetime::components_many(&datetimes).is_err());
+        assert!(CFDatetime::components_many::<CFDatetime>(&[])
+            .unwrap()
+            .is_empty());
+    }
+    #[test]
+    fn test_min_max() {
+        let calendars = [
+            Calendar::Standard,
+            Calendar::ProlepticGregorian,
+            Calendar::Julian,
+            Calendar::NoLeap,
+            Calendar::AllLeap,
+            Calendar::Day360,
+        ];
+        for calendar in calendars {
+            let min = CFDatetime::min(calendar);
+            let max = CFDatetime::max(calendar);
+            let (year, month, day, hour, minute, second) = min.ymd_hms().unwrap();
+            let from_ymd_hms =
+                CFDatetime::from_ymd_hms(year, month, day, hour, minute, second as f32, calendar)
+                    .unwrap();
+            assert_eq!(from_ymd_hms.timestamp(), constants::MIN_TIMESTAMP);
+            let (year, month, day, hour, minute, second) = max.ymd_hms().unwrap();
+            let from_ymd_hms =
+                CFDatetime::from_ymd_hms(year, month, day, hour, minute, second as f32, calendar)
+                    .unwrap();
+            assert_eq!(from_ymd_hms.timestamp(), constants::MAX_TIMESTAMP);
+            assert!(CFDatetime::from_ymd(year + 1, 1, 1, calendar).is_err());
+            assert!(min.ymd().unwrap().0 < -291_000_000_000);
+            assert!(max.ymd().unwrap().0 > 291_000_000_000);
+            // The difference between the bounds does not fit in a duration
+            assert!(matches!(
+                &max - &min,
+                Err(crate::errors::Error::InvalidDuration(_))
+            ));
+            assert!(max.elapsed_since(&min, Unit::Day).is_err());
+            let epoch = CFDatetime::from_timestamp(0, 0, calendar).unwrap();
+            assert_eq!((&min - &epoch).unwrap().seconds, constants::MIN_TIMESTAMP);
+        }
+        assert_eq!(
+            CFDatetime::max(Calendar::ProlepticGregorian).timestamp_nanos(),
+            constants::MAX_TIMESTAMP_NANOS
+        );
+    }
+    #[test]
+    fn test_clamp_to_valid_range() {
+        let calendar = Calendar::NoLeap;
+        assert!(CFDatetime::is_in_valid_range(0));
+        assert!(!CFDatetime::is_in_valid_range(i128::MAX));
+        let clamped = CFDatetime::clamp_to_valid_range(i128::MAX, calendar);
+        assert!(clamped == CFDatetime::max(calendar));
+        let clamped = CFDatetime::clamp_to_valid_range(i128::MIN, calendar);
+        assert!(clamped == CFDatetime::min(calendar));
+        let clamped = CFDatetime::clamp_to_valid_range(1_500_000_000, calendar);
+        assert_eq!(clamped.timestamp(), 1);
+        assert_eq!(clamped.nanoseconds(), 500_000_000);
+    }
+    #[test]
+    fn test_ordinal() {
+        // Julian Day Number of the J2000 epoch
+        let datetime =
+            CFDatetime::from_ymd_hms(2000, 1, 1, 12, 0, 0.0, Calendar::Standard).unwrap();
+        assert_eq!(datetime.to_ordinal(), 2_451_545);
+        assert_eq!(datetime.to_ordinal_fractional(), 2_451_545.0);
+        let datetime = CFDatetime::from_ymd(-4712, 1, 1, Calendar::Julian).unwrap();
+        assert_eq!(datetime.to_ordinal(), 0);
+        let datetime = CFDatetime::from_ymd(1582, 10, 15, Calendar::Standard).unwrap();
+        assert_eq!(datetime.to_ordinal(), 2_299_161);
+        assert_eq!(datetime.to_ordinal_fractional(), 2_299_160.5);
+        let datetime = CFDatetime::from_ymd(0, 1, 1, Calendar::Day360).unwrap();
+        assert_eq!(datetime.to_ordinal(), 0);
+        let datetime = CFDatetime::from_ymd(0, 1, 1, Calendar::NoLeap).unwrap();
+        assert_eq!(datetime.to_ordinal(), 0);
+
+        let calendars = [
+            Calendar::Standard,
+            Calendar::ProlepticGregorian,
+            Calendar::Julian,
+            Calendar::NoLeap,
+            Calendar::AllLeap,
+            Calendar::Day360,
+        ];
+        for calendar in calendars {
+            let datetime = CFDatetime::from_ymd_hms(1999, 12, 30, 18, 30, 0.0, calendar).unwrap();
+            let ordinal = datetime.to_ordinal_fractional();
+            let from_ordinal = CFDatetime::from_ordinal(ordinal, calendar).unwrap();
+            assert_eq!(from_ordinal.ymd_hms().unwrap(), (1999, 12, 30, 18, 30, 0));
+            let from_ordinal =
+                CFDatetime::from_ordinal(datetime.to_ordinal() as f64, calendar).unwrap();
+            assert_eq!(from_ordinal.ymd_hms().unwrap(), (1999, 12, 30, 12, 0, 0));
+        }
+        assert!(CFDatetime::from_ordinal(f64::NAN, Calendar::Standard).is_err());
+    }
+
+    #[test]
+    fn test_julian_century() {
+        let epoch = CFDatetime::j2000(Calendar::Standard);
+        assert_eq!(epoch.to_ordinal_fractional(), 2_451_545.0);
+        assert_eq!(epoch.julian_century(), 0.0);
+        // Julian Day 2460676.5 is 2025-01-01 00:00:00
+        let datetime = CFDatetime::from_ymd(2025, 1, 1, Calendar::Standard).unwrap();
+        assert_eq!(datetime.days_since(&epoch).unwrap(), 9131.5);
+        assert_eq!(datetime.julian_century(), 9131.5 / 36525.0);
+        for (calendar, days) in [
+            (Calendar::NoLeap, 36500.0),
+            (Calendar::AllLeap, 36600.0),
+            (Calendar::Day360, 36000.0),
+        ] {
+            // A century of the model calendars is exactly a hundred years
+            let datetime = CFDatetime::from_ymd_hms(1900, 1, 1, 12, 0, 0.0, calendar).unwrap();
+            assert_eq!(
+                datetime.days_since(&CFDatetime::j2000(calendar)).unwrap(),
+                -days
+            );
+            assert_eq!(datetime.julian_century(), -1.0);
+        }
+        let datetime = CFDatetime::from_ymd_hms(2000, 1, 2, 0, 0, 0.0, Calendar::NoLeap).unwrap();
+        let origin = CFDatetime::from_ymd(2000, 1, 1, Calendar::NoLeap).unwrap();
+        assert_eq!(datetime.days_since(&origin).unwrap(), 1.0);
+        assert_eq!(
+            datetime.julian_centuries_since(&origin).unwrap(),
+            1.0 / 36500.0
+        );
+        assert!(datetime
+            .days_since(&epoch)
+            .err()
+            .unwrap()
+            .is_calendar_error());
+    }
+    #[test]
+    fn test_elapsed_since() {
+        use crate::encoder::CFEncoder;
+        let origin = CFDatetime::from_ymd(2000, 1, 1, Calendar::Standard).unwrap();
+        let datetime =
+            CFDatetime::from_ymd_hms(2001, 3, 4, 5, 6, 7.25, Calendar::Standard).unwrap();
+        let units = [
+            Unit::Year,
+            Unit::Month,
+            Unit::Week,
+            Unit::Day,
+            Unit::Hour,
+            Unit::Minute,
+            Unit::Second,
+            Unit::Millisecond,
+            Unit::Microsecond,
+            Unit::Nanosecond,
+        ];
+        for unit in units {
+            let encoded: f64 = datetime
+                .encode_cf(
+                    &format!("{} since 2000-01-01", unit.cf_name()),
+                    Calendar::Standard,
+                )
+                .unwrap();
+            assert_eq!(datetime.elapsed_since(&origin, unit).unwrap(), encoded);
+        }
+        assert_eq!(
+            origin.elapsed_since(&datetime, Unit::Second).unwrap(),
+            -36_997_567.25
+        );
+        assert_eq!(origin.elapsed_since(&origin, Unit::Day).unwrap(), 0.0);
+        let origin = CFDatetime::from_ymd(2000, 1, 1, Calendar::Day360).unwrap();
+        let datetime = CFDatetime::from_ymd(2001, 7, 1, Calendar::Day360).unwrap();
+        assert_eq!(datetime.elapsed_since(&origin, Unit::Year).unwrap(), 1.5);
+        assert_eq!(datetime.elapsed_since(&origin, Unit::Month).unwrap(), 18.0);
+        let other = CFDatetime::from_ymd(2000, 1, 1, Calendar::NoLeap).unwrap();
+        assert!(datetime
+            .elapsed_since(&other, Unit::Day)
+            .err()
+            .unwrap()
+            .is_calendar_error());
+    }
 }
@@ -1,7 +1,7 @@
 use crate::calendars::Calendar;
-use crate::datetimes::traits::{CalendarDatetime, IsLeap};
+use crate::datetimes::traits::{CalendarDatetime, CalendarOps};
 use crate::timezone::Tz;
-use crate::utils::{get_timestamp_from_hms, get_timestamp_from_ymd, get_ymd_hms_from_timestamp};
+use crate::utils::{get_timestamp_from_ymd_hms, get_ymd_hms_from_timestamp};
 
 use super::traits::CalendarDatetimeCreator;
 
@@ -22,7 +22,8 @@ impl AllLeapDatetime {
         }
     }
 }
-impl IsLeap for AllLeapDatetime {
+impl CalendarOps for AllLeapDatetime {
+    const LEAP_CYCLE: Option<(i64, i64)> = Some((1, 366));
     fn is_leap(_year: i64) -> bool {
         true
     }
@@ -41,7 +42,7 @@ impl CalendarDatetime for AllLeapDatetime {
     fn timezone(&self) -> Tz {
         self.tz
     }
-    fn ymd_hms(&self) -> Result<(i64, u8, u8, u8, u8, u8), crate::errors::Error> {
+    fn ymd_hms(&self) -> crate::errors::Result<(i64, u8, u8, u8, u8, u8)> {
         Ok(get_ymd_hms_from_timestamp::<AllLeapDatetime>(
             self.timestamp,
         ))
@@ -63,9 +64,9 @@ impl CalendarDatetimeCreator for AllLeapDatetime {
         hour: u8,
         minute: u8,
         second: f32,
-    ) -> Result<Self, crate::errors::Error> {
-        let (mut timestamp, nanoseconds) = get_timestamp_from_hms(hour, minute, second)?;
-        timestamp += get_timestamp_from_ymd::<AllLeapDatetime>(year, month, day)?;
+    ) -> crate::errors::Result<Self> {
+        let (timestamp, nanoseconds) =
+            get_timestamp_from_ymd_hms::<AllLeapDatetime>(year, month, day, hour, minute, second)?;
         Ok(Self {
             timestamp,
             nanoseconds,
@@ -1,8 +1,8 @@
 use crate::calendars::Calendar;
 use crate::constants;
-use crate::datetimes::traits::CalendarDatetime;
+use crate::datetimes::traits::{month_index, CalendarDatetime, CalendarOps};
 use crate::timezone::Tz;
-use crate::utils::{get_hms_from_timestamp, get_timestamp_from_hms};
+use crate::utils::{get_timestamp_from_ymd_hms, get_ymd_hms_from_timestamp};
 
 use super::traits::CalendarDatetimeCreator;
 pub struct Day360Datetime {
@@ -23,6 +23,35 @@ impl Day360Datetime {
     }
 }
 
+impl CalendarOps for Day360Datetime {
+    fn is_leap(_year: i64) -> bool {
+        false
+    }
+    fn days_in_month(_year: i64, month: u8) -> crate::errors::Result<u8> {
+        Ok(constants::DAYS_PER_MONTH_360[month_index(month)?])
+    }
+    fn ymd_to_days(year: i64, month: u8, day: u8) -> crate::errors::Result<i64> {
+        month_index(month)?;
+        let days = (year as i128 - constants::UNIX_DEFAULT_YEAR as i128) * 360
+            + (month as i128 - 1) * 30
+            + day as i128
+            - 1;
+        i64::try_from(days).map_err(|_| {
+            crate::errors::Error::InvalidDate(format!(
+                "{year}-{month:02}-{day:02} is out of the range of the calendar"
+            ))
+        })
+    }
+    fn days_to_ymd(days: i64) -> (i64, u8, u8) {
+        let (nb_year, remaining_days) = (days.div_euclid(360), days.rem_euclid(360));
+        (
+            constants::UNIX_DEFAULT_YEAR + nb_year,
+            (remaining_days / 30 + 1) as u8,
+            (remaining_days % 30 + 1) as u8,
+        )
+    }
+}
+
 impl CalendarDatetime for Day360Datetime {
     fn timestamp(&self) -> i64 {
         self.timestamp
@@ -37,26 +66,8 @@ impl CalendarDatetime for Day360Datetime {
         self.calendar
     }
 
-    fn ymd_hms(&self) -> Result<(i64, u8, u8, u8, u8, u8), crate::errors::Error> {
-        let mut nb_days = self.timestamp / constants::SECS_PER_DAY as i64;
-        let remaining_seconds = self.timestamp % constants::SECS_PER_DAY as i64;
-        if remaining_seconds < 0 {
-            nb_days -= 1
-        }
-        let (nb_year, remaining_days) = (nb_days.div_euclid(360), nb_days.rem_euclid(360));
-
-        let (mut month, day) = (remaining_days / 30, remaining_days % 30);
-        month = (month + 12) % 12;
-        let year = constants::UNIX_DEFAULT_YEAR + nb_year;
-        let (hour, minute, second) = get_hms_from_timestamp(remaining_seconds);
-        Ok((
-            year,
-            (month + 1) as u8,
-            (day + 1) as u8,
-            hour,
-            minute,
-            second,
-        ))
+    fn ymd_hms(&self) -> crate::errors::Result<(i64, u8, u8, u8, u8, u8)> {
+        Ok(get_ymd_hms_from_timestamp::<Day360Datetime>(self.timestamp))
     }
 }
 
@@ -76,30 +87,9 @@ impl CalendarDatetimeCreator for Day360Datetime {
         hour: u8,
         minute: u8,
         second: f32,
-    ) -> Result<Self, crate::errors::Error> {
-        let (mut timestamp, nanoseconds) = get_timestamp_from_hms(hour, minute, second)?;
-
-        // Calculate years and months
-        let mut year = year;
-        let month = month as i64 - 1;
-        let day = day as i64 - 1;
-
-        loop {
-            if year == constants::UNIX_DEFAULT_YEAR {
-                break;
-            }
-
-            if year > constants::UNIX_DEFAULT_YEAR {
-                timestamp += 360 * constants::SECS_PER_DAY as i64;
-                year -= 1;
-            } else {
-                timestamp -= 360 * constants::SECS_PER_DAY as i64;
-                year += 1;
-            }
-        }
-
-        // Calculate days
-        timestamp += (month * 30 + day) * constants::SECS_PER_DAY as i64;
+    ) -> crate::errors::Result<Self> {
+        let (timestamp, nanoseconds) =
+            get_timestamp_from_ymd_hms::<Day360Datetime>(year, month, day, hour, minute, second)?;
 
         Ok(Self {
             calendar: Calendar::Day360,
@@ -1,9 +1,10 @@
 use crate::calendars::Calendar;
-use crate::datetimes::traits::{CalendarDatetime, IsLeap};
+use crate::datetimes::traits::month_index;
+use crate::datetimes::traits::{CalendarDatetime, CalendarOps};
 use crate::timezone::Tz;
 use crate::utils::{
-    get_timestamp_from_hms, get_timestamp_from_ymd, get_ymd_hms_from_timestamp,
-    is_leap_julian,
+    get_days_from_ymd_in_era, get_timestamp_from_ymd_hms, get_ymd_from_days_in_era,
+    get_ymd_hms_from_timestamp, is_leap_julian, MarchEra,
 };
 
 use super::traits::CalendarDatetimeCreator;
@@ -24,10 +25,18 @@ impl JulianDatetime {
         }
     }
 }
-impl IsLeap for JulianDatetime {
+impl CalendarOps for JulianDatetime {
+    const LEAP_CYCLE: Option<(i64, i64)> = Some((4, 1461));
     fn is_leap(year: i64) -> bool {
         is_leap_julian(year)
     }
+    fn ymd_to_days(year: i64, month: u8, day: u8) -> crate::errors::Result<i64> {
+        month_index(month)?;
+        get_days_from_ymd_in_era(MarchEra::Julian, year, month, day)
+    }
+    fn days_to_ymd(days: i64) -> (i64, u8, u8) {
+        get_ymd_from_days_in_era(MarchEra::Julian, days)
+    }
 }
 
 impl CalendarDatetime for JulianDatetime {
@@ -44,7 +53,7 @@ impl CalendarDatetime for JulianDatetime {
         self.tz
     }
 
-    fn ymd_hms(&self) -> Result<(i64, u8, u8, u8, u8, u8), crate::errors::Error> {
+    fn ymd_hms(&self) -> crate::errors::Result<(i64, u8, u8, u8, u8, u8)> {
         Ok(get_ymd_hms_from_timestamp::<JulianDatetime>(self.timestamp))
     }
 }
@@ -65,9 +74,9 @@ impl CalendarDatetimeCreator for JulianDatetime {
         hour: u8,
         minute: u8,
         second: f32,
-    ) -> Result<Self, crate::errors::Error> {
-        let (mut timestamp, nanoseconds) = get_timestamp_from_hms(hour, minute, second)?;
-        timestamp += get_timestamp_from_ymd::<JulianDatetime>(year, month, day)?;
+    ) -> crate::errors::Result<Self> {
+        let (timestamp, nanoseconds) =
+            get_timestamp_from_ymd_hms::<JulianDatetime>(year, month, day, hour, minute, second)?;
         Ok(Self {
             timestamp,
             nanoseconds,
@@ -14,4 +14,5 @@ pub mod julian;
 pub mod no_leap;
 pub mod proleptic_gregorian;
 pub mod standard;
+pub(crate) mod storage;
 pub mod traits;
@@ -1,10 +1,12 @@
 use crate::calendars::Calendar;
-use crate::datetimes::traits::{CalendarDatetime, IsLeap};
+use crate::constants;
+use crate::datetimes::traits::{month_index, CalendarDatetime, CalendarOps};
+use crate::datetimes::{julian::JulianDatetime, proleptic_gregorian::ProlepticGregorianDatetime};
 use crate::timezone::Tz;
 use crate::utils::{
-    get_timestamp_from_hms, get_timestamp_from_ymd, get_ymd_hms_from_timestamp, is_leap_gregorian,
-    is_leap_julian,
+    get_timestamp_from_ymd_hms, get_ymd_hms_from_timestamp, is_leap_gregorian, is_leap_julian,
 };
+use alloc::string::ToString;
 
 use super::traits::CalendarDatetimeCreator;
 pub struct StandardDatetime {
@@ -24,7 +26,18 @@ impl StandardDatetime {
         }
     }
 }
-impl IsLeap for StandardDatetime {
+/// Number of days between 1970-01-01 and 1582-10-15, the first day of the gregorian calendar
+const GREGORIAN_BEGIN_DAYS: i64 = -141_427;
+
+/// Number of days between 1970-01-01 in the gregorian calendar and 1970-01-01 in the julian calendar
+const JULIAN_EPOCH_OFFSET_DAYS: i64 = 13;
+
+/// Returns `true` if the date is before the switch to the gregorian calendar on 1582-10-15
+fn is_julian_date(year: i64, month: u8, day: u8) -> bool {
+    (year, month, day) < (1582, 10, 15)
+}
+
+impl CalendarOps for StandardDatetime {
     fn is_leap(year: i64) -> bool {
         if year < 1582 {
             is_leap_julian(year)
@@ -32,6 +45,53 @@ impl IsLeap for StandardDatetime {
             is_leap_gregorian(year)
         }
     }
+    /// October 1582 has 21 days because of the switch from the julian to the gregorian calendar
+    fn days_in_month(year: i64, month: u8) -> crate::errors::Result<u8> {
+        let index = month_index(month)?;
+        Ok(match (year, month) {
+            (1582, 10) => 21,
+            _ if Self::is_leap(year) => constants::DAYS_PER_MONTH_LEAP[index],
+            _ => constants::DAYS_PER_MONTH[index],
+        })
+    }
+    /// Dates from 1582-10-15 are converted with the gregorian calendar and earlier dates with the
+    /// julian calendar, both of which skip whole leap cycles
+    fn ymd_to_days(year: i64, month: u8, day: u8) -> crate::errors::Result<i64> {
+        if year >= 1582 {
+            let days = ProlepticGregorianDatetime::ymd_to_days(year, month, day)?;
+            if is_julian_date(year, month, day) {
+                // Add 10 days from julian / gregorian break
+                Ok(days + 10)
+            } else {
+                Ok(days)
+            }
+        } else {
+            // The julian calendar counts from its own 1970-01-01, 13 days after the gregorian one
+            JulianDatetime::ymd_to_days(year, month, day)?
+                .checked_add(JULIAN_EPOCH_OFFSET_DAYS)
+                .ok_or_else(|| {
+                    crate::errors::Error::InvalidDate(format!(
+                        "{year}-{month:02}-{day:02} is out of the range of the calendar"
+                    ))
+                })
+        }
+    }
+    fn days_to_ymd(days: i64) -> (i64, u8, u8) {
+        if days < GREGORIAN_BEGIN_DAYS {
+            match days.checked_sub(JULIAN_EPOCH_OFFSET_DAYS) {
+                Some(days) => JulianDatetime::days_to_ymd(days),
+                // Near i64::MIN the date is computed one julian leap cycle of 4 years of 1461
+                // days later, then moved back by the 4 years
+                None => {
+                    let (year, month, day) =
+                        JulianDatetime::days_to_ymd(days + (1461 - JULIAN_EPOCH_OFFSET_DAYS));
+                    (year - 4, month, day)
+                }
+            }
+        } else {
+            ProlepticGregorianDatetime::days_to_ymd(days)
+        }
+    }
 }
 
 impl CalendarDatetime for StandardDatetime {
@@ -47,22 +107,18 @@ impl CalendarDatetime for StandardDatetime {
     fn timezone(&self) -> Tz {
         self.tz
     }
-    fn ymd_hms(&self) -> Result<(i64, u8, u8, u8, u8, u8), crate::errors::Error> {
-        let gregorian_begin = get_timestamp_from_ymd::<StandardDatetime>(1582, 10, 15)?;
-        let mut timestamp = self.timestamp;
-        if self.timestamp < gregorian_begin {
-            let seconds_in_10_days = 10 * 24 * 60 * 60;
-            timestamp -= seconds_in_10_days
-        }
-        Ok(get_ymd_hms_from_timestamp::<StandardDatetime>(timestamp))
+    fn ymd_hms(&self) -> crate::errors::Result<(i64, u8, u8, u8, u8, u8)> {
+        Ok(get_ymd_hms_from_timestamp::<StandardDatetime>(
+            self.timestamp,
+        ))
     }
 }
 
 impl CalendarDatetimeCreator for StandardDatetime {
-    fn from_timestamp(timestamp: i64, _nanoseconds: u32) -> Self {
+    fn from_timestamp(timestamp: i64, nanoseconds: u32) -> Self {
         Self {
             timestamp,
-            nanoseconds: 0,
+            nanoseconds,
             tz: Tz::new(0, 0).unwrap(),
             calendar: Calendar::Standard,
         }
@@ -74,8 +130,7 @@ impl CalendarDatetimeCreator for StandardDatetime {
         hour: u8,
         minute: u8,
         second: f32,
-    ) -> Result<Self, crate::errors::Error> {
-        let (mut timestamp, nanoseconds) = get_timestamp_from_hms(hour, minute, second)?;
+    ) -> crate::errors::Result<Self> {
         if year == 1582
             && month == 10
             && ((day == 4 && (hour > 0 || minute > 0 || second > 0.0)) || (5..15).contains(&day))
@@ -85,13 +140,8 @@ impl CalendarDatetimeCreator for StandardDatetime {
                     .to_string(),
             ));
         }
-        if year < 1582 || (year == 1582 && month < 10) || (year == 1582 && month == 10 && day < 15)
-        {
-            // Add 10 days from julian / gregorian break
-            timestamp += 10 * 24 * 60 * 60
-        }
-
-        timestamp += get_timestamp_from_ymd::<StandardDatetime>(year, month, day)?;
+        let (timestamp, nanoseconds) =
+            get_timestamp_from_ymd_hms::<StandardDatetime>(year, month, day, hour, minute, second)?;
         Ok(Self {
             timestamp,
             nanoseconds,
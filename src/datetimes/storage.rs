@@ -0,0 +1,91 @@
+//! Storage of the calendar datetime wrapped by a [crate::datetime::CFDatetime].
+//!
+//! By default every CFDatetime boxes its calendar datetime, which is one heap allocation per
+//! datetime. With the `inline-datetimes` feature the calendar datetimes are stored inline in an
+//! enum instead, so decoding a time axis only allocates the `Vec` of the datetimes.
+use crate::datetimes::all_leap::AllLeapDatetime;
+use crate::datetimes::day_360::Day360Datetime;
+use crate::datetimes::julian::JulianDatetime;
+use crate::datetimes::no_leap::NoLeapDatetime;
+use crate::datetimes::proleptic_gregorian::ProlepticGregorianDatetime;
+use crate::datetimes::standard::StandardDatetime;
+use crate::datetimes::traits::CalendarDatetime;
+#[cfg(feature = "inline-datetimes")]
+use crate::{calendars::Calendar, timezone::Tz};
+#[cfg(not(feature = "inline-datetimes"))]
+use alloc::boxed::Box;
+
+/// Calendar datetime of a CFDatetime, boxed without the `inline-datetimes` feature
+#[cfg(not(feature = "inline-datetimes"))]
+pub(crate) type DatetimeStorage = Box<dyn CalendarDatetime + Send + Sync>;
+
+/// Calendar datetime of a CFDatetime, stored inline with the `inline-datetimes` feature
+#[cfg(feature = "inline-datetimes")]
+pub(crate) enum DatetimeStorage {
+    ProlepticGregorian(ProlepticGregorianDatetime),
+    Standard(StandardDatetime),
+    Day360(Day360Datetime),
+    Julian(JulianDatetime),
+    NoLeap(NoLeapDatetime),
+    AllLeap(AllLeapDatetime),
+}
+
+#[cfg(feature = "inline-datetimes")]
+macro_rules! dispatch {
+    ($storage:expr, $datetime:ident => $call:expr) => {
+        match $storage {
+            DatetimeStorage::ProlepticGregorian($datetime) => $call,
+            DatetimeStorage::Standard($datetime) => $call,
+            DatetimeStorage::Day360($datetime) => $call,
+            DatetimeStorage::Julian($datetime) => $call,
+            DatetimeStorage::NoLeap($datetime) => $call,
+            DatetimeStorage::AllLeap($datetime) => $call,
+        }
+    };
+}
+
+#[cfg(feature = "inline-datetimes")]
+impl CalendarDatetime for DatetimeStorage {
+    fn ymd_hms(&self) -> crate::errors::Result<(i64, u8, u8, u8, u8, u8)> {
+        dispatch!(self, datetime => datetime.ymd_hms())
+    }
+    fn timestamp(&self) -> i64 {
+        dispatch!(self, datetime => datetime.timestamp())
+    }
+    fn nanoseconds(&self) -> u32 {
+        dispatch!(self, datetime => datetime.nanoseconds())
+    }
+    fn timezone(&self) -> Tz {
+        dispatch!(self, datetime => datetime.timezone())
+    }
+    fn calendar(&self) -> Calendar {
+        dispatch!(self, datetime => datetime.calendar())
+    }
+}
+
+/// Moves a calendar datetime into the storage of a CFDatetime
+pub(crate) trait IntoStorage {
+    fn into_storage(self) -> DatetimeStorage;
+}
+
+macro_rules! impl_into_storage {
+    ($datetime:ty, $variant:ident) => {
+        impl IntoStorage for $datetime {
+            #[cfg(not(feature = "inline-datetimes"))]
+            fn into_storage(self) -> DatetimeStorage {
+                Box::new(self)
+            }
+            #[cfg(feature = "inline-datetimes")]
+            fn into_storage(self) -> DatetimeStorage {
+                DatetimeStorage::$variant(self)
+            }
+        }
+    };
+}
+
+impl_into_storage!(ProlepticGregorianDatetime, ProlepticGregorian);
+impl_into_storage!(StandardDatetime, Standard);
+impl_into_storage!(Day360Datetime, Day360);
+impl_into_storage!(JulianDatetime, Julian);
+impl_into_storage!(NoLeapDatetime, NoLeap);
+impl_into_storage!(AllLeapDatetime, AllLeap);
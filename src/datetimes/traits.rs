@@ -1,11 +1,84 @@
-use crate::{calendars::Calendar, timezone::Tz};
+use crate::utils::{get_days_from_ymd, get_ymd_from_days};
+use crate::{calendars::Calendar, constants, timezone::Tz};
 
+/// Year and month arithmetic of a calendar, with the days counted from 1970-01-01.
+///
+/// Each datetime of [crate::datetimes] implements it, so array types of other crates can reuse the
+/// month lengths and leap year rules of the calendars without going through a CFDatetime.
+/// Only [CalendarOps::is_leap] is required, the other methods follow the 12 months of the
+/// gregorian and julian calendars.
+pub trait CalendarOps {
+    /// Number of years and days after which the leap years repeat, e.g. 400 years of 146097 days in
+    /// the gregorian calendar. Whole cycles are skipped when converting far dates, `None` scans
+    /// every year.
+    const LEAP_CYCLE: Option<(i64, i64)> = None;
+    /// Returns `true` if the year is a leap year
+    fn is_leap(year: i64) -> bool;
+    /// Returns the number of days of the month.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the number of days or an error of type `crate::errors::Error::InvalidDate`
+    /// if the month is not between 1 and 12
+    fn days_in_month(year: i64, month: u8) -> crate::errors::Result<u8> {
+        let index = month_index(month)?;
+        if Self::is_leap(year) {
+            Ok(constants::DAYS_PER_MONTH_LEAP[index])
+        } else {
+            Ok(constants::DAYS_PER_MONTH[index])
+        }
+    }
+    /// Returns the number of days between 1970-01-01 and the date. The day is not checked against
+    /// the length of the month.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the number of days or an error of type `crate::errors::Error::InvalidDate`
+    /// if the month is not between 1 and 12 or if the number of days does not fit in an `i64`
+    fn ymd_to_days(year: i64, month: u8, day: u8) -> crate::errors::Result<i64>
+    where
+        Self: Sized,
+    {
+        month_index(month)?;
+        get_days_from_ymd::<Self>(year, month, day)
+    }
+    /// Returns the year, month and day of the given number of days since 1970-01-01
+    fn days_to_ymd(days: i64) -> (i64, u8, u8)
+    where
+        Self: Sized,
+    {
+        get_ymd_from_days::<Self>(days)
+    }
+}
+
+/// Leap year rule of a calendar, which is now the required method of [CalendarOps]
+#[deprecated(
+    since = "0.1.7",
+    note = "use `CalendarOps`, which every datetime implements"
+)]
 pub trait IsLeap {
     fn is_leap(year: i64) -> bool;
 }
 
+#[allow(deprecated)]
+impl<T: CalendarOps> IsLeap for T {
+    fn is_leap(year: i64) -> bool {
+        <T as CalendarOps>::is_leap(year)
+    }
+}
+
+/// Returns the index of the month in the constants tables
+pub(crate) fn month_index(month: u8) -> crate::errors::Result<usize> {
+    if !(1..=12).contains(&month) {
+        return Err(crate::errors::Error::InvalidDate(format!(
+            "Month {month} is out of bounds"
+        )));
+    }
+    Ok((month - 1) as usize)
+}
+
 pub trait CalendarDatetime {
-    fn ymd_hms(&self) -> Result<(i64, u8, u8, u8, u8, u8), crate::errors::Error>;
+    fn ymd_hms(&self) -> crate::errors::Result<(i64, u8, u8, u8, u8, u8)>;
     fn timestamp(&self) -> i64;
     fn nanoseconds(&self) -> u32;
     fn timezone(&self) -> Tz;
@@ -22,6 +95,115 @@ where
         hour: u8,
         minute: u8,
         second: f32,
-    ) -> Result<Self, crate::errors::Error>;
+    ) -> crate::errors::Result<Self>;
     fn from_timestamp(timestamp: i64, nanoseconds: u32) -> Self;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::datetimes::{
+        all_leap::AllLeapDatetime, day_360::Day360Datetime, julian::JulianDatetime,
+        no_leap::NoLeapDatetime, proleptic_gregorian::ProlepticGregorianDatetime,
+        standard::StandardDatetime,
+    };
+
+    fn assert_round_trip<T: CalendarOps>() {
+        for days in (-400_000..400_000).step_by(367) {
+            let (year, month, day) = T::days_to_ymd(days);
+            assert!(day <= T::days_in_month(year, month).unwrap() || (year, month) == (1582, 10));
+            assert_eq!(T::ymd_to_days(year, month, day).unwrap(), days);
+        }
+    }
+
+    #[test]
+    fn test_calendar_ops_round_trip() {
+        assert_round_trip::<StandardDatetime>();
+        assert_round_trip::<ProlepticGregorianDatetime>();
+        assert_round_trip::<JulianDatetime>();
+        assert_round_trip::<NoLeapDatetime>();
+        assert_round_trip::<AllLeapDatetime>();
+        assert_round_trip::<Day360Datetime>();
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_is_leap() {
+        fn is_leap<T: IsLeap>(year: i64) -> bool {
+            <T as IsLeap>::is_leap(year)
+        }
+        assert!(is_leap::<JulianDatetime>(1900));
+        assert!(!is_leap::<StandardDatetime>(1900));
+        assert!(is_leap::<AllLeapDatetime>(2001));
+    }
+
+    #[test]
+    fn test_calendar_ops() {
+        assert_eq!(
+            ProlepticGregorianDatetime::ymd_to_days(2000, 1, 1).unwrap(),
+            10957
+        );
+        assert_eq!(Day360Datetime::ymd_to_days(1969, 12, 30).unwrap(), -1);
+        assert_eq!(Day360Datetime::days_to_ymd(-361), (1968, 12, 30));
+        assert_eq!(NoLeapDatetime::days_to_ymd(365), (1971, 1, 1));
+        // The day after 1582-10-04 is 1582-10-15 in the standard calendar
+        let julian_end = StandardDatetime::ymd_to_days(1582, 10, 4).unwrap();
+        assert_eq!(
+            StandardDatetime::days_to_ymd(julian_end + 1),
+            (1582, 10, 15)
+        );
+        assert_eq!(StandardDatetime::days_in_month(1582, 10).unwrap(), 21);
+        assert_eq!(JulianDatetime::days_in_month(1900, 2).unwrap(), 29);
+        assert_eq!(
+            ProlepticGregorianDatetime::days_in_month(1900, 2).unwrap(),
+            28
+        );
+        assert!(AllLeapDatetime::ymd_to_days(2000, 13, 1).is_err());
+        assert!(NoLeapDatetime::days_in_month(2000, 0).is_err());
+    }
+
+    fn assert_far_round_trip<T: CalendarOps>() {
+        let max_days = i64::MAX / crate::constants::SECS_PER_DAY as i64;
+        for days in [
+            -max_days - 1,
+            -max_days / 3,
+            -1_000_000_007,
+            1_000_000_007,
+            max_days,
+        ] {
+            let (year, month, day) = T::days_to_ymd(days);
+            assert_eq!(T::ymd_to_days(year, month, day).unwrap(), days);
+        }
+        assert!(T::ymd_to_days(i64::MAX, 12, 31).is_err());
+        assert!(T::ymd_to_days(i64::MIN, 1, 1).is_err());
+        // The bounds of the days do not overflow and their dates are ordered, including across the
+        // julian offset of the standard calendar near i64::MIN
+        for (days, next) in [
+            (i64::MIN, i64::MIN + 1),
+            (i64::MIN + 12, i64::MIN + 13),
+            (i64::MAX - 1, i64::MAX),
+        ] {
+            let date = T::days_to_ymd(days);
+            let next_date = T::days_to_ymd(next);
+            assert!(date < next_date, "{:?} {:?}", date, next_date);
+        }
+    }
+
+    #[test]
+    fn test_calendar_ops_far_dates() {
+        assert_far_round_trip::<StandardDatetime>();
+        assert_far_round_trip::<ProlepticGregorianDatetime>();
+        assert_far_round_trip::<JulianDatetime>();
+        assert_far_round_trip::<NoLeapDatetime>();
+        assert_far_round_trip::<AllLeapDatetime>();
+        assert_far_round_trip::<Day360Datetime>();
+        assert_eq!(
+            StandardDatetime::ymd_to_days(1582, 10, 15).unwrap(),
+            ProlepticGregorianDatetime::ymd_to_days(1582, 10, 15).unwrap()
+        );
+        assert_eq!(
+            StandardDatetime::ymd_to_days(-4713, 1, 1).unwrap(),
+            JulianDatetime::ymd_to_days(-4713, 1, 1).unwrap() + 13
+        );
+    }
+}
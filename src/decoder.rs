@@ -1,7 +1,181 @@
 //! Module that implements the decode_cf method for `i32`, `i64`, `f32`, `f64`,  `Vec<i32>`, `Vec<i64>`, `Vec<f32>` and `Vec<f64>`.
+//! Vectors of `Option` of these types are also supported and keep `None` as missing values.
+//! Pairs of values such as the CF `time_bnds` variable can be decoded with [BoundsCFDecoder].
+//!
+//! The vector decoders are implemented on slices so `&[T]`, `[T; N]` and `Box<[T]>` can also be decoded.
+//!
+//! Values read chunk by chunk from a large store can be decoded with [decode_cf_chunked].
+//!
+//! Raw buffers, e.g. a memory-mapped netCDF classic file or an HDF5 chunk, can be decoded without
+//! first copying them into a vector of numbers with [decode_cf_bytes].
+//!
+//! The non CF absolute time units written by some tools such as cdo, i.e. `day as %Y%m%d.%f` and
+//! `month as %Y%m.%f`, can be decoded with the opt-in [AbsoluteCFDecoder] and [VecAbsoluteCFDecoder].
+//!
+//! NaN and infinite values are rejected with an error of type `crate::errors::Error::InvalidValue`
+//! giving their index. Missing values must be given as `None` to [VecOptionCFDecoder].
 
+use crate::duration::CFDuration;
+use crate::float;
+use crate::instrument::trace_span;
+use crate::parser::{parse_absolute_time, AbsoluteUnit, Unit};
 use crate::utils::get_datetime_and_unit_from_units;
 use crate::{calendars::Calendar, datetime::CFDatetime};
+use alloc::{string::ToString, vec::Vec};
+
+/// Numbers scaling the duration of the units when decoding, i.e. `i32`, `i64`, `u32`, `u64`,
+/// `f32` and `f64`
+///
+/// The integers are multiplied exactly with [CFDuration::checked_mul], without going through
+/// a float, so decoding an `i32` or an `i64` gives the same datetime whatever its magnitude.
+pub trait ScaleDuration: Copy + core::fmt::Display {
+    /// Multiplies the duration, failing instead of overflowing
+    fn checked_scale(self, duration: &CFDuration) -> Option<CFDuration>;
+    /// Multiplies the duration, returning an error of type
+    /// `crate::errors::Error::OutOfBoundsCalendar` on overflow
+    fn scale(self, duration: &CFDuration) -> crate::errors::Result<CFDuration> {
+        self.checked_scale(duration).ok_or_else(|| {
+            crate::errors::Error::OutOfBoundsCalendar(
+                duration.calendar().to_string(),
+                format!("{} times {} overflows", self, duration),
+            )
+        })
+    }
+    /// Returns the value if it is finite, i.e. not NaN nor infinite, or an error of type
+    /// `crate::errors::Error::InvalidValue` with the index of the value
+    fn check_finite(self, index: usize) -> crate::errors::Result<Self>;
+    /// Multiplies a number of nanoseconds exactly, failing instead of overflowing. Returns `None`
+    /// by default, e.g. for the floats, whose product is made with [Self::checked_scale] instead.
+    fn checked_scale_nanoseconds(self, _nanoseconds: i128) -> Option<i128> {
+        None
+    }
+}
+
+macro_rules! impl_scale_duration {
+    // The integers multiply the nanoseconds exactly, as CFDuration::checked_mul
+    (@nanoseconds exact) => {
+        fn checked_scale_nanoseconds(self, nanoseconds: i128) -> Option<i128> {
+            nanoseconds.checked_mul(self as i128)
+        }
+    };
+    ($type:ty, $method:ident, $into:ty $(, $exact:ident)?) => {
+        impl ScaleDuration for $type {
+            fn checked_scale(self, duration: &CFDuration) -> Option<CFDuration> {
+                duration.$method(self as $into)
+            }
+            fn check_finite(self, index: usize) -> crate::errors::Result<Self> {
+                let value = self as f64;
+                if value.is_finite() {
+                    Ok(self)
+                } else {
+                    Err(crate::errors::Error::InvalidValue(index, value))
+                }
+            }
+            $(impl_scale_duration!(@nanoseconds $exact);)?
+        }
+    };
+}
+
+impl_scale_duration!(i64, checked_mul, i64, exact);
+impl_scale_duration!(i32, checked_mul, i64, exact);
+impl_scale_duration!(u32, checked_mul, i64, exact);
+
+impl ScaleDuration for u64 {
+    fn checked_scale(self, duration: &CFDuration) -> Option<CFDuration> {
+        duration.checked_mul(i64::try_from(self).ok()?)
+    }
+    fn check_finite(self, _index: usize) -> crate::errors::Result<Self> {
+        Ok(self)
+    }
+    impl_scale_duration!(@nanoseconds exact);
+}
+impl_scale_duration!(f64, checked_mul_f64, f64);
+impl_scale_duration!(f32, checked_mul_f64, f64);
+
+/// Reference datetime and duration of the units, prepared once to decode many values.
+///
+/// The durations of the units have no calendar months, so a value is decoded by adding its
+/// number of nanoseconds to the ones of the reference datetime and making a single datetime from
+/// the sum, instead of adding a [CFDuration] to the reference datetime.
+pub(crate) struct Decoding {
+    origin: CFDatetime,
+    origin_nanoseconds: i128,
+    duration: CFDuration,
+    duration_nanoseconds: i128,
+}
+
+impl Decoding {
+    /// Makes a new decoding from the reference datetime and the unit of the units, whose years
+    /// and months are approximated in seconds. Fails if one increment of the units overflows the
+    /// duration
+    pub(crate) fn new(
+        origin: CFDatetime,
+        unit: &Unit,
+        multiplier: i64,
+    ) -> crate::errors::Result<Self> {
+        let duration = unit.to_duration_with_multiplier(origin.calendar(), multiplier)?;
+        Ok(Self {
+            origin_nanoseconds: origin.timestamp() as i128 * 1_000_000_000
+                + origin.nanoseconds() as i128,
+            duration_nanoseconds: duration.total_nanoseconds(),
+            origin,
+            duration,
+        })
+    }
+    /// Parses the units and calendar
+    pub(crate) fn from_units(units: &str, calendar: Calendar) -> crate::errors::Result<Self> {
+        let (origin, unit, multiplier) = get_datetime_and_unit_from_units(units, calendar)?;
+        Self::new(origin, &unit, multiplier)
+    }
+    /// Returns the reference datetime of the units
+    pub(crate) fn origin(&self) -> &CFDatetime {
+        &self.origin
+    }
+    /// Returns the duration of one increment of the units
+    pub(crate) fn duration(&self) -> &CFDuration {
+        &self.duration
+    }
+    /// Decodes the value at the given index of the values
+    pub(crate) fn decode<T: ScaleDuration>(
+        &self,
+        value: T,
+        index: usize,
+    ) -> crate::errors::Result<CFDatetime> {
+        let calendar = self.origin.calendar();
+        let out_of_bounds = || {
+            crate::errors::Error::OutOfBoundsCalendar(
+                calendar.to_string(),
+                format!("{} + {} times {}", self.origin, value, self.duration),
+            )
+        };
+        let value = value.check_finite(index)?;
+        // An overflow of the integers also overflows the product of the duration
+        let nanoseconds = value
+            .checked_scale_nanoseconds(self.duration_nanoseconds)
+            .or_else(|| {
+                value
+                    .checked_scale(&self.duration)
+                    .map(|duration| duration.total_nanoseconds())
+            })
+            .and_then(|nanoseconds| nanoseconds.checked_add(self.origin_nanoseconds))
+            .ok_or_else(out_of_bounds)?;
+        let timestamp =
+            i64::try_from(nanoseconds.div_euclid(1_000_000_000)).map_err(|_| out_of_bounds())?;
+        let nanoseconds = nanoseconds.rem_euclid(1_000_000_000) as u32;
+        CFDatetime::from_timestamp(timestamp, nanoseconds, calendar)
+    }
+    /// Decodes the values into a preallocated vector
+    pub(crate) fn decode_many<T: ScaleDuration>(
+        &self,
+        values: &[T],
+    ) -> crate::errors::Result<Vec<CFDatetime>> {
+        let mut datetimes = Vec::with_capacity(values.len());
+        for (index, value) in values.iter().enumerate() {
+            datetimes.push(self.decode(*value, index)?);
+        }
+        Ok(datetimes)
+    }
+}
 
 /// Trait for decoding CFDatetime from units and calendar
 pub trait CFDecoder {
@@ -15,11 +189,7 @@ pub trait CFDecoder {
     /// # Returns
     ///
     /// A Result containing the decoded CFDatetime if successful, or an Error if decoding fails.
-    fn decode_cf(
-        &self,
-        units: &str,
-        calendar: Calendar,
-    ) -> Result<CFDatetime, crate::errors::Error>;
+    fn decode_cf(&self, units: &str, calendar: Calendar) -> crate::errors::Result<CFDatetime>;
 }
 
 macro_rules! impl_cf_decoder {
@@ -29,10 +199,12 @@ macro_rules! impl_cf_decoder {
                 &self,
                 units: &str,
                 calendar: Calendar,
-            ) -> Result<CFDatetime, crate::errors::Error> {
-                let (cf_datetime, unit) = get_datetime_and_unit_from_units(units, calendar)?;
-                let duration = unit.to_duration(calendar);
-                let result = (&cf_datetime + (&duration * *self))?;
+            ) -> crate::errors::Result<CFDatetime> {
+                trace_span!(TRACE, "decode_cf", calendar = %calendar);
+                let (cf_datetime, unit, multiplier) =
+                    get_datetime_and_unit_from_units(units, calendar)?;
+                let duration = unit.to_duration_with_multiplier(calendar, multiplier)?;
+                let result = (&cf_datetime + self.check_finite(0)?.scale(&duration)?)?;
 
                 Ok(result)
             }
@@ -46,39 +218,471 @@ impl_cf_decoder!(f32);
 impl_cf_decoder!(f64);
 
 pub trait VecCFDecoder {
+    fn decode_cf(&self, units: &str, calendar: Calendar) -> crate::errors::Result<Vec<CFDatetime>>;
+}
+
+macro_rules! impl_vec_cf_decoder {
+    ($type:ty) => {
+        impl VecCFDecoder for [$type] {
+            fn decode_cf(
+                &self,
+                units: &str,
+                calendar: Calendar,
+            ) -> crate::errors::Result<Vec<CFDatetime>> {
+                trace_span!(DEBUG, "decode_cf", calendar = %calendar, values = self.len());
+                Decoding::from_units(units, calendar)?.decode_many(self)
+            }
+        }
+        impl VecCFDecoder for Vec<$type> {
+            fn decode_cf(
+                &self,
+                units: &str,
+                calendar: Calendar,
+            ) -> crate::errors::Result<Vec<CFDatetime>> {
+                self.as_slice().decode_cf(units, calendar)
+            }
+        }
+    };
+}
+
+impl_vec_cf_decoder!(i64);
+impl_vec_cf_decoder!(i32);
+impl_vec_cf_decoder!(f32);
+impl_vec_cf_decoder!(f64);
+
+/// Trait for decoding a vector of optional values into optional CFDatetimes
+///
+/// `None` values are considered as missing values and are kept as `None`.
+pub trait VecOptionCFDecoder {
+    /// Decodes the given values with the units and calendar.
+    ///
+    /// # Arguments
+    ///
+    /// * `units` - The units to decode.
+    /// * `calendar` - The calendar to use for decoding.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the decoded `Vec<Option<CFDatetime>>` if successful, or an Error if decoding fails.
     fn decode_cf(
         &self,
         units: &str,
         calendar: Calendar,
-    ) -> Result<Vec<CFDatetime>, crate::errors::Error>;
+    ) -> crate::errors::Result<Vec<Option<CFDatetime>>>;
 }
 
-macro_rules! impl_vec_cf_decoder {
+macro_rules! impl_vec_option_cf_decoder {
     ($type:ty) => {
-        impl VecCFDecoder for Vec<$type> {
+        impl VecOptionCFDecoder for [Option<$type>] {
             fn decode_cf(
                 &self,
                 units: &str,
                 calendar: Calendar,
-            ) -> Result<Vec<CFDatetime>, crate::errors::Error> {
-                let (cf_datetime, unit) = get_datetime_and_unit_from_units(units, calendar)?;
-                let duration = unit.to_duration(calendar);
+            ) -> crate::errors::Result<Vec<Option<CFDatetime>>> {
+                trace_span!(DEBUG, "decode_cf", calendar = %calendar, values = self.len());
+                let decoding = Decoding::from_units(units, calendar)?;
                 let mut datetimes = Vec::with_capacity(self.len());
-                for value in self {
-                    let new_datetime = &cf_datetime + (&duration * *value);
-                    datetimes.push(new_datetime?);
+                for (index, value) in self.iter().enumerate() {
+                    match value {
+                        Some(value) => datetimes.push(Some(decoding.decode(*value, index)?)),
+                        None => datetimes.push(None),
+                    }
                 }
 
                 Ok(datetimes)
             }
         }
+        impl VecOptionCFDecoder for Vec<Option<$type>> {
+            fn decode_cf(
+                &self,
+                units: &str,
+                calendar: Calendar,
+            ) -> crate::errors::Result<Vec<Option<CFDatetime>>> {
+                self.as_slice().decode_cf(units, calendar)
+            }
+        }
     };
 }
 
-impl_vec_cf_decoder!(i64);
-impl_vec_cf_decoder!(i32);
-impl_vec_cf_decoder!(f32);
-impl_vec_cf_decoder!(f64);
+impl_vec_option_cf_decoder!(i64);
+impl_vec_option_cf_decoder!(i32);
+impl_vec_option_cf_decoder!(f32);
+impl_vec_option_cf_decoder!(f64);
+
+/// Trait for decoding paired values such as the CF `time_bnds` variable
+///
+/// Each `[start, end]` pair is decoded into a `(start, end)` tuple of CFDatetime. The order of the pair is
+/// kept as is, so climatology bounds are decoded like any other bounds.
+pub trait BoundsCFDecoder {
+    /// Decodes the given pairs with the units and calendar.
+    ///
+    /// # Arguments
+    ///
+    /// * `units` - The units to decode.
+    /// * `calendar` - The calendar to use for decoding.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the decoded `Vec<(CFDatetime, CFDatetime)>` if successful, or an Error if decoding fails.
+    fn decode_bounds_cf(
+        &self,
+        units: &str,
+        calendar: Calendar,
+    ) -> crate::errors::Result<Vec<(CFDatetime, CFDatetime)>>;
+}
+
+macro_rules! impl_bounds_cf_decoder {
+    ($type:ty) => {
+        impl BoundsCFDecoder for [[$type; 2]] {
+            fn decode_bounds_cf(
+                &self,
+                units: &str,
+                calendar: Calendar,
+            ) -> crate::errors::Result<Vec<(CFDatetime, CFDatetime)>> {
+                trace_span!(DEBUG, "decode_bounds_cf", calendar = %calendar, bounds = self.len());
+                let decoding = Decoding::from_units(units, calendar)?;
+                let mut bounds = Vec::with_capacity(self.len());
+                for (index, [start, end]) in self.iter().enumerate() {
+                    bounds.push((decoding.decode(*start, index)?, decoding.decode(*end, index)?));
+                }
+
+                Ok(bounds)
+            }
+        }
+        impl BoundsCFDecoder for Vec<[$type; 2]> {
+            fn decode_bounds_cf(
+                &self,
+                units: &str,
+                calendar: Calendar,
+            ) -> crate::errors::Result<Vec<(CFDatetime, CFDatetime)>> {
+                self.as_slice().decode_bounds_cf(units, calendar)
+            }
+        }
+    };
+}
+
+impl_bounds_cf_decoder!(i64);
+impl_bounds_cf_decoder!(i32);
+impl_bounds_cf_decoder!(f32);
+impl_bounds_cf_decoder!(f64);
+
+/// Decodes the values chunk by chunk with the units and calendar, calling `sink` with the
+/// datetimes of each chunk.
+///
+/// The units are parsed once for all the chunks and the buffer given to `sink` is reused from
+/// one chunk to the next, so the datetimes must be copied by `sink` if they are kept.
+///
+/// # Arguments
+///
+/// * `chunks` - The chunks of values, e.g. read from a store.
+/// * `units` - The units to decode.
+/// * `calendar` - The calendar to use for decoding.
+/// * `sink` - Called with the decoded datetimes of each chunk, in order.
+///
+/// # Returns
+///
+/// A Result containing the total number of decoded values, or an Error if decoding fails. The
+/// chunks decoded before the error have already been given to `sink`.
+pub fn decode_cf_chunked<'a, T, I, F>(
+    chunks: I,
+    units: &str,
+    calendar: Calendar,
+    mut sink: F,
+) -> crate::errors::Result<usize>
+where
+    T: ScaleDuration + 'a,
+    I: IntoIterator<Item = &'a [T]>,
+    F: FnMut(&[CFDatetime]),
+{
+    trace_span!(DEBUG, "decode_cf_chunked", calendar = %calendar);
+    let decoding = Decoding::from_units(units, calendar)?;
+    let mut datetimes = Vec::new();
+    let mut count = 0;
+    for chunk in chunks {
+        datetimes.clear();
+        datetimes.reserve(chunk.len());
+        for (index, value) in chunk.iter().enumerate() {
+            datetimes.push(decoding.decode(*value, count + index)?);
+        }
+        count += datetimes.len();
+        sink(&datetimes);
+    }
+    Ok(count)
+}
+
+/// Type of encoded values, e.g. of a raw buffer, see [decode_cf_bytes]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[non_exhaustive]
+pub enum DType {
+    Int32,
+    Int64,
+    UInt32,
+    UInt64,
+    Float32,
+    Float64,
+}
+
+impl DType {
+    /// Every dtype, in the order of [DType::names]
+    pub const ALL: [DType; 6] = [
+        DType::Int32,
+        DType::Int64,
+        DType::UInt32,
+        DType::UInt64,
+        DType::Float32,
+        DType::Float64,
+    ];
+    /// Returns the number of bytes of a value
+    pub fn size(&self) -> usize {
+        match self {
+            DType::Int32 | DType::UInt32 | DType::Float32 => 4,
+            DType::Int64 | DType::UInt64 | DType::Float64 => 8,
+        }
+    }
+    /// Returns the names parsed into the dtype, the first one being its display name. The
+    /// numpy names of the unsigned integers count their bytes, e.g. `u8` is a `u64`.
+    pub fn names(&self) -> &'static [&'static str] {
+        match self {
+            DType::Int32 => &["i32", "int32"],
+            DType::Int64 => &["i64", "int64", "i", "integer", "int"],
+            DType::UInt32 => &["u32", "uint32", "u4"],
+            DType::UInt64 => &["u64", "uint64", "u8"],
+            DType::Float32 => &["f32", "float32"],
+            DType::Float64 => &["f64", "float64", "f", "float"],
+        }
+    }
+}
+
+impl core::fmt::Display for DType {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{}", self.names()[0])
+    }
+}
+
+impl core::str::FromStr for DType {
+    type Err = crate::errors::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().to_lowercase();
+        DType::ALL
+            .into_iter()
+            .find(|dtype| dtype.names().contains(&name.as_str()))
+            .ok_or_else(|| {
+                crate::errors::Error::InvalidDType(format!(
+                    "`{s}`, expected one of i32, i64, u32, u64, f32 or f64"
+                ))
+            })
+    }
+}
+
+/// Byte order of the values of a raw buffer, see [decode_cf_bytes]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum ByteOrder {
+    /// Little endian, e.g. the `<` of numpy
+    Little,
+    /// Big endian, e.g. the `>` of numpy and the netCDF classic format
+    Big,
+    /// Byte order of the platform
+    #[default]
+    Native,
+}
+
+/// Numbers read from the bytes of a raw buffer
+trait FromBytes: ScaleDuration {
+    const SIZE: usize;
+    fn from_bytes(bytes: &[u8], byteorder: ByteOrder) -> Self;
+}
+
+macro_rules! impl_from_bytes {
+    ($type:ty) => {
+        impl FromBytes for $type {
+            const SIZE: usize = core::mem::size_of::<$type>();
+            fn from_bytes(bytes: &[u8], byteorder: ByteOrder) -> Self {
+                let bytes = bytes.try_into().expect("Chunks have the size of the type");
+                match byteorder {
+                    ByteOrder::Little => <$type>::from_le_bytes(bytes),
+                    ByteOrder::Big => <$type>::from_be_bytes(bytes),
+                    ByteOrder::Native => <$type>::from_ne_bytes(bytes),
+                }
+            }
+        }
+    };
+}
+
+impl_from_bytes!(i64);
+impl_from_bytes!(i32);
+impl_from_bytes!(u64);
+impl_from_bytes!(u32);
+impl_from_bytes!(f32);
+impl_from_bytes!(f64);
+
+fn decode_bytes<T: FromBytes>(
+    bytes: &[u8],
+    byteorder: ByteOrder,
+    units: &str,
+    calendar: Calendar,
+) -> crate::errors::Result<Vec<CFDatetime>> {
+    let decoding = Decoding::from_units(units, calendar)?;
+    let mut datetimes = Vec::with_capacity(bytes.len() / T::SIZE);
+    for (index, chunk) in bytes.chunks_exact(T::SIZE).enumerate() {
+        datetimes.push(decoding.decode(T::from_bytes(chunk, byteorder), index)?);
+    }
+    Ok(datetimes)
+}
+
+/// Decodes the values of a raw buffer with the units and calendar.
+///
+/// The values are read one by one from the bytes, swapping them if the byte order is not the
+/// one of the platform, so the buffer is never copied into a vector of numbers.
+///
+/// # Arguments
+///
+/// * `bytes` - The raw buffer, whose length must be a multiple of the size of `dtype`.
+/// * `dtype` - The type of the values.
+/// * `byteorder` - The byte order of the values.
+/// * `units` - The units to decode.
+/// * `calendar` - The calendar to use for decoding.
+///
+/// # Returns
+///
+/// A Result containing the decoded `Vec<CFDatetime>` if successful, or an error of type
+/// `crate::errors::Error::InvalidBuffer` if the length of the buffer is not a multiple of the
+/// size of `dtype`, or any decoding error.
+pub fn decode_cf_bytes(
+    bytes: &[u8],
+    dtype: DType,
+    byteorder: ByteOrder,
+    units: &str,
+    calendar: Calendar,
+) -> crate::errors::Result<Vec<CFDatetime>> {
+    trace_span!(DEBUG, "decode_cf_bytes", calendar = %calendar, dtype = ?dtype, bytes = bytes.len());
+    if bytes.len() % dtype.size() != 0 {
+        return Err(crate::errors::Error::InvalidBuffer(format!(
+            "{} bytes is not a multiple of the {} bytes of {:?}",
+            bytes.len(),
+            dtype.size(),
+            dtype
+        )));
+    }
+    match dtype {
+        DType::Int32 => decode_bytes::<i32>(bytes, byteorder, units, calendar),
+        DType::Int64 => decode_bytes::<i64>(bytes, byteorder, units, calendar),
+        DType::UInt32 => decode_bytes::<u32>(bytes, byteorder, units, calendar),
+        DType::UInt64 => decode_bytes::<u64>(bytes, byteorder, units, calendar),
+        DType::Float32 => decode_bytes::<f32>(bytes, byteorder, units, calendar),
+        DType::Float64 => decode_bytes::<f64>(bytes, byteorder, units, calendar),
+    }
+}
+
+/// Decodes a date written as a number such as `20000115.5` for `day as %Y%m%d.%f`
+fn decode_absolute_time(
+    value: f64,
+    unit: AbsoluteUnit,
+    calendar: Calendar,
+) -> crate::errors::Result<CFDatetime> {
+    let invalid = || {
+        crate::errors::Error::InvalidDate(format!(
+            "{value} is not a valid absolute time in the {calendar} calendar"
+        ))
+    };
+    if !value.is_finite() {
+        return Err(invalid());
+    }
+    let integer = float::trunc(value) as i64;
+    let fraction = float::abs(float::fract(value));
+    let (year, month, day) = match unit {
+        AbsoluteUnit::Day => (
+            integer / 10_000,
+            (integer.abs() / 100 % 100) as u8,
+            (integer.abs() % 100) as u8,
+        ),
+        AbsoluteUnit::Month => (integer / 100, (integer.abs() % 100) as u8, 1),
+    };
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return Err(invalid());
+    }
+    let start = CFDatetime::from_ymd(year, month, day, calendar)?;
+    // Days that do not exist in the calendar overflow on the next month
+    if start.ymd()? != (year, month, day) {
+        return Err(invalid());
+    }
+    let length = match unit {
+        AbsoluteUnit::Day => CFDuration::from_days(1, calendar),
+        AbsoluteUnit::Month => (&start.add_months(1)? - &start)?,
+    };
+    &start + (&length * fraction)?
+}
+
+/// Trait for decoding CFDatetime from the non CF absolute time units
+/// `day as %Y%m%d.%f` and `month as %Y%m.%f`
+pub trait AbsoluteCFDecoder {
+    /// Decodes the date written as a number with the given units and calendar.
+    ///
+    /// # Arguments
+    ///
+    /// * `units` - The absolute time units, e.g. `day as %Y%m%d.%f`.
+    /// * `calendar` - The calendar to use for decoding.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the decoded CFDatetime if successful, or an Error if decoding fails.
+    fn decode_absolute_cf(
+        &self,
+        units: &str,
+        calendar: Calendar,
+    ) -> crate::errors::Result<CFDatetime>;
+}
+
+/// Trait for decoding a vector of CFDatetime from the non CF absolute time units
+/// `day as %Y%m%d.%f` and `month as %Y%m.%f`
+pub trait VecAbsoluteCFDecoder {
+    fn decode_absolute_cf(
+        &self,
+        units: &str,
+        calendar: Calendar,
+    ) -> crate::errors::Result<Vec<CFDatetime>>;
+}
+
+macro_rules! impl_absolute_cf_decoder {
+    ($type:ty) => {
+        impl AbsoluteCFDecoder for $type {
+            fn decode_absolute_cf(
+                &self,
+                units: &str,
+                calendar: Calendar,
+            ) -> crate::errors::Result<CFDatetime> {
+                let unit = parse_absolute_time(units)?;
+                decode_absolute_time(*self as f64, unit, calendar)
+            }
+        }
+        impl VecAbsoluteCFDecoder for [$type] {
+            fn decode_absolute_cf(
+                &self,
+                units: &str,
+                calendar: Calendar,
+            ) -> crate::errors::Result<Vec<CFDatetime>> {
+                trace_span!(DEBUG, "decode_absolute_cf", calendar = %calendar, values = self.len());
+                let unit = parse_absolute_time(units)?;
+                self.iter()
+                    .map(|value| decode_absolute_time(*value as f64, unit, calendar))
+                    .collect()
+            }
+        }
+        impl VecAbsoluteCFDecoder for Vec<$type> {
+            fn decode_absolute_cf(
+                &self,
+                units: &str,
+                calendar: Calendar,
+            ) -> crate::errors::Result<Vec<CFDatetime>> {
+                self.as_slice().decode_absolute_cf(units, calendar)
+            }
+        }
+    };
+}
+
+impl_absolute_cf_decoder!(i64);
+impl_absolute_cf_decoder!(i32);
+impl_absolute_cf_decoder!(f32);
+impl_absolute_cf_decoder!(f64);
 
 #[cfg(test)]
 mod tests {
@@ -216,13 +820,14 @@ mod tests {
         );
     }
     #[test]
+    #[allow(clippy::excessive_precision)]
     fn test_vec_decode_cf_days() {
         // Inverse function of test_vec_encode_cf_days
         let units = "days since 0000-01-01 00:00:00";
         // Tests with f64
         let numbers = vec![730487.0, 730488.0416666666, 730489.0833333334];
         let result = numbers.decode_cf(units, Calendar::Standard).unwrap();
-        let datetimes = vec![
+        let datetimes = [
             CFDatetime::from_ymd_hms(2000, 1, 1, 0, 0, 0.0, Calendar::Standard).unwrap(),
             CFDatetime::from_ymd_hms(2000, 1, 2, 1, 0, 0.0, Calendar::Standard).unwrap(),
             CFDatetime::from_ymd_hms(2000, 1, 3, 2, 0, 0.0, Calendar::Standard).unwrap(),
@@ -247,7 +852,7 @@ mod tests {
         let units = "days since 0000-01-01 00:00:00";
         let numbers: Vec<f32> = vec![730487.0, 730488.0416666666, 730489.0833333334];
         let result = numbers.decode_cf(units, Calendar::Standard).unwrap();
-        let datetimes = vec![
+        let datetimes = [
             CFDatetime::from_ymd_hms(2000, 1, 1, 0, 0, 0.0, Calendar::Standard).unwrap(),
             CFDatetime::from_ymd_hms(2000, 1, 2, 1, 30, 0.0, Calendar::Standard).unwrap(),
             CFDatetime::from_ymd_hms(2000, 1, 3, 1, 30, 0.0, Calendar::Standard).unwrap(),
@@ -259,5 +864,451 @@ mod tests {
             assert_eq!(expected_ymd_hms, result_ymd_hms);
         }
     }
+    #[test]
+    fn test_decode_weeks() {
+        let to_decode = vec![0, 1, 2];
+        let units = "weeks since 2000-01-01";
+        let datetimes = to_decode.decode_cf(units, Calendar::Standard).unwrap();
+        let expected = [(2000, 1, 1), (2000, 1, 8), (2000, 1, 15)];
+        for (datetime, expected) in datetimes.iter().zip(expected) {
+            assert_eq!(datetime.ymd().unwrap(), expected);
+        }
+    }
+    #[test]
+    fn test_decode_vec_option() {
+        let to_decode: Vec<Option<f64>> = vec![Some(0.0), None, Some(2.5)];
+        let units = "days since 2000-01-01";
+        let datetimes = to_decode.decode_cf(units, Calendar::Standard).unwrap();
+        assert_eq!(datetimes.len(), 3);
+        assert_eq!(
+            datetimes[0].as_ref().unwrap().ymd_hms().unwrap(),
+            (2000, 1, 1, 0, 0, 0)
+        );
+        assert!(datetimes[1].is_none());
+        assert_eq!(
+            datetimes[2].as_ref().unwrap().ymd_hms().unwrap(),
+            (2000, 1, 3, 12, 0, 0)
+        );
+
+        let to_decode: Vec<Option<i32>> = vec![None, Some(1)];
+        let datetimes = to_decode.decode_cf(units, Calendar::NoLeap).unwrap();
+        assert!(datetimes[0].is_none());
+        assert_eq!(datetimes[1].as_ref().unwrap().ymd().unwrap(), (2000, 1, 2));
+    }
+    #[test]
+    fn test_decode_containers() {
+        let units = "days since 2000-01-01";
+        let calendar = Calendar::Standard;
+        let expected = [(2000, 1, 1), (2000, 1, 2), (2000, 1, 3)];
+
+        let array: [f64; 3] = [0.0, 1.0, 2.0];
+        let slice: &[i32] = &[0, 1, 2];
+        let boxed: Box<[i64]> = vec![0, 1, 2].into_boxed_slice();
+        let options: [Option<f32>; 2] = [Some(1.0), None];
+        for datetimes in [
+            array.decode_cf(units, calendar).unwrap(),
+            slice.decode_cf(units, calendar).unwrap(),
+            boxed.decode_cf(units, calendar).unwrap(),
+        ] {
+            for (datetime, expected) in datetimes.iter().zip(expected) {
+                assert_eq!(datetime.ymd().unwrap(), expected);
+            }
+        }
+        let datetimes = options.decode_cf(units, calendar).unwrap();
+        assert_eq!(datetimes[0].as_ref().unwrap().ymd().unwrap(), (2000, 1, 2));
+        assert!(datetimes[1].is_none());
+    }
+    #[test]
+    fn test_decode_with_multiplier() {
+        let to_decode = vec![0, 1, 2];
+        let units = "3 hours since 2000-01-01";
+        let datetimes = to_decode.decode_cf(units, Calendar::Standard).unwrap();
+        let expected = [(0, 0, 0), (3, 0, 0), (6, 0, 0)];
+        for (datetime, expected) in datetimes.iter().zip(expected) {
+            assert_eq!(datetime.hms().unwrap(), expected);
+        }
+        // One increment of the units overflows the duration
+        let units = "9223372036854775807 days since 2000-01-01";
+        assert!(matches!(
+            1.decode_cf(units, Calendar::Standard),
+            Err(crate::errors::Error::InvalidDuration(_))
+        ));
+        assert!(to_decode.decode_cf(units, Calendar::Standard).is_err());
+        assert!(crate::converter::CFConverter::new(units, Calendar::Standard).is_err());
+    }
+    #[test]
+    fn test_decode_absolute_time() {
+        let datetime = 20000115.5
+            .decode_absolute_cf("day as %Y%m%d.%f", Calendar::Standard)
+            .unwrap();
+        assert_eq!(datetime.ymd_hms().unwrap(), (2000, 1, 15, 12, 0, 0));
+        let datetimes = vec![200002, 200101]
+            .decode_absolute_cf("month as %Y%m", Calendar::NoLeap)
+            .unwrap();
+        assert_eq!(datetimes[0].ymd().unwrap(), (2000, 2, 1));
+        assert_eq!(datetimes[1].ymd().unwrap(), (2001, 1, 1));
+        // Half of february 2000 is 14.5 days
+        let datetime = 200002.5
+            .decode_absolute_cf("month as %Y%m.%f", Calendar::Standard)
+            .unwrap();
+        assert_eq!(datetime.ymd_hms().unwrap(), (2000, 2, 15, 12, 0, 0));
+        // 30th of february only exists in the 360_day calendar
+        assert!(20000230
+            .decode_absolute_cf("day as %Y%m%d", Calendar::Standard)
+            .is_err());
+        assert!(20000230
+            .decode_absolute_cf("day as %Y%m%d", Calendar::Day360)
+            .is_ok());
+        assert!(20001301
+            .decode_absolute_cf("day as %Y%m%d", Calendar::Standard)
+            .is_err());
+        assert!(f64::NAN
+            .decode_absolute_cf("day as %Y%m%d.%f", Calendar::Standard)
+            .is_err());
+        assert!(20000101
+            .decode_absolute_cf("days since 2000-01-01", Calendar::Standard)
+            .is_err());
+    }
     // Add more test cases for other scenarios as needed
+
+    #[test]
+    fn test_decode_bounds_cf() {
+        let bounds: Vec<[i64; 2]> = vec![[0, 1], [1, 2], [31, 0]];
+        let decoded = bounds
+            .decode_bounds_cf("days since 2000-01-01", Calendar::Standard)
+            .unwrap();
+        let ymd: Vec<_> = decoded
+            .iter()
+            .map(|(start, end)| (start.ymd().unwrap(), end.ymd().unwrap()))
+            .collect();
+        assert_eq!(
+            ymd,
+            vec![
+                ((2000, 1, 1), (2000, 1, 2)),
+                ((2000, 1, 2), (2000, 1, 3)),
+                ((2000, 2, 1), (2000, 1, 1)),
+            ]
+        );
+        let bounds = [[0.5f64, 1.5]];
+        let decoded = bounds
+            .decode_bounds_cf("hours since 2000-01-01", Calendar::NoLeap)
+            .unwrap();
+        assert_eq!(decoded[0].0.hms().unwrap(), (0, 30, 0));
+        assert_eq!(decoded[0].1.hms().unwrap(), (1, 30, 0));
+    }
+
+    #[test]
+    fn test_decode_extreme_values() {
+        // Fine units do not overflow for any i64
+        let datetime = i64::MAX
+            .decode_cf("nanoseconds since 1970-01-01", Calendar::ProlepticGregorian)
+            .unwrap();
+        assert_eq!(datetime.timestamp_nanos(), i64::MAX as i128);
+        let datetime = i64::MIN
+            .decode_cf("milliseconds since 1970-01-01", Calendar::NoLeap)
+            .unwrap();
+        assert_eq!(datetime.timestamp_nanos(), i64::MIN as i128 * 1_000_000);
+        // Coarse units fail loudly instead of overflowing
+        for units in ["days since 1970-01-01", "common_years since 1970-01-01"] {
+            assert!(matches!(
+                i64::MAX.decode_cf(units, Calendar::Standard),
+                Err(crate::errors::Error::OutOfBoundsCalendar(_, _))
+            ));
+        }
+        assert!(matches!(
+            1e300f64.decode_cf("seconds since 1970-01-01", Calendar::Standard),
+            Err(crate::errors::Error::OutOfBoundsCalendar(_, _))
+        ));
+        assert!(f64::NAN
+            .decode_cf("seconds since 1970-01-01", Calendar::Standard)
+            .is_err());
+        // The product fits in an i128 but not its sum with the reference date, the bulk decoders
+        // fail like the scalar one
+        let units = "18446744074 seconds since 1000000-01-01";
+        let calendar = Calendar::ProlepticGregorian;
+        let value = 9223372036709551616i64;
+        assert!(matches!(
+            value.decode_cf(units, calendar),
+            Err(crate::errors::Error::OutOfBoundsCalendar(_, _))
+        ));
+        assert!(matches!(
+            vec![value].decode_cf(units, calendar),
+            Err(crate::errors::Error::OutOfBoundsCalendar(_, _))
+        ));
+        assert!(decode_cf_chunked([[value].as_slice()], units, calendar, |_| {}).is_err());
+        assert!(crate::converter::CFConverter::new(units, calendar)
+            .unwrap()
+            .decode(&[value])
+            .is_err());
+        let result = decode_cf_bytes(
+            &u64::MAX.to_le_bytes(),
+            DType::UInt64,
+            ByteOrder::Little,
+            "9223372036842006191 nanoseconds since 137047614746-05-02",
+            Calendar::NoLeap,
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_decode_non_finite_values() {
+        let units = "seconds since 1970-01-01";
+        let calendar = Calendar::Standard;
+        let is_invalid = |result: crate::errors::Result<_>, index: usize| match result {
+            Err(crate::errors::Error::InvalidValue(i, value)) => i == index && !value.is_finite(),
+            _ => false,
+        };
+        assert!(is_invalid(
+            f64::NAN.decode_cf(units, calendar).map(|_| ()),
+            0
+        ));
+        assert!(is_invalid(
+            f32::INFINITY.decode_cf(units, calendar).map(|_| ()),
+            0
+        ));
+        let values = vec![0.0, 1.0, f64::NEG_INFINITY];
+        assert!(is_invalid(values.decode_cf(units, calendar).map(|_| ()), 2));
+        let values = vec![None, Some(f64::NAN)];
+        assert!(is_invalid(values.decode_cf(units, calendar).map(|_| ()), 1));
+        assert!(vec![None, Some(1.0f32)].decode_cf(units, calendar).is_ok());
+        let bounds = vec![[0.0, 1.0], [1.0, f64::NAN]];
+        assert!(is_invalid(
+            bounds.decode_bounds_cf(units, calendar).map(|_| ()),
+            1
+        ));
+        let bytes: Vec<u8> = [1.0f32, f32::NAN]
+            .iter()
+            .flat_map(|v| v.to_ne_bytes())
+            .collect();
+        let result = decode_cf_bytes(&bytes, DType::Float32, ByteOrder::Native, units, calendar);
+        assert!(is_invalid(result.map(|_| ()), 1));
+        let chunks: Vec<&[f64]> = vec![&[1.0, 2.0], &[3.0, f64::INFINITY]];
+        let result = decode_cf_chunked(chunks, units, calendar, |_| ());
+        assert!(is_invalid(result.map(|_| ()), 3));
+    }
+
+    #[test]
+    fn test_decode_fractional_reference_second() {
+        let units = "secs since 2020-06-01 12:03:45.250";
+        let cases = [
+            (0.0, (12, 3, 45), 250_000_000),
+            (0.5, (12, 3, 45), 750_000_000),
+            (0.75, (12, 3, 46), 0),
+            (1.8, (12, 3, 47), 50_000_000),
+            (-0.25, (12, 3, 45), 0),
+            (-0.3, (12, 3, 44), 950_000_000),
+        ];
+        for (value, hms, nanoseconds) in cases {
+            let datetime = value
+                .decode_cf(units, Calendar::ProlepticGregorian)
+                .unwrap();
+            assert_eq!(datetime.ymd().unwrap(), (2020, 6, 1), "{value}");
+            assert_eq!(datetime.hms().unwrap(), hms, "{value}");
+            assert_eq!(datetime.nanoseconds(), nanoseconds, "{value}");
+        }
+        let values: Vec<i64> = vec![-251, 0, 750, 1_000];
+        let datetimes = values
+            .decode_cf(
+                "milliseconds since 2020-06-01 12:03:45.250",
+                Calendar::ProlepticGregorian,
+            )
+            .unwrap();
+        let decoded: Vec<_> = datetimes
+            .iter()
+            .map(|datetime| (datetime.hms().unwrap(), datetime.nanoseconds()))
+            .collect();
+        assert_eq!(
+            decoded,
+            vec![
+                ((12, 3, 44), 999_000_000),
+                ((12, 3, 45), 250_000_000),
+                ((12, 3, 46), 0),
+                ((12, 3, 46), 250_000_000),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_decode_integers_exactly() {
+        // The products do not fit in the 53 bits of the mantissa of a f64
+        let calendar = Calendar::Standard;
+        let cases: [(&str, i64, i128); 4] = [
+            ("nanoseconds since 1970-01-01", 9_007_199_254_740_993, 1),
+            ("microseconds since 1970-01-01", i64::MAX / 2 + 1, 1_000),
+            ("days since 1970-01-01", 106_751_991_167, 86_400_000_000_000),
+            ("3 seconds since 1970-01-01", i64::MAX / 4, 3_000_000_000),
+        ];
+        for (units, value, nanoseconds_per_value) in cases {
+            let datetime = value.decode_cf(units, calendar).unwrap();
+            assert_eq!(
+                datetime.timestamp_nanos(),
+                value as i128 * nanoseconds_per_value,
+                "{units}"
+            );
+        }
+        for value in [i32::MAX, i32::MIN, 16_777_217] {
+            let units = "milliseconds since 1970-01-01";
+            let datetime = value.decode_cf(units, calendar).unwrap();
+            assert_eq!(datetime.timestamp_nanos(), value as i128 * 1_000_000);
+            let datetimes = [value, -value.saturating_add(1)]
+                .decode_cf("seconds since 1970-01-01", calendar)
+                .unwrap();
+            assert_eq!(datetimes[0].timestamp(), value as i64);
+            assert_eq!(datetimes[1].timestamp(), -(value.saturating_add(1) as i64));
+        }
+    }
+    #[test]
+    fn test_vec_decode_matches_scalar_decode() {
+        // The vectors add nanoseconds to the reference date, the scalars add a CFDuration
+        let units = [
+            "hours since 2000-01-01",
+            "3 days since 1582-10-15 12:00:00",
+            "secs since -1000-06-01 12:03:45.250",
+            "common_years since 1970-01-01",
+            "microseconds since 2262-04-11 23:47:16",
+        ];
+        let integers: Vec<i64> = vec![0, 1, -1, 17, -1_000_003, 123_456_789];
+        let floats: Vec<f64> = vec![0.0, 0.5, -0.3, 1.8, 1e6 + 0.25, -12_345.678];
+        for calendar in [Calendar::Standard, Calendar::Day360, Calendar::Julian] {
+            for units in units {
+                let datetimes = integers.decode_cf(units, calendar).unwrap();
+                for (value, datetime) in integers.iter().zip(&datetimes) {
+                    let expected = value.decode_cf(units, calendar).unwrap();
+                    assert!(datetime == &expected, "{units} {value}");
+                }
+                let datetimes = floats.decode_cf(units, calendar).unwrap();
+                for (value, datetime) in floats.iter().zip(&datetimes) {
+                    let expected = value.decode_cf(units, calendar).unwrap();
+                    assert!(datetime == &expected, "{units} {value}");
+                }
+            }
+        }
+        // Overflow of the sum with the reference date
+        let result = vec![0, i64::MAX].decode_cf("seconds since 2000-01-01", Calendar::Standard);
+        assert!(matches!(
+            result,
+            Err(crate::errors::Error::OutOfBoundsCalendar(_, _))
+        ));
+    }
+    #[test]
+    fn test_decode_cf_bytes() {
+        let units = "hours since 2000-01-01";
+        let calendar = Calendar::NoLeap;
+        let values: Vec<f64> = vec![0.0, 1.5, -24.0, 8760.0];
+        let expected = values.decode_cf(units, calendar).unwrap();
+        let big_endian: Vec<u8> = values
+            .iter()
+            .flat_map(|value| value.to_be_bytes())
+            .collect();
+        let result =
+            decode_cf_bytes(&big_endian, DType::Float64, ByteOrder::Big, units, calendar).unwrap();
+        assert!(result == expected);
+        let values: Vec<i32> = vec![0, 1, -24, 8760];
+        let little_endian: Vec<u8> = values
+            .iter()
+            .flat_map(|value| value.to_le_bytes())
+            .collect();
+        let result = decode_cf_bytes(
+            &little_endian,
+            DType::Int32,
+            ByteOrder::Little,
+            units,
+            calendar,
+        )
+        .unwrap();
+        assert!(result == values.decode_cf(units, calendar).unwrap());
+        // Reading little endian values as big endian swaps the bytes
+        let result = decode_cf_bytes(
+            &little_endian[4..8],
+            DType::Int32,
+            ByteOrder::Big,
+            units,
+            calendar,
+        )
+        .unwrap();
+        assert!(
+            result[0]
+                == i32::from_be_bytes([1, 0, 0, 0])
+                    .decode_cf(units, calendar)
+                    .unwrap()
+        );
+        assert!(
+            decode_cf_bytes(&[], DType::Int64, ByteOrder::Native, units, calendar)
+                .unwrap()
+                .is_empty()
+        );
+        let error = decode_cf_bytes(&[0; 6], DType::Float32, ByteOrder::Native, units, calendar);
+        assert!(matches!(error, Err(crate::errors::Error::InvalidBuffer(_))));
+        // The unsigned integers above i64::MAX do not fit in a duration
+        let values: Vec<u64> = vec![24, u64::MAX];
+        let little_endian: Vec<u8> = values
+            .iter()
+            .flat_map(|value| value.to_le_bytes())
+            .collect();
+        let result = decode_cf_bytes(
+            &little_endian[..8],
+            DType::UInt64,
+            ByteOrder::Little,
+            units,
+            calendar,
+        )
+        .unwrap();
+        assert_eq!(result[0].ymd().unwrap(), (2000, 1, 2));
+        assert!(decode_cf_bytes(
+            &little_endian,
+            DType::UInt64,
+            ByteOrder::Little,
+            units,
+            calendar
+        )
+        .is_err_and(|e| e.is_out_of_bounds()));
+        let result = decode_cf_bytes(
+            &u32::MAX.to_be_bytes(),
+            DType::UInt32,
+            ByteOrder::Big,
+            "seconds since 1970-01-01",
+            calendar,
+        )
+        .unwrap();
+        assert_eq!(result[0].timestamp(), u32::MAX as i64);
+    }
+
+    #[test]
+    fn test_dtype_from_str() {
+        for dtype in DType::ALL {
+            for name in dtype.names() {
+                assert_eq!(name.parse::<DType>().unwrap(), dtype);
+            }
+            assert_eq!(dtype.to_string().parse::<DType>().unwrap(), dtype);
+        }
+        assert_eq!(" Float64 ".parse::<DType>().unwrap(), DType::Float64);
+        assert_eq!("u8".parse::<DType>().unwrap(), DType::UInt64);
+        assert!("u16"
+            .parse::<DType>()
+            .is_err_and(|e| matches!(e, crate::errors::Error::InvalidDType(_))));
+    }
+
+    #[test]
+    fn test_decode_cf_chunked() {
+        let units = "days since 2000-01-01";
+        let calendar = Calendar::AllLeap;
+        let values: Vec<f64> = (0..10).map(|value| value as f64 * 1.5).collect();
+        let expected = values.decode_cf(units, calendar).unwrap();
+        let mut decoded = Vec::new();
+        let mut sizes = Vec::new();
+        let count = decode_cf_chunked(values.chunks(4), units, calendar, |datetimes| {
+            sizes.push(datetimes.len());
+            decoded.extend(datetimes.iter().map(|dt| dt.timestamp_nanos()));
+        })
+        .unwrap();
+        assert_eq!(count, 10);
+        assert_eq!(sizes, vec![4, 4, 2]);
+        assert_eq!(decoded, CFDatetime::to_timestamps_nanos(&expected));
+
+        let chunks: Vec<&[i64]> = vec![&[1, 2], &[i64::MAX]];
+        let mut calls = 0;
+        let result = decode_cf_chunked(chunks, units, calendar, |_| calls += 1);
+        assert!(result.is_err());
+        assert_eq!(calls, 1);
+    }
 }
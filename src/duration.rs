@@ -1,12 +1,33 @@
 //! Module related to duration
 //! These CFDuration can be added to a CFDateTime by using the `+` or `-` operator
 //! Internally it uses the timestamp in seconds representation
+//!
+//! Durations made of months or years are kept symbolically in the `months` component and are
+//! applied with the calendar arithmetic when added to a CFDateTime, i.e. adding one month to
+//! `2000-01-15` gives `2000-02-15`. Use [CFDuration::to_approximate_seconds] to get the
+//! definitions of the udunits package instead.
 
-use crate::{calendars::Calendar, constants, utils::normalize_nanoseconds};
+use crate::float;
+use crate::{calendars::Calendar, utils::normalize_nanoseconds};
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// What a constructor of [CFDuration] does when the duration does not fit in it
+#[derive(Clone, Copy)]
+enum OverflowPolicy {
+    /// Returns an error of type `crate::errors::Error::InvalidDuration`
+    Error,
+    /// Clamps the months or the seconds to the bounds of an `i64`
+    Saturate,
+}
 
 /// A CF duration
 #[derive(Debug)]
 pub struct CFDuration {
+    /// Number of calendar months, applied before the seconds when added to a CFDatetime
+    pub months: i64,
     pub seconds: i64,
     pub nanoseconds: u32,
     pub calendar: Calendar,
@@ -16,11 +37,95 @@ impl CFDuration {
     pub fn new(seconds: i64, nanoseconds: i64, calendar: Calendar) -> Self {
         let (remaining_seconds, remaining_nanoseconds) = normalize_nanoseconds(nanoseconds);
         Self {
-            seconds: seconds + remaining_seconds,
+            months: 0,
+            seconds: seconds.saturating_add(remaining_seconds),
             nanoseconds: (remaining_nanoseconds),
             calendar,
         }
     }
+    /// Makes a new `Duration` with the given number of calendar months, seconds and nanoseconds
+    pub fn with_months(months: i64, seconds: i64, nanoseconds: i64, calendar: Calendar) -> Self {
+        Self {
+            months,
+            ..Self::new(seconds, nanoseconds, calendar)
+        }
+    }
+    /// Makes a new `Duration` with the given number of calendar months and nanoseconds, returning
+    /// `None` if the number of seconds does not fit in an `i64`
+    pub(crate) fn checked_with_months(
+        months: i64,
+        nanoseconds: i128,
+        calendar: Calendar,
+    ) -> Option<Self> {
+        let seconds = i64::try_from(nanoseconds.div_euclid(1_000_000_000)).ok()?;
+        let nanoseconds = nanoseconds.rem_euclid(1_000_000_000) as i64;
+        Some(Self::with_months(months, seconds, nanoseconds, calendar))
+    }
+    /// Returns the seconds and nanoseconds of the duration in nanoseconds, without the months
+    pub(crate) fn total_nanoseconds(&self) -> i128 {
+        self.seconds as i128 * 1_000_000_000 + self.nanoseconds as i128
+    }
+    /// Makes a new `Duration` with the given number of calendar months and nanoseconds, saturating
+    /// the seconds at the bounds of an `i64`
+    fn saturating_with_months(months: i64, nanoseconds: i128, calendar: Calendar) -> Self {
+        let nanoseconds = nanoseconds.clamp(
+            i64::MIN as i128 * 1_000_000_000,
+            i64::MAX as i128 * 1_000_000_000 + 999_999_999,
+        );
+        Self::checked_with_months(months, nanoseconds, calendar)
+            .expect("A clamped duration always fits")
+    }
+    /// Negates the duration, saturating at the bounds of the months and seconds
+    fn saturating_neg(&self) -> Self {
+        Self::saturating_with_months(
+            self.months.saturating_neg(),
+            -self.total_nanoseconds(),
+            self.calendar,
+        )
+    }
+    /// Makes a new `Duration` of `value` times `unit_nanoseconds` nanoseconds. The product is
+    /// computed in an `i128` so only the number of seconds can overflow.
+    fn from_scaled(
+        value: i64,
+        unit_nanoseconds: i128,
+        unit: &str,
+        calendar: Calendar,
+        policy: OverflowPolicy,
+    ) -> crate::errors::Result<Self> {
+        let nanoseconds = value as i128 * unit_nanoseconds;
+        match (Self::checked_with_months(0, nanoseconds, calendar), policy) {
+            (Some(duration), _) => Ok(duration),
+            (None, OverflowPolicy::Error) => Err(crate::errors::Error::InvalidDuration(format!(
+                "{value} {unit} do not fit in a duration of {} seconds at most",
+                i64::MAX
+            ))),
+            // The negative bound is the negation of the positive one, so that the saturated
+            // durations can be negated
+            (None, OverflowPolicy::Saturate) if nanoseconds < 0 => {
+                Ok(-Self::new(i64::MAX, 999_999_999, calendar))
+            }
+            (None, OverflowPolicy::Saturate) => Ok(Self::new(i64::MAX, 999_999_999, calendar)),
+        }
+    }
+    /// Makes a new `Duration` of `years` years, see [CFDuration::from_years]
+    fn from_years_with_policy(
+        years: i64,
+        calendar: Calendar,
+        policy: OverflowPolicy,
+    ) -> crate::errors::Result<Self> {
+        let months = match (years.checked_mul(12), policy) {
+            (Some(months), _) => months,
+            (None, OverflowPolicy::Error) => {
+                return Err(crate::errors::Error::InvalidDuration(format!(
+                    "{years} years do not fit in a duration of {} months at most",
+                    i64::MAX
+                )))
+            }
+            (None, OverflowPolicy::Saturate) if years < 0 => -i64::MAX,
+            (None, OverflowPolicy::Saturate) => i64::MAX,
+        };
+        Ok(Self::from_months(months, calendar))
+    }
 }
 
 impl CFDuration {
@@ -29,39 +134,189 @@ impl CFDuration {
         self.calendar
     }
     /// Makes a new `Duration` with given number of years.
-    /// Depends on the Calendar definitions found in  the CF conventions
-    /// See also [Calendar]
+    /// The years are stored as 12 calendar months, see [CFDuration::from_months]
+    ///
+    /// The months saturate at `i64::MAX` or `-i64::MAX`, use [CFDuration::try_from_years] to get
+    /// an error instead.
     pub fn from_years(years: i64, calendar: Calendar) -> CFDuration {
-        let secs_per_year = match calendar {
-            Calendar::ProlepticGregorian | Calendar::Standard => 3.15569259747e7,
-            Calendar::NoLeap => 365.0 * constants::SECS_PER_DAY as f64,
-            Calendar::AllLeap => 366.0 * constants::SECS_PER_DAY as f64,
-            Calendar::Julian => 365.25 * constants::SECS_PER_DAY as f64,
-            Calendar::Day360 => 360.0 * constants::SECS_PER_DAY as f64,
-        };
-        let secs = secs_per_year as i64 * years;
-        Self::new(secs, 0, calendar)
+        Self::from_years_with_policy(years, calendar, OverflowPolicy::Saturate)
+            .expect("A saturated duration always fits")
     }
-    /// Makes a new `Duration` with given number of months.
+    /// Makes a new `Duration` with given number of years, see [CFDuration::from_years].
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the duration or an error of type
+    /// `crate::errors::Error::InvalidDuration` if the number of months does not fit in an `i64`
+    ///
+    /// # Example
+    /// ```
+    /// use cftime_rs::calendars::Calendar;
+    /// use cftime_rs::duration::CFDuration;
+    /// assert_eq!(CFDuration::try_from_years(2, Calendar::NoLeap).unwrap().months, 24);
+    /// assert!(CFDuration::try_from_years(i64::MAX, Calendar::NoLeap).is_err());
+    /// assert!(CFDuration::try_from_days(i64::MAX, Calendar::NoLeap).is_err());
+    /// ```
+    pub fn try_from_years(years: i64, calendar: Calendar) -> crate::errors::Result<CFDuration> {
+        Self::from_years_with_policy(years, calendar, OverflowPolicy::Error)
+    }
+    /// Makes a new `Duration` with given number of calendar months.
+    /// The months are applied with the calendar arithmetic when added to a CFDatetime,
+    /// the day being clamped to the last day of the resulting month.
     pub fn from_months(months: i64, calendar: Calendar) -> CFDuration {
-        let seconds_for_one_year = CFDuration::from_years(1, calendar).seconds;
-        Self::new(seconds_for_one_year / 12 * months, 0, calendar)
+        Self::with_months(months, 0, 0, calendar)
+    }
+    /// Returns a new `Duration` where the months are converted to seconds.
+    ///
+    /// Depends on the Calendar definitions found in the CF conventions, i.e. a year in
+    /// the standard calendar is 365.242198781 days and a month is 1/12 of a year.
+    /// The year is the exact number of nanoseconds with which the units are encoded, so the years
+    /// and months of the units round-trip. See also [Calendar]
+    pub fn to_approximate_seconds(&self) -> CFDuration {
+        // A year is a whole number of nanoseconds divisible by 12 in every calendar
+        let ns_per_month = self.calendar.nanoseconds_per_year() / 12;
+        // Saturates for the durations of billions of years
+        Self::saturating_with_months(
+            0,
+            self.months as i128 * ns_per_month + self.total_nanoseconds(),
+            self.calendar,
+        )
+    }
+    /// Multiplies the duration by an integer, returning `None` on overflow.
+    ///
+    /// The product is computed in nanoseconds with an `i128`, so fine units such as
+    /// nanoseconds can be multiplied by any `i64` as long as the number of seconds fits in an `i64`.
+    pub fn checked_mul(&self, rhs: i64) -> Option<CFDuration> {
+        let months = self.months.checked_mul(rhs)?;
+        let nanoseconds = self.total_nanoseconds().checked_mul(rhs as i128)?;
+        CFDuration::checked_with_months(months, nanoseconds, self.calendar)
+    }
+    /// Multiplies the duration by a float, returning `None` if the result is not finite or
+    /// the number of seconds does not fit in an `i64`.
+    ///
+    /// The months are converted to seconds first, see [CFDuration::to_approximate_seconds].
+    pub fn checked_mul_f64(&self, rhs: f64) -> Option<CFDuration> {
+        // A fraction of month is not defined so the months are converted to seconds
+        let duration = self.to_approximate_seconds();
+        // Classic (a+b)(d+c)
+        // f32 to i64 does not give the same result all the time
+        // i.e. 8276688000.0 gives 8276687872
+        let seconds = duration.seconds as f64 * rhs;
+        let nanoseconds = duration.nanoseconds as f64 * rhs;
+        // i64::MAX as f64 rounds up to 2^63 which is out of bounds
+        let bound = i64::MAX as f64;
+        if !seconds.is_finite() || !nanoseconds.is_finite() || float::abs(seconds) >= bound {
+            return None;
+        }
+        // The fraction of the seconds product is carried to the nanoseconds, e.g. 0.5 seconds
+        let nanoseconds = float::round(nanoseconds + float::fract(seconds) * 1e9);
+        let (remaining_seconds, remaining_nanoseconds) = normalize_nanoseconds(nanoseconds as i64);
+        let seconds = (float::trunc(seconds) as i64).checked_add(remaining_seconds)?;
+        Some(CFDuration::new(
+            seconds,
+            remaining_nanoseconds as i64,
+            duration.calendar,
+        ))
     }
-    /// Makes a new `Duration` with given number of weeks
+    /// Makes a new `Duration` with given number of weeks.
+    ///
+    /// The seconds saturate at `i64::MAX` seconds and 999_999_999 nanoseconds or at its negation,
+    /// use [CFDuration::try_from_weeks] to get an error instead.
     pub fn from_weeks(weeks: i64, calendar: Calendar) -> CFDuration {
-        Self::new(weeks * 7 * 24 * 60 * 60, 0, calendar)
+        Self::from_scaled(
+            weeks,
+            7 * 86_400 * 1_000_000_000,
+            "weeks",
+            calendar,
+            OverflowPolicy::Saturate,
+        )
+        .expect("A saturated duration always fits")
     }
-    /// Makes a new `Duration` with given number of days
+    /// Makes a new `Duration` with given number of weeks, or an error of type
+    /// `crate::errors::Error::InvalidDuration` if the number of seconds does not fit in an `i64`
+    pub fn try_from_weeks(weeks: i64, calendar: Calendar) -> crate::errors::Result<CFDuration> {
+        Self::from_scaled(
+            weeks,
+            7 * 86_400 * 1_000_000_000,
+            "weeks",
+            calendar,
+            OverflowPolicy::Error,
+        )
+    }
+    /// Makes a new `Duration` with given number of days.
+    ///
+    /// The seconds saturate at `i64::MAX` seconds and 999_999_999 nanoseconds or at its negation,
+    /// use [CFDuration::try_from_days] to get an error instead.
     pub fn from_days(days: i64, calendar: Calendar) -> CFDuration {
-        Self::new(days * 24 * 60 * 60, 0, calendar)
+        Self::from_scaled(
+            days,
+            86_400 * 1_000_000_000,
+            "days",
+            calendar,
+            OverflowPolicy::Saturate,
+        )
+        .expect("A saturated duration always fits")
     }
-    /// Makes a new `Duration` with given number of hours
+    /// Makes a new `Duration` with given number of days, or an error of type
+    /// `crate::errors::Error::InvalidDuration` if the number of seconds does not fit in an `i64`
+    pub fn try_from_days(days: i64, calendar: Calendar) -> crate::errors::Result<CFDuration> {
+        Self::from_scaled(
+            days,
+            86_400 * 1_000_000_000,
+            "days",
+            calendar,
+            OverflowPolicy::Error,
+        )
+    }
+    /// Makes a new `Duration` with given number of hours.
+    ///
+    /// The seconds saturate at `i64::MAX` seconds and 999_999_999 nanoseconds or at its negation,
+    /// use [CFDuration::try_from_hours] to get an error instead.
     pub fn from_hours(hours: i64, calendar: Calendar) -> CFDuration {
-        Self::new(hours * 60 * 60, 0, calendar)
+        Self::from_scaled(
+            hours,
+            3_600 * 1_000_000_000,
+            "hours",
+            calendar,
+            OverflowPolicy::Saturate,
+        )
+        .expect("A saturated duration always fits")
+    }
+    /// Makes a new `Duration` with given number of hours, or an error of type
+    /// `crate::errors::Error::InvalidDuration` if the number of seconds does not fit in an `i64`
+    pub fn try_from_hours(hours: i64, calendar: Calendar) -> crate::errors::Result<CFDuration> {
+        Self::from_scaled(
+            hours,
+            3_600 * 1_000_000_000,
+            "hours",
+            calendar,
+            OverflowPolicy::Error,
+        )
     }
-    /// Makes a new `Duration` with given number of minutes
+    /// Makes a new `Duration` with given number of minutes.
+    ///
+    /// The seconds saturate at `i64::MAX` seconds and 999_999_999 nanoseconds or at its negation,
+    /// use [CFDuration::try_from_minutes] to get an error instead.
     pub fn from_minutes(minutes: i64, calendar: Calendar) -> CFDuration {
-        Self::new(minutes * 60, 0, calendar)
+        Self::from_scaled(
+            minutes,
+            60 * 1_000_000_000,
+            "minutes",
+            calendar,
+            OverflowPolicy::Saturate,
+        )
+        .expect("A saturated duration always fits")
+    }
+    /// Makes a new `Duration` with given number of minutes, or an error of type
+    /// `crate::errors::Error::InvalidDuration` if the number of seconds does not fit in an `i64`
+    pub fn try_from_minutes(minutes: i64, calendar: Calendar) -> crate::errors::Result<CFDuration> {
+        Self::from_scaled(
+            minutes,
+            60 * 1_000_000_000,
+            "minutes",
+            calendar,
+            OverflowPolicy::Error,
+        )
     }
     /// Makes a new `Duration` with given number of seconds
     pub fn from_seconds(seconds: i64, calendar: Calendar) -> CFDuration {
@@ -69,11 +324,25 @@ impl CFDuration {
     }
     /// Makes a new `Duration` with given number of milliseconds
     pub fn from_milliseconds(milliseconds: i64, calendar: Calendar) -> CFDuration {
-        Self::new(0, milliseconds * 1_000_000, calendar)
+        Self::from_scaled(
+            milliseconds,
+            1_000_000,
+            "milliseconds",
+            calendar,
+            OverflowPolicy::Saturate,
+        )
+        .expect("A saturated duration always fits")
     }
     /// Makes a new `Duration` with given number of microseconds
     pub fn from_microseconds(microseconds: i64, calendar: Calendar) -> CFDuration {
-        Self::new(0, 1_000 * microseconds, calendar)
+        Self::from_scaled(
+            microseconds,
+            1_000,
+            "microseconds",
+            calendar,
+            OverflowPolicy::Saturate,
+        )
+        .expect("A saturated duration always fits")
     }
     /// Makes a new `Duration` with given number of nanoseconds
     pub fn from_nanoseconds(nanoseconds: i64, calendar: Calendar) -> CFDuration {
@@ -113,7 +382,8 @@ impl CFDuration {
     }
     /// Return the total number of seconds in the duration.
     pub fn num_seconds(&self) -> f64 {
-        self.seconds as f64 + self.nanoseconds as f64 / 1e9
+        let duration = self.to_approximate_seconds();
+        duration.seconds as f64 + duration.nanoseconds as f64 / 1e9
     }
     /// Return the total number of milliseconds in the duration.
     pub fn num_milliseconds(&self) -> f64 {
@@ -125,37 +395,270 @@ impl CFDuration {
     }
     /// Return the total number of nanoseconds in the duration.
     pub fn num_nanoseconds(&self) -> f64 {
-        (self.seconds * 1_000_000_000 + self.nanoseconds as i64) as f64
+        self.to_approximate_seconds().total_nanoseconds() as f64
+    }
+}
+
+impl CFDuration {
+    /// Returns the number of whole seconds truncated toward zero, without the months,
+    /// e.g. -1 for -1.5 seconds
+    ///
+    /// The `seconds` field is rounded toward negative infinity and the `nanoseconds` field is
+    /// always positive, e.g. -1.5 seconds is stored as -2 seconds and 500_000_000 nanoseconds.
+    /// This accessor and [CFDuration::subsec_nanos] truncate toward zero instead, as
+    /// `std::time::Duration` would.
+    pub fn whole_seconds(&self) -> i64 {
+        if self.seconds < 0 && self.nanoseconds > 0 {
+            self.seconds + 1
+        } else {
+            self.seconds
+        }
+    }
+    /// Returns the fraction of second in nanoseconds with the sign of the duration, without the
+    /// months, e.g. -500_000_000 for -1.5 seconds
+    ///
+    /// `whole_seconds() * 1_000_000_000 + subsec_nanos()` is the number of nanoseconds of the duration.
+    pub fn subsec_nanos(&self) -> i32 {
+        if self.seconds < 0 && self.nanoseconds > 0 {
+            self.nanoseconds as i32 - 1_000_000_000
+        } else {
+            self.nanoseconds as i32
+        }
+    }
+    /// Returns -1 if the duration is negative, 0 if it is zero and 1 if it is positive
+    ///
+    /// The sign of a duration made of both months and seconds, e.g. one month minus one day, is
+    /// the sign of its approximate number of seconds, see [CFDuration::to_approximate_seconds].
+    pub fn signum(&self) -> i64 {
+        let duration = self.to_approximate_seconds();
+        if duration.seconds < 0 {
+            -1
+        } else if duration.seconds == 0 && duration.nanoseconds == 0 {
+            0
+        } else {
+            1
+        }
+    }
+    /// Returns `true` if the duration is strictly negative
+    pub fn is_negative(&self) -> bool {
+        self.signum() < 0
+    }
+    /// Returns `true` if the duration is zero
+    pub fn is_zero(&self) -> bool {
+        self.signum() == 0
+    }
+    /// Returns the absolute value of the duration, keeping the months if any
+    ///
+    /// The absolute value saturates like the negation, e.g. the absolute value of `i64::MIN`
+    /// seconds is `i64::MAX` seconds and 999_999_999 nanoseconds.
+    pub fn abs(&self) -> CFDuration {
+        if self.is_negative() {
+            self.saturating_neg()
+        } else {
+            CFDuration::with_months(
+                self.months,
+                self.seconds,
+                self.nanoseconds as i64,
+                self.calendar,
+            )
+        }
     }
 }
 
-/// Display a CFDuration with te ISO 8601 format of duration.
+/// Display a CFDuration with the ISO 8601 format of duration, e.g. `P1Y2M3DT4H5M6.5S`.
+///
+/// The years and months are the calendar months of the duration and the days, hours, minutes
+/// and seconds are its seconds, so the output is parsed back by [CFDuration::from_iso8601].
+/// A negative duration is prefixed with a minus sign, e.g. `-P0Y0M1DT0H0M0S`. The days, hours,
+/// minutes and seconds of a duration mixing months and seconds of opposite signs are signed
+/// themselves, e.g. `P0Y1M-1DT0H0M0S` for one month minus one day.
 ///
 /// # Example
 /// ```
-/// CFDuration::from_days(1).__repr__()
-/// assert_eq!(CFDuration::from_days(1).__repr__(),  "P0Y0M1DT0H0M0S");
+/// use cftime_rs::calendars::Calendar;
+/// use cftime_rs::duration::CFDuration;
+/// assert_eq!(CFDuration::from_days(1, Calendar::Standard).to_string(), "P0Y0M1DT0H0M0S");
+/// assert_eq!((-CFDuration::from_days(1, Calendar::Standard)).to_string(), "-P0Y0M1DT0H0M0S");
 /// ```
-///
-impl std::fmt::Display for CFDuration {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+impl core::fmt::Display for CFDuration {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        // The magnitude is computed in an i128 as the absolute value of `i64::MIN` seconds or
+        // months does not fit in an i64
+        let sign: i128 = if self.is_negative() {
+            write!(f, "-")?;
+            -1
+        } else {
+            1
+        };
+        let months = sign * self.months as i128;
+        let total_nanoseconds = sign * self.total_nanoseconds();
+        let seconds = total_nanoseconds / 1_000_000_000;
+        let nanoseconds = total_nanoseconds % 1_000_000_000;
+        write!(
+            f,
+            "P{}Y{}M{}DT{}H{}M",
+            months / 12,
+            months % 12,
+            seconds / 86_400,
+            seconds / 3_600 % 24,
+            seconds / 60 % 60,
+        )?;
+        if nanoseconds == 0 {
+            return write!(f, "{}S", seconds % 60);
+        }
+        // The sign of a fraction of second is lost if there are no whole seconds
+        let sign = if seconds % 60 == 0 && nanoseconds < 0 {
+            "-"
+        } else {
+            ""
+        };
+        let fraction = format!("{:09}", nanoseconds.unsigned_abs());
         write!(
             f,
-            "P{}Y{}M{}DT{}H{}M{}S",
-            self.num_years() as i64,
-            self.num_months() as i64 % 12,
-            self.num_days() as i64 % 31,
-            self.num_hours() as i64 % 24,
-            self.num_minutes() as i64 % 60,
-            self.num_seconds() as i64 % 60
+            "{sign}{}.{}S",
+            seconds % 60,
+            fraction.trim_end_matches('0')
         )
     }
 }
 
+impl CFDuration {
+    /// Parses an ISO 8601 duration such as `P1Y2M3DT4H5M6.5S`, as written by the `Display`
+    /// implementation of [CFDuration].
+    ///
+    /// Every component is optional, e.g. `PT6H` or `P2W`, and only the seconds can have a fraction.
+    /// The years and months are kept as calendar months. The duration can be prefixed with a sign,
+    /// e.g. `-P1D`, and each component can be signed, e.g. `P1M-1D`.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the duration or an error of type `crate::errors::Error::InvalidDuration`
+    /// if the string is not a valid duration or does not fit in a CFDuration
+    pub fn from_iso8601(duration: &str, calendar: Calendar) -> crate::errors::Result<Self> {
+        let invalid = || {
+            crate::errors::Error::InvalidDuration(format!(
+                "'{duration}' is not an ISO 8601 duration such as P1Y2M3DT4H5M6S"
+            ))
+        };
+        let trimmed = duration.trim();
+        let (sign, rest) = match trimmed.strip_prefix('-') {
+            Some(rest) => (-1, rest),
+            None => (1, trimmed.strip_prefix('+').unwrap_or(trimmed)),
+        };
+        let rest = rest.strip_prefix('P').ok_or_else(invalid)?;
+        let (date, time) = match rest.split_once('T') {
+            Some((_, "")) => return Err(invalid()),
+            Some((date, time)) => (date, time),
+            None if rest.is_empty() => return Err(invalid()),
+            None => (rest, ""),
+        };
+
+        // The months are summed in an i128 so that the magnitude of `i64::MIN` months written by
+        // the `Display` implementation is parsed back when the duration is negative
+        let mut months: i128 = 0;
+        let mut nanoseconds: i128 = 0;
+        for (value, designator) in
+            iso8601_components(date, &['Y', 'M', 'W', 'D']).ok_or_else(invalid)?
+        {
+            let value = value.whole().ok_or_else(invalid)?;
+            match designator {
+                'Y' => months += value as i128 * 12,
+                'M' => months += value as i128,
+                'W' => nanoseconds += value as i128 * 7 * 86_400 * 1_000_000_000,
+                _ => nanoseconds += value as i128 * 86_400 * 1_000_000_000,
+            }
+        }
+        for (value, designator) in iso8601_components(time, &['H', 'M', 'S']).ok_or_else(invalid)? {
+            let unit: i128 = match designator {
+                'H' => 3_600,
+                'M' => 60,
+                _ => 1,
+            };
+            let value = match designator {
+                'S' => value.nanoseconds,
+                _ => value.whole().ok_or_else(invalid)? as i128 * 1_000_000_000,
+            };
+            nanoseconds += value * unit;
+        }
+
+        let months = i64::try_from(months * sign).map_err(|_| invalid())?;
+        let nanoseconds = nanoseconds * sign;
+        let seconds =
+            i64::try_from(nanoseconds.div_euclid(1_000_000_000)).map_err(|_| invalid())?;
+        Ok(Self::with_months(
+            months,
+            seconds,
+            nanoseconds.rem_euclid(1_000_000_000) as i64,
+            calendar,
+        ))
+    }
+}
+
+/// Number of an ISO 8601 duration component in nanoseconds, only the seconds can have a fraction
+struct Iso8601Value {
+    nanoseconds: i128,
+}
+
+impl Iso8601Value {
+    /// Returns the value if it has no fraction
+    fn whole(&self) -> Option<i64> {
+        if self.nanoseconds % 1_000_000_000 != 0 {
+            return None;
+        }
+        i64::try_from(self.nanoseconds / 1_000_000_000).ok()
+    }
+}
+
+/// Splits the components of an ISO 8601 duration such as `1Y-2M3D` into values and designators.
+/// The designators must be in the given order and appear at most once.
+fn iso8601_components(components: &str, designators: &[char]) -> Option<Vec<(Iso8601Value, char)>> {
+    let mut result = Vec::new();
+    let mut next_designator = 0;
+    let mut rest = components;
+    while !rest.is_empty() {
+        let end = rest.find(|c: char| c.is_ascii_alphabetic())?;
+        let (number, designator) = (&rest[..end], rest[end..].chars().next()?);
+        rest = &rest[end + 1..];
+        let position = designators[next_designator..]
+            .iter()
+            .position(|&d| d == designator)?;
+        next_designator += position + 1;
+
+        let (sign, number) = match number.strip_prefix('-') {
+            Some(number) => (-1, number),
+            None => (1, number.strip_prefix('+').unwrap_or(number)),
+        };
+        let (whole, fraction) = number.split_once(['.', ',']).unwrap_or((number, ""));
+        if whole.is_empty() || !whole.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        if !fraction.chars().all(|c| c.is_ascii_digit()) || fraction.len() > 9 {
+            return None;
+        }
+        let digits: String = fraction
+            .chars()
+            .chain("000000000".chars())
+            .take(9)
+            .collect();
+        let nanoseconds =
+            whole.parse::<i128>().ok()? * 1_000_000_000 + digits.parse::<i128>().ok()?;
+        if nanoseconds > i64::MAX as i128 * 1_000_000_000 {
+            return None;
+        }
+        result.push((
+            Iso8601Value {
+                nanoseconds: sign * nanoseconds,
+            },
+            designator,
+        ));
+    }
+    Some(result)
+}
+
 macro_rules! impl_add_for_cf_duration {
     ($self_dur:ty, $rhs_dur:ty) => {
-        impl std::ops::Add for $self_dur {
-            type Output = Result<CFDuration, crate::errors::Error>;
+        impl core::ops::Add for $self_dur {
+            type Output = crate::errors::Result<CFDuration>;
             fn add(self, rhs: $rhs_dur) -> Self::Output {
                 if self.calendar() != rhs.calendar() {
                     return Err(crate::errors::Error::DifferentCalendars(
@@ -163,11 +666,18 @@ macro_rules! impl_add_for_cf_duration {
                         rhs.calendar().to_string(),
                     ));
                 }
-                Ok(CFDuration::new(
-                    self.seconds + rhs.seconds,
-                    self.nanoseconds as i64 + rhs.nanoseconds as i64,
-                    self.calendar,
-                ))
+                let months = self.months.checked_add(rhs.months);
+                let nanoseconds = self.total_nanoseconds() + rhs.total_nanoseconds();
+                months
+                    .and_then(|months| {
+                        CFDuration::checked_with_months(months, nanoseconds, self.calendar)
+                    })
+                    .ok_or_else(|| {
+                        crate::errors::Error::InvalidDuration(format!(
+                            "Overflow when adding {} and {}",
+                            self, rhs
+                        ))
+                    })
             }
         }
     };
@@ -177,8 +687,8 @@ impl_add_for_cf_duration!(&CFDuration, &CFDuration);
 
 macro_rules! impl_sub_for_cf_duration {
     ($self_dur:ty, $rhs_dur:ty) => {
-        impl std::ops::Sub for $self_dur {
-            type Output = Result<CFDuration, crate::errors::Error>;
+        impl core::ops::Sub for $self_dur {
+            type Output = crate::errors::Result<CFDuration>;
             fn sub(self, rhs: $rhs_dur) -> Self::Output {
                 if self.calendar() != rhs.calendar() {
                     return Err(crate::errors::Error::DifferentCalendars(
@@ -186,11 +696,18 @@ macro_rules! impl_sub_for_cf_duration {
                         rhs.calendar().to_string(),
                     ));
                 }
-                Ok(CFDuration::new(
-                    self.seconds - rhs.seconds,
-                    self.nanoseconds as i64 - rhs.nanoseconds as i64,
-                    self.calendar,
-                ))
+                let months = self.months.checked_sub(rhs.months);
+                let nanoseconds = self.total_nanoseconds() - rhs.total_nanoseconds();
+                months
+                    .and_then(|months| {
+                        CFDuration::checked_with_months(months, nanoseconds, self.calendar)
+                    })
+                    .ok_or_else(|| {
+                        crate::errors::Error::InvalidDuration(format!(
+                            "Overflow when subtracting {} and {}",
+                            self, rhs
+                        ))
+                    })
             }
         }
     };
@@ -199,34 +716,40 @@ macro_rules! impl_sub_for_cf_duration {
 impl_sub_for_cf_duration!(CFDuration, CFDuration);
 impl_sub_for_cf_duration!(&CFDuration, &CFDuration);
 
-impl std::ops::Neg for CFDuration {
+impl core::ops::Neg for CFDuration {
     type Output = CFDuration;
+    /// Saturates at the bounds of the duration, e.g. the negation of `i64::MIN` seconds is
+    /// `i64::MAX` seconds and 999_999_999 nanoseconds
     fn neg(self) -> Self::Output {
-        Self::new(-self.seconds, -(self.nanoseconds as i64), self.calendar)
+        self.saturating_neg()
     }
 }
-impl std::ops::Neg for &CFDuration {
+impl core::ops::Neg for &CFDuration {
     type Output = CFDuration;
+    /// Saturates at the bounds of the duration, see the negation of [CFDuration]
     fn neg(self) -> Self::Output {
-        CFDuration::new(-self.seconds, -(self.nanoseconds as i64), self.calendar)
+        self.saturating_neg()
     }
 }
 
 macro_rules! impl_mul_for_cf_duration_int {
     ($which_dur:ty, $rhs_type:ty) => {
-        impl std::ops::Mul<$rhs_type> for $which_dur {
-            type Output = CFDuration;
+        impl core::ops::Mul<$rhs_type> for $which_dur {
+            type Output = crate::errors::Result<CFDuration>;
+            /// Fails on overflow
             fn mul(self, rhs: $rhs_type) -> Self::Output {
-                CFDuration::new(
-                    self.seconds * rhs as i64,
-                    self.nanoseconds as i64 * rhs as i64,
-                    self.calendar,
-                )
+                self.checked_mul(rhs as i64).ok_or_else(|| {
+                    crate::errors::Error::InvalidDuration(format!(
+                        "Overflow when multiplying {} by {}",
+                        self, rhs
+                    ))
+                })
             }
         }
     };
 }
 
+// The integers are multiplied exactly in nanoseconds, see [CFDuration::checked_mul]
 impl_mul_for_cf_duration_int!(CFDuration, i64);
 impl_mul_for_cf_duration_int!(CFDuration, i32);
 impl_mul_for_cf_duration_int!(&CFDuration, i64);
@@ -234,19 +757,16 @@ impl_mul_for_cf_duration_int!(&CFDuration, i32);
 
 macro_rules! impl_mul_for_cf_duration_float {
     ($which_dur:ty, $rhs_type:ty) => {
-        impl std::ops::Mul<$rhs_type> for $which_dur {
-            type Output = CFDuration;
+        impl core::ops::Mul<$rhs_type> for $which_dur {
+            type Output = crate::errors::Result<CFDuration>;
+            /// Fails on overflow or if the float is not finite
             fn mul(self, rhs: $rhs_type) -> Self::Output {
-                // Classic (a+b)(d+c)
-                // f32 to i64 does not give the same result all the time
-                // i.e. 8276688000.0 gives 8276687872
-                let _rhs: f64 = rhs.into();
-                let mut new_seconds = (self.seconds as f64 * _rhs) as i64;
-                let mut new_ns = (self.nanoseconds as f64 * _rhs) as i64;
-                let (remaining_seconds, remaining_nanoseconds) = normalize_nanoseconds(new_ns);
-                new_seconds += remaining_seconds;
-                new_ns += remaining_nanoseconds as i64;
-                CFDuration::new(new_seconds, new_ns, self.calendar)
+                self.checked_mul_f64(rhs.into()).ok_or_else(|| {
+                    crate::errors::Error::InvalidDuration(format!(
+                        "Overflow when multiplying {} by {}",
+                        self, rhs
+                    ))
+                })
             }
         }
     };
@@ -321,4 +841,262 @@ mod tests {
             assert!((duration_result - 1.0).abs() < epsilon);
         }
     }
+
+    #[test]
+    fn test_months_are_symbolic() {
+        let duration = CFDuration::from_years(1, Calendar::Standard);
+        assert_eq!(duration.months, 12);
+        assert_eq!(duration.seconds, 0);
+        assert_eq!(
+            parts(&duration.to_approximate_seconds()),
+            (0, 31556925, 974_700_000)
+        );
+        assert_eq!(
+            parts(&CFDuration::from_months(1, Calendar::Standard).to_approximate_seconds()),
+            (0, 2629743, 831_225_000)
+        );
+        let duration = (CFDuration::from_months(1, Calendar::NoLeap)
+            + CFDuration::from_days(1, Calendar::NoLeap))
+        .unwrap();
+        assert_eq!((duration.months, duration.seconds), (1, 86400));
+        let duration = -(duration * 2i64).unwrap();
+        assert_eq!((duration.months, duration.seconds), (-2, -172800));
+        // Fraction of months falls back to the approximation
+        let duration = (CFDuration::from_months(1, Calendar::Day360) * 0.5f64).unwrap();
+        assert_eq!((duration.months, duration.seconds), (0, 15 * 86400));
+    }
+
+    #[test]
+    fn test_checked_mul() {
+        let duration = CFDuration::from_milliseconds(1, Calendar::Standard);
+        let result = duration.checked_mul(i64::MAX).unwrap();
+        assert_eq!(result.seconds, i64::MAX / 1_000);
+        assert_eq!(result.nanoseconds, (i64::MAX % 1_000) as u32 * 1_000_000);
+        let result = CFDuration::from_nanoseconds(-1, Calendar::Standard)
+            .checked_mul(3)
+            .unwrap();
+        assert_eq!((result.seconds, result.nanoseconds), (-1, 999_999_997));
+        assert!(CFDuration::from_days(1, Calendar::Standard)
+            .checked_mul(i64::MAX)
+            .is_none());
+        let result = CFDuration::new(1, 500_000_000, Calendar::Standard)
+            .checked_mul_f64(3.0)
+            .unwrap();
+        assert_eq!((result.seconds, result.nanoseconds), (4, 500_000_000));
+        assert!(CFDuration::from_seconds(1, Calendar::Standard)
+            .checked_mul_f64(f64::INFINITY)
+            .is_none());
+        assert!(CFDuration::from_seconds(1, Calendar::Standard)
+            .checked_mul_f64(1e19)
+            .is_none());
+        // The operators fail instead of panicking
+        let duration = CFDuration::from_days(1, Calendar::Standard);
+        assert!(matches!(
+            &duration * i64::MAX,
+            Err(crate::errors::Error::InvalidDuration(_))
+        ));
+        assert!((&duration * f64::NAN).is_err());
+        assert!((duration * f32::INFINITY).is_err());
+    }
+
+    #[test]
+    fn test_constructors_overflow() {
+        let calendar = Calendar::Standard;
+        assert!(CFDuration::try_from_years(i64::MAX / 12, calendar).is_ok());
+        let error = CFDuration::try_from_years(i64::MAX / 12 + 1, calendar)
+            .err()
+            .unwrap();
+        assert!(matches!(error, crate::errors::Error::InvalidDuration(_)));
+        assert_eq!(CFDuration::from_years(i64::MAX, calendar).months, i64::MAX);
+        assert_eq!(CFDuration::from_years(i64::MIN, calendar).months, -i64::MAX);
+        let days = i64::MAX / 86_400;
+        let duration = CFDuration::try_from_days(days, calendar).unwrap();
+        assert_eq!(duration.seconds, days * 86_400);
+        assert!(CFDuration::try_from_days(days + 1, calendar).is_err());
+        assert!(CFDuration::try_from_days(-days - 1, calendar).is_err());
+        assert!(CFDuration::try_from_weeks(i64::MAX, calendar).is_err());
+        assert!(CFDuration::try_from_hours(i64::MIN, calendar).is_err());
+        assert!(CFDuration::try_from_minutes(i64::MAX, calendar).is_err());
+        let duration = CFDuration::from_days(i64::MAX, calendar);
+        assert_eq!(
+            (duration.seconds, duration.nanoseconds),
+            (i64::MAX, 999_999_999)
+        );
+        assert_eq!(
+            duration.to_string(),
+            "P0Y0M106751991167300DT15H30M7.999999999S"
+        );
+        let duration = CFDuration::from_hours(i64::MIN, calendar);
+        assert_eq!((duration.seconds, duration.nanoseconds), (i64::MIN, 1));
+        assert_eq!(
+            duration.to_string(),
+            "-P0Y0M106751991167300DT15H30M7.999999999S"
+        );
+        assert_eq!(
+            parts(&-duration),
+            parts(&CFDuration::from_hours(i64::MAX, calendar))
+        );
+        // The sub-second units always fit
+        let duration = CFDuration::from_milliseconds(i64::MAX, calendar);
+        assert_eq!(duration.seconds, i64::MAX / 1_000);
+        let duration = CFDuration::from_microseconds(i64::MIN, calendar);
+        assert_eq!(duration.whole_seconds(), i64::MIN / 1_000_000);
+        assert_eq!(
+            CFDuration::from_nanoseconds(i64::MAX, calendar).num_nanoseconds(),
+            i64::MAX as f64
+        );
+        // The sums are checked
+        let max = CFDuration::from_seconds(i64::MAX, calendar);
+        let one = CFDuration::from_seconds(1, calendar);
+        assert!((&max + &one).is_err());
+        assert!((&max - &one).is_ok());
+        assert!((-&max - CFDuration::from_seconds(2, calendar)).is_err());
+        let months = CFDuration::from_months(i64::MAX, calendar);
+        assert!((&months + &CFDuration::from_months(1, calendar)).is_err());
+        assert_eq!(months.to_approximate_seconds().seconds, i64::MAX);
+    }
+
+    #[test]
+    fn test_sign_and_components() {
+        let duration = CFDuration::from_milliseconds(-1_500, Calendar::Standard);
+        assert_eq!((duration.seconds, duration.nanoseconds), (-2, 500_000_000));
+        assert_eq!(duration.whole_seconds(), -1);
+        assert_eq!(duration.subsec_nanos(), -500_000_000);
+        assert_eq!(duration.signum(), -1);
+        assert!(duration.is_negative());
+        let duration = duration.abs();
+        assert_eq!(
+            (duration.whole_seconds(), duration.subsec_nanos()),
+            (1, 500_000_000)
+        );
+        assert_eq!(duration.signum(), 1);
+        let duration = CFDuration::from_seconds(-3, Calendar::Standard);
+        assert_eq!((duration.whole_seconds(), duration.subsec_nanos()), (-3, 0));
+        let zero = CFDuration::from_seconds(0, Calendar::Standard);
+        assert!(zero.is_zero() && !zero.is_negative());
+        // One month minus one day is positive
+        let duration = CFDuration::with_months(1, -86_400, 0, Calendar::NoLeap);
+        assert_eq!(duration.signum(), 1);
+        let duration = duration.abs();
+        assert_eq!((duration.months, duration.seconds), (1, -86_400));
+        let duration = CFDuration::from_months(-1, Calendar::NoLeap).abs();
+        assert_eq!((duration.months, duration.seconds), (1, 0));
+        // The absolute value of the smallest duration saturates
+        let duration = CFDuration::from_seconds(i64::MIN, Calendar::Standard).abs();
+        assert_eq!(parts(&duration), (0, i64::MAX, 999_999_999));
+        let duration = CFDuration::from_months(i64::MIN, Calendar::NoLeap).abs();
+        assert_eq!(parts(&duration), (i64::MAX, 0, 0));
+        let duration = -CFDuration::new(i64::MIN, 1, Calendar::Standard);
+        assert_eq!(parts(&duration), (0, i64::MAX, 999_999_999));
+    }
+
+    fn parts(duration: &CFDuration) -> (i64, i64, u32) {
+        (duration.months, duration.seconds, duration.nanoseconds)
+    }
+
+    #[test]
+    fn test_display_iso8601() {
+        let cases = [
+            (
+                CFDuration::from_days(1, Calendar::Standard),
+                "P0Y0M1DT0H0M0S",
+            ),
+            (
+                -CFDuration::from_days(1, Calendar::Standard),
+                "-P0Y0M1DT0H0M0S",
+            ),
+            (
+                CFDuration::from_months(14, Calendar::NoLeap),
+                "P1Y2M0DT0H0M0S",
+            ),
+            (
+                CFDuration::from_seconds(3 * 86_400 + 4 * 3_600 + 5 * 60 + 6, Calendar::Standard),
+                "P0Y0M3DT4H5M6S",
+            ),
+            (
+                CFDuration::from_milliseconds(-1_500, Calendar::Standard),
+                "-P0Y0M0DT0H0M1.5S",
+            ),
+            (
+                CFDuration::with_months(1, -86_400, 0, Calendar::NoLeap),
+                "P0Y1M-1DT0H0M0S",
+            ),
+            (
+                CFDuration::with_months(1, -1, 500_000_000, Calendar::NoLeap),
+                "P0Y1M0DT0H0M-0.5S",
+            ),
+        ];
+        for (duration, expected) in cases {
+            assert_eq!(duration.to_string(), expected);
+            let parsed = CFDuration::from_iso8601(expected, duration.calendar()).unwrap();
+            assert_eq!(parts(&parsed), parts(&duration), "{expected}");
+        }
+        // The magnitude of the smallest durations does not fit in an i64
+        assert_eq!(
+            CFDuration::from_seconds(i64::MIN, Calendar::Standard).to_string(),
+            "-P0Y0M106751991167300DT15H30M8S"
+        );
+        assert_eq!(
+            CFDuration::from_months(i64::MIN, Calendar::NoLeap).to_string(),
+            "-P768614336404564650Y8M0DT0H0M0S"
+        );
+        // and is parsed back
+        for duration in [
+            CFDuration::from_seconds(i64::MIN, Calendar::Standard),
+            CFDuration::from_months(i64::MIN, Calendar::NoLeap),
+        ] {
+            let parsed = CFDuration::from_iso8601(&duration.to_string(), duration.calendar());
+            assert_eq!(parts(&parsed.unwrap()), parts(&duration));
+        }
+    }
+
+    #[test]
+    fn test_from_iso8601() {
+        let parse = |s| CFDuration::from_iso8601(s, Calendar::Standard);
+        assert_eq!(
+            parts(&parse("PT6H").unwrap()),
+            parts(&CFDuration::from_hours(6, Calendar::Standard))
+        );
+        assert_eq!(
+            parts(&parse("P2W").unwrap()),
+            parts(&CFDuration::from_weeks(2, Calendar::Standard))
+        );
+        assert_eq!(
+            parts(&parse("-P1D").unwrap()),
+            parts(&(-CFDuration::from_days(1, Calendar::Standard)))
+        );
+        assert_eq!(
+            parts(&parse("+P1Y").unwrap()),
+            parts(&CFDuration::from_years(1, Calendar::Standard))
+        );
+        assert_eq!(
+            parts(&parse("-P1M-1D").unwrap()),
+            parts(&CFDuration::with_months(-1, 86_400, 0, Calendar::Standard))
+        );
+        assert_eq!(
+            parts(&parse("PT0,25S").unwrap()),
+            parts(&CFDuration::from_milliseconds(250, Calendar::Standard))
+        );
+        for invalid in [
+            "",
+            "P",
+            "PT",
+            "1D",
+            "P1.5D",
+            "P1H",
+            "PT1D",
+            "P1D2Y",
+            "P1D1D",
+            "PT1.0000000001S",
+            "P-D",
+            // The negation of i64::MIN months overflows
+            "-P-768614336404564650Y-8M",
+            "P768614336404564650Y8M",
+        ] {
+            assert!(
+                parse(invalid).is_err_and(|e| e.is_parse_error()),
+                "{invalid}"
+            );
+        }
+    }
 }
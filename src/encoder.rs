@@ -1,10 +1,82 @@
 //! Module that implements the encode_cf method for`CFDatetime` and `Vec<CFDatetime>`.
+//! References `&CFDatetime` and `Option<CFDatetime>` are also supported, `None` being kept as missing value.
+//!
+//! The encoded values are the exact quotients rounded to the nearest float, ties to even, so the
+//! encoding is reproducible across platforms, see [crate::utils::encode_duration].
+//! Integers are the exact quotients rounded toward negative infinity, e.g. a date 1.5 days before
+//! the reference date of `days since ...` units is encoded to -2, see
+//! [crate::utils::encode_duration_floor]. An error is returned if an integer does not fit in its type.
+//!
+//! [encode_cf_lossy] encodes every datetime it can and returns the errors of the others with their
+//! indices, instead of stopping at the first one.
+//!
+//! [encode_for_netcdf] chooses the units and the type of the values, and returns them with the
+//! attributes to write in a netCDF file.
 
 use crate::{
     calendars::Calendar,
+    converter::CFConverter,
     datetime::CFDatetime,
-    utils::{get_datetime_and_unit_from_units, unit_to_encode},
+    decoder::DType,
+    duration::CFDuration,
+    parser::Unit,
+    utils::{encode_duration, encode_duration_floor, get_datetime_and_unit_from_units},
 };
+use alloc::{string::String, vec::Vec};
+use core::borrow::Borrow;
+
+/// Types of the encoded values, i.e. `i32`, `i64`, `f32` and `f64`
+pub trait EncodeDuration: Sized {
+    /// Converts a duration into a number of `multiplier` units, rounded to the nearest float or
+    /// toward negative infinity for the integers
+    fn encode_duration(
+        unit: &Unit,
+        duration: &CFDuration,
+        multiplier: i64,
+    ) -> crate::errors::Result<Self>;
+}
+
+macro_rules! impl_encode_duration_float {
+    ($type:ty) => {
+        impl EncodeDuration for $type {
+            fn encode_duration(
+                unit: &Unit,
+                duration: &CFDuration,
+                multiplier: i64,
+            ) -> crate::errors::Result<Self> {
+                Ok(encode_duration(unit, duration, multiplier) as $type)
+            }
+        }
+    };
+}
+
+impl_encode_duration_float!(f32);
+impl_encode_duration_float!(f64);
+
+macro_rules! impl_encode_duration_integer {
+    ($type:ty) => {
+        impl EncodeDuration for $type {
+            fn encode_duration(
+                unit: &Unit,
+                duration: &CFDuration,
+                multiplier: i64,
+            ) -> crate::errors::Result<Self> {
+                let value = encode_duration_floor(unit, duration, multiplier);
+                <$type>::try_from(value).map_err(|_| {
+                    crate::errors::Error::OutOfBoundsEncoding(format!(
+                        "{value} does not fit in {}",
+                        stringify!($type)
+                    ))
+                })
+            }
+        }
+    };
+}
+
+impl_encode_duration_integer!(i32);
+impl_encode_duration_integer!(i64);
+impl_encode_duration_integer!(u32);
+impl_encode_duration_integer!(u64);
 
 /// This trait represents a CFEncoder.
 /// A CFEncoder is responsible for encoding [CFDatetime] into a specific format.
@@ -18,22 +90,18 @@ pub trait CFEncoder<T> {
     ///
     /// # Returns
     ///
-    /// The encoded data as a Result<T, crate::errors::Error>.
-    fn encode_cf(&self, units: &str, calendar: Calendar) -> Result<T, crate::errors::Error>;
+    /// The encoded data as a `crate::errors::Result<T>`.
+    fn encode_cf(&self, units: &str, calendar: Calendar) -> crate::errors::Result<T>;
 }
 
 macro_rules! impl_cf_encoder {
     ($type:ty) => {
         impl CFEncoder<$type> for CFDatetime {
-            fn encode_cf(
-                &self,
-                units: &str,
-                calendar: Calendar,
-            ) -> Result<$type, crate::errors::Error> {
-                let (cf_datetime, unit) = get_datetime_and_unit_from_units(units, calendar)?;
+            fn encode_cf(&self, units: &str, calendar: Calendar) -> crate::errors::Result<$type> {
+                let (cf_datetime, unit, multiplier) =
+                    get_datetime_and_unit_from_units(units, calendar)?;
                 let duration = (self - cf_datetime)?;
-                let result = unit_to_encode(&unit, duration);
-                Ok(result as $type)
+                <$type>::encode_duration(&unit, &duration, multiplier)
             }
         }
     };
@@ -44,6 +112,65 @@ impl_cf_encoder!(i32);
 impl_cf_encoder!(f32);
 impl_cf_encoder!(f64);
 
+macro_rules! impl_ref_and_option_cf_encoder {
+    ($type:ty) => {
+        impl CFEncoder<$type> for &CFDatetime {
+            fn encode_cf(&self, units: &str, calendar: Calendar) -> crate::errors::Result<$type> {
+                (*self).encode_cf(units, calendar)
+            }
+        }
+        impl CFEncoder<Option<$type>> for Option<CFDatetime> {
+            fn encode_cf(
+                &self,
+                units: &str,
+                calendar: Calendar,
+            ) -> crate::errors::Result<Option<$type>> {
+                self.as_ref()
+                    .map(|datetime| datetime.encode_cf(units, calendar))
+                    .transpose()
+            }
+        }
+        impl CFEncoder<Option<$type>> for Option<&CFDatetime> {
+            fn encode_cf(
+                &self,
+                units: &str,
+                calendar: Calendar,
+            ) -> crate::errors::Result<Option<$type>> {
+                self.map(|datetime| datetime.encode_cf(units, calendar))
+                    .transpose()
+            }
+        }
+        impl CFEncoder<Vec<Option<$type>>> for Vec<Option<CFDatetime>> {
+            fn encode_cf(
+                &self,
+                units: &str,
+                calendar: Calendar,
+            ) -> crate::errors::Result<Vec<Option<$type>>> {
+                let (cf_datetime, unit, multiplier) =
+                    get_datetime_and_unit_from_units(units, calendar)?;
+                let mut result: Vec<Option<$type>> = Vec::with_capacity(self.len());
+                for datetime in self {
+                    match datetime {
+                        Some(datetime) => {
+                            let duration = (datetime - &cf_datetime)?;
+                            result.push(Some(<$type>::encode_duration(
+                                &unit, &duration, multiplier,
+                            )?));
+                        }
+                        None => result.push(None),
+                    }
+                }
+                Ok(result)
+            }
+        }
+    };
+}
+
+impl_ref_and_option_cf_encoder!(i64);
+impl_ref_and_option_cf_encoder!(i32);
+impl_ref_and_option_cf_encoder!(f32);
+impl_ref_and_option_cf_encoder!(f64);
+
 macro_rules! impl_vec_cf_encoder {
     ($type:ty) => {
         impl CFEncoder<Vec<$type>> for Vec<CFDatetime> {
@@ -51,12 +178,13 @@ macro_rules! impl_vec_cf_encoder {
                 &self,
                 units: &str,
                 calendar: Calendar,
-            ) -> Result<Vec<$type>, crate::errors::Error> {
-                let (cf_datetime, unit) = get_datetime_and_unit_from_units(units, calendar)?;
+            ) -> crate::errors::Result<Vec<$type>> {
+                let (cf_datetime, unit, multiplier) =
+                    get_datetime_and_unit_from_units(units, calendar)?;
                 let mut result: Vec<$type> = Vec::with_capacity(self.len());
                 for datetime in self {
                     let duration = (datetime - &cf_datetime)?;
-                    result.push(unit_to_encode(&unit, duration) as $type);
+                    result.push(<$type>::encode_duration(&unit, &duration, multiplier)?);
                 }
                 Ok(result)
             }
@@ -76,12 +204,13 @@ macro_rules! impl_vec_ref_cf_encoder {
                 &self,
                 units: &str,
                 calendar: Calendar,
-            ) -> Result<Vec<$type>, crate::errors::Error> {
-                let (cf_datetime, unit) = get_datetime_and_unit_from_units(units, calendar)?;
+            ) -> crate::errors::Result<Vec<$type>> {
+                let (cf_datetime, unit, multiplier) =
+                    get_datetime_and_unit_from_units(units, calendar)?;
                 let mut result: Vec<$type> = Vec::with_capacity(self.len());
                 for datetime in self {
                     let duration = (*datetime - &cf_datetime)?;
-                    result.push(unit_to_encode(&unit, duration) as $type);
+                    result.push(<$type>::encode_duration(&unit, &duration, multiplier)?);
                 }
                 Ok(result)
             }
@@ -94,6 +223,159 @@ impl_vec_ref_cf_encoder!(i32);
 impl_vec_ref_cf_encoder!(f32);
 impl_vec_ref_cf_encoder!(f64);
 
+/// Values of [encode_cf_lossy] and [CFConverter::encode_lossy], with the index and the error of
+/// every datetime that could not be encoded
+pub type LossyEncoding<T> = (Vec<T>, Vec<(usize, crate::errors::Error)>);
+
+/// Encodes the datetimes without stopping at the first invalid one, see
+/// [CFConverter::encode_lossy]. The values of the datetimes that can not be encoded are
+/// `fill_value`.
+///
+/// # Example
+/// ```
+/// # use cftime_rs::{calendars::Calendar, datetime::CFDatetime, encoder::encode_cf_lossy};
+/// let datetimes = [
+///     CFDatetime::from_ymd(2000, 1, 2, Calendar::NoLeap).unwrap(),
+///     CFDatetime::from_ymd(2000, 1, 2, Calendar::Day360).unwrap(),
+///     CFDatetime::from_ymd(2000, 1, 3, Calendar::NoLeap).unwrap(),
+/// ];
+/// let (values, errors) =
+///     encode_cf_lossy(&datetimes, "days since 2000-01-01", Calendar::NoLeap, -9999_i64).unwrap();
+/// assert_eq!(values, vec![1, -9999, 2]);
+/// assert_eq!(errors.len(), 1);
+/// assert_eq!(errors[0].0, 1);
+/// assert!(errors[0].1.is_calendar_error());
+/// ```
+///
+/// # Returns
+///
+/// A Result containing the values and the indexed errors of the datetimes that could not be
+/// encoded, or an error if the units can not be parsed
+pub fn encode_cf_lossy<T: EncodeDuration + Copy, D: Borrow<CFDatetime>>(
+    datetimes: &[D],
+    units: &str,
+    calendar: Calendar,
+    fill_value: T,
+) -> crate::errors::Result<LossyEncoding<T>> {
+    Ok(CFConverter::new(units, calendar)?.encode_lossy(datetimes, fill_value))
+}
+
+/// Choice of the units and of the type of the values of [encode_for_netcdf]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[non_exhaustive]
+pub enum EncodingPolicy {
+    /// `int64` seconds, or the coarsest of milliseconds, microseconds and nanoseconds encoding
+    /// every datetime exactly
+    #[default]
+    Integer,
+    /// `double` days, which is readable by most tools but rounds the fractions of days
+    DoubleDays,
+}
+
+/// Values encoded by [encode_for_netcdf]
+#[derive(Debug, PartialEq, Clone)]
+pub enum EncodedValues {
+    Int64(Vec<i64>),
+    Float64(Vec<f64>),
+}
+
+/// Values and attributes of a time variable encoded by [encode_for_netcdf]
+#[derive(Debug, PartialEq, Clone)]
+pub struct EncodedTime {
+    /// The encoded values, of type `dtype`
+    pub values: EncodedValues,
+    /// The `units` attribute, e.g. `seconds since 2000-01-01 00:00:00`
+    pub units: String,
+    /// The `calendar` attribute, i.e. the CF name of the calendar
+    pub calendar_attr: &'static str,
+    /// The type of the variable
+    pub dtype: DType,
+}
+
+/// Returns the coarsest unit of at most a second in which every fraction of seconds is an integer
+fn finest_exact_unit<D: Borrow<CFDatetime>>(datetimes: &[D]) -> Unit {
+    [
+        (1_000_000_000, Unit::Second),
+        (1_000_000, Unit::Millisecond),
+        (1_000, Unit::Microsecond),
+    ]
+    .into_iter()
+    .find(|(factor, _)| {
+        datetimes
+            .iter()
+            .all(|datetime| datetime.borrow().nanoseconds() % factor == 0)
+    })
+    .map_or(Unit::Nanosecond, |(_, unit)| unit)
+}
+
+/// Encodes the datetimes with units and a type following the CF best practices, to write a time
+/// variable in a netCDF file.
+///
+/// The reference date of the units is the midnight before the earliest datetime, and the unit
+/// and the type are chosen by the policy. Without datetimes the units are
+/// `seconds since 1970-01-01 00:00:00` in the standard calendar.
+///
+/// # Returns
+///
+/// A Result containing the values and the attributes, or an error of type
+/// `crate::errors::Error::DifferentCalendars` if the datetimes are not all of the same calendar or
+/// `crate::errors::Error::OutOfBoundsEncoding` if an integer does not fit in an `i64`
+///
+/// # Example
+/// ```
+/// # use cftime_rs::{calendars::Calendar, datetime::CFDatetime, encoder::*};
+/// let datetimes = [
+///     CFDatetime::from_ymd_hms(2000, 1, 1, 6, 0, 0.0, Calendar::NoLeap).unwrap(),
+///     CFDatetime::from_ymd_hms(2000, 1, 1, 18, 0, 0.0, Calendar::NoLeap).unwrap(),
+/// ];
+/// let encoded = encode_for_netcdf(&datetimes, EncodingPolicy::Integer).unwrap();
+/// assert_eq!(encoded.units, "seconds since 2000-01-01 00:00:00");
+/// assert_eq!(encoded.calendar_attr, "noleap");
+/// assert_eq!(encoded.values, EncodedValues::Int64(vec![21600, 64800]));
+/// ```
+pub fn encode_for_netcdf<D: Borrow<CFDatetime>>(
+    datetimes: &[D],
+    policy: EncodingPolicy,
+) -> crate::errors::Result<EncodedTime> {
+    let earliest = datetimes
+        .iter()
+        .map(|datetime| datetime.borrow())
+        .min_by_key(|datetime| datetime.timestamp_nanos());
+    let (reference, calendar) = match earliest {
+        Some(earliest) => {
+            let calendar = earliest.calendar();
+            let (year, month, day) = earliest.ymd()?;
+            (CFDatetime::from_ymd(year, month, day, calendar)?, calendar)
+        }
+        None => (
+            CFDatetime::from_timestamp(0, 0, Calendar::default())?,
+            Calendar::default(),
+        ),
+    };
+    let unit = match policy {
+        EncodingPolicy::Integer => finest_exact_unit(datetimes),
+        EncodingPolicy::DoubleDays => Unit::Day,
+    };
+    let units = format!("{} since {}", unit.cf_name(), reference);
+    let converter = CFConverter::new(&units, calendar)?;
+    let (values, dtype) = match policy {
+        EncodingPolicy::Integer => (
+            EncodedValues::Int64(converter.encode(datetimes)?),
+            DType::Int64,
+        ),
+        EncodingPolicy::DoubleDays => (
+            EncodedValues::Float64(converter.encode(datetimes)?),
+            DType::Float64,
+        ),
+    };
+    Ok(EncodedTime {
+        values,
+        units,
+        calendar_attr: calendar.cf_name(),
+        dtype,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -112,6 +394,38 @@ mod tests {
         assert_eq!(result, 1672531200);
     }
     #[test]
+    fn test_encode_ref_and_option() {
+        let units = "days since 2000-01-01 00:00:00";
+        let datetime = CFDatetime::from_ymd(2000, 1, 3, Calendar::Standard).unwrap();
+        let reference = &datetime;
+        let result: i64 = CFEncoder::encode_cf(&reference, units, Calendar::Standard).unwrap();
+        assert_eq!(result, 2);
+        let result: Option<f64> = Some(reference)
+            .encode_cf(units, Calendar::Standard)
+            .unwrap();
+        assert_eq!(result, Some(2.0));
+        let missing: Option<CFDatetime> = None;
+        let result: Option<i32> = missing.encode_cf(units, Calendar::Standard).unwrap();
+        assert_eq!(result, None);
+        let datetimes = vec![
+            Some(CFDatetime::from_ymd(2000, 1, 2, Calendar::Standard).unwrap()),
+            None,
+        ];
+        let result: Vec<Option<i64>> = datetimes.encode_cf(units, Calendar::Standard).unwrap();
+        assert_eq!(result, vec![Some(1), None]);
+    }
+    #[test]
+    fn test_encode_cf_is_correctly_rounded() {
+        let units = "days since 2000-01-01 00:00:00";
+        for seconds in [1, 7, 3_600, 43_201, 86_399, 123_456_789] {
+            let datetime =
+                CFDatetime::from_timestamp(946_684_800 + seconds, 0, Calendar::Standard).unwrap();
+            let result: f64 = datetime.encode_cf(units, Calendar::Standard).unwrap();
+            // The division of two exactly representable floats is correctly rounded
+            assert_eq!(result, seconds as f64 / 86_400.0);
+        }
+    }
+    #[test]
     fn test_vec_encode_cf() {
         let datetimes = vec![
             CFDatetime::from_ymd(2000, 1, 1, Calendar::Standard).unwrap(),
@@ -134,4 +448,160 @@ mod tests {
         let result: Vec<f64> = datetimes.encode_cf(units, Calendar::Standard).unwrap();
         assert_eq!(result, vec![730487.0, 730488.0416666666, 730489.0833333334]);
     }
+    #[test]
+    fn test_vec_encode_cf_weeks() {
+        let datetimes = vec![
+            CFDatetime::from_ymd(2000, 1, 1, Calendar::Standard).unwrap(),
+            CFDatetime::from_ymd(2000, 1, 8, Calendar::Standard).unwrap(),
+            CFDatetime::from_ymd(2000, 1, 11, Calendar::Standard).unwrap(),
+        ];
+        let result: Vec<f64> = datetimes
+            .encode_cf("weeks since 2000-01-01", Calendar::Standard)
+            .unwrap();
+        assert_eq!(result, vec![0.0, 1.0, 10.0 / 7.0]);
+    }
+    #[test]
+    fn test_vec_encode_cf_with_multiplier() {
+        let datetimes = vec![
+            CFDatetime::from_ymd_hms(2000, 1, 1, 0, 0, 0.0, Calendar::Standard).unwrap(),
+            CFDatetime::from_ymd_hms(2000, 1, 1, 3, 0, 0.0, Calendar::Standard).unwrap(),
+            CFDatetime::from_ymd_hms(2000, 1, 1, 7, 30, 0.0, Calendar::Standard).unwrap(),
+        ];
+        let result: Vec<f64> = datetimes
+            .encode_cf("3 hours since 2000-01-01", Calendar::Standard)
+            .unwrap();
+        assert_eq!(result, vec![0.0, 1.0, 2.5]);
+    }
+    #[test]
+    fn test_encode_cf_before_reference_date() {
+        let units = "days since 2000-01-01 00:00:00";
+        // 1.5 days before the reference date
+        let datetime =
+            CFDatetime::from_ymd_hms(1999, 12, 30, 12, 0, 0.0, Calendar::ProlepticGregorian)
+                .unwrap();
+        let result: f64 = datetime
+            .encode_cf(units, Calendar::ProlepticGregorian)
+            .unwrap();
+        assert_eq!(result, -1.5);
+        let result: f32 = datetime
+            .encode_cf(units, Calendar::ProlepticGregorian)
+            .unwrap();
+        assert_eq!(result, -1.5);
+        let result: i64 = datetime
+            .encode_cf(units, Calendar::ProlepticGregorian)
+            .unwrap();
+        assert_eq!(result, -2);
+        let result: i32 = datetime
+            .encode_cf(units, Calendar::ProlepticGregorian)
+            .unwrap();
+        assert_eq!(result, -2);
+        let datetimes = vec![
+            CFDatetime::from_ymd(1999, 12, 31, Calendar::ProlepticGregorian).unwrap(),
+            CFDatetime::from_timestamp(946_684_799, 999_999_999, Calendar::ProlepticGregorian)
+                .unwrap(),
+            CFDatetime::from_ymd(2000, 1, 1, Calendar::ProlepticGregorian).unwrap(),
+        ];
+        let result: Vec<i64> = datetimes
+            .encode_cf(units, Calendar::ProlepticGregorian)
+            .unwrap();
+        assert_eq!(result, vec![-1, -1, 0]);
+        let result: Vec<i32> = datetimes
+            .encode_cf(units, Calendar::ProlepticGregorian)
+            .unwrap();
+        assert_eq!(result, vec![-1, -1, 0]);
+        let result: Vec<i64> = datetimes
+            .encode_cf("nanoseconds since 2000-01-01", Calendar::ProlepticGregorian)
+            .unwrap();
+        assert_eq!(result, vec![-86_400_000_000_000, -1, 0]);
+        let result: Vec<f64> = datetimes
+            .encode_cf("2 days since 2000-01-01", Calendar::ProlepticGregorian)
+            .unwrap();
+        assert_eq!(result, vec![-0.5, -0.5 / 86_400e9, 0.0]);
+        // The decoded integers are at or before the encoded dates
+        let result: Vec<i64> = datetimes
+            .encode_cf("hours since 2000-01-01", Calendar::ProlepticGregorian)
+            .unwrap();
+        assert_eq!(result, vec![-24, -1, 0]);
+    }
+    #[test]
+    fn test_encode_cf_integers_are_exact() {
+        // Nanoseconds since 1970 do not fit in the 53 bits of a f64
+        let datetime =
+            CFDatetime::from_timestamp(946_684_800, 1, Calendar::ProlepticGregorian).unwrap();
+        let result: i64 = datetime
+            .encode_cf("nanoseconds since 1970-01-01", Calendar::ProlepticGregorian)
+            .unwrap();
+        assert_eq!(result, 946_684_800_000_000_001);
+        let datetime =
+            CFDatetime::from_timestamp(-946_684_800, 1, Calendar::ProlepticGregorian).unwrap();
+        let result: i64 = datetime
+            .encode_cf("nanoseconds since 1970-01-01", Calendar::ProlepticGregorian)
+            .unwrap();
+        assert_eq!(result, -946_684_799_999_999_999);
+    }
+    #[test]
+    fn test_encode_cf_integer_out_of_bounds() {
+        let datetime = CFDatetime::from_ymd(2100, 1, 1, Calendar::Standard).unwrap();
+        let result: Result<i32, _> =
+            datetime.encode_cf("seconds since 1970-01-01", Calendar::Standard);
+        assert!(result.is_err_and(|e| e.is_out_of_bounds()));
+        let datetime = CFDatetime::from_ymd(1800, 1, 1, Calendar::Standard).unwrap();
+        let result: Result<Vec<i32>, _> =
+            vec![datetime].encode_cf("seconds since 1970-01-01", Calendar::Standard);
+        assert!(result.is_err_and(|e| e.is_out_of_bounds()));
+        let result: i64 = CFDatetime::from_ymd(1800, 1, 1, Calendar::Standard)
+            .unwrap()
+            .encode_cf("seconds since 1970-01-01", Calendar::Standard)
+            .unwrap();
+        assert_eq!(result, -5_364_662_400);
+    }
+
+    #[test]
+    fn test_encode_for_netcdf() {
+        let calendar = Calendar::Day360;
+        let datetimes = vec![
+            CFDatetime::from_ymd_hms(2000, 2, 30, 12, 0, 0.0, calendar).unwrap(),
+            CFDatetime::from_ymd_hms(2000, 2, 29, 18, 0, 0.0, calendar).unwrap(),
+        ];
+        let encoded = encode_for_netcdf(&datetimes, EncodingPolicy::default()).unwrap();
+        assert_eq!(
+            encoded,
+            EncodedTime {
+                values: EncodedValues::Int64(vec![129600, 64800]),
+                units: "seconds since 2000-02-29 00:00:00".to_string(),
+                calendar_attr: "360_day",
+                dtype: DType::Int64,
+            }
+        );
+        let encoded = encode_for_netcdf(&datetimes, EncodingPolicy::DoubleDays).unwrap();
+        assert_eq!(encoded.values, EncodedValues::Float64(vec![1.5, 0.75]));
+        assert_eq!(encoded.units, "days since 2000-02-29 00:00:00");
+        assert_eq!(encoded.dtype, DType::Float64);
+
+        // Fractions of seconds are encoded exactly in the coarsest unit
+        let datetimes = [
+            CFDatetime::from_timestamp(-1, 250_000_000, Calendar::Standard).unwrap(),
+            CFDatetime::from_timestamp(1, 500_000, Calendar::Standard).unwrap(),
+        ];
+        let encoded = encode_for_netcdf(&datetimes, EncodingPolicy::Integer).unwrap();
+        assert_eq!(encoded.units, "microseconds since 1969-12-31 00:00:00");
+        let decoded = CFConverter::new(&encoded.units, Calendar::Standard)
+            .unwrap()
+            .decode(match &encoded.values {
+                EncodedValues::Int64(values) => values,
+                EncodedValues::Float64(_) => unreachable!(),
+            })
+            .unwrap();
+        assert!(decoded.iter().zip(&datetimes).all(|(a, b)| a == b));
+
+        let encoded = encode_for_netcdf::<CFDatetime>(&[], EncodingPolicy::Integer).unwrap();
+        assert_eq!(encoded.units, "seconds since 1970-01-01 00:00:00");
+        assert_eq!(encoded.calendar_attr, "standard");
+        let datetimes = [
+            CFDatetime::from_ymd(2000, 1, 1, Calendar::Standard).unwrap(),
+            CFDatetime::from_ymd(2000, 1, 1, Calendar::NoLeap).unwrap(),
+        ];
+        assert!(encode_for_netcdf(&datetimes, EncodingPolicy::Integer)
+            .is_err_and(|e| e.is_calendar_error()));
+    }
 }
@@ -1,23 +1,277 @@
-use thiserror::Error;
+use alloc::{boxed::Box, string::String};
+use core::fmt;
 
-#[derive(Error, Debug)]
+/// Result of the fallible functions of the crate
+pub type Result<T> = core::result::Result<T, Error>;
+
+/// Who has to act on an [Error], see [Error::severity]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Severity {
+    /// An argument such as units, a calendar, a timezone or a configuration is not valid.
+    /// Retrying with the same arguments fails again, they have to be fixed.
+    UserInput,
+    /// A value or a date of the data is not valid or out of range. The record can be skipped.
+    Data,
+}
+
+/// Errors of the crate. A new kind of failure gets its own variant, so a caller classifies the
+/// errors with [Error::severity] and the predicates such as [Error::is_out_of_bounds] rather than
+/// by matching every variant.
+#[derive(Debug)]
+#[non_exhaustive]
 pub enum Error {
-    #[error("Out of bounds for calendar {0} : {1}")]
     OutOfBoundsCalendar(String, String),
-    #[error("Invalid date : {0}")]
+    OutOfBoundsEncoding(String),
     InvalidDate(String),
-    #[error("Invalid time : {0}")]
     InvalidTime(String),
-    #[error("Invalid tz : {0}")]
+    InvalidCalendar(String),
     InvalidTz(String),
-    #[error("Invalid unit : {0}")]
     UnitParserError(String),
-    #[error("Different calendars found : {0} and {1}.")]
+    UnitParserDiagnostic(crate::parser::Diagnostic),
+    InvalidDuration(String),
+    InvalidConfig(String),
+    InvalidValue(usize, f64),
+    InvalidBuffer(String),
+    InvalidDType(String),
+    /// The reference date of the units, given first, is not valid in the calendar
+    InvalidReferenceDate(String, Box<Error>),
+    UnsortedAxis(usize),
     DifferentCalendars(String, String),
-    // Parseint error from std
-    #[error("{0}")]
-    ParseIntError(#[from] std::num::ParseIntError),
-    // Parsefloat error from std
-    #[error("{0}")]
-    ParseFloatError(#[from] std::num::ParseFloatError),
+    /// Arrays that are zipped together do not have the same length
+    DifferentLengths(usize, usize),
+    /// Error on the element at the index of an array, e.g. of a bulk constructor
+    AtIndex(usize, Box<Error>),
+    // Parseint error from core
+    ParseIntError(core::num::ParseIntError),
+    // Parsefloat error from core
+    ParseFloatError(core::num::ParseFloatError),
+}
+
+// Implemented by hand instead of derived so the errors do not need std
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::OutOfBoundsCalendar(calendar, message) => {
+                write!(f, "Out of bounds for calendar {calendar} : {message}")
+            }
+            Error::OutOfBoundsEncoding(message) => {
+                write!(f, "Out of bounds for encoding : {message}")
+            }
+            Error::InvalidDate(message) => write!(f, "Invalid date : {message}"),
+            Error::InvalidTime(message) => write!(f, "Invalid time : {message}"),
+            Error::InvalidCalendar(message) => write!(f, "Invalid calendar : {message}"),
+            Error::InvalidTz(message) => write!(f, "Invalid tz : {message}"),
+            Error::UnitParserError(message) => write!(f, "Invalid unit : {message}"),
+            Error::UnitParserDiagnostic(diagnostic) => write!(f, "Invalid unit : {diagnostic}"),
+            Error::InvalidDuration(message) => write!(f, "Invalid duration : {message}"),
+            Error::InvalidConfig(message) => write!(f, "Invalid config : {message}"),
+            Error::InvalidValue(index, value) => {
+                write!(f, "Invalid value at index {index} : {value}")
+            }
+            Error::InvalidBuffer(message) => write!(f, "Invalid buffer : {message}"),
+            Error::InvalidDType(message) => write!(f, "Invalid dtype : {message}"),
+            Error::InvalidReferenceDate(units, error) => {
+                write!(f, "Invalid reference date of the units `{units}` : {error}")
+            }
+            Error::UnsortedAxis(index) => write!(
+                f,
+                "Time axis is not sorted in increasing order at index {index}"
+            ),
+            Error::DifferentCalendars(first, second) => {
+                write!(f, "Different calendars found : {first} and {second}.")
+            }
+            Error::DifferentLengths(first, second) => {
+                write!(f, "Different lengths found : {first} and {second}")
+            }
+            Error::AtIndex(index, error) => write!(f, "At index {index} : {error}"),
+            Error::ParseIntError(error) => write!(f, "{error}"),
+            Error::ParseFloatError(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl core::error::Error for Error {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Error::ParseIntError(error) => Some(error),
+            Error::ParseFloatError(error) => Some(error),
+            Error::AtIndex(_, error) | Error::InvalidReferenceDate(_, error) => {
+                Some(error.as_ref())
+            }
+            _ => None,
+        }
+    }
+}
+
+impl From<core::num::ParseIntError> for Error {
+    fn from(error: core::num::ParseIntError) -> Self {
+        Error::ParseIntError(error)
+    }
+}
+
+impl From<core::num::ParseFloatError> for Error {
+    fn from(error: core::num::ParseFloatError) -> Self {
+        Error::ParseFloatError(error)
+    }
+}
+
+impl Error {
+    /// Returns the error without the index of [Error::AtIndex] or the units of
+    /// [Error::InvalidReferenceDate], which the predicates look through
+    fn without_index(&self) -> &Error {
+        match self {
+            Error::AtIndex(_, error) | Error::InvalidReferenceDate(_, error) => {
+                error.without_index()
+            }
+            error => error,
+        }
+    }
+    /// Returns the index of the element of an array the error is about, if any
+    pub fn index(&self) -> Option<usize> {
+        match self {
+            Error::AtIndex(index, _)
+            | Error::InvalidValue(index, _)
+            | Error::UnsortedAxis(index) => Some(*index),
+            _ => None,
+        }
+    }
+    /// Returns `true` if a value does not fit in the range of the calendar or of the encoded type
+    pub fn is_out_of_bounds(&self) -> bool {
+        matches!(
+            self.without_index(),
+            Error::OutOfBoundsCalendar(..) | Error::OutOfBoundsEncoding(_)
+        )
+    }
+    /// Returns `true` if a date or a time is not valid
+    pub fn is_invalid_datetime(&self) -> bool {
+        matches!(
+            self.without_index(),
+            Error::InvalidDate(_) | Error::InvalidTime(_)
+        )
+    }
+    /// Returns `true` if a calendar is not valid or calendars do not match
+    pub fn is_calendar_error(&self) -> bool {
+        matches!(
+            self.without_index(),
+            Error::InvalidCalendar(_) | Error::DifferentCalendars(..)
+        )
+    }
+    /// Returns `true` if units, a timezone, a duration, a configuration, a dtype or a number could not
+    /// be parsed
+    pub fn is_parse_error(&self) -> bool {
+        matches!(
+            self.without_index(),
+            Error::UnitParserError(_)
+                | Error::UnitParserDiagnostic(_)
+                | Error::InvalidTz(_)
+                | Error::InvalidDuration(_)
+                | Error::InvalidConfig(_)
+                | Error::InvalidDType(_)
+                | Error::ParseIntError(_)
+                | Error::ParseFloatError(_)
+        )
+    }
+    /// Returns whether the error comes from the arguments or from the data, to decide whether to
+    /// abort or skip the record. An invalid date is an error of the data, unless it is the
+    /// reference date of the units.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cftime_rs::{calendars::Calendar, decoder::CFDecoder, errors::Severity};
+    /// let error = 1.decode_cf("days since 2000-13-01", Calendar::Standard).err().unwrap();
+    /// assert_eq!(error.severity(), Severity::UserInput);
+    /// let error = 1.decode_cf("days after 2000-01-01", Calendar::Standard).err().unwrap();
+    /// assert_eq!(error.severity(), Severity::UserInput);
+    /// let error = i64::MAX.decode_cf("days since 2000-01-01", Calendar::Standard).err().unwrap();
+    /// assert_eq!(error.severity(), Severity::Data);
+    /// ```
+    pub fn severity(&self) -> Severity {
+        match self {
+            Error::OutOfBoundsCalendar(..)
+            | Error::OutOfBoundsEncoding(_)
+            | Error::InvalidDate(_)
+            | Error::InvalidTime(_)
+            | Error::InvalidValue(..)
+            | Error::UnsortedAxis(_) => Severity::Data,
+            Error::InvalidCalendar(_)
+            | Error::InvalidTz(_)
+            | Error::UnitParserError(_)
+            | Error::UnitParserDiagnostic(_)
+            | Error::InvalidDuration(_)
+            | Error::InvalidConfig(_)
+            | Error::InvalidBuffer(_)
+            | Error::InvalidDType(_)
+            | Error::InvalidReferenceDate(..)
+            | Error::DifferentCalendars(..)
+            | Error::DifferentLengths(..)
+            | Error::ParseIntError(_)
+            | Error::ParseFloatError(_) => Severity::UserInput,
+            Error::AtIndex(_, error) => error.severity(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_predicates() {
+        let error = Error::OutOfBoundsCalendar("Standard".to_string(), "date".to_string());
+        assert!(error.is_out_of_bounds());
+        assert!(!error.is_parse_error());
+        assert!(Error::InvalidTime("25:00".to_string()).is_invalid_datetime());
+        assert!(Error::DifferentCalendars("a".to_string(), "b".to_string()).is_calendar_error());
+        let error: Error = "a".parse::<i64>().unwrap_err().into();
+        assert!(error.is_parse_error());
+        assert!(!error.is_invalid_datetime());
+    }
+
+    #[test]
+    fn test_severity() {
+        assert_eq!(Error::InvalidValue(3, f64::NAN).severity(), Severity::Data);
+        assert_eq!(Error::UnsortedAxis(1).severity(), Severity::Data);
+        assert_eq!(
+            Error::OutOfBoundsEncoding("i32".to_string()).severity(),
+            Severity::Data
+        );
+        assert_eq!(
+            Error::InvalidCalendar("gregorien".to_string()).severity(),
+            Severity::UserInput
+        );
+        let error: Error = "a".parse::<f64>().unwrap_err().into();
+        assert_eq!(error.severity(), Severity::UserInput);
+        assert_eq!(
+            Error::DifferentLengths(1, 2).severity(),
+            Severity::UserInput
+        );
+    }
+
+    #[test]
+    fn test_at_index() {
+        let error = Error::AtIndex(4, Box::new(Error::InvalidDate("2001-02-29".to_string())));
+        assert!(error.is_invalid_datetime());
+        assert_eq!(error.severity(), Severity::Data);
+        assert_eq!(error.index(), Some(4));
+        assert_eq!(error.to_string(), "At index 4 : Invalid date : 2001-02-29");
+        assert!(core::error::Error::source(&error).is_some());
+        assert_eq!(Error::InvalidCalendar("a".to_string()).index(), None);
+    }
+
+    #[test]
+    fn test_invalid_reference_date() {
+        let error = Error::InvalidReferenceDate(
+            "days since 2000-13-01".to_string(),
+            Box::new(Error::InvalidDate("2000-13-01".to_string())),
+        );
+        assert!(error.is_invalid_datetime());
+        assert_eq!(error.severity(), Severity::UserInput);
+        assert_eq!(
+            error.to_string(),
+            "Invalid reference date of the units `days since 2000-13-01` : Invalid date : 2000-13-01"
+        );
+        assert!(core::error::Error::source(&error).is_some());
+    }
 }
@@ -0,0 +1,90 @@
+//! Rounding and absolute value of floats, which `core` does not provide without std before rust
+//! 1.85.
+//!
+//! The floats whose magnitude is at least 2^52 have no fraction, so only the smaller ones are
+//! rounded through an `i64`. NaN and infinities are returned as is.
+
+/// Floats from this magnitude are integers
+const INTEGER_BOUND: f64 = 4_503_599_627_370_496.0;
+
+/// Returns the magnitude of the value, as `f64::abs`
+pub(crate) fn abs(value: f64) -> f64 {
+    f64::from_bits(value.to_bits() & !(1 << 63))
+}
+
+/// Rounds toward zero, as `f64::trunc`
+pub(crate) fn trunc(value: f64) -> f64 {
+    if abs(value) < INTEGER_BOUND {
+        value as i64 as f64
+    } else {
+        value
+    }
+}
+
+/// Returns the fraction with the sign of the value, as `f64::fract`
+pub(crate) fn fract(value: f64) -> f64 {
+    value - trunc(value)
+}
+
+/// Rounds toward negative infinity, as `f64::floor`
+pub(crate) fn floor(value: f64) -> f64 {
+    let truncated = trunc(value);
+    if truncated > value {
+        truncated - 1.0
+    } else {
+        truncated
+    }
+}
+
+/// Rounds half away from zero, as `f64::round`
+pub(crate) fn round(value: f64) -> f64 {
+    let truncated = trunc(value);
+    // Exact as the value and its truncation are close
+    let fraction = value - truncated;
+    if fraction >= 0.5 {
+        truncated + 1.0
+    } else if fraction <= -0.5 {
+        truncated - 1.0
+    } else {
+        truncated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_as_std() {
+        let values = [
+            0.0,
+            -0.0,
+            0.5,
+            -0.5,
+            1.5,
+            -2.5,
+            0.49999999999999994,
+            -1.0000000000000002,
+            123456.789,
+            -987654.321,
+            4503599627370495.5,
+            -4503599627370495.5,
+            9007199254740993.0,
+            1e300,
+            -1e-300,
+            f64::MAX,
+            f64::MIN,
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+        ];
+        // NaN is not equal to itself
+        let same = |a: f64, b: f64| a == b || (a.is_nan() && b.is_nan());
+        for value in values.into_iter().chain([f64::NAN]) {
+            assert!(same(abs(value), value.abs()), "abs({value})");
+            assert!(same(trunc(value), value.trunc()), "trunc({value})");
+            assert!(same(fract(value), value.fract()), "fract({value})");
+            assert!(same(floor(value), value.floor()), "floor({value})");
+            assert!(same(round(value), value.round()), "round({value})");
+        }
+    }
+}
@@ -0,0 +1,122 @@
+//! Module that combines the two time axes of forecast data into valid times.
+//!
+//! Forecasts have a reference time coordinate, with the `forecast_reference_time` standard name
+//! and units such as `hours since 2000-01-01`, and a lead time coordinate, with the
+//! `forecast_period` standard name and units such as `hours` or `hours since forecast_reference_time`.
+//! The valid time of a forecast is its reference time plus its lead time.
+
+use crate::decoder::ScaleDuration;
+use crate::instrument::trace_span;
+use crate::parser::parse_lead_time_units;
+use crate::utils::get_datetime_and_unit_from_units;
+use crate::{calendars::Calendar, datetime::CFDatetime};
+use alloc::vec::Vec;
+
+/// Computes the valid times of every reference time and lead time.
+///
+/// # Arguments
+///
+/// * `reference_times` - The values of the reference time coordinate.
+/// * `reference_units` - The units of the reference time coordinate, e.g. `hours since 2000-01-01`.
+/// * `lead_times` - The values of the lead time coordinate.
+/// * `lead_units` - The units of the lead time coordinate, see [parse_lead_time_units].
+/// * `calendar` - The calendar to use for decoding.
+///
+/// # Returns
+///
+/// A Result containing the valid times with one row per reference time and one column per lead
+/// time, i.e. `valid_times[i][j]` is `reference_times[i] + lead_times[j]`, or an Error if the units
+/// are not valid or a valid time is out of the range of the calendar.
+pub fn forecast_valid_times<R: ScaleDuration, L: ScaleDuration>(
+    reference_times: &[R],
+    reference_units: &str,
+    lead_times: &[L],
+    lead_units: &str,
+    calendar: Calendar,
+) -> crate::errors::Result<Vec<Vec<CFDatetime>>> {
+    trace_span!(
+        DEBUG,
+        "forecast_valid_times",
+        calendar = %calendar,
+        reference_times = reference_times.len(),
+        lead_times = lead_times.len()
+    );
+    let (origin, unit, multiplier) = get_datetime_and_unit_from_units(reference_units, calendar)?;
+    let reference_duration = unit.to_duration_with_multiplier(calendar, multiplier)?;
+    let (lead_unit, lead_multiplier) = parse_lead_time_units(lead_units)?;
+    let lead_duration = lead_unit.to_duration_with_multiplier(calendar, lead_multiplier)?;
+    // The lead times are the same for every reference time
+    let lead_durations = lead_times
+        .iter()
+        .enumerate()
+        .map(|(index, lead_time)| lead_time.check_finite(index)?.scale(&lead_duration))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut valid_times = Vec::with_capacity(reference_times.len());
+    for (index, reference_time) in reference_times.iter().enumerate() {
+        let reference = (&origin
+            + reference_time
+                .check_finite(index)?
+                .scale(&reference_duration)?)?;
+        let row = lead_durations
+            .iter()
+            .map(|lead_duration| &reference + lead_duration)
+            .collect::<Result<Vec<_>, _>>()?;
+        valid_times.push(row);
+    }
+    Ok(valid_times)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_forecast_valid_times() {
+        let valid_times = forecast_valid_times(
+            &[0, 12],
+            "hours since 2000-01-01",
+            &[0.0, 6.0, 30.0],
+            "hours since forecast_reference_time",
+            Calendar::Standard,
+        )
+        .unwrap();
+        assert_eq!(valid_times.len(), 2);
+        let ymd_hms: Vec<Vec<_>> = valid_times
+            .iter()
+            .map(|row| row.iter().map(|dt| dt.ymd_hms().unwrap()).collect())
+            .collect();
+        assert_eq!(
+            ymd_hms,
+            vec![
+                vec![
+                    (2000, 1, 1, 0, 0, 0),
+                    (2000, 1, 1, 6, 0, 0),
+                    (2000, 1, 2, 6, 0, 0)
+                ],
+                vec![
+                    (2000, 1, 1, 12, 0, 0),
+                    (2000, 1, 1, 18, 0, 0),
+                    (2000, 1, 2, 18, 0, 0)
+                ],
+            ]
+        );
+        let valid_times = forecast_valid_times(
+            &[0i64],
+            "days since 2000-02-29",
+            &[1i32],
+            "days",
+            Calendar::Day360,
+        )
+        .unwrap();
+        assert_eq!(valid_times[0][0].ymd().unwrap(), (2000, 2, 30));
+        assert!(forecast_valid_times(
+            &[0],
+            "hours since 2000-01-01",
+            &[1],
+            "hours since 2000-01-01",
+            Calendar::Standard
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,234 @@
+//! Module that infers the sampling frequency of a time axis.
+//!
+//! [diff] returns the durations between consecutive datetimes and [infer_freq] finds the step of a
+//! supposedly regular time axis, either an exact duration such as 3 hours or a number of calendar
+//! months, together with the indices of the datetimes that do not follow this step.
+
+use crate::calendars::Calendar;
+use crate::datetime::CFDatetime;
+use crate::duration::CFDuration;
+use crate::instrument::trace_span;
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+
+/// Step between two consecutive datetimes of a time axis
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub enum Step {
+    /// Number of calendar months, e.g. monthly means on the 15th or at the end of each month
+    Months(i64),
+    /// Exact number of nanoseconds
+    Nanoseconds(i128),
+}
+
+impl Step {
+    /// Returns the step as a CFDuration in the given calendar
+    pub fn to_duration(&self, calendar: Calendar) -> CFDuration {
+        match *self {
+            Step::Months(months) => CFDuration::from_months(months, calendar),
+            Step::Nanoseconds(nanoseconds) => CFDuration::new(
+                nanoseconds.div_euclid(1_000_000_000) as i64,
+                nanoseconds.rem_euclid(1_000_000_000) as i64,
+                calendar,
+            ),
+        }
+    }
+}
+
+/// Result of [infer_freq]
+#[derive(Debug, PartialEq, Clone)]
+pub struct InferredFrequency {
+    /// The most common step of the time axis
+    pub step: Step,
+    /// Indices `i` such that the step between `datetimes[i - 1]` and `datetimes[i]` is not [Self::step]
+    pub anomalies: Vec<usize>,
+}
+
+impl InferredFrequency {
+    /// Returns `true` if every datetime follows the step
+    pub fn is_regular(&self) -> bool {
+        self.anomalies.is_empty()
+    }
+}
+
+/// Returns the durations between consecutive datetimes, i.e. `datetimes[i + 1] - datetimes[i]`.
+///
+/// # Returns
+///
+/// A Result containing the `datetimes.len() - 1` durations or an error of type
+/// `crate::errors::Error::DifferentCalendars` if the datetimes are not all in the same calendar.
+pub fn diff<T: Borrow<CFDatetime>>(datetimes: &[T]) -> crate::errors::Result<Vec<CFDuration>> {
+    datetimes
+        .windows(2)
+        .map(|pair| pair[1].borrow() - pair[0].borrow())
+        .collect()
+}
+
+/// Returns the step between two datetimes.
+///
+/// The step is a number of calendar months if the datetimes are at the same time of the day and
+/// either on the same day of the month or both on the last day of their month, e.g. `2000-01-31`
+/// and `2000-02-29`. Otherwise it is the exact duration between the datetimes.
+pub fn step_between(start: &CFDatetime, end: &CFDatetime) -> crate::errors::Result<Step> {
+    let duration = (end - start)?;
+    let nanoseconds = duration.seconds as i128 * 1_000_000_000 + duration.nanoseconds as i128;
+    if start.hms()? == end.hms()? && start.nanoseconds() == end.nanoseconds() {
+        let (start_year, start_month, start_day) = start.ymd()?;
+        let (end_year, end_month, end_day) = end.ymd()?;
+        let months = (end_year - start_year) * 12 + end_month as i64 - start_month as i64;
+        let month_ends = start_day == start.days_in_month()? && end_day == end.days_in_month()?;
+        if months != 0 && (start_day == end_day || month_ends) {
+            return Ok(Step::Months(months));
+        }
+    }
+    Ok(Step::Nanoseconds(nanoseconds))
+}
+
+/// Infers the step of a time axis.
+///
+/// The step is the most common [step_between] consecutive datetimes, the first one to appear in
+/// case of a tie, and the anomalies are the indices of the datetimes that are not one step after
+/// the previous datetime.
+///
+/// # Returns
+///
+/// A Result containing `None` if there are less than two datetimes, the inferred frequency
+/// otherwise, or an error of type `crate::errors::Error::DifferentCalendars` if the datetimes are
+/// not all in the same calendar.
+pub fn infer_freq<T: Borrow<CFDatetime>>(
+    datetimes: &[T],
+) -> crate::errors::Result<Option<InferredFrequency>> {
+    trace_span!(DEBUG, "infer_freq", len = datetimes.len());
+    let steps = datetimes
+        .windows(2)
+        .map(|pair| step_between(pair[0].borrow(), pair[1].borrow()))
+        .collect::<Result<Vec<_>, _>>()?;
+    // Number of occurrences and first index of every step
+    let mut counts: BTreeMap<Step, (usize, usize)> = BTreeMap::new();
+    for (index, step) in steps.iter().enumerate() {
+        counts.entry(*step).or_insert((0, index)).0 += 1;
+    }
+    let step = match counts
+        .into_iter()
+        .max_by_key(|(_, (count, first))| (*count, core::cmp::Reverse(*first)))
+    {
+        Some((step, _)) => step,
+        None => return Ok(None),
+    };
+    let anomalies = steps
+        .iter()
+        .enumerate()
+        .filter(|(_, other)| **other != step)
+        .map(|(index, _)| index + 1)
+        .collect();
+    Ok(Some(InferredFrequency { step, anomalies }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn datetimes(dates: &[(i64, u8, u8, u8)], calendar: Calendar) -> Vec<CFDatetime> {
+        dates
+            .iter()
+            .map(|&(year, month, day, hour)| {
+                CFDatetime::from_ymd_hms(year, month, day, hour, 0, 0.0, calendar).unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_infer_regular_hours() {
+        let axis = datetimes(
+            &[
+                (2000, 1, 1, 0),
+                (2000, 1, 1, 3),
+                (2000, 1, 1, 6),
+                (2000, 1, 1, 9),
+            ],
+            Calendar::Standard,
+        );
+        let frequency = infer_freq(&axis).unwrap().unwrap();
+        assert_eq!(frequency.step, Step::Nanoseconds(3 * 3_600 * 1_000_000_000));
+        assert!(frequency.is_regular());
+        let durations = diff(&axis).unwrap();
+        assert_eq!(durations.len(), 3);
+        assert!(durations.iter().all(|d| d.num_hours() == 3.0));
+        let step = frequency.step.to_duration(Calendar::Standard);
+        assert_eq!((step.seconds, step.nanoseconds), (3 * 3_600, 0));
+    }
+
+    #[test]
+    fn test_infer_calendar_months() {
+        // Monthly means at the end of the month and on the 15th
+        let axis = datetimes(
+            &[
+                (2000, 1, 31, 0),
+                (2000, 2, 29, 0),
+                (2000, 3, 31, 0),
+                (2000, 4, 30, 0),
+            ],
+            Calendar::Standard,
+        );
+        let frequency = infer_freq(&axis).unwrap().unwrap();
+        assert_eq!(frequency.step, Step::Months(1));
+        assert!(frequency.is_regular());
+        let axis = datetimes(
+            &[(2000, 1, 15, 12), (2000, 4, 15, 12), (2000, 7, 15, 12)],
+            Calendar::NoLeap,
+        );
+        let frequency = infer_freq(&axis).unwrap().unwrap();
+        assert_eq!(frequency.step, Step::Months(3));
+        assert_eq!(frequency.step.to_duration(Calendar::NoLeap).months, 3);
+        // Daily data crossing the end of a month is not monthly
+        let axis = datetimes(
+            &[(2000, 1, 30, 0), (2000, 1, 31, 0), (2000, 2, 1, 0)],
+            Calendar::Standard,
+        );
+        let frequency = infer_freq(&axis).unwrap().unwrap();
+        assert_eq!(frequency.step, Step::Nanoseconds(86_400 * 1_000_000_000));
+        assert!(frequency.is_regular());
+    }
+
+    #[test]
+    fn test_infer_anomalies() {
+        // A missing and a duplicated time step
+        let axis = datetimes(
+            &[
+                (2000, 1, 1, 0),
+                (2000, 1, 2, 0),
+                (2000, 1, 4, 0),
+                (2000, 1, 5, 0),
+                (2000, 1, 5, 0),
+                (2000, 1, 6, 0),
+            ],
+            Calendar::Day360,
+        );
+        let frequency = infer_freq(&axis).unwrap().unwrap();
+        assert_eq!(frequency.step, Step::Nanoseconds(86_400 * 1_000_000_000));
+        assert_eq!(frequency.anomalies, vec![2, 4]);
+        assert!(!frequency.is_regular());
+        // The first step wins a tie
+        let axis = datetimes(
+            &[(2000, 1, 1, 0), (2000, 1, 1, 1), (2000, 1, 1, 3)],
+            Calendar::Standard,
+        );
+        let frequency = infer_freq(&axis).unwrap().unwrap();
+        assert_eq!(frequency.step, Step::Nanoseconds(3_600 * 1_000_000_000));
+        assert_eq!(frequency.anomalies, vec![2]);
+    }
+
+    #[test]
+    fn test_infer_errors() {
+        assert!(infer_freq::<CFDatetime>(&[]).unwrap().is_none());
+        let axis = datetimes(&[(2000, 1, 1, 0)], Calendar::Standard);
+        assert!(infer_freq(&axis).unwrap().is_none());
+        assert!(diff(&axis).unwrap().is_empty());
+        let mixed = [
+            CFDatetime::from_ymd(2000, 1, 1, Calendar::Standard).unwrap(),
+            CFDatetime::from_ymd(2000, 1, 2, Calendar::NoLeap).unwrap(),
+        ];
+        assert!(infer_freq(&mixed).is_err_and(|e| e.is_calendar_error()));
+        assert!(diff(&mixed).is_err_and(|e| e.is_calendar_error()));
+    }
+}
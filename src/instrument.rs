@@ -0,0 +1,17 @@
+//! Optional instrumentation with [tracing](https://docs.rs/tracing) spans, enabled with the
+//! `tracing` feature. Without the feature the spans are not compiled at all.
+
+/// Enters a span at the given level for the rest of the scope when the `tracing` feature is enabled
+///
+/// # Example
+/// ```ignore
+/// trace_span!(DEBUG, "decode_cf", calendar = %calendar, values = values.len());
+/// ```
+macro_rules! trace_span {
+    ($level:ident, $name:expr $(, $($fields:tt)*)?) => {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::span!(tracing::Level::$level, $name $(, $($fields)*)?).entered();
+    };
+}
+
+pub(crate) use trace_span;
@@ -11,7 +11,7 @@
 //! ```rust
 //! use cftime_rs::calendars::Calendar;
 //! use cftime_rs::decoder::*;
-//! use std::str::FromStr;
+//! use core::str::FromStr;
 //!
 //! let to_decode = vec![0, 1, 2, 3, 4, 5];
 //! let units = "days since 2000-01-01 00:00:00";
@@ -23,12 +23,12 @@
 //! ```
 //! will print :
 //! ```shell
-//! 2000-01-01 00:00:00.000
-//! 2000-01-02 00:00:00.000
-//! 2000-01-03 00:00:00.000
-//! 2000-01-04 00:00:00.000
-//! 2000-01-05 00:00:00.000
-//! 2000-01-06 00:00:00.000
+//! 2000-01-01 00:00:00
+//! 2000-01-02 00:00:00
+//! 2000-01-03 00:00:00
+//! 2000-01-04 00:00:00
+//! 2000-01-05 00:00:00
+//! 2000-01-06 00:00:00
 //! ```
 //!
 //! ### Encoding
@@ -40,7 +40,7 @@
 //! use cftime_rs::datetime::CFDatetime;
 //! use cftime_rs::encoder::*;
 //! use cftime_rs::errors::Error;
-//! use std::str::FromStr;
+//! use core::str::FromStr;
 //! let calendar = Calendar::from_str("standard").unwrap();
 //! // Create vector of datetimes and convert Vec<Result<CFDatetime, Error>>
 //! // into Result<Vec<CFDatetime>, Error>
@@ -75,27 +75,51 @@
 //!
 //! ## Known issues
 //!
-//! While this date calculation library can handle a wide range of dates, from approximately -291,672,107,014 BC to 291,672,107,014 AD, there are some performance considerations you should be aware of.
-//! As you move further away from the reference date of 1970-01-01 00:00:00, the time of calculation increases. This is because the library needs to account for leap years in various calendars.
+//! This date calculation library can handle a wide range of dates, from approximately -291,672,107,014 BC to 291,672,107,014 AD, which is the range of 64-bit seconds since 1970-01-01 00:00:00.
+//! The earliest and latest dates of each calendar are given by `CFDatetime::min` and `CFDatetime::max`, and `CFDatetime::clamp_to_valid_range` clamps a number of nanoseconds since the epoch to this range.
+//! Computing the year, month and day of a date skips whole leap cycles (400 years in the gregorian calendar, 4 years in the julian calendar), so it takes the same time for far dates as for dates close to 1970-01-01.
 //!
 //! Here is an example of the computation of 1_000_000_000_000_000 seconds using the units "seconds since 2000-01-01 00:00:00" on my personal computer in release mode :
 //!
 //! | Calendar          | Computation Time |
 //! |-------------------|------------------|
-//! | Standard Calendar | 44.470405ms      |
-//! | Leap Day Calendar | 8.052179ms       |
-//! | 360-Day Calendar  | 12.834µs         |
+//! | Standard Calendar | 20.415µs         |
+//! | Leap Day Calendar | 2.439µs          |
+//! | 360-Day Calendar  | 1.805µs          |
 //!
+//! ## `no_std`
+//!
+//! Without the default `std` feature the crate only needs `alloc`, e.g. to decode times on an
+//! embedded target. The conversions with `std::time::SystemTime` and the python bindings need `std`.
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+#[cfg_attr(not(feature = "std"), macro_use)]
+extern crate alloc;
 
+pub mod array;
 pub mod calendars;
+pub mod config;
 pub mod constants;
+pub mod converter;
 pub mod datetime;
 pub mod datetimes;
 pub mod decoder;
 pub mod duration;
 pub mod encoder;
 pub mod errors;
+mod float;
+pub mod forecast;
+pub mod frequency;
+mod instrument;
+mod macros;
+pub mod merge;
 pub mod parser;
+#[cfg(feature = "python")]
 pub mod py_bindings;
+pub mod raw;
+pub mod resample;
 pub mod timezone;
+pub mod typed;
 pub mod utils;
+pub mod validation;
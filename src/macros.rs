@@ -0,0 +1,252 @@
+//! Macros to write datetimes and durations as literals, e.g. in tests and examples.
+
+/// Makes a [CFDatetime](crate::datetime::CFDatetime) from a date written as in the units, with
+/// an optional time, and the name of a [Calendar](crate::calendars::Calendar) variant.
+///
+/// The macro expands to [CFDatetime::from_ymd](crate::datetime::CFDatetime::from_ymd) or
+/// [CFDatetime::from_ymd_hms](crate::datetime::CFDatetime::from_ymd_hms), so it returns their
+/// Result and an invalid date is an error, not a panic.
+///
+/// # Example
+/// ```
+/// use cftime_rs::{calendars::Calendar, cf_datetime, datetime::CFDatetime};
+/// let datetime = cf_datetime!(2000-02-30 12:30:15.5, Day360).unwrap();
+/// assert!(datetime == CFDatetime::from_ymd_hms(2000, 2, 30, 12, 30, 15.5, Calendar::Day360).unwrap());
+/// assert_eq!(cf_datetime!(-100-01-01, Julian).unwrap().ymd().unwrap(), (-100, 1, 1));
+/// assert!(cf_datetime!(2000-13-01, Standard).is_err());
+/// ```
+// The allowed leading zeros are the ones of the caller e.g. 2000-01-01
+#[macro_export]
+macro_rules! cf_datetime {
+    (- $year:literal - $month:literal - $day:literal $hour:literal : $minute:literal : $second:literal, $calendar:ident) => {{
+        #[allow(clippy::zero_prefixed_literal)]
+        let datetime = $crate::datetime::CFDatetime::from_ymd_hms(
+            -$year,
+            $month,
+            $day,
+            $hour,
+            $minute,
+            $second as f32,
+            $crate::calendars::Calendar::$calendar,
+        );
+        datetime
+    }};
+    ($year:literal - $month:literal - $day:literal $hour:literal : $minute:literal : $second:literal, $calendar:ident) => {{
+        #[allow(clippy::zero_prefixed_literal)]
+        let datetime = $crate::datetime::CFDatetime::from_ymd_hms(
+            $year,
+            $month,
+            $day,
+            $hour,
+            $minute,
+            $second as f32,
+            $crate::calendars::Calendar::$calendar,
+        );
+        datetime
+    }};
+    (- $year:literal - $month:literal - $day:literal, $calendar:ident) => {{
+        #[allow(clippy::zero_prefixed_literal)]
+        let datetime = $crate::datetime::CFDatetime::from_ymd(
+            -$year,
+            $month,
+            $day,
+            $crate::calendars::Calendar::$calendar,
+        );
+        datetime
+    }};
+    ($year:literal - $month:literal - $day:literal, $calendar:ident) => {{
+        #[allow(clippy::zero_prefixed_literal)]
+        let datetime = $crate::datetime::CFDatetime::from_ymd(
+            $year,
+            $month,
+            $day,
+            $crate::calendars::Calendar::$calendar,
+        );
+        datetime
+    }};
+}
+
+/// Makes a [CFDuration](crate::duration::CFDuration) from a number of units, written in the
+/// singular or the plural e.g. `6 hours` or `1 day`, and the name of a
+/// [Calendar](crate::calendars::Calendar) variant.
+///
+/// The macro expands to the checked constructor of the unit e.g.
+/// [CFDuration::try_from_hours](crate::duration::CFDuration::try_from_hours), so it returns a
+/// Result with an error instead of a saturated duration. An unknown unit does not compile.
+///
+/// # Example
+/// ```
+/// use cftime_rs::{calendars::Calendar, cf_duration, duration::CFDuration};
+/// let duration = cf_duration!(6 hours, Standard).unwrap();
+/// assert_eq!(duration.seconds, CFDuration::from_hours(6, Calendar::Standard).seconds);
+/// assert_eq!(cf_duration!(-1 month, NoLeap).unwrap().months, -1);
+/// assert!(cf_duration!(9_223_372_036_854_775_807 days, Standard).is_err());
+/// ```
+#[macro_export]
+macro_rules! cf_duration {
+    (@unit years, $value:expr, $calendar:ident) => {
+        $crate::duration::CFDuration::try_from_years($value, $crate::calendars::Calendar::$calendar)
+    };
+    (@unit months, $value:expr, $calendar:ident) => {
+        ::core::result::Result::<_, $crate::errors::Error>::Ok(
+            $crate::duration::CFDuration::from_months($value, $crate::calendars::Calendar::$calendar),
+        )
+    };
+    (@unit weeks, $value:expr, $calendar:ident) => {
+        $crate::duration::CFDuration::try_from_weeks($value, $crate::calendars::Calendar::$calendar)
+    };
+    (@unit days, $value:expr, $calendar:ident) => {
+        $crate::duration::CFDuration::try_from_days($value, $crate::calendars::Calendar::$calendar)
+    };
+    (@unit hours, $value:expr, $calendar:ident) => {
+        $crate::duration::CFDuration::try_from_hours($value, $crate::calendars::Calendar::$calendar)
+    };
+    (@unit minutes, $value:expr, $calendar:ident) => {
+        $crate::duration::CFDuration::try_from_minutes(
+            $value,
+            $crate::calendars::Calendar::$calendar,
+        )
+    };
+    (@unit seconds, $value:expr, $calendar:ident) => {
+        ::core::result::Result::<_, $crate::errors::Error>::Ok(
+            $crate::duration::CFDuration::from_seconds($value, $crate::calendars::Calendar::$calendar),
+        )
+    };
+    (@unit milliseconds, $value:expr, $calendar:ident) => {
+        ::core::result::Result::<_, $crate::errors::Error>::Ok(
+            $crate::duration::CFDuration::from_milliseconds(
+                $value,
+                $crate::calendars::Calendar::$calendar,
+            ),
+        )
+    };
+    (@unit microseconds, $value:expr, $calendar:ident) => {
+        ::core::result::Result::<_, $crate::errors::Error>::Ok(
+            $crate::duration::CFDuration::from_microseconds(
+                $value,
+                $crate::calendars::Calendar::$calendar,
+            ),
+        )
+    };
+    (@unit nanoseconds, $value:expr, $calendar:ident) => {
+        ::core::result::Result::<_, $crate::errors::Error>::Ok(
+            $crate::duration::CFDuration::from_nanoseconds(
+                $value,
+                $crate::calendars::Calendar::$calendar,
+            ),
+        )
+    };
+    (@unit year, $($rest:tt)*) => { $crate::cf_duration!(@unit years, $($rest)*) };
+    (@unit month, $($rest:tt)*) => { $crate::cf_duration!(@unit months, $($rest)*) };
+    (@unit week, $($rest:tt)*) => { $crate::cf_duration!(@unit weeks, $($rest)*) };
+    (@unit day, $($rest:tt)*) => { $crate::cf_duration!(@unit days, $($rest)*) };
+    (@unit hour, $($rest:tt)*) => { $crate::cf_duration!(@unit hours, $($rest)*) };
+    (@unit minute, $($rest:tt)*) => { $crate::cf_duration!(@unit minutes, $($rest)*) };
+    (@unit second, $($rest:tt)*) => { $crate::cf_duration!(@unit seconds, $($rest)*) };
+    (@unit millisecond, $($rest:tt)*) => { $crate::cf_duration!(@unit milliseconds, $($rest)*) };
+    (@unit microsecond, $($rest:tt)*) => { $crate::cf_duration!(@unit microseconds, $($rest)*) };
+    (@unit nanosecond, $($rest:tt)*) => { $crate::cf_duration!(@unit nanoseconds, $($rest)*) };
+    (@unit $unit:ident, $($rest:tt)*) => {
+        compile_error!(concat!("Unknown unit '", stringify!($unit), "'"))
+    };
+    (- $value:literal $unit:ident, $calendar:ident) => {
+        $crate::cf_duration!(@unit $unit, -$value, $calendar)
+    };
+    ($value:literal $unit:ident, $calendar:ident) => {
+        $crate::cf_duration!(@unit $unit, $value, $calendar)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{calendars::Calendar, datetime::CFDatetime, duration::CFDuration};
+
+    #[test]
+    #[rustfmt::skip]
+    fn test_cf_datetime() {
+        let datetime = cf_datetime!(2000-01-01 00:00:00, Standard).unwrap();
+        assert!(datetime == CFDatetime::from_ymd(2000, 1, 1, Calendar::Standard).unwrap());
+        let datetime = cf_datetime!(1999-12-31 23:59:59.25, NoLeap).unwrap();
+        assert_eq!(datetime.ymd_hms().unwrap(), (1999, 12, 31, 23, 59, 59));
+        assert_eq!(datetime.nanoseconds(), 250_000_000);
+        let datetime = cf_datetime!(-0100-03-01 06:00:00, ProlepticGregorian).unwrap();
+        assert_eq!(datetime.ymd_hms().unwrap(), (-100, 3, 1, 6, 0, 0));
+        let datetime = cf_datetime!(2000-02-30, Day360).unwrap();
+        assert_eq!(datetime.calendar(), Calendar::Day360);
+        assert_eq!(datetime.ymd().unwrap(), (2000, 2, 30));
+        assert!(cf_datetime!(2000-13-01, Standard).is_err());
+        assert!(cf_datetime!(1582-10-10 00:00:00, Standard).is_err());
+        assert!(cf_datetime!(2000-01-01 24:00:00, AllLeap).is_err());
+    }
+    #[test]
+    fn test_cf_duration() {
+        let calendar = Calendar::Standard;
+        let durations = [
+            (
+                cf_duration!(2 years, Standard),
+                CFDuration::from_years(2, calendar),
+            ),
+            (
+                cf_duration!(1 year, Standard),
+                CFDuration::from_years(1, calendar),
+            ),
+            (
+                cf_duration!(3 months, Standard),
+                CFDuration::from_months(3, calendar),
+            ),
+            (
+                cf_duration!(1 week, Standard),
+                CFDuration::from_weeks(1, calendar),
+            ),
+            (
+                cf_duration!(-10 days, Standard),
+                CFDuration::from_days(-10, calendar),
+            ),
+            (
+                cf_duration!(6 hours, Standard),
+                CFDuration::from_hours(6, calendar),
+            ),
+            (
+                cf_duration!(30 minute, Standard),
+                CFDuration::from_minutes(30, calendar),
+            ),
+            (
+                cf_duration!(1 second, Standard),
+                CFDuration::from_seconds(1, calendar),
+            ),
+            (
+                cf_duration!(1_500 milliseconds, Standard),
+                CFDuration::from_milliseconds(1_500, calendar),
+            ),
+            (
+                cf_duration!(-7 microseconds, Standard),
+                CFDuration::from_microseconds(-7, calendar),
+            ),
+            (
+                cf_duration!(1 nanosecond, Standard),
+                CFDuration::from_nanoseconds(1, calendar),
+            ),
+        ];
+        for (duration, expected) in durations {
+            let duration = duration.unwrap();
+            assert_eq!(
+                (duration.months, duration.seconds, duration.nanoseconds),
+                (expected.months, expected.seconds, expected.nanoseconds)
+            );
+            assert_eq!(duration.calendar(), calendar);
+        }
+        assert_eq!(
+            cf_duration!(1 day, Day360).unwrap().calendar(),
+            Calendar::Day360
+        );
+        assert!(cf_duration!(9_223_372_036_854_775_807 hours, Standard).is_err());
+        assert!(cf_duration!(9_223_372_036_854_775_807 years, Standard).is_err());
+    }
+    #[test]
+    #[rustfmt::skip]
+    fn test_macros_together() {
+        let datetime = cf_datetime!(2000-01-01, NoLeap).unwrap();
+        let shifted = (&datetime + cf_duration!(59 days, NoLeap).unwrap()).unwrap();
+        assert!(shifted == cf_datetime!(2000-03-01 00:00:00, NoLeap).unwrap());
+    }
+}
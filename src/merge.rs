@@ -0,0 +1,389 @@
+//! Module that merges the time axes of several files into a single axis.
+//!
+//! When the files of a dataset are concatenated along time, their axes may overlap or repeat the
+//! boundary step, e.g. a restart file starting at the last datetime of the previous file.
+//! [merge_time_axes] sorts the datetimes of all the axes, removes the duplicates according to a
+//! [DuplicatePolicy], reports the overlapping axes and keeps where every datetime comes from, so
+//! the variables can be gathered in the same order.
+//!
+//! ```
+//! use cftime_rs::calendars::Calendar;
+//! use cftime_rs::decoder::VecCFDecoder;
+//! use cftime_rs::merge::{merge_time_axes, MergePolicy, SourceIndex};
+//!
+//! let calendar = Calendar::NoLeap;
+//! let first = vec![0, 1, 2].decode_cf("days since 2000-01-01", calendar).unwrap();
+//! let second = vec![0, 1].decode_cf("days since 2000-01-03", calendar).unwrap();
+//! let merged = merge_time_axes(vec![first, second], MergePolicy::default()).unwrap();
+//! assert_eq!(merged.datetimes.len(), 4);
+//! assert_eq!(merged.sources[3], SourceIndex { axis: 1, index: 1 });
+//! assert_eq!(merged.report.overlaps, vec![(0, 1)]);
+//! assert_eq!(merged.report.duplicates.len(), 1);
+//! ```
+
+use crate::datetime::CFDatetime;
+use alloc::{boxed::Box, format, string::ToString, vec::Vec};
+use core::borrow::Borrow;
+
+/// Which datetime [merge_time_axes] keeps when several axes contain the same datetime
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum DuplicatePolicy {
+    /// Keeps the datetime of the first axis, e.g. to ignore the restart steps
+    #[default]
+    KeepFirst,
+    /// Keeps the datetime of the last axis, e.g. to prefer the reprocessed files
+    KeepLast,
+    /// Fails on the first duplicate
+    Error,
+}
+
+impl core::str::FromStr for DuplicatePolicy {
+    type Err = crate::errors::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "first" | "keep_first" => Ok(DuplicatePolicy::KeepFirst),
+            "last" | "keep_last" => Ok(DuplicatePolicy::KeepLast),
+            "error" | "raise" => Ok(DuplicatePolicy::Error),
+            _ => Err(crate::errors::Error::InvalidConfig(format!(
+                "Invalid duplicate policy `{s}`, expected one of first, last or error"
+            ))),
+        }
+    }
+}
+
+/// Options of [merge_time_axes]
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub struct MergePolicy {
+    pub duplicates: DuplicatePolicy,
+    /// Requires the axes to follow each other, i.e. the concatenation of the axes without the
+    /// duplicates to be strictly increasing. The axes are interleaved otherwise.
+    pub require_monotonic: bool,
+}
+
+impl MergePolicy {
+    /// Sets the policy for the datetimes found in several axes
+    pub fn with_duplicates(mut self, duplicates: DuplicatePolicy) -> Self {
+        self.duplicates = duplicates;
+        self
+    }
+    /// Sets whether the axes have to follow each other
+    pub fn with_require_monotonic(mut self, require_monotonic: bool) -> Self {
+        self.require_monotonic = require_monotonic;
+        self
+    }
+}
+
+/// Position of a datetime in the axes given to [merge_time_axes]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord, Hash)]
+pub struct SourceIndex {
+    /// Index of the axis
+    pub axis: usize,
+    /// Index of the datetime in its axis
+    pub index: usize,
+}
+
+/// A datetime found more than once, see [DuplicatePolicy]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Duplicate {
+    /// Position of the datetime kept in the merged axis
+    pub kept: SourceIndex,
+    /// Position of the datetime removed from the merged axis
+    pub dropped: SourceIndex,
+}
+
+/// Conflicts found by [merge_time_axes]
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct MergeReport {
+    /// Pairs of axes `(i, j)`, `i < j`, whose ranges of datetimes intersect
+    pub overlaps: Vec<(usize, usize)>,
+    /// Datetimes removed from the merged axis, in the order of the merged axis
+    pub duplicates: Vec<Duplicate>,
+}
+
+impl MergeReport {
+    /// Returns `true` if the axes neither overlap nor repeat a datetime
+    pub fn is_clean(&self) -> bool {
+        self.overlaps.is_empty() && self.duplicates.is_empty()
+    }
+}
+
+/// Result of [merge_time_axes]
+pub struct MergedAxis<T> {
+    /// Sorted datetimes without duplicates
+    pub datetimes: Vec<T>,
+    /// Position of every datetime of [Self::datetimes] in the axes
+    pub sources: Vec<SourceIndex>,
+    pub report: MergeReport,
+}
+
+/// Merges time axes into a sorted axis without duplicates.
+///
+/// Each axis has to be sorted in increasing order, and may repeat a datetime, which is then a
+/// duplicate as well. The datetimes are moved into the merged axis, so `T` can be `CFDatetime` or
+/// a shared reference to it.
+///
+/// # Returns
+///
+/// A Result containing the merged axis, or an error of type `crate::errors::Error::AtIndex` with
+/// the index of the axis and:
+/// - `crate::errors::Error::UnsortedAxis` with the index of the first datetime of the axis that is
+///   before its predecessor, or of the first datetime that is not after the previous axes if
+///   [MergePolicy::require_monotonic] is set
+/// - `crate::errors::Error::InvalidDate` if a datetime is a duplicate and the policy is
+///   [DuplicatePolicy::Error]
+/// - `crate::errors::Error::DifferentCalendars` if the axis is not in the calendar of the first
+///   datetime
+pub fn merge_time_axes<T: Borrow<CFDatetime>>(
+    axes: Vec<Vec<T>>,
+    policy: MergePolicy,
+) -> crate::errors::Result<MergedAxis<T>> {
+    let at = |axis: usize, error: crate::errors::Error| {
+        crate::errors::Error::AtIndex(axis, Box::new(error))
+    };
+    let calendar = axes
+        .iter()
+        .flatten()
+        .next()
+        .map(|datetime| datetime.borrow().calendar());
+    // First and last nanoseconds since epoch of the non empty axes
+    let mut ranges = Vec::with_capacity(axes.len());
+    let mut keys = Vec::new();
+    for (axis, datetimes) in axes.iter().enumerate() {
+        for (index, datetime) in datetimes.iter().enumerate() {
+            let datetime = datetime.borrow();
+            if Some(datetime.calendar()) != calendar {
+                return Err(at(
+                    axis,
+                    crate::errors::Error::DifferentCalendars(
+                        calendar.map(|c| c.to_string()).unwrap_or_default(),
+                        datetime.calendar().to_string(),
+                    ),
+                ));
+            }
+            let nanos = datetime.timestamp_nanos();
+            if index > 0 && nanos < datetimes[index - 1].borrow().timestamp_nanos() {
+                return Err(at(axis, crate::errors::Error::UnsortedAxis(index)));
+            }
+            keys.push((nanos, SourceIndex { axis, index }));
+        }
+        if let (Some(first), Some(last)) = (datetimes.first(), datetimes.last()) {
+            ranges.push((
+                axis,
+                first.borrow().timestamp_nanos(),
+                last.borrow().timestamp_nanos(),
+            ));
+        }
+    }
+
+    let mut report = MergeReport::default();
+    for (i, &(first_axis, first_start, first_end)) in ranges.iter().enumerate() {
+        for &(second_axis, second_start, second_end) in &ranges[i + 1..] {
+            if first_start <= second_end && second_start <= first_end {
+                report.overlaps.push((first_axis, second_axis));
+            }
+        }
+    }
+
+    // The sort is stable, so the equal datetimes stay in the order of the axes
+    keys.sort_by_key(|(nanos, _)| *nanos);
+    let mut sources: Vec<SourceIndex> = Vec::with_capacity(keys.len());
+    let mut group_start = 0;
+    while group_start < keys.len() {
+        let nanos = keys[group_start].0;
+        let group_end = group_start
+            + keys[group_start..]
+                .iter()
+                .take_while(|(other, _)| *other == nanos)
+                .count();
+        let group = &keys[group_start..group_end];
+        let kept = match policy.duplicates {
+            DuplicatePolicy::KeepFirst => group[0].1,
+            DuplicatePolicy::KeepLast => group[group.len() - 1].1,
+            DuplicatePolicy::Error if group.len() > 1 => {
+                let (first, second) = (group[0].1, group[1].1);
+                return Err(at(
+                    second.axis,
+                    crate::errors::Error::InvalidDate(format!(
+                        "{} at index {} is already at index {} of axis {}",
+                        axes[second.axis][second.index].borrow(),
+                        second.index,
+                        first.index,
+                        first.axis
+                    )),
+                ));
+            }
+            DuplicatePolicy::Error => group[0].1,
+        };
+        for &(_, source) in group {
+            if source != kept {
+                report.duplicates.push(Duplicate {
+                    kept,
+                    dropped: source,
+                });
+            }
+        }
+        sources.push(kept);
+        group_start = group_end;
+    }
+
+    if policy.require_monotonic {
+        // The kept datetimes in the order of the concatenated axes
+        let mut concatenated = sources.clone();
+        concatenated.sort();
+        for pair in concatenated.windows(2) {
+            let previous = axes[pair[0].axis][pair[0].index].borrow();
+            let source = pair[1];
+            if axes[source.axis][source.index].borrow().timestamp_nanos()
+                <= previous.timestamp_nanos()
+            {
+                return Err(at(
+                    source.axis,
+                    crate::errors::Error::UnsortedAxis(source.index),
+                ));
+            }
+        }
+    }
+
+    // Moves the kept datetimes out of the axes
+    let mut slots: Vec<Vec<Option<T>>> = axes
+        .into_iter()
+        .map(|datetimes| datetimes.into_iter().map(Some).collect())
+        .collect();
+    let datetimes = sources
+        .iter()
+        .map(|source| {
+            slots[source.axis][source.index]
+                .take()
+                .expect("A datetime is kept once")
+        })
+        .collect();
+    Ok(MergedAxis {
+        datetimes,
+        sources,
+        report,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendars::Calendar;
+    use core::str::FromStr;
+
+    fn axis(days: &[u8], calendar: Calendar) -> Vec<CFDatetime> {
+        days.iter()
+            .map(|day| CFDatetime::from_ymd(2000, 1, *day, calendar).unwrap())
+            .collect()
+    }
+
+    fn days(datetimes: &[CFDatetime]) -> Vec<u8> {
+        datetimes.iter().map(|dt| dt.ymd().unwrap().2).collect()
+    }
+
+    #[test]
+    fn test_merge_disjoint_and_interleaved_axes() {
+        let calendar = Calendar::Day360;
+        let merged = merge_time_axes(
+            vec![axis(&[4, 5], calendar), axis(&[1, 2, 3], calendar)],
+            MergePolicy::default(),
+        )
+        .unwrap();
+        assert_eq!(days(&merged.datetimes), vec![1, 2, 3, 4, 5]);
+        assert_eq!(merged.sources[0], SourceIndex { axis: 1, index: 0 });
+        assert_eq!(merged.sources[4], SourceIndex { axis: 0, index: 1 });
+        assert!(merged.report.is_clean());
+
+        let merged = merge_time_axes(
+            vec![axis(&[1, 3, 5], calendar), axis(&[2, 4], calendar)],
+            MergePolicy::default(),
+        )
+        .unwrap();
+        assert_eq!(days(&merged.datetimes), vec![1, 2, 3, 4, 5]);
+        assert_eq!(merged.report.overlaps, vec![(0, 1)]);
+        assert!(merged.report.duplicates.is_empty());
+        let error = merge_time_axes(
+            vec![axis(&[1, 3, 5], calendar), axis(&[2, 4], calendar)],
+            MergePolicy::default().with_require_monotonic(true),
+        )
+        .err()
+        .unwrap();
+        assert!(matches!(
+            error,
+            crate::errors::Error::AtIndex(1, ref e) if matches!(**e, crate::errors::Error::UnsortedAxis(0))
+        ));
+
+        let empty: Vec<Vec<CFDatetime>> = vec![vec![], vec![]];
+        let merged = merge_time_axes(empty, MergePolicy::default()).unwrap();
+        assert!(merged.datetimes.is_empty());
+    }
+
+    #[test]
+    fn test_merge_duplicates() {
+        let calendar = Calendar::NoLeap;
+        let axes = || vec![axis(&[1, 2, 3], calendar), axis(&[3, 4], calendar)];
+        let policy = MergePolicy::default().with_require_monotonic(true);
+        let merged = merge_time_axes(axes(), policy).unwrap();
+        assert_eq!(days(&merged.datetimes), vec![1, 2, 3, 4]);
+        assert_eq!(merged.sources[2], SourceIndex { axis: 0, index: 2 });
+        assert_eq!(
+            merged.report.duplicates,
+            vec![Duplicate {
+                kept: SourceIndex { axis: 0, index: 2 },
+                dropped: SourceIndex { axis: 1, index: 0 },
+            }]
+        );
+        assert_eq!(merged.report.overlaps, vec![(0, 1)]);
+
+        let policy = policy.with_duplicates(DuplicatePolicy::KeepLast);
+        let merged = merge_time_axes(axes(), policy).unwrap();
+        assert_eq!(merged.sources[2], SourceIndex { axis: 1, index: 0 });
+        assert_eq!(merged.sources[3], SourceIndex { axis: 1, index: 1 });
+
+        let policy = policy.with_duplicates(DuplicatePolicy::Error);
+        let error = merge_time_axes(axes(), policy).err().unwrap();
+        assert_eq!(error.index(), Some(1));
+        assert!(error.is_invalid_datetime());
+
+        // Repeated datetimes within an axis are duplicates too
+        let merged =
+            merge_time_axes(vec![axis(&[1, 1, 2], calendar)], MergePolicy::default()).unwrap();
+        assert_eq!(days(&merged.datetimes), vec![1, 2]);
+        assert_eq!(merged.report.duplicates.len(), 1);
+        assert!(merged.report.overlaps.is_empty());
+    }
+
+    #[test]
+    fn test_merge_errors() {
+        let error = merge_time_axes(
+            vec![axis(&[1], Calendar::NoLeap), axis(&[2], Calendar::Day360)],
+            MergePolicy::default(),
+        )
+        .err()
+        .unwrap();
+        assert_eq!(error.index(), Some(1));
+        assert!(error.is_calendar_error());
+        let error = merge_time_axes(
+            vec![axis(&[1, 3, 2], Calendar::NoLeap)],
+            MergePolicy::default(),
+        )
+        .err()
+        .unwrap();
+        assert!(matches!(
+            error,
+            crate::errors::Error::AtIndex(0, ref e) if matches!(**e, crate::errors::Error::UnsortedAxis(2))
+        ));
+        // Shared references are merged without copying the datetimes
+        let datetimes = axis(&[2, 1], Calendar::NoLeap);
+        let merged = merge_time_axes(
+            vec![vec![&datetimes[0]], vec![&datetimes[1]]],
+            MergePolicy::default(),
+        )
+        .unwrap();
+        assert!(core::ptr::eq(merged.datetimes[0], &datetimes[1]));
+        assert_eq!(
+            DuplicatePolicy::from_str("Last").unwrap(),
+            DuplicatePolicy::KeepLast
+        );
+        assert!(DuplicatePolicy::from_str("newest").is_err());
+    }
+}
@@ -1,12 +1,33 @@
 //! Module related to parsing the date units
 //! Create a `ParsedDatetime` from units
 
-use crate::{calendars::Calendar, duration::CFDuration};
+use crate::instrument::trace_span;
+use crate::{calendars::Calendar, datetime::CFDatetime, duration::CFDuration, timezone::Tz};
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
 
-#[derive(Debug, PartialEq)]
+/// Unit of the time values. Other units of udunits, e.g. `fortnights`, are not read yet and would
+/// be new variants, [Unit::is_calendar_dependent] and [Unit::is_subsecond] tell how any unit
+/// converts.
+///
+/// The unit is displayed with its name in the units and parsed from any of its
+/// [aliases](Unit::aliases), the same ones as in the units.
+///
+/// # Example
+///
+/// ```
+/// # use cftime_rs::parser::Unit;
+/// assert_eq!("hrs".parse::<Unit>().unwrap(), Unit::Hour);
+/// assert_eq!(format!("3 {} since 2000-01-01", Unit::Hour), "3 hours since 2000-01-01");
+/// ```
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[non_exhaustive]
 pub enum Unit {
     Year,
     Month,
+    Week,
     Day,
     Hour,
     Minute,
@@ -16,11 +37,67 @@ pub enum Unit {
     Nanosecond,
 }
 
+/// Every unit, in decreasing length
+const UNITS: [Unit; 10] = [
+    Unit::Year,
+    Unit::Month,
+    Unit::Week,
+    Unit::Day,
+    Unit::Hour,
+    Unit::Minute,
+    Unit::Second,
+    Unit::Millisecond,
+    Unit::Microsecond,
+    Unit::Nanosecond,
+];
+
 impl Unit {
+    /// Returns the names of the unit accepted in the units, the first one being [Unit::cf_name]
+    pub fn aliases(&self) -> &'static [&'static str] {
+        match self {
+            Unit::Year => &["common_years", "common_year"],
+            Unit::Month => &["months", "month"],
+            Unit::Week => &["weeks", "week", "wk"],
+            Unit::Day => &["days", "day", "d"],
+            Unit::Hour => &["hours", "hour", "hrs", "hr", "h"],
+            Unit::Minute => &["minutes", "minute", "mins", "min"],
+            Unit::Second => &["seconds", "second", "secs", "sec", "s"],
+            Unit::Millisecond => &[
+                "milliseconds",
+                "millisecond",
+                "millisecs",
+                "millisec",
+                "msecs",
+                "msec",
+                "ms",
+            ],
+            Unit::Microsecond => &["microseconds", "microsecond", "microsecs", "microsec"],
+            Unit::Nanosecond => &["nanoseconds", "nanosecond", "nanosecs", "nanosec", "ns"],
+        }
+    }
+    /// Returns the name of the unit as written in the units, e.g. `hours`
+    pub fn cf_name(&self) -> &'static str {
+        self.aliases()[0]
+    }
+    /// Returns `true` for months and years, whose actual length depends on the calendar and the
+    /// date while [Unit::to_duration] uses the fixed length of the udunits definitions
+    pub fn is_calendar_dependent(&self) -> bool {
+        matches!(self, Unit::Year | Unit::Month)
+    }
+    /// Returns `true` for the units shorter than a second
+    pub fn is_subsecond(&self) -> bool {
+        matches!(
+            self,
+            Unit::Millisecond | Unit::Microsecond | Unit::Nanosecond
+        )
+    }
+    /// Returns the duration of one unit. Months and years have the fixed length of the
+    /// udunits definitions, see [CFDuration::to_approximate_seconds]
     pub fn to_duration(&self, calendar: Calendar) -> CFDuration {
         match self {
-            Unit::Year => CFDuration::from_years(1, calendar),
-            Unit::Month => CFDuration::from_months(1, calendar),
+            Unit::Year => CFDuration::from_years(1, calendar).to_approximate_seconds(),
+            Unit::Month => CFDuration::from_months(1, calendar).to_approximate_seconds(),
+            Unit::Week => CFDuration::from_weeks(1, calendar),
             Unit::Day => CFDuration::from_days(1, calendar),
             Unit::Hour => CFDuration::from_hours(1, calendar),
             Unit::Minute => CFDuration::from_minutes(1, calendar),
@@ -30,64 +107,183 @@ impl Unit {
             Unit::Nanosecond => CFDuration::from_nanoseconds(1, calendar),
         }
     }
+    /// Returns the duration of one increment of `multiplier` units, e.g. 3 hours for
+    /// `3 hours since ...`, or `crate::errors::Error::InvalidDuration` if it overflows
+    pub fn to_duration_with_multiplier(
+        &self,
+        calendar: Calendar,
+        multiplier: i64,
+    ) -> crate::errors::Result<CFDuration> {
+        self.to_duration(calendar)
+            .checked_mul(multiplier)
+            .ok_or_else(|| {
+                crate::errors::Error::InvalidDuration(format!(
+                    "Overflow when multiplying one {} unit by {multiplier}",
+                    self.cf_name()
+                ))
+            })
+    }
 }
+/// Unit of the non CF absolute time axis written by some tools such as cdo, e.g. `day as %Y%m%d.%f`
+/// where the values are the dates written as numbers
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum AbsoluteUnit {
+    /// `day as %Y%m%d.%f` where the fraction is the fraction of the day
+    Day,
+    /// `month as %Y%m.%f` where the fraction is the fraction of the month
+    Month,
+}
+
+/// Parse the absolute time units `day as %Y%m%d.%f` and `month as %Y%m.%f`.
+/// The fraction part of the format is optional
+pub fn parse_absolute_time(unit: &str) -> crate::errors::Result<AbsoluteUnit> {
+    let matches: Vec<&str> = unit.split_whitespace().collect();
+    match matches.as_slice() {
+        ["day" | "days", "as", "%Y%m%d" | "%Y%m%d.%f"] => Ok(AbsoluteUnit::Day),
+        ["month" | "months", "as", "%Y%m" | "%Y%m.%f"] => Ok(AbsoluteUnit::Month),
+        _ => Err(crate::errors::Error::UnitParserError(
+            format!("Invalid absolute time units '{unit}'").to_string(),
+        )),
+    }
+}
+
 #[derive(Debug)]
 pub struct ParsedDatetime {
     pub ymd: (i64, u8, u8),
     pub hms: Option<(u8, u8, f32)>,
-    pub tz: Option<(i8, u8)>,
+    pub tz: Option<Tz>,
+    /// Fraction of seconds in nanoseconds, kept exactly from the units
     pub nanosecond: Option<i64>,
 }
 #[derive(Debug)]
 pub struct ParsedCFTime {
     pub unit: Unit,
+    /// Number of units of one increment e.g. 3 for "3 hours since 2000-01-01". Default to 1
+    pub multiplier: i64,
     pub datetime: ParsedDatetime,
 }
-pub fn parse_cf_time(unit: &str) -> Result<ParsedCFTime, crate::errors::Error> {
+/// Parse CF time units such as `days since 2000-01-01 00:00:00`
+pub fn parse_cf_time(unit: &str) -> crate::errors::Result<ParsedCFTime> {
+    trace_span!(TRACE, "parse_cf_time", units = unit);
     let mut matches: Vec<&str> = unit.split(' ').collect();
     // Remove empty strings
     matches.retain(|&s| !s.trim().is_empty());
+    parse_tokens(unit, &matches, &mut 0)
+}
+
+/// Parse the units of a lead time coordinate such as `hours` or
+/// `3 hours since forecast_reference_time`, where the origin is the reference time of each forecast.
+///
+/// # Returns
+///
+/// A Result containing the unit and the multiplier or an error of type
+/// `crate::errors::Error::UnitParserError` if the units are not valid
+pub fn parse_lead_time_units(units: &str) -> crate::errors::Result<(Unit, i64)> {
+    trace_span!(TRACE, "parse_lead_time_units", units = units);
+    let invalid = || {
+        crate::errors::Error::UnitParserError(format!(
+            "Invalid lead time units '{units}', expected e.g. 'hours' or 'hours since forecast_reference_time'"
+        ))
+    };
+    let tokens: Vec<&str> = units.split_whitespace().collect();
+    let (multiplier, tokens) = match tokens.first().map(|s| s.parse::<i64>()) {
+        Some(Ok(multiplier)) if multiplier > 0 => (multiplier, &tokens[1..]),
+        Some(Ok(_)) => return Err(invalid()),
+        _ => (1, tokens.as_slice()),
+    };
+    let unit = match tokens {
+        [unit] => unit,
+        [unit, since, "forecast_reference_time"] if since.eq_ignore_ascii_case("since") => unit,
+        _ => return Err(invalid()),
+    };
+    let unit = parse_unit(unit).ok_or_else(invalid)?;
+    Ok((unit, multiplier))
+}
+
+/// Parse the duration unit e.g. `days` in `days since 2000-01-01`
+fn parse_unit(unit: &str) -> Option<Unit> {
+    UNITS
+        .into_iter()
+        .find(|candidate| candidate.aliases().contains(&unit))
+}
+
+impl core::fmt::Display for Unit {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str(self.cf_name())
+    }
+}
+
+impl core::str::FromStr for Unit {
+    type Err = crate::errors::Error;
+
+    fn from_str(s: &str) -> crate::errors::Result<Self> {
+        parse_unit(s).ok_or_else(|| {
+            crate::errors::Error::UnitParserError(format!("Invalid duration unit '{s}'"))
+        })
+    }
+}
+
+/// Parse the tokens of the units. `position` is set to the index of the token being parsed
+/// so the caller can point at the token in error
+fn parse_tokens(
+    unit: &str,
+    tokens: &[&str],
+    position: &mut usize,
+) -> crate::errors::Result<ParsedCFTime> {
+    // Optional multiplier before the unit e.g. "3 hours since 2000-01-01"
+    let (multiplier, matches) = match tokens.first().map(|s| s.parse::<i64>()) {
+        Some(Ok(multiplier)) => (multiplier, &tokens[1..]),
+        _ => (1, tokens),
+    };
+    let offset = tokens.len() - matches.len();
+    if multiplier <= 0 {
+        *position = 0;
+        return Err(crate::errors::Error::UnitParserError(
+            format!("Multiplier must be strictly positive in '{unit}'").to_string(),
+        ));
+    }
     if matches.len() < 3 {
+        *position = tokens.len();
         return Err(crate::errors::Error::UnitParserError(unit.to_string()));
     }
 
-    let duration_unit = match matches[0] {
-        "common_years" | "common_year" => Unit::Year,
-        "months" | "month" => Unit::Month,
-        "days" | "day" | "d" => Unit::Day,
-        "hours" | "hour" | "hrs" | "hr" | "h" => Unit::Hour,
-        "minutes" | "minute" | "mins" | "min" => Unit::Minute,
-        "seconds" | "second" | "secs" | "sec" | "s" => Unit::Second,
-        "milliseconds" | "millisecond" | "millisecs" | "millisec" | "msecs" | "msec" | "ms" => {
-            Unit::Millisecond
-        }
-        "microseconds" | "microsecond" | "microsecs" | "microsec" => Unit::Microsecond,
-        _ => {
+    *position = offset;
+    let duration_unit = match parse_unit(matches[0]) {
+        Some(duration_unit) => duration_unit,
+        None => {
             return Err(crate::errors::Error::UnitParserError(
                 format!("Invalid duration unit '{}' in '{unit}'", matches[0]).to_string(),
             ))
         }
     };
 
-    if matches[1] != "since" {
+    *position = offset + 1;
+    if !matches[1].eq_ignore_ascii_case("since") {
         return Err(crate::errors::Error::UnitParserError(
             format!("Expected 'since' found : '{}'", matches[1]).to_string(),
         ));
     }
 
-    let date: Vec<&str> = matches[2].split('-').collect();
+    *position = offset + 2;
+    // Negative years are prefixed with a minus sign e.g. -0100-01-01
+    let (year_sign, date) = match matches[2].strip_prefix('-') {
+        Some(date) => (-1, date),
+        None => (1, matches[2]),
+    };
+    let date: Vec<&str> = date.split('-').collect();
     if date.len() != 3 {
         return Err(crate::errors::Error::UnitParserError(
             format!("Invalid date: {unit}").to_string(),
         ));
     }
-    let year = date[0].parse::<i64>()?;
+    let year = year_sign * date[0].parse::<i64>()?;
     let month = date[1].parse::<u8>()?;
     let day = date[2].parse::<u8>()?;
 
     if matches.len() <= 3 {
         return Ok(ParsedCFTime {
             unit: duration_unit,
+            multiplier,
             datetime: ParsedDatetime {
                 ymd: (year, month, day),
                 hms: None,
@@ -97,6 +293,7 @@ pub fn parse_cf_time(unit: &str) -> Result<ParsedCFTime, crate::errors::Error> {
         });
     }
 
+    *position = offset + 3;
     let time: Vec<&str> = matches[3].split(':').collect();
     if time.len() != 3 {
         return Err(crate::errors::Error::UnitParserError(
@@ -106,45 +303,537 @@ pub fn parse_cf_time(unit: &str) -> Result<ParsedCFTime, crate::errors::Error> {
     let hour = time[0].parse::<u8>()?;
     let minute = time[1].parse::<u8>()?;
     let second = time[2].parse::<f32>()?;
+    // Keep the fraction of seconds exactly as f32 can not represent nanoseconds
+    let nanosecond = match time[2].split_once('.') {
+        Some((_, fraction)) if !fraction.is_empty() => {
+            let digits: String = fraction
+                .chars()
+                .chain("000000000".chars())
+                .take(9)
+                .collect();
+            Some(digits.parse::<i64>()?)
+        }
+        _ => None,
+    };
 
     if matches.len() <= 4 {
         return Ok(ParsedCFTime {
             unit: duration_unit,
+            multiplier,
             datetime: ParsedDatetime {
                 ymd: (year, month, day),
                 hms: Some((hour, minute, second)),
                 tz: None,
-                nanosecond: None,
+                nanosecond,
             },
         });
     }
 
-    let tz: Vec<&str> = matches[4].split(':').collect();
-    if tz.len() > 2 || tz.len() <= 0 {
-        return Err(crate::errors::Error::UnitParserError(
-            format!("Invalid time '{}' in '{unit}'", matches[4]).to_string(),
-        ));
-    }
-    let mut tzhour = 0;
-    let mut tzminute = 0;
-    if tz.len() == 1 {
-        tzhour = tz[0].parse::<i8>()?;
-        tzminute = 0;
-    } else if tz.len() == 2 {
-        tzhour = tz[0].parse::<i8>()?;
-        tzminute = tz[1].parse::<u8>()?;
-    }
+    *position = offset + 4;
+    let tz = matches[4].parse::<Tz>()?;
     Ok(ParsedCFTime {
         unit: duration_unit,
+        multiplier,
         datetime: ParsedDatetime {
             ymd: (year, month, day),
             hms: Some((hour, minute, second)),
-            tz: Some((tzhour, tzminute)),
-            nanosecond: None,
+            tz: Some(tz),
+            nanosecond,
         },
     })
 }
 
+/// Format CF time units such as `hours since 1900-01-01 00:00:00` from a unit and an origin.
+///
+/// The timezone of the origin is written only if it is not UTC. The output is parsed back
+/// by [parse_cf_time] into the same unit and origin.
+pub fn format_units(unit: &Unit, origin: &CFDatetime) -> String {
+    let timezone = origin.timezone();
+    if timezone.offset_minutes() == 0 {
+        format!("{} since {}", unit.cf_name(), origin)
+    } else {
+        format!("{} since {} {}", unit.cf_name(), origin, timezone)
+    }
+}
+
+/// Mode of [parse_cf_time_with_mode]
+#[derive(Debug, Default, PartialEq, Clone, Copy)]
+pub enum ParseMode {
+    /// Fails on the first token that does not follow `[<multiplier>] <unit> since <date> [<time> [<tz>]]`
+    /// separated by single spaces.
+    ///
+    /// It is stricter than [parse_cf_time], used by the decoders and encoders, which accepts
+    /// repeated spaces and ignores the tokens after the timezone
+    #[default]
+    Strict,
+    /// Applies the following repairs and reports them as diagnostics :
+    /// - Whitespaces are collapsed into single spaces
+    /// - The unit is lowercased and repeated trailing `s` are removed e.g. `Dayss`
+    /// - `after` and the udunits `@` e.g. `seconds @ 2000-01-01` are accepted as synonyms of
+    ///   `since`, spaces are inserted around a `@` glued to the unit or the date
+    /// - A `T` separator between the date and the time is replaced by a space
+    /// - A date without day such as `2000-01`, or without month such as `2000`, is completed
+    ///   with the first month and day e.g. `2000-01-01`
+    /// - A compact date such as `20000101` is read as `2000-01-01`
+    /// - A `Z` or `UTC` suffix on the time is moved to the timezone
+    /// - A timezone directly following the date is preceded by `00:00:00`
+    /// - A time with fractional hours or minutes such as `06.5` or `06:30.5`, or without seconds
+    ///   such as `06:30`, is folded into `HH:MM:SS` e.g. `06:30:00`
+    /// - `GMT` is replaced by `UTC`
+    /// - A date with a two-digit year such as `70-01-01` is interpreted with the
+    ///   [TwoDigitYears] policy of the [ParseOptions]
+    /// - Trailing tokens are ignored
+    ///
+    /// The decoders and encoders parse the units with [parse_cf_time], use
+    /// [crate::converter::CFConverter::new_with_options] to convert values with repaired units
+    Lenient,
+}
+
+/// Interpretation of a date written with a two-digit year such as `70-01-01` in
+/// [ParseMode::Lenient]
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum TwoDigitYears {
+    /// The year is kept as written e.g. `70-01-01` is the year 70, as in [ParseMode::Strict]
+    #[default]
+    Literal,
+    /// Years below the pivot are in the 2000s and the others in the 1900s e.g. with a pivot of
+    /// 50, `70-01-01` is 1970-01-01 and `05-01-01` is 2005-01-01
+    Pivot(u8),
+    /// The units are rejected as ambiguous
+    Reject,
+}
+
+/// Options of [parse_cf_time_with_options]
+#[derive(Debug, Default, PartialEq, Clone, Copy)]
+pub struct ParseOptions {
+    /// Whether the units are parsed strictly or repaired, [ParseMode::Strict] by default
+    pub mode: ParseMode,
+    /// Only used in [ParseMode::Lenient]
+    pub two_digit_years: TwoDigitYears,
+}
+
+impl ParseOptions {
+    /// Returns the options with the given parsing mode
+    pub fn with_mode(mut self, mode: ParseMode) -> Self {
+        self.mode = mode;
+        self
+    }
+    /// Returns the options with the given interpretation of the two-digit years, which only
+    /// applies with [ParseMode::Lenient]
+    pub fn with_two_digit_years(mut self, two_digit_years: TwoDigitYears) -> Self {
+        self.two_digit_years = two_digit_years;
+        self
+    }
+}
+
+/// Message of the units parser pointing at a byte range of the units
+#[derive(Debug, PartialEq, Clone)]
+pub struct Diagnostic {
+    /// Byte range in the original units
+    pub span: core::ops::Range<usize>,
+    pub message: String,
+}
+
+impl core::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(
+            f,
+            "{} at {}..{}",
+            self.message, self.span.start, self.span.end
+        )
+    }
+}
+
+/// Result of [parse_cf_time_with_mode]
+#[derive(Debug)]
+pub struct ParseReport {
+    pub parsed: ParsedCFTime,
+    /// Repairs applied in [ParseMode::Lenient], always empty in [ParseMode::Strict]
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+type Token = (core::ops::Range<usize>, String);
+
+/// Split the units on whitespaces and keep the byte range of each token
+fn tokenize(unit: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut start = None;
+    for (index, c) in unit.char_indices() {
+        match (c.is_whitespace(), start) {
+            (true, Some(token_start)) => {
+                tokens.push((token_start..index, unit[token_start..index].to_string()));
+                start = None;
+            }
+            (false, None) => start = Some(index),
+            _ => {}
+        }
+    }
+    if let Some(token_start) = start {
+        tokens.push((token_start..unit.len(), unit[token_start..].to_string()));
+    }
+    tokens
+}
+
+/// Returns the byte ranges of the whitespaces that are not a single space between two tokens
+fn irregular_whitespaces(unit: &str, tokens: &[Token]) -> Vec<core::ops::Range<usize>> {
+    let mut bounds = vec![0];
+    for (span, _) in tokens {
+        bounds.push(span.start);
+        bounds.push(span.end);
+    }
+    bounds.push(unit.len());
+    bounds
+        .chunks(2)
+        .enumerate()
+        .filter(|(index, gap)| {
+            let whitespace = &unit[gap[0]..gap[1]];
+            let is_edge = *index == 0 || *index == tokens.len();
+            if is_edge {
+                !whitespace.is_empty()
+            } else {
+                whitespace != " "
+            }
+        })
+        .map(|(_, gap)| gap[0]..gap[1])
+        .collect()
+}
+
+/// Split a time with a timezone suffix e.g. `00:00:00Z`
+fn split_time_suffix(time: &str) -> Option<(String, String)> {
+    let upper = time.to_uppercase();
+    ["Z", "UTC", "GMT"].iter().find_map(|suffix| {
+        let time_len = time.len().checked_sub(suffix.len())?;
+        if upper.ends_with(suffix) && time_len > 0 && time.is_char_boundary(time_len) {
+            Some((time[..time_len].to_string(), suffix.to_string()))
+        } else {
+            None
+        }
+    })
+}
+
+/// Fold a time with fractional hours or minutes, or without seconds, into `HH:MM:SS[.fffffffff]`
+/// e.g. `06.5` into `06:30:00`. Only the last component can have a fraction.
+fn fold_time(time: &str) -> Option<String> {
+    let components: Vec<&str> = time.split(':').collect();
+    let is_partial = components.len() < 3;
+    let has_fraction = components[..components.len().min(2)]
+        .iter()
+        .any(|component| component.contains('.'));
+    if components.len() > 3 || !(is_partial || has_fraction) {
+        return None;
+    }
+    // Nanoseconds of one hour, one minute and one second
+    let units: [i128; 3] = [3_600_000_000_000, 60_000_000_000, 1_000_000_000];
+    let mut nanoseconds: i128 = 0;
+    for (index, component) in components.iter().enumerate() {
+        let (whole, fraction) = match component.split_once('.') {
+            Some((_, "")) => return None,
+            Some(parts) => parts,
+            None => (*component, ""),
+        };
+        let is_last = index == components.len() - 1;
+        if whole.is_empty()
+            || !whole
+                .chars()
+                .chain(fraction.chars())
+                .all(|c| c.is_ascii_digit())
+            || whole.len() > 2
+            || (!fraction.is_empty() && !is_last)
+        {
+            return None;
+        }
+        nanoseconds += whole.parse::<i128>().ok()? * units[index];
+        // The fraction is truncated to the nanosecond
+        let digits = &fraction[..fraction.len().min(18)];
+        if !digits.is_empty() {
+            nanoseconds +=
+                digits.parse::<i128>().ok()? * units[index] / 10_i128.pow(digits.len() as u32);
+        }
+    }
+    if nanoseconds >= 24 * units[0] {
+        return None;
+    }
+    let seconds = nanoseconds / units[2];
+    let fraction = nanoseconds % units[2];
+    let mut folded = format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3_600,
+        seconds / 60 % 60,
+        seconds % 60
+    );
+    if fraction != 0 {
+        let digits = format!("{fraction:09}");
+        folded.push('.');
+        folded.push_str(digits.trim_end_matches('0'));
+    }
+    Some(folded)
+}
+
+/// Complete a date without day or month e.g. `2000-01` into `2000-01-01`, or split a compact
+/// date e.g. `20000101` into `2000-01-01`, with the assumption made. A minus sign before the
+/// year is kept.
+fn complete_date(date: &str) -> Option<(String, &'static str)> {
+    let (sign, unsigned) = match date.strip_prefix('-') {
+        Some(unsigned) => ("-", unsigned),
+        None => ("", date),
+    };
+    let components: Vec<&str> = unsigned.split('-').collect();
+    if components
+        .iter()
+        .any(|component| component.is_empty() || !component.chars().all(|c| c.is_ascii_digit()))
+    {
+        return None;
+    }
+    match components.as_slice() {
+        [year] if year.len() <= 4 => Some((format!("{date}-01-01"), "Assumed month 01 and day 01")),
+        [compact] if compact.len() == 8 => {
+            let (year, month_day) = compact.split_at(4);
+            let (month, day) = month_day.split_at(2);
+            Some((
+                format!("{sign}{year}-{month}-{day}"),
+                "Read the compact date YYYYMMDD as YYYY-MM-DD",
+            ))
+        }
+        [_, _] => Some((format!("{date}-01"), "Assumed day 01")),
+        _ => None,
+    }
+}
+
+/// Split a date such as `70-01-01` into its two-digit year and the rest of the date
+fn split_two_digit_year(date: &str) -> Option<(u8, &str)> {
+    let (year, rest) = date.split_once('-')?;
+    if year.len() != 2 || !year.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    Some((year.parse().ok()?, rest))
+}
+
+fn is_timezone_name(token: &str) -> bool {
+    ["Z", "UTC", "GMT"]
+        .iter()
+        .any(|name| token.eq_ignore_ascii_case(name))
+}
+
+/// Apply the repairs of [ParseMode::Lenient]
+fn repair_tokens(
+    unit: &str,
+    tokens: Vec<Token>,
+    two_digit_years: TwoDigitYears,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> crate::errors::Result<Vec<Token>> {
+    for span in irregular_whitespaces(unit, &tokens) {
+        diagnostics.push(Diagnostic {
+            span,
+            message: "Collapsed whitespaces into a single space".to_string(),
+        });
+    }
+    let mut tokens = tokens;
+    // The udunits shift operator glued to its operands e.g. seconds@2000-01-01
+    if let Some(index) = tokens
+        .iter()
+        .position(|(_, token)| token.contains('@') && token != "@")
+    {
+        let (span, token) = tokens.remove(index);
+        diagnostics.push(Diagnostic {
+            span: span.clone(),
+            message: format!("Inserted spaces around '@' in '{token}'"),
+        });
+        let (before, after) = token.split_once('@').unwrap_or((&token, ""));
+        let at = span.start + before.len();
+        let parts = [
+            (span.start..at, before),
+            (at..at + 1, "@"),
+            (at + 1..span.end, after),
+        ];
+        for (offset, (part_span, part)) in parts
+            .into_iter()
+            .filter(|(_, part)| !part.is_empty())
+            .enumerate()
+        {
+            tokens.insert(index + offset, (part_span, part.to_string()));
+        }
+    }
+    let replace =
+        |tokens: &mut Vec<Token>, index: usize, new: String, diagnostics: &mut Vec<Diagnostic>| {
+            let (span, old) = &tokens[index];
+            diagnostics.push(Diagnostic {
+                span: span.clone(),
+                message: format!("Replaced '{old}' by '{new}'"),
+            });
+            tokens[index].1 = new;
+        };
+    let unit_index = match tokens.first() {
+        Some((_, token)) if token.parse::<i64>().is_ok() => 1,
+        _ => 0,
+    };
+    // Unit
+    if let Some((_, token)) = tokens.get(unit_index) {
+        if parse_unit(token).is_none() {
+            let mut candidate = token.to_lowercase();
+            while parse_unit(&candidate).is_none() && candidate.ends_with("ss") {
+                candidate.pop();
+            }
+            if parse_unit(&candidate).is_some() {
+                replace(&mut tokens, unit_index, candidate, diagnostics);
+            }
+        }
+    }
+    // After used instead of since e.g. in files converted from GRIB, or the udunits shift
+    // operator @ e.g. seconds @ 2000-01-01
+    if let Some((_, token)) = tokens.get(unit_index + 1) {
+        if token.eq_ignore_ascii_case("after") || token == "@" {
+            replace(
+                &mut tokens,
+                unit_index + 1,
+                "since".to_string(),
+                diagnostics,
+            );
+        }
+    }
+    // Date and time separated by a T e.g. 2000-01-01T00:00:00
+    if let Some((span, token)) = tokens.get(unit_index + 2).cloned() {
+        if let Some((date, time)) = token.split_once('T') {
+            if !time.is_empty() {
+                replace(&mut tokens, unit_index + 2, date.to_string(), diagnostics);
+                tokens.insert(unit_index + 3, (span, time.to_string()));
+            }
+        }
+    }
+    // Date without day or month e.g. 2000-01
+    if let Some((span, token)) = tokens.get(unit_index + 2).cloned() {
+        if let Some((date, assumption)) = complete_date(&token) {
+            diagnostics.push(Diagnostic {
+                span,
+                message: format!("{assumption} in '{token}'"),
+            });
+            tokens[unit_index + 2].1 = date;
+        }
+    }
+    // Two-digit year e.g. 70-01-01 in legacy files
+    if let Some((span, token)) = tokens.get(unit_index + 2).cloned() {
+        if let Some((year, rest)) = split_two_digit_year(&token) {
+            match two_digit_years {
+                TwoDigitYears::Literal => {}
+                TwoDigitYears::Pivot(pivot) => {
+                    let century = if year < pivot { 2000 } else { 1900 };
+                    let full_year = century + i64::from(year);
+                    diagnostics.push(Diagnostic {
+                        span,
+                        message: format!(
+                            "Interpreted the two-digit year '{year:02}' as {full_year} with the pivot {pivot}"
+                        ),
+                    });
+                    tokens[unit_index + 2].1 = format!("{full_year}-{rest}");
+                }
+                TwoDigitYears::Reject => {
+                    return Err(crate::errors::Error::UnitParserDiagnostic(Diagnostic {
+                        span,
+                        message: format!("Ambiguous two-digit year '{year:02}'"),
+                    }))
+                }
+            }
+        }
+    }
+    // Time followed by a timezone suffix or a timezone without time
+    if let Some((span, token)) = tokens.get(unit_index + 3).cloned() {
+        if is_timezone_name(&token) {
+            diagnostics.push(Diagnostic {
+                span: span.clone(),
+                message: "Assumed 00:00:00 before the timezone".to_string(),
+            });
+            tokens.insert(unit_index + 3, (span, "00:00:00".to_string()));
+        } else if let Some((time, tz)) = split_time_suffix(&token) {
+            replace(&mut tokens, unit_index + 3, time, diagnostics);
+            tokens.insert(unit_index + 4, (span, tz));
+        }
+    }
+    // Fractional hours or minutes e.g. 06.5 for 06:30:00
+    if let Some((_, token)) = tokens.get(unit_index + 3) {
+        if let Some(time) = fold_time(token) {
+            replace(&mut tokens, unit_index + 3, time, diagnostics);
+        }
+    }
+    // Timezone
+    if let Some((_, token)) = tokens.get(unit_index + 4) {
+        if token.eq_ignore_ascii_case("GMT") {
+            replace(&mut tokens, unit_index + 4, "UTC".to_string(), diagnostics);
+        }
+    }
+    Ok(tokens)
+}
+
+/// Parse CF time units with the given [ParseMode].
+///
+/// # Returns
+///
+/// A Result containing a [ParseReport] with the repairs applied in [ParseMode::Lenient], or an
+/// error of type `crate::errors::Error::UnitParserDiagnostic` pointing at the token in error
+pub fn parse_cf_time_with_mode(unit: &str, mode: ParseMode) -> crate::errors::Result<ParseReport> {
+    parse_cf_time_with_options(unit, ParseOptions::default().with_mode(mode))
+}
+
+/// Parse CF time units with the given [ParseOptions].
+///
+/// # Returns
+///
+/// A Result containing a [ParseReport] with the repairs applied in [ParseMode::Lenient], including
+/// the interpretation of a two-digit year, or an error of type
+/// `crate::errors::Error::UnitParserDiagnostic` pointing at the token in error
+pub fn parse_cf_time_with_options(
+    unit: &str,
+    options: ParseOptions,
+) -> crate::errors::Result<ParseReport> {
+    let mode = options.mode;
+    trace_span!(TRACE, "parse_cf_time_with_options", units = unit, mode = ?mode);
+    let mut diagnostics = Vec::new();
+    let tokens = tokenize(unit);
+    let tokens = match mode {
+        ParseMode::Strict => {
+            if let Some(span) = irregular_whitespaces(unit, &tokens).into_iter().next() {
+                return Err(crate::errors::Error::UnitParserDiagnostic(Diagnostic {
+                    span,
+                    message: "Expected a single space between tokens".to_string(),
+                }));
+            }
+            tokens
+        }
+        ParseMode::Lenient => {
+            repair_tokens(unit, tokens, options.two_digit_years, &mut diagnostics)?
+        }
+    };
+    let values: Vec<&str> = tokens.iter().map(|(_, token)| token.as_str()).collect();
+    let mut position = 0;
+    let parsed = parse_tokens(unit, &values, &mut position).map_err(|e| {
+        let span = match tokens.get(position) {
+            Some((span, _)) => span.clone(),
+            None => unit.len()..unit.len(),
+        };
+        crate::errors::Error::UnitParserDiagnostic(Diagnostic {
+            span,
+            message: e.to_string(),
+        })
+    })?;
+    if let Some((first, _)) = tokens.get(position + 1) {
+        let span = first.start..unit.len();
+        match mode {
+            ParseMode::Strict => {
+                return Err(crate::errors::Error::UnitParserDiagnostic(Diagnostic {
+                    span,
+                    message: "Unexpected trailing tokens".to_string(),
+                }))
+            }
+            ParseMode::Lenient => diagnostics.push(Diagnostic {
+                span,
+                message: "Ignored trailing tokens".to_string(),
+            }),
+        }
+    }
+    Ok(ParseReport {
+        parsed,
+        diagnostics,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -155,7 +844,11 @@ mod tests {
         let units = vec![
             ("common_years since 2023-01-01", Unit::Year),
             ("months since 2023-01-01", Unit::Month),
+            ("weeks since 2023-01-01", Unit::Week),
+            ("wk since 2023-01-01", Unit::Week),
             ("day since 2023-01-01", Unit::Day),
+            ("days SINCE 2023-01-01", Unit::Day),
+            ("hours Since 2023-01-01", Unit::Hour),
             // Add more valid units here
         ];
 
@@ -169,6 +862,52 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_lead_time_units() {
+        assert_eq!(parse_lead_time_units("hours").unwrap(), (Unit::Hour, 1));
+        assert_eq!(
+            parse_lead_time_units("3 hours since forecast_reference_time").unwrap(),
+            (Unit::Hour, 3)
+        );
+        assert_eq!(parse_lead_time_units("days").unwrap(), (Unit::Day, 1));
+        assert!(parse_lead_time_units("hours since 2000-01-01").is_err());
+        assert!(parse_lead_time_units("0 hours").is_err());
+        assert!(parse_lead_time_units("fortnights").is_err());
+    }
+
+    #[test]
+    fn test_unit_predicates() {
+        assert!(Unit::Month.is_calendar_dependent());
+        assert!(Unit::Year.is_calendar_dependent());
+        assert!(!Unit::Week.is_calendar_dependent());
+        assert!(Unit::Nanosecond.is_subsecond());
+        assert!(!Unit::Second.is_subsecond());
+    }
+
+    #[test]
+    fn test_unit_display_and_from_str() {
+        for unit in UNITS {
+            assert_eq!(unit.to_string(), unit.cf_name());
+            for alias in unit.aliases() {
+                assert_eq!(alias.parse::<Unit>().unwrap(), unit);
+                // The same aliases as in the units
+                let units = format!("{alias} since 2000-01-01");
+                assert_eq!(parse_cf_time(&units).unwrap().unit, unit);
+            }
+            assert_eq!(
+                parse_cf_time(&format!("{unit} since 2000-01-01"))
+                    .unwrap()
+                    .unit,
+                unit
+            );
+        }
+        assert_eq!(Unit::Year.to_string(), "common_years");
+        assert!("fortnights"
+            .parse::<Unit>()
+            .is_err_and(|e| e.is_parse_error()));
+        assert!("".parse::<Unit>().is_err());
+    }
+
     #[test]
     fn test_valid_date_time_units() {
         // Test valid date and time units with different combinations
@@ -177,12 +916,13 @@ mod tests {
             (
                 "seconds since 1992-10-8 15:15:42.5 -6:00",
                 ParsedCFTime {
+                    multiplier: 1,
                     unit: Unit::Second,
                     datetime: ParsedDatetime {
                         ymd: (1992, 10, 8),
                         hms: Some((15, 15, 42.5)),
-                        tz: Some((-6, 0)),
-                        nanosecond: None,
+                        tz: Some(Tz::new(-6, 0).unwrap()),
+                        nanosecond: Some(500_000_000),
                     },
                 },
             ),
@@ -190,6 +930,7 @@ mod tests {
             (
                 "seconds since 1992-10-08",
                 ParsedCFTime {
+                    multiplier: 1,
                     unit: Unit::Second,
                     datetime: ParsedDatetime {
                         ymd: (1992, 10, 8),
@@ -202,6 +943,7 @@ mod tests {
             (
                 "minutes since 2000-01-01",
                 ParsedCFTime {
+                    multiplier: 1,
                     unit: Unit::Minute,
                     datetime: ParsedDatetime {
                         ymd: (2000, 1, 1),
@@ -214,6 +956,7 @@ mod tests {
             (
                 "hour since 1985-12-31",
                 ParsedCFTime {
+                    multiplier: 1,
                     unit: Unit::Hour,
                     datetime: ParsedDatetime {
                         ymd: (1985, 12, 31),
@@ -223,10 +966,52 @@ mod tests {
                     },
                 },
             ),
+            // Negative and expanded years
+            (
+                "days since -0100-01-01",
+                ParsedCFTime {
+                    multiplier: 1,
+                    unit: Unit::Day,
+                    datetime: ParsedDatetime {
+                        ymd: (-100, 1, 1),
+                        hms: None,
+                        tz: None,
+                        nanosecond: None,
+                    },
+                },
+            ),
+            (
+                "days since +10000-01-01",
+                ParsedCFTime {
+                    multiplier: 1,
+                    unit: Unit::Day,
+                    datetime: ParsedDatetime {
+                        ymd: (10000, 1, 1),
+                        hms: None,
+                        tz: None,
+                        nanosecond: None,
+                    },
+                },
+            ),
+            // Multiplier before the unit
+            (
+                "3 hours since 2000-01-01",
+                ParsedCFTime {
+                    multiplier: 3,
+                    unit: Unit::Hour,
+                    datetime: ParsedDatetime {
+                        ymd: (2000, 1, 1),
+                        hms: None,
+                        tz: None,
+                        nanosecond: None,
+                    },
+                },
+            ),
             // Date and time, no timezone
             (
                 "seconds since 2022-11-30 10:15:20",
                 ParsedCFTime {
+                    multiplier: 1,
                     unit: Unit::Second,
                     datetime: ParsedDatetime {
                         ymd: (2022, 11, 30),
@@ -239,6 +1024,7 @@ mod tests {
             (
                 "minutes since 2010-05-15 05:30:00",
                 ParsedCFTime {
+                    multiplier: 1,
                     unit: Unit::Minute,
                     datetime: ParsedDatetime {
                         ymd: (2010, 5, 15),
@@ -251,6 +1037,7 @@ mod tests {
             (
                 "hour since 1999-03-20 12:00:01",
                 ParsedCFTime {
+                    multiplier: 1,
                     unit: Unit::Hour,
                     datetime: ParsedDatetime {
                         ymd: (1999, 3, 20),
@@ -264,11 +1051,12 @@ mod tests {
             (
                 "seconds since 2015-07-04 16:45:30 +02:30",
                 ParsedCFTime {
+                    multiplier: 1,
                     unit: Unit::Second,
                     datetime: ParsedDatetime {
                         ymd: (2015, 7, 4),
                         hms: Some((16, 45, 30.0)),
-                        tz: Some((2, 30)),
+                        tz: Some(Tz::new(2, 30).unwrap()),
                         nanosecond: None,
                     },
                 },
@@ -276,11 +1064,12 @@ mod tests {
             (
                 "minutes since 2023-12-25 08:00:00 -05:00",
                 ParsedCFTime {
+                    multiplier: 1,
                     unit: Unit::Minute,
                     datetime: ParsedDatetime {
                         ymd: (2023, 12, 25),
                         hms: Some((8, 0, 0.0)),
-                        tz: Some((-5, 0)),
+                        tz: Some(Tz::new(-5, 0).unwrap()),
                         nanosecond: None,
                     },
                 },
@@ -288,11 +1077,51 @@ mod tests {
             (
                 "hour since 2018-09-10 00:00:00 -03:30",
                 ParsedCFTime {
+                    multiplier: 1,
+                    unit: Unit::Hour,
+                    datetime: ParsedDatetime {
+                        ymd: (2018, 9, 10),
+                        hms: Some((0, 0, 0.0)),
+                        tz: Some(Tz::new(-3, 30).unwrap()),
+                        nanosecond: None,
+                    },
+                },
+            ),
+            (
+                "hours since 2018-09-10 00:00:00 -0:30",
+                ParsedCFTime {
+                    multiplier: 1,
+                    unit: Unit::Hour,
+                    datetime: ParsedDatetime {
+                        ymd: (2018, 9, 10),
+                        hms: Some((0, 0, 0.0)),
+                        tz: Some("-00:30".parse::<Tz>().unwrap()),
+                        nanosecond: None,
+                    },
+                },
+            ),
+            (
+                "hours since 2018-09-10 00:00:00 -0600",
+                ParsedCFTime {
+                    multiplier: 1,
+                    unit: Unit::Hour,
+                    datetime: ParsedDatetime {
+                        ymd: (2018, 9, 10),
+                        hms: Some((0, 0, 0.0)),
+                        tz: Some("-06:00".parse::<Tz>().unwrap()),
+                        nanosecond: None,
+                    },
+                },
+            ),
+            (
+                "hours since 2018-09-10 00:00:00 Z",
+                ParsedCFTime {
+                    multiplier: 1,
                     unit: Unit::Hour,
                     datetime: ParsedDatetime {
                         ymd: (2018, 9, 10),
                         hms: Some((0, 0, 0.0)),
-                        tz: Some((-3, 30)),
+                        tz: Some("+00:00".parse::<Tz>().unwrap()),
                         nanosecond: None,
                     },
                 },
@@ -302,6 +1131,7 @@ mod tests {
         for (input, expected_unit) in units {
             let result = parse_cf_time(input).unwrap();
             assert!(result.unit == expected_unit.unit);
+            assert_eq!(result.multiplier, expected_unit.multiplier);
             assert_eq!(result.datetime.ymd, expected_unit.datetime.ymd);
             assert_eq!(result.datetime.hms, expected_unit.datetime.hms);
             assert_eq!(result.datetime.tz, expected_unit.datetime.tz);
@@ -316,10 +1146,13 @@ mod tests {
         // Test valid date and time units with different combinations
         let units = vec![
             "seconds since 2019-06-15 -07:00",
-            "nanoseconds since 2020-01-01 9876543210", // nanoseconds not permitted
+            "nanoseconds since 2020-01-01 9876543210", // Invalid time
             "invalid_unit since 2023-01-01",           // Invalid unit
             "hou since 2023-01-01",                    // Missing 'rs' in 'hours'
             "minutes 2023-01-01",                      // Missing 'since'
+            "0 hours since 2023-01-01",                // Multiplier must be positive
+            "-3 hours since 2023-01-01",               // Multiplier must be positive
+            "3 since 2023-01-01",                      // Missing unit
         ];
 
         for input in units {
@@ -330,5 +1163,357 @@ mod tests {
             ))
         }
     }
+    #[test]
+    fn test_not_valid_timezones() {
+        let units = vec![
+            "hours since 2018-09-10 00:00:00 -0:60",
+            "hours since 2018-09-10 00:00:00 +24:00",
+            "hours since 2018-09-10 00:00:00 05:30:00",
+        ];
+        for input in units {
+            let result = parse_cf_time(input);
+            assert!(matches!(
+                result.err().unwrap(),
+                crate::errors::Error::InvalidTz(_)
+            ))
+        }
+    }
+    #[test]
+    fn test_parse_absolute_time() {
+        let units = vec![
+            ("day as %Y%m%d.%f", AbsoluteUnit::Day),
+            ("days as  %Y%m%d", AbsoluteUnit::Day),
+            ("month as %Y%m", AbsoluteUnit::Month),
+            ("month as %Y%m.%f", AbsoluteUnit::Month),
+        ];
+        for (input, expected) in units {
+            assert_eq!(parse_absolute_time(input).unwrap(), expected);
+        }
+        for input in ["days since 2000-01-01", "day as %Y-%m-%d", "year as %Y"] {
+            assert!(parse_absolute_time(input).is_err());
+        }
+    }
+    #[test]
+    fn test_parse_mode_strict() {
+        let units = "days since 2000-01-01 00:00:00";
+        let report = parse_cf_time_with_mode(units, ParseMode::Strict).unwrap();
+        assert_eq!(report.parsed.datetime.hms, Some((0, 0, 0.0)));
+        assert!(report.diagnostics.is_empty());
+        let errors = vec![
+            ("days  since 2000-01-01", 4..6),
+            (" days since 2000-01-01", 0..1),
+            ("dayss since 2000-01-01", 0..5),
+            ("days since 2000-13-01x", 11..22),
+            ("days since 2000-01-01 00:00", 22..27),
+            ("days since 2000-01-01 00:00:00 +01:00 UTC", 38..41),
+            ("days since", 10..10),
+        ];
+        for (units, span) in errors {
+            match parse_cf_time_with_mode(units, ParseMode::Strict) {
+                Err(crate::errors::Error::UnitParserDiagnostic(diagnostic)) => {
+                    assert_eq!(diagnostic.span, span, "{units}")
+                }
+                other => panic!("Expected a diagnostic for '{units}' found {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_mode_lenient() {
+        let units = vec![
+            ("Dayss  since 2000-01-01", 2, None, None),
+            (
+                "days SINCE 2000-01-01T12:00:00",
+                1,
+                Some((12, 0, 0.0)),
+                None,
+            ),
+            (
+                "days since 2000-01-01 00:00:00Z",
+                1,
+                Some((0, 0, 0.0)),
+                Some(0),
+            ),
+            ("days since 2000-01-01 UTC", 1, Some((0, 0, 0.0)), Some(0)),
+            (
+                "days since 2000-01-01 00:00:00 GMT",
+                1,
+                Some((0, 0, 0.0)),
+                Some(0),
+            ),
+            (
+                "days since 2000-01-01 00:00:00 +01:00 UTC",
+                1,
+                Some((0, 0, 0.0)),
+                Some(60),
+            ),
+            ("days since 2000-01-01", 0, None, None),
+        ];
+        for (input, diagnostics, hms, tz) in units {
+            let report = parse_cf_time_with_mode(input, ParseMode::Lenient).unwrap();
+            assert_eq!(report.diagnostics.len(), diagnostics, "{input}");
+            assert_eq!(report.parsed.unit, Unit::Day);
+            assert_eq!(report.parsed.datetime.ymd, (2000, 1, 1));
+            assert_eq!(report.parsed.datetime.hms, hms);
+            assert_eq!(report.parsed.datetime.tz.map(|tz| tz.offset_minutes()), tz);
+        }
+        let report = parse_cf_time_with_mode("dayss since 2000-01-01", ParseMode::Lenient).unwrap();
+        assert_eq!(
+            report.diagnostics,
+            vec![Diagnostic {
+                span: 0..5,
+                message: "Replaced 'dayss' by 'days'".to_string()
+            }]
+        );
+        let report = parse_cf_time_with_mode("hours AFTER 2000-01-01", ParseMode::Lenient).unwrap();
+        assert_eq!(report.parsed.unit, Unit::Hour);
+        assert_eq!(report.diagnostics.len(), 1);
+        assert!(parse_cf_time_with_mode("hours after 2000-01-01", ParseMode::Strict).is_err());
+        assert!(parse_cf_time_with_mode("days before 2000-01-01", ParseMode::Lenient).is_err());
+    }
+    #[test]
+    fn test_parse_mode_lenient_at_separator() {
+        let units = [
+            ("seconds @ 2000-01-01", Unit::Second, 1, 1),
+            ("seconds @ 2000-01-01 06:30:00", Unit::Second, 1, 1),
+            ("3 hours @ 2000-01-01 06:30:00 +01:00", Unit::Hour, 3, 1),
+            ("days@2000-01-01", Unit::Day, 1, 2),
+            ("days @2000-01-01T06:30:00", Unit::Day, 1, 3),
+            ("Days@ 2000-01-01", Unit::Day, 1, 3),
+        ];
+        for (input, unit, multiplier, diagnostics) in units {
+            let report = parse_cf_time_with_mode(input, ParseMode::Lenient).unwrap();
+            assert_eq!(report.diagnostics.len(), diagnostics, "{input}");
+            assert!(
+                report
+                    .diagnostics
+                    .iter()
+                    .any(|d| d.message == "Replaced '@' by 'since'"),
+                "{input}"
+            );
+            let parsed = report.parsed;
+            assert_eq!(parsed.unit, unit, "{input}");
+            assert_eq!(parsed.multiplier, multiplier, "{input}");
+            assert_eq!(parsed.datetime.ymd, (2000, 1, 1), "{input}");
+            assert!(parse_cf_time(input).is_err(), "{input}");
+            // Round trip through the CF units
+            let since = input.replacen('@', " since ", 1);
+            let expected = parse_cf_time_with_mode(&since, ParseMode::Lenient)
+                .unwrap()
+                .parsed;
+            assert_eq!(parsed.datetime.hms, expected.datetime.hms, "{input}");
+            assert_eq!(
+                parsed.datetime.tz.map(|tz| tz.offset_minutes()),
+                expected.datetime.tz.map(|tz| tz.offset_minutes())
+            );
+            let origin = CFDatetime::from_ymd(2000, 1, 1, Calendar::NoLeap).unwrap();
+            let formatted = format_units(&parsed.unit, &origin);
+            assert_eq!(parse_cf_time(&formatted).unwrap().unit, unit);
+        }
+        let report = parse_cf_time_with_mode("days@2000-01-01", ParseMode::Lenient).unwrap();
+        assert_eq!(report.diagnostics[0].span, 0..15);
+        assert_eq!(report.diagnostics[1].span, 4..5);
+        for input in ["days @", "@ 2000-01-01", "days @ @ 2000-01-01"] {
+            assert!(
+                parse_cf_time_with_mode(input, ParseMode::Lenient).is_err(),
+                "{input}"
+            );
+        }
+    }
+    #[test]
+    fn test_parse_mode_lenient_partial_date() {
+        let units = [
+            (
+                "days since 2000-03",
+                (2000, 3, 1),
+                "Assumed day 01 in '2000-03'",
+            ),
+            (
+                "days since 2000",
+                (2000, 1, 1),
+                "Assumed month 01 and day 01 in '2000'",
+            ),
+            (
+                "days since -0100-06 12:00:00",
+                (-100, 6, 1),
+                "Assumed day 01 in '-0100-06'",
+            ),
+            (
+                "days since 2000-03T06:00",
+                (2000, 3, 1),
+                "Assumed day 01 in '2000-03'",
+            ),
+            (
+                "days since 19700215",
+                (1970, 2, 15),
+                "Read the compact date YYYYMMDD as YYYY-MM-DD in '19700215'",
+            ),
+        ];
+        for (input, ymd, message) in units {
+            let report = parse_cf_time_with_mode(input, ParseMode::Lenient).unwrap();
+            assert_eq!(report.parsed.datetime.ymd, ymd, "{input}");
+            assert!(
+                report.diagnostics.iter().any(|d| d.message == message),
+                "{input}"
+            );
+            assert!(parse_cf_time(input).is_err(), "{input}");
+        }
+        let report = parse_cf_time_with_mode("days since 2000-03", ParseMode::Lenient).unwrap();
+        assert_eq!(report.diagnostics[0].span, 11..18);
+        for input in [
+            "days since 2000-",
+            "days since 20x0-01",
+            "days since --2000",
+            "days since 197001",
+        ] {
+            assert!(
+                parse_cf_time_with_mode(input, ParseMode::Lenient).is_err(),
+                "{input}"
+            );
+        }
+    }
+    #[test]
+    fn test_parse_mode_lenient_fractional_time() {
+        let units = [
+            ("hours since 2000-01-01 06.5", "06:30:00", None),
+            ("hours since 2000-01-01 6", "06:00:00", None),
+            ("hours since 2000-01-01 06:30", "06:30:00", None),
+            ("hours since 2000-01-01 06:07.5", "06:07:30", None),
+            ("hours since 2000-01-01T12.25Z", "12:15:00", Some(0)),
+            ("hours since 2000-01-01 00.000001", "00:00:00.0036", None),
+            (
+                "hours since 2000-01-01 00:00.1234567891",
+                "00:00:07.407407346",
+                None,
+            ),
+        ];
+        for (input, folded, tz) in units {
+            let report = parse_cf_time_with_mode(input, ParseMode::Lenient).unwrap();
+            let expected = parse_cf_time(&format!("hours since 2000-01-01 {folded}")).unwrap();
+            assert_eq!(report.parsed.datetime.hms, expected.datetime.hms, "{input}");
+            assert_eq!(
+                report.parsed.datetime.nanosecond, expected.datetime.nanosecond,
+                "{input}"
+            );
+            assert_eq!(report.parsed.datetime.tz.map(|tz| tz.offset_minutes()), tz);
+            assert!(report
+                .diagnostics
+                .iter()
+                .any(|d| d.message.ends_with(&format!("by '{folded}'"))));
+            assert!(parse_cf_time(input).is_err(), "{input}");
+        }
+        let report =
+            parse_cf_time_with_mode("hours since 2000-01-01 06:30:00", ParseMode::Lenient).unwrap();
+        assert!(report.diagnostics.is_empty());
+        for input in [
+            "hours since 2000-01-01 24.5",
+            "hours since 2000-01-01 06.5:30",
+            "hours since 2000-01-01 06.:30:00",
+            "hours since 2000-01-01 1e2",
+        ] {
+            assert!(
+                parse_cf_time_with_mode(input, ParseMode::Lenient).is_err(),
+                "{input}"
+            );
+        }
+    }
+    #[test]
+    fn test_parse_mode_lenient_two_digit_years() {
+        let lenient = ParseOptions::default().with_mode(ParseMode::Lenient);
+        let pivot = lenient.with_two_digit_years(TwoDigitYears::Pivot(50));
+        let units = [
+            ("days since 70-01-01", (1970, 1, 1), 1970),
+            ("days since 05-03-01", (2005, 3, 1), 2005),
+            ("days since 50-01", (1950, 1, 1), 1950),
+            ("days since 49-12-31 00:00:00", (2049, 12, 31), 2049),
+            ("days since 70", (1970, 1, 1), 1970),
+        ];
+        for (input, ymd, year) in units {
+            let report = parse_cf_time_with_options(input, pivot).unwrap();
+            assert_eq!(report.parsed.datetime.ymd, ymd, "{input}");
+            assert!(
+                report
+                    .diagnostics
+                    .iter()
+                    .any(|d| d.message.starts_with("Interpreted the two-digit year")
+                        && d.message.contains(&format!("as {year} with the pivot 50"))),
+                "{input}"
+            );
+        }
+        let report = parse_cf_time_with_options("days since 70-01-01", pivot).unwrap();
+        assert_eq!(report.diagnostics[0].span, 11..19);
+        let report = parse_cf_time_with_options(
+            "days since 99-01-01",
+            lenient.with_two_digit_years(TwoDigitYears::Pivot(100)),
+        )
+        .unwrap();
+        assert_eq!(report.parsed.datetime.ymd, (2099, 1, 1));
+        // The year is kept as written by default and in strict mode
+        for options in [lenient, pivot.with_mode(ParseMode::Strict)] {
+            let report = parse_cf_time_with_options("days since 70-01-01", options).unwrap();
+            assert_eq!(report.parsed.datetime.ymd, (70, 1, 1));
+            assert!(report.diagnostics.is_empty());
+        }
+        // Years with more or less than two digits are not affected
+        for (input, year) in [
+            ("days since 0070-01-01", 70),
+            ("days since 7-01-01", 7),
+            ("days since 170-01-01", 170),
+            ("days since -70-01-01", -70),
+        ] {
+            let report = parse_cf_time_with_options(input, pivot).unwrap();
+            assert_eq!(report.parsed.datetime.ymd.0, year, "{input}");
+        }
+        let reject = lenient.with_two_digit_years(TwoDigitYears::Reject);
+        match parse_cf_time_with_options("days since 70-01-01", reject) {
+            Err(crate::errors::Error::UnitParserDiagnostic(diagnostic)) => {
+                assert_eq!(diagnostic.span, 11..19);
+                assert_eq!(diagnostic.message, "Ambiguous two-digit year '70'");
+            }
+            _ => panic!("Expected a diagnostic"),
+        }
+        assert!(parse_cf_time_with_options("days since 1970-01-01", reject).is_ok());
+    }
+    #[test]
+    fn test_format_units() {
+        let units = [
+            Unit::Year,
+            Unit::Month,
+            Unit::Week,
+            Unit::Day,
+            Unit::Hour,
+            Unit::Minute,
+            Unit::Second,
+            Unit::Millisecond,
+            Unit::Microsecond,
+            Unit::Nanosecond,
+        ];
+        let origins = [
+            CFDatetime::from_ymd(1900, 1, 1, Calendar::Standard).unwrap(),
+            CFDatetime::from_ymd_hms(-100, 2, 30, 12, 30, 15.0, Calendar::Day360).unwrap(),
+            CFDatetime::from_timestamp_nanos(123_456_789, Calendar::NoLeap).unwrap(),
+        ];
+        assert_eq!(
+            format_units(&Unit::Hour, &origins[0]),
+            "hours since 1900-01-01 00:00:00"
+        );
+        for unit in units {
+            for origin in &origins {
+                let formatted = format_units(&unit, origin);
+                let parsed = parse_cf_time(&formatted).unwrap();
+                assert_eq!(parsed.unit, unit);
+                let (year, month, day, hour, minute, second) = origin.ymd_hms().unwrap();
+                assert_eq!(parsed.datetime.ymd, (year, month, day));
+                let (parsed_hour, parsed_minute, parsed_second) = parsed.datetime.hms.unwrap();
+                assert_eq!((parsed_hour, parsed_minute), (hour, minute));
+                assert_eq!(parsed_second.trunc() as u8, second);
+                assert_eq!(
+                    parsed.datetime.nanosecond.unwrap_or(0),
+                    origin.nanoseconds() as i64
+                );
+                assert_eq!(parsed.datetime.tz, None);
+            }
+        }
+    }
     // Add more tests for different valid date and time scenarios
 }
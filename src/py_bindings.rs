@@ -1,14 +1,25 @@
+#![allow(non_local_definitions)]
+use crate::array::CFTimeArray;
 use crate::calendars::Calendar;
-use crate::datetime::CFDatetime;
+use crate::converter::{rebase_cf, CFConverter};
+use crate::datetime::{CFDatetime, CalendarChangeAction, InvalidDatePolicy};
 use crate::duration::CFDuration;
-use crate::encoder::CFEncoder;
+use crate::merge::{DuplicatePolicy, MergePolicy, SourceIndex};
+use crate::parser::{parse_cf_time, Unit};
+use crate::resample::{Period, TimeUnitsConverter};
+use crate::timezone::Tz;
+use crate::utils::{get_datetime_and_unit_from_units, get_reference_datetime};
 use crate::{constants, decoder::*};
+use pyo3::basic::CompareOp;
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
-use pyo3::types::PyDateTime;
+#[cfg(not(feature = "abi3"))]
+use pyo3::types::{PyDate, PyDateTime};
+use pyo3::types::{PyDict, PyList, PyTuple};
+use std::borrow::Borrow;
 use std::str::FromStr;
 use std::sync::Arc;
-#[pyclass]
+#[pyclass(module = "cftime_rs")]
 #[derive(Clone)]
 pub struct PyCFCalendar {
     pub calendar: Calendar,
@@ -20,7 +31,7 @@ pub struct PyCFCalendar {
 ///
 /// This duration can be added to a PyCFDatetime
 /// The result of the substraction between a PyCFDatetime and a PyCFDatetime gives a PyCFDuration
-#[pyclass]
+#[pyclass(module = "cftime_rs")]
 pub struct PyCFDuration {
     pub duration: CFDuration,
 }
@@ -28,28 +39,141 @@ pub struct PyCFDuration {
 #[pymethods]
 impl PyCFCalendar {
     #[staticmethod]
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: String) -> PyResult<Self> {
         let calendar = Calendar::from_str(s.as_str())
             .map_err(|e| PyValueError::new_err(format!("Could not parse calendar: {}", e)))?;
         Ok(Self { calendar })
     }
+    /// Returns the canonical name of the calendar as written in the CF conventions.
+    #[getter]
+    pub fn name(&self) -> &'static str {
+        self.calendar.cf_name()
+    }
+    /// Returns the number of seconds in a year as defined in the udunits package.
+    pub fn units_seconds_per_year(&self) -> f64 {
+        self.calendar.seconds_per_year()
+    }
+    /// Returns `True` if the dates of the calendar are dates of the real world.
+    pub fn is_real_world(&self) -> bool {
+        self.calendar.is_real_world()
+    }
+    /// Returns `True` if the calendar has both leap and common years.
+    pub fn has_leap_years(&self) -> bool {
+        self.calendar.has_leap_years()
+    }
+    /// Returns the number of days of every year if the years have the same length.
+    pub fn fixed_year_length(&self) -> Option<u16> {
+        self.calendar.fixed_year_length()
+    }
+    /// Returns `True` if the datetimes can be converted to python or numpy datetimes as is.
+    pub fn convertible_to_unix(&self) -> bool {
+        self.calendar.convertible_to_unix()
+    }
+    /// Returns `True` if the date exists in the calendar.
+    pub fn is_valid_date(&self, year: i64, month: u8, day: u8) -> bool {
+        self.calendar.is_valid_date(year, month, day)
+    }
+    /// Returns `is_valid_date` for the dates given as one list per component.
+    pub fn are_valid_dates(
+        &self,
+        years: Vec<i64>,
+        months: Vec<u8>,
+        days: Vec<u8>,
+    ) -> PyResult<Vec<bool>> {
+        self.calendar
+            .are_valid_dates(&years, &months, &days)
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+    pub fn __richcmp__(&self, other: &PyCFCalendar, op: CompareOp, py: Python) -> PyObject {
+        match op {
+            CompareOp::Eq => (self.calendar == other.calendar).into_py(py),
+            CompareOp::Ne => (self.calendar != other.calendar).into_py(py),
+            _ => py.NotImplemented(),
+        }
+    }
+    pub fn __hash__(&self) -> u64 {
+        use std::hash::{Hash, Hasher};
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        self.calendar.hash(&mut hasher);
+        hasher.finish()
+    }
+    pub fn __repr__(&self) -> String {
+        format!("PyCFCalendar({})", self.calendar.cf_name())
+    }
+    pub fn __str__(&self) -> &'static str {
+        self.calendar.cf_name()
+    }
+    /// Support for pickle, the calendar is rebuilt from its CF name.
+    pub fn __reduce__(&self, py: Python) -> PyResult<(PyObject, (&'static str,))> {
+        let from_str = py.get_type::<Self>().getattr("from_str")?;
+        Ok((from_str.into(), (self.calendar.cf_name(),)))
+    }
 }
 
 #[pymethods]
 impl PyCFDuration {
-    /// Makes a new `PyCFDuration` with given number of seconds, nanoseconds and specific calendar.
+    /// Makes a new `PyCFDuration` with given number of seconds, nanoseconds, calendar months and specific calendar.
     #[new]
-    pub fn new(seconds: i64, nanoseconds: i64, calendar: PyCFCalendar) -> Self {
+    #[pyo3(signature = (seconds, nanoseconds, calendar, months=0))]
+    pub fn new(seconds: i64, nanoseconds: i64, calendar: PyCFCalendar, months: i64) -> Self {
         Self {
-            duration: CFDuration::new(seconds, nanoseconds, calendar.calendar),
+            duration: CFDuration::with_months(months, seconds, nanoseconds, calendar.calendar),
         }
     }
+    /// Support for pickle
+    pub fn __reduce__(&self, py: Python) -> (PyObject, (i64, u32, PyCFCalendar, i64)) {
+        (
+            py.get_type::<Self>().into(),
+            (
+                self.duration.seconds,
+                self.duration.nanoseconds,
+                PyCFCalendar {
+                    calendar: self.duration.calendar,
+                },
+                self.duration.months,
+            ),
+        )
+    }
+    /// Returns the number of calendar months of the duration
+    #[getter]
+    pub fn months(&self) -> i64 {
+        self.duration.months
+    }
+    /// Returns the number of seconds of the duration rounded toward negative infinity, without
+    /// the months
+    #[getter]
+    pub fn seconds(&self) -> i64 {
+        self.duration.seconds
+    }
+    /// Returns the nanoseconds to add to the seconds, between 0 and 999_999_999
+    #[getter]
+    pub fn nanoseconds(&self) -> u32 {
+        self.duration.nanoseconds
+    }
+    /// Returns the calendar of the duration
+    #[getter]
+    pub fn calendar(&self) -> PyCFCalendar {
+        PyCFCalendar {
+            calendar: self.duration.calendar,
+        }
+    }
+    /// Returns the exact months, seconds and nanoseconds of the duration, the arguments of the
+    /// constructor without the calendar
+    pub fn exact_components(&self) -> (i64, i64, u32) {
+        (
+            self.duration.months,
+            self.duration.seconds,
+            self.duration.nanoseconds,
+        )
+    }
     /// Makes a new `PyCFDuration` with given number of years and specific calendar.
     #[staticmethod]
-    pub fn from_years(years: i64, calendar: PyCFCalendar) -> PyCFDuration {
-        Self {
-            duration: CFDuration::from_years(years, calendar.calendar),
-        }
+    pub fn from_years(years: i64, calendar: PyCFCalendar) -> PyResult<PyCFDuration> {
+        Ok(Self {
+            duration: CFDuration::try_from_years(years, calendar.calendar)
+                .map_err(|e| PyValueError::new_err(format!("{}", e)))?,
+        })
     }
     /// Makes a new `PyCFDuration` with given number of months and specific calendar.
     #[staticmethod]
@@ -60,31 +184,35 @@ impl PyCFDuration {
     }
     /// Makes a new `PyCFDuration` with given number of weeks and specific calendar.
     #[staticmethod]
-    pub fn from_weeks(weeks: i64, calendar: PyCFCalendar) -> PyCFDuration {
-        Self {
-            duration: CFDuration::from_weeks(weeks, calendar.calendar),
-        }
+    pub fn from_weeks(weeks: i64, calendar: PyCFCalendar) -> PyResult<PyCFDuration> {
+        Ok(Self {
+            duration: CFDuration::try_from_weeks(weeks, calendar.calendar)
+                .map_err(|e| PyValueError::new_err(format!("{}", e)))?,
+        })
     }
     /// Makes a new `PyCFDuration` with given number of days and specific calendar.
     #[staticmethod]
-    pub fn from_days(days: i64, calendar: PyCFCalendar) -> PyCFDuration {
-        Self {
-            duration: CFDuration::from_days(days, calendar.calendar),
-        }
+    pub fn from_days(days: i64, calendar: PyCFCalendar) -> PyResult<PyCFDuration> {
+        Ok(Self {
+            duration: CFDuration::try_from_days(days, calendar.calendar)
+                .map_err(|e| PyValueError::new_err(format!("{}", e)))?,
+        })
     }
     /// Makes a new `PyCFDuration` with given number of hours and specific calendar.
     #[staticmethod]
-    pub fn from_hours(hours: i64, calendar: PyCFCalendar) -> PyCFDuration {
-        Self {
-            duration: CFDuration::from_hours(hours, calendar.calendar),
-        }
+    pub fn from_hours(hours: i64, calendar: PyCFCalendar) -> PyResult<PyCFDuration> {
+        Ok(Self {
+            duration: CFDuration::try_from_hours(hours, calendar.calendar)
+                .map_err(|e| PyValueError::new_err(format!("{}", e)))?,
+        })
     }
     /// Makes a new `PyCFDuration` with given number of minutes and specific calendar.
     #[staticmethod]
-    pub fn from_minutes(minutes: i64, calendar: PyCFCalendar) -> PyCFDuration {
-        Self {
-            duration: CFDuration::from_minutes(minutes, calendar.calendar),
-        }
+    pub fn from_minutes(minutes: i64, calendar: PyCFCalendar) -> PyResult<PyCFDuration> {
+        Ok(Self {
+            duration: CFDuration::try_from_minutes(minutes, calendar.calendar)
+                .map_err(|e| PyValueError::new_err(format!("{}", e)))?,
+        })
     }
     /// Makes a new `PyCFDuration` with given number of seconds and specific calendar.
     #[staticmethod]
@@ -114,6 +242,21 @@ impl PyCFDuration {
             duration: CFDuration::from_nanoseconds(nanoseconds, calendar.calendar),
         }
     }
+    /// Parses an ISO 8601 duration such as `P1Y2M3DT4H5M6S` or `-P1D` in a specific calendar.
+    #[staticmethod]
+    pub fn from_iso8601(duration: &str, calendar: PyCFCalendar) -> PyResult<PyCFDuration> {
+        Ok(Self {
+            duration: CFDuration::from_iso8601(duration, calendar.calendar)
+                .map_err(|e| PyValueError::new_err(format!("{}", e)))?,
+        })
+    }
+    /// Returns a new `PyCFDuration` where the months are converted to seconds with the
+    /// udunits definitions.
+    pub fn to_approximate_seconds(&self) -> PyCFDuration {
+        PyCFDuration {
+            duration: self.duration.to_approximate_seconds(),
+        }
+    }
     /// Returns the total number of years in the duration.
     pub fn num_years(&self) -> f64 {
         self.duration.num_years()
@@ -154,6 +297,26 @@ impl PyCFDuration {
     pub fn num_nanoseconds(&self) -> f64 {
         self.duration.num_nanoseconds()
     }
+    /// Returns the number of whole seconds truncated toward zero, without the months.
+    pub fn whole_seconds(&self) -> i64 {
+        self.duration.whole_seconds()
+    }
+    /// Returns the fraction of second in nanoseconds with the sign of the duration.
+    pub fn subsec_nanos(&self) -> i32 {
+        self.duration.subsec_nanos()
+    }
+    /// Returns -1, 0 or 1 depending on the sign of the duration.
+    pub fn signum(&self) -> i64 {
+        self.duration.signum()
+    }
+    /// Returns `True` if the duration is strictly negative.
+    pub fn is_negative(&self) -> bool {
+        self.duration.is_negative()
+    }
+    /// Returns `True` if the duration is zero.
+    pub fn is_zero(&self) -> bool {
+        self.duration.is_zero()
+    }
     /// Returns an ISO 8601 formatted string.
     pub fn __repr__(&self) -> String {
         format!("{}", self.duration)
@@ -179,7 +342,13 @@ impl PyCFDuration {
 
     pub fn __neg__(&self) -> PyCFDuration {
         let duration = -&self.duration;
-        PyCFDuration { duration: duration }
+        PyCFDuration { duration }
+    }
+
+    pub fn __abs__(&self) -> PyCFDuration {
+        PyCFDuration {
+            duration: self.duration.abs(),
+        }
     }
 }
 
@@ -187,7 +356,7 @@ impl PyCFDuration {
 /// It represents a date in a specific calendar
 /// All the methods depends on the Calendar definitions found in
 /// [udunits package](https://github.com/nco/nco/blob/master/data/udunits.dat)
-#[pyclass]
+#[pyclass(module = "cftime_rs")]
 #[derive(Clone, PartialEq)]
 pub struct PyCFDatetime {
     pub dt: Arc<CFDatetime>,
@@ -231,6 +400,156 @@ impl PyCFDatetime {
             .ymd_hms()
             .map_err(|e| PyValueError::new_err(e.to_string()))
     }
+    /// Returns the date at midnight.
+    pub fn date(&self) -> PyResult<Self> {
+        let dt = self
+            .dt
+            .date()
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(Self { dt: dt.into() })
+    }
+    /// Returns the datetime of the next day at the same time of the day.
+    pub fn succ_day(&self) -> PyResult<Self> {
+        let dt = self
+            .dt
+            .succ_day()
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(Self { dt: dt.into() })
+    }
+    /// Returns the datetime of the previous day at the same time of the day.
+    pub fn pred_day(&self) -> PyResult<Self> {
+        let dt = self
+            .dt
+            .pred_day()
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(Self { dt: dt.into() })
+    }
+    /// Returns the first day of the month at midnight.
+    pub fn start_of_month(&self) -> PyResult<Self> {
+        let dt = self
+            .dt
+            .start_of_month()
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(Self { dt: dt.into() })
+    }
+    /// Returns the last day of the month at midnight.
+    pub fn end_of_month(&self) -> PyResult<Self> {
+        let dt = self
+            .dt
+            .end_of_month()
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(Self { dt: dt.into() })
+    }
+    /// Returns the first of January of the year at midnight.
+    pub fn start_of_year(&self) -> PyResult<Self> {
+        let dt = self
+            .dt
+            .start_of_year()
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(Self { dt: dt.into() })
+    }
+    /// Returns the last day of the year at midnight.
+    pub fn end_of_year(&self) -> PyResult<Self> {
+        let dt = self
+            .dt
+            .end_of_year()
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(Self { dt: dt.into() })
+    }
+    /// Returns the duration elapsed since midnight.
+    pub fn time_of_day(&self) -> PyCFDuration {
+        PyCFDuration {
+            duration: self.dt.time_of_day(),
+        }
+    }
+    /// Returns the number of seconds elapsed since midnight, including the fraction of seconds.
+    pub fn seconds_since_midnight(&self) -> f64 {
+        self.dt.seconds_since_midnight()
+    }
+    /// Returns the ordinal day number of the date in its calendar, as `toordinal` of cftime.
+    /// The fraction of the day is included if `fractional` is `True`.
+    #[pyo3(signature = (fractional=false))]
+    pub fn toordinal(&self, py: Python, fractional: bool) -> PyObject {
+        if fractional {
+            self.dt.to_ordinal_fractional().into_py(py)
+        } else {
+            self.dt.to_ordinal().into_py(py)
+        }
+    }
+    /// Returns the J2000.0 epoch of the calendar, `2000-01-01 12:00:00`.
+    #[staticmethod]
+    pub fn j2000(calendar: PyCFCalendar) -> Self {
+        Self {
+            dt: CFDatetime::j2000(calendar.calendar).into(),
+        }
+    }
+    /// Returns the number of days elapsed since the epoch, including the fraction of the day.
+    pub fn days_since(&self, epoch: &PyCFDatetime) -> PyResult<f64> {
+        self.dt
+            .days_since(&epoch.dt)
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+    /// Returns the number of centuries of the calendar elapsed since the epoch.
+    pub fn julian_centuries_since(&self, epoch: &PyCFDatetime) -> PyResult<f64> {
+        self.dt
+            .julian_centuries_since(&epoch.dt)
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+    /// Returns the number of Julian centuries since the J2000.0 epoch of the calendar.
+    pub fn julian_century(&self) -> f64 {
+        self.dt.julian_century()
+    }
+    /// Returns the time elapsed since the origin as a number of units e.g. `"hours"`, the value
+    /// encoded with the units `<unit> since <origin>`.
+    pub fn elapsed_since(&self, origin: &PyCFDatetime, unit: &str) -> PyResult<f64> {
+        let unit = unit
+            .parse::<Unit>()
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        self.dt
+            .elapsed_since(&origin.dt, unit)
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+    /// Returns `True` if the year of the date is a leap year in its calendar.
+    pub fn is_leap_year(&self) -> PyResult<bool> {
+        self.dt
+            .is_leap_year()
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+    /// Returns the number of days of the month of the date in its calendar.
+    pub fn days_in_month(&self) -> PyResult<u8> {
+        self.dt
+            .days_in_month()
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+    /// Returns the number of days of the year of the date in its calendar.
+    pub fn days_in_year(&self) -> PyResult<u16> {
+        self.dt
+            .days_in_year()
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+    /// Returns the day of the week from 0 for Monday to 6 for Sunday, as `dayofwk` of python-cftime.
+    pub fn weekday(&self) -> u8 {
+        self.dt.weekday()
+    }
+    /// Returns the name of the day of the week, in english or in the 7 given names starting on Monday.
+    #[pyo3(signature = (names=None))]
+    pub fn weekday_name(&self, names: Option<[&str; 7]>) -> String {
+        match names {
+            Some(names) => self.dt.weekday_name_in(&names),
+            None => self.dt.weekday_name(),
+        }
+        .to_string()
+    }
+    /// Returns the name of the month, in english or in the 12 given names starting with January.
+    #[pyo3(signature = (names=None))]
+    pub fn month_name(&self, names: Option<[&str; 12]>) -> PyResult<String> {
+        match names {
+            Some(names) => self.dt.month_name_in(&names),
+            None => self.dt.month_name(),
+        }
+        .map(str::to_string)
+        .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
     /// Makes a new `PyCFDatetime` with given year, month, day, hour, minute, second and specific calendar
     #[staticmethod]
     pub fn from_ymd_hms(
@@ -282,6 +601,161 @@ impl PyCFDatetime {
             .map_err(|e| PyValueError::new_err(e.to_string()))?;
         Ok(Self { dt: dt.into() })
     }
+    /// Makes a new `PyCFDatetime` with given number of nanoseconds since epoch and specific calendar.
+    #[staticmethod]
+    pub fn from_timestamp_nanos(timestamp_nanos: i128, calendar: PyCFCalendar) -> PyResult<Self> {
+        let dt = CFDatetime::from_timestamp_nanos(timestamp_nanos, calendar.calendar)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(Self { dt: dt.into() })
+    }
+    /// Makes a new `PyCFDatetime` from a float number of seconds since epoch and specific calendar,
+    /// as `datetime.fromtimestamp`. The fractional seconds are rounded to the nanosecond.
+    #[staticmethod]
+    pub fn fromtimestamp(timestamp: f64, calendar: PyCFCalendar) -> PyResult<Self> {
+        let dt = CFDatetime::from_timestamp_f64(timestamp, calendar.calendar)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(Self { dt: dt.into() })
+    }
+    /// Makes a new `PyCFDatetime` from a RFC 3339 string and specific calendar, as
+    /// `datetime.fromisoformat`. Calendars that are not real world calendars need `force`.
+    #[staticmethod]
+    #[pyo3(signature = (date_string, calendar, force=false))]
+    pub fn fromisoformat(date_string: &str, calendar: PyCFCalendar, force: bool) -> PyResult<Self> {
+        let dt = CFDatetime::parse_rfc3339(date_string, calendar.calendar, force)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(Self { dt: dt.into() })
+    }
+    /// Makes a new `PyCFDatetime` from an ordinal day number and specific calendar, as `fromordinal` of cftime.
+    #[staticmethod]
+    pub fn fromordinal(jday: f64, calendar: PyCFCalendar) -> PyResult<Self> {
+        let dt = CFDatetime::from_ordinal(jday, calendar.calendar)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(Self { dt: dt.into() })
+    }
+    /// Returns the earliest `PyCFDatetime` representable in the calendar.
+    #[staticmethod]
+    pub fn min(calendar: PyCFCalendar) -> Self {
+        Self {
+            dt: CFDatetime::min(calendar.calendar).into(),
+        }
+    }
+    /// Returns the latest `PyCFDatetime` representable in the calendar.
+    #[staticmethod]
+    pub fn max(calendar: PyCFCalendar) -> Self {
+        Self {
+            dt: CFDatetime::max(calendar.calendar).into(),
+        }
+    }
+    /// Makes a new `PyCFDatetime` from nanoseconds since epoch clamped to the range of the calendar.
+    #[staticmethod]
+    pub fn clamp_to_valid_range(timestamp_nanos: i128, calendar: PyCFCalendar) -> Self {
+        Self {
+            dt: CFDatetime::clamp_to_valid_range(timestamp_nanos, calendar.calendar).into(),
+        }
+    }
+    /// Makes a list of `PyCFDatetime` from seconds since epoch and specific calendar.
+    #[staticmethod]
+    pub fn from_timestamps(timestamps: Vec<i64>, calendar: PyCFCalendar) -> PyResult<Vec<Self>> {
+        let datetimes = CFDatetime::from_timestamps(&timestamps, calendar.calendar)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(datetimes
+            .into_iter()
+            .map(|dt| Self { dt: dt.into() })
+            .collect())
+    }
+    /// Makes a list of `PyCFDatetime` from lists of components and specific calendar, e.g. the
+    /// columns of a table. The error message gives the index of the first invalid datetime.
+    #[staticmethod]
+    #[allow(clippy::too_many_arguments)]
+    pub fn from_components(
+        years: Vec<i64>,
+        months: Vec<u8>,
+        days: Vec<u8>,
+        hours: Vec<u8>,
+        minutes: Vec<u8>,
+        seconds: Vec<f32>,
+        calendar: PyCFCalendar,
+    ) -> PyResult<Vec<Self>> {
+        let array = CFTimeArray::from_components(
+            &years,
+            &months,
+            &days,
+            &hours,
+            &minutes,
+            &seconds,
+            calendar.calendar,
+        )
+        .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(array
+            .into_datetimes()
+            .into_iter()
+            .map(|dt| Self { dt: dt.into() })
+            .collect())
+    }
+    /// Makes a list of `PyCFDatetime` from nanoseconds since epoch and specific calendar.
+    #[staticmethod]
+    pub fn from_timestamps_nanos(
+        timestamps_nanos: Vec<i128>,
+        calendar: PyCFCalendar,
+    ) -> PyResult<Vec<Self>> {
+        let datetimes = CFDatetime::from_timestamps_nanos(&timestamps_nanos, calendar.calendar)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(datetimes
+            .into_iter()
+            .map(|dt| Self { dt: dt.into() })
+            .collect())
+    }
+    /// Returns the seconds since epoch of a list of `PyCFDatetime`.
+    #[staticmethod]
+    pub fn to_timestamps(datetimes: Vec<PyCFDatetime>) -> Vec<i64> {
+        datetimes
+            .iter()
+            .map(|datetime| datetime.dt.timestamp())
+            .collect()
+    }
+    /// Returns the nanoseconds since epoch of a list of `PyCFDatetime`.
+    #[staticmethod]
+    pub fn to_timestamps_nanos(datetimes: Vec<PyCFDatetime>) -> Vec<i128> {
+        datetimes
+            .iter()
+            .map(|datetime| datetime.dt.timestamp_nanos())
+            .collect()
+    }
+    /// Returns the RFC 3339 string of the datetime, as `datetime.isoformat`, with `sep` between
+    /// the date and the time. Calendars that are not real world calendars need `force`.
+    #[pyo3(signature = (sep="T", force=false))]
+    pub fn isoformat(&self, sep: &str, force: bool) -> PyResult<String> {
+        if sep.chars().count() != 1 {
+            return Err(PyValueError::new_err("sep must be a single character"));
+        }
+        let iso = self
+            .dt
+            .to_rfc3339(force)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        // The date always has 10 characters, followed by the `T` separator
+        Ok(format!("{}{}{}", &iso[..10], sep, &iso[11..]))
+    }
+    /// Returns the number of seconds since epoch as a float, as `datetime.timestamp`.
+    pub fn timestamp(&self) -> f64 {
+        self.dt.timestamp_f64()
+    }
+    /// Returns the number of nanoseconds since epoch.
+    pub fn timestamp_nanos(&self) -> i128 {
+        self.dt.timestamp_nanos()
+    }
+    /// Support for pickle, the datetime is rebuilt from the number of nanoseconds since epoch.
+    pub fn __reduce__(&self, py: Python) -> PyResult<(PyObject, (i128, PyCFCalendar))> {
+        let from_timestamp_nanos = py.get_type::<Self>().getattr("from_timestamp_nanos")?;
+        Ok((
+            from_timestamp_nanos.into(),
+            (
+                self.dt.timestamp_nanos(),
+                PyCFCalendar {
+                    calendar: self.dt.calendar(),
+                },
+            ),
+        ))
+    }
     /// Returns the hours of the date.
     pub fn hours(&self) -> PyResult<u8> {
         let (hour, _, _) = self.hms()?;
@@ -301,6 +775,26 @@ impl PyCFDatetime {
     pub fn nanoseconds(&self) -> u32 {
         self.dt.nanoseconds()
     }
+    /// Returns a new `PyCFDatetime` with the given fields replaced.
+    /// The fields that are not given are kept from the current date.
+    #[pyo3(signature = (year=None, month=None, day=None, hour=None, minute=None, second=None, nanosecond=None))]
+    #[allow(clippy::too_many_arguments)]
+    pub fn replace(
+        &self,
+        year: Option<i64>,
+        month: Option<u8>,
+        day: Option<u8>,
+        hour: Option<u8>,
+        minute: Option<u8>,
+        second: Option<u8>,
+        nanosecond: Option<u32>,
+    ) -> PyResult<Self> {
+        let dt = self
+            .dt
+            .replace(year, month, day, hour, minute, second, nanosecond)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(Self { dt: dt.into() })
+    }
     /// Change the calendar of the PyCFDateTime.
     ///
     /// # Arguments
@@ -318,6 +812,65 @@ impl PyCFDatetime {
         Ok(Self { dt: new_dt.into() })
     }
 
+    /// Changes the calendar of many `PyCFDatetime` and reports the dates that do not exist in the
+    /// new calendar, which are dropped or clamped to the last day of their month depending on `policy`.
+    #[staticmethod]
+    #[pyo3(signature = (datetimes, calendar, policy="drop"))]
+    pub fn change_calendar_many<'py>(
+        py: Python<'py>,
+        datetimes: Vec<PyCFDatetime>,
+        calendar: PyCFCalendar,
+        policy: &str,
+    ) -> PyResult<(Vec<PyCFDatetime>, Vec<&'py PyDict>)> {
+        let policy = match policy {
+            "drop" => InvalidDatePolicy::Drop,
+            "clamp" => InvalidDatePolicy::Clamp,
+            _ => {
+                return Err(PyValueError::new_err(format!(
+                    "Invalid policy `{policy}`, expected `drop` or `clamp`"
+                )))
+            }
+        };
+        // The datetimes are shared with python so they are copied into a slice
+        let datetimes = datetimes
+            .iter()
+            .map(|datetime| {
+                CFDatetime::from_timestamp(
+                    datetime.dt.timestamp(),
+                    datetime.dt.nanoseconds(),
+                    datetime.dt.calendar(),
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        let (converted, report) =
+            CFDatetime::change_calendar_many(&datetimes, calendar.calendar, policy)
+                .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        let issues = report
+            .issues
+            .into_iter()
+            .map(|issue| {
+                let py_issue = PyDict::new(py);
+                py_issue.set_item("index", issue.index)?;
+                py_issue.set_item("original", issue.original)?;
+                let action = match issue.action {
+                    CalendarChangeAction::Dropped => "dropped",
+                    CalendarChangeAction::Clamped { .. } => "clamped",
+                };
+                py_issue.set_item("action", action)?;
+                py_issue.set_item("message", issue.to_string())?;
+                Ok(py_issue)
+            })
+            .collect::<PyResult<Vec<_>>>()?;
+        Ok((
+            converted
+                .into_iter()
+                .map(|dt| PyCFDatetime { dt: dt.into() })
+                .collect(),
+            issues,
+        ))
+    }
+
     /// Changes the calendar of the DateTime based on the internal timestamp.
     ///
     /// # Arguments
@@ -342,26 +895,57 @@ impl PyCFDatetime {
         Ok(Self { dt: new_dt.into() })
     }
 
-    fn to_pydatetime<'a>(&self, py: Python<'a>) -> PyResult<&'a PyDateTime> {
-        let (year, month, day, hour, minute, second) = self
+    /// Changes the calendar of the DateTime by keeping its relative position within the year.
+    ///
+    /// # Arguments
+    ///
+    /// * `calendar` - The new calendar to use.
+    ///
+    /// # Returns
+    ///
+    /// A new PyCFDateTime object.
+    ///
+    /// # Errors
+    ///
+    /// Returns a PyValueError if an error occurs while changing the calendar.
+    pub fn interp_calendar(&self, calendar: PyCFCalendar) -> PyResult<Self> {
+        let new_dt = self
+            .dt
+            .interp_calendar(calendar.calendar)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(Self { dt: new_dt.into() })
+    }
+
+    #[pyo3(signature = (tz=None))]
+    fn to_pydatetime<'a>(&self, py: Python<'a>, tz: Option<&str>) -> PyResult<&'a PyAny> {
+        let (datetime, tzinfo) = match tz {
+            Some(tz) => {
+                let target = Tz::from_str(tz).map_err(|e| PyValueError::new_err(e.to_string()))?;
+                let datetime = shift_timezone(&self.dt, self.dt.timezone(), target)?;
+                (Some(datetime), Some(fixed_offset_tzinfo(py, target)?))
+            }
+            None => (None, None),
+        };
+        let datetime = datetime.as_ref().unwrap_or(&self.dt);
+        let (year, month, day, hour, minute, second) = datetime
             .ymd_hms()
             .map_err(|e| PyValueError::new_err(format!("Could not convert to datetime: {}", e)))?;
-        let nanoseconds = self.nanoseconds();
+        let nanoseconds = datetime.nanoseconds();
         let microsecond = nanoseconds / 1_000;
-        PyDateTime::new(
+        new_pydatetime(
             py,
-            year as i32,
+            pydatetime_year(year)?,
             month,
             day,
             hour,
             minute,
             second,
-            microsecond as u32,
-            None,
+            microsecond,
+            tzinfo,
         )
     }
-    fn to_pydatetime_from_timestamp<'a>(&self, py: Python<'a>) -> PyResult<&'a PyDateTime> {
-        PyDateTime::from_timestamp(
+    fn to_pydatetime_from_timestamp<'a>(&self, py: Python<'a>) -> PyResult<&'a PyAny> {
+        pydatetime_from_timestamp(
             py,
             self.dt.timestamp() as f64 + self.dt.nanoseconds() as f64 / 1e9,
             None,
@@ -376,14 +960,18 @@ impl PyCFDatetime {
     fn __sub__(&self, other: &PyCFDatetime) -> PyResult<PyCFDuration> {
         let duration =
             (&*self.dt - &*other.dt).map_err(|e| PyValueError::new_err(e.to_string()))?;
-        Ok(PyCFDuration { duration: duration })
+        Ok(PyCFDuration { duration })
     }
     fn __add__(&self, other: &PyCFDuration) -> PyResult<PyCFDatetime> {
         let dt = (&*self.dt + &other.duration).map_err(|e| PyValueError::new_err(e.to_string()))?;
         Ok(PyCFDatetime { dt: dt.into() })
     }
-    fn __eq__(&self, other: &PyCFDatetime) -> PyResult<bool> {
-        Ok(self.dt == other.dt)
+    fn __richcmp__(&self, other: &PyCFDatetime, op: CompareOp, py: Python) -> PyObject {
+        match op {
+            CompareOp::Eq => (self.dt == other.dt).into_py(py),
+            CompareOp::Ne => (self.dt != other.dt).into_py(py),
+            _ => py.NotImplemented(),
+        }
     }
 }
 
@@ -393,12 +981,45 @@ impl std::fmt::Display for PyCFDatetime {
     }
 }
 
+/// Copies the numbers of a one-dimensional object exposing the buffer protocol, e.g.
+/// `array.array` or `memoryview`, if its items are of type `T`. The masked arrays of numpy are
+/// left to the caller so the masked values are not read.
+#[cfg(not(feature = "abi3"))]
+fn buffer_to_vec<T: pyo3::buffer::Element>(numbers: &PyAny) -> Option<Vec<T>> {
+    if numbers.hasattr("mask").unwrap_or(true) {
+        return None;
+    }
+    let buffer = pyo3::buffer::PyBuffer::<T>::get(numbers).ok()?;
+    if buffer.dimensions() != 1 {
+        return None;
+    }
+    buffer.to_vec(numbers.py()).ok()
+}
+
+/// The buffer protocol is not part of the stable ABI before python 3.11, the numbers are read
+/// one by one as a sequence
+#[cfg(feature = "abi3")]
+fn buffer_to_vec<T>(_numbers: &PyAny) -> Option<Vec<T>> {
+    None
+}
+
 macro_rules! decode_numbers {
-    ($numbers:expr, $units:expr, $calendar:expr, $($t:ty),+) => {
+    ($numbers:expr, $units:expr, $calendar:expr, $method:ident, $($t:ty),+) => {
         {
+            // Buffers are read in one copy, before the types are tried on each python number
+            let mut from_buffer = None;
+            $(
+                if from_buffer.is_none() {
+                    from_buffer = buffer_to_vec::<$t>($numbers)
+                        .map(|numbers| numbers.$method($units.as_str(), $calendar));
+                }
+            )+
+            if let Some(datetimes) = from_buffer {
+                datetimes.map_err(|e| PyValueError::new_err(format!("Could not decode numbers {} into PyCFDatetime: {}", $numbers, e)))?
+            } else
             $(
                 if let Ok(numbers) = $numbers.extract::<Vec<$t>>() {
-                    numbers.decode_cf($units.as_str(), $calendar)
+                    numbers.$method($units.as_str(), $calendar)
                         .map_err(|e| PyValueError::new_err(format!("Could not decode numbers {} into PyCFDatetime: {}", $numbers, e)))?
                 } else
             )+
@@ -412,133 +1033,737 @@ macro_rules! decode_numbers {
     };
 }
 
-#[pyfunction]
-fn num2date(numbers: &PyAny, units: String, calendar: String) -> PyResult<Vec<PyCFDatetime>> {
+/// Decodes the numbers coming from python into CFDatetime
+fn decode_cf_datetimes(
+    numbers: &PyAny,
+    units: String,
+    calendar: String,
+    absolute_time: bool,
+) -> PyResult<Vec<CFDatetime>> {
     let calendar = Calendar::from_str(calendar.as_str())
         .map_err(|e| PyValueError::new_err(format!("Could not parse calendar: {}", e)))?;
     // The order is important always prefer a bigger representation such as i64 or f64 to a
     // smaller representation such as i32 or f32
     // This is because if we convert some number coming from python we can downcast from f64 to f32
     // and lose precision
-    let datetimes = decode_numbers!(numbers, units, calendar, i64, i32, f64, f32);
-    Ok(datetimes
-        .into_iter()
-        .map(|dt| PyCFDatetime { dt: dt.into() })
-        .collect())
+    let datetimes = if absolute_time {
+        decode_numbers!(
+            numbers,
+            units,
+            calendar,
+            decode_absolute_cf,
+            i64,
+            i32,
+            f64,
+            f32
+        )
+    } else {
+        decode_numbers!(numbers, units, calendar, decode_cf, i64, i32, f64, f32)
+    };
+    Ok(datetimes)
 }
 
-#[pyfunction]
-#[pyo3(signature = (numbers, units, calendar, from_timestamp=false))]
-fn num2pydate<'a>(
-    py: Python<'a>,
-    numbers: &'a PyAny,
-    units: String,
-    calendar: String,
-    from_timestamp: Option<bool>,
-) -> PyResult<Vec<&'a PyDateTime>> {
-    match from_timestamp {
-        Some(true) => num2date(numbers, units, calendar)?
-            .iter()
-            .map(|dt| dt.to_pydatetime_from_timestamp(py))
-            .collect::<Result<Vec<_>, _>>(),
-        _ => num2date(numbers, units, calendar)?
-            .iter()
-            .map(|dt| dt.to_pydatetime(py))
-            .collect::<Result<Vec<_>, _>>(),
-    }
-}
-enum DType {
-    Int32,
-    Int64,
-    Float32,
-    Float64,
-    Unknown,
+/// Returns `true` for the lists and tuples that are nested in the numbers
+fn is_nested_sequence(obj: &PyAny) -> bool {
+    obj.downcast::<PyList>().is_ok() || obj.downcast::<PyTuple>().is_ok()
 }
 
-const INT_32_TYPES: &[&str] = &["i32"];
-const INT_64_TYPES: &[&str] = &["i64", "i", "integer", "int"];
-const FLOAT_32_TYPES: &[&str] = &["f32"];
-const FLOAT_64_TYPES: &[&str] = &["f64", "f", "float"];
+/// Appends the numbers of nested lists and tuples to `flat`, depth first
+fn flatten_numbers<'a>(obj: &'a PyAny, flat: &'a PyList) -> PyResult<()> {
+    if is_nested_sequence(obj) {
+        for item in obj.iter()? {
+            flatten_numbers(item?, flat)?;
+        }
+        Ok(())
+    } else {
+        flat.append(obj)
+    }
+}
 
-impl FromStr for DType {
-    type Err = String;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.to_lowercase().as_str() {
-            s if INT_32_TYPES.iter().any(|&x| x == s) => Ok(DType::Int32),
-            s if INT_64_TYPES.iter().any(|&x| x == s) => Ok(DType::Int64),
-            s if FLOAT_32_TYPES.iter().any(|&x| x == s) => Ok(DType::Float32),
-            s if FLOAT_64_TYPES.iter().any(|&x| x == s) => Ok(DType::Float64),
-            _ => Ok(DType::Unknown),
+/// Rebuilds the nesting of `obj` as lists with the decoded datetimes in place of the numbers
+fn unflatten_datetimes(
+    py: Python,
+    obj: &PyAny,
+    datetimes: &mut impl Iterator<Item = CFDatetime>,
+) -> PyResult<PyObject> {
+    if is_nested_sequence(obj) {
+        let nested = PyList::empty(py);
+        for item in obj.iter()? {
+            nested.append(unflatten_datetimes(py, item?, datetimes)?)?;
         }
+        Ok(nested.into_py(py))
+    } else {
+        let datetime = datetimes
+            .next()
+            .ok_or_else(|| PyValueError::new_err("Missing decoded datetime"))?;
+        Ok(PyCFDatetime {
+            dt: datetime.into(),
+        }
+        .into_py(py))
     }
 }
 
 #[pyfunction]
-fn date2num(
+#[pyo3(signature = (numbers, units, calendar, absolute_time=false))]
+fn num2date(
     py: Python,
-    datetimes: Vec<PyCFDatetime>,
+    numbers: &PyAny,
     units: String,
     calendar: String,
-    dtype: String,
+    absolute_time: bool,
 ) -> PyResult<PyObject> {
-    let calendar = Calendar::from_str(calendar.as_str())
-        .map_err(|e| PyValueError::new_err(format!("Could not parse calendar: {}", e)))?;
-    let dtype_enum = DType::from_str(dtype.as_str())
-        .map_err(|e| PyValueError::new_err(format!("Could not parse dtype: {}", e)))?;
-    let dts: Vec<&CFDatetime> = datetimes.iter().map(|pydatetime| &*pydatetime.dt).collect();
-    match dtype_enum {
+    // Arrays of more than one dimension, e.g. numpy arrays, are decoded as nested lists
+    let numbers = match numbers.getattr("ndim") {
+        Ok(ndim) if ndim.extract::<usize>().is_ok_and(|ndim| ndim > 1) => {
+            numbers.call_method0("tolist")?
+        }
+        _ => numbers,
+    };
+    let nested = is_nested_sequence(numbers)
+        && numbers
+            .iter()?
+            .any(|item| item.is_ok_and(is_nested_sequence));
+    if !nested {
+        let datetimes: Vec<PyCFDatetime> =
+            decode_cf_datetimes(numbers, units, calendar, absolute_time)?
+                .into_iter()
+                .map(|dt| PyCFDatetime { dt: dt.into() })
+                .collect();
+        return Ok(datetimes.into_py(py));
+    }
+    // The numbers of nested sequences, e.g. 2-D time bounds, are decoded at once
+    let flat = PyList::empty(py);
+    flatten_numbers(numbers, flat)?;
+    let datetimes = decode_cf_datetimes(flat, units, calendar, absolute_time)?;
+    unflatten_datetimes(py, numbers, &mut datetimes.into_iter())
+}
+
+#[pyfunction]
+fn num2bounds(
+    numbers: &PyAny,
+    units: String,
+    calendar: String,
+) -> PyResult<Vec<(PyCFDatetime, PyCFDatetime)>> {
+    let calendar = Calendar::from_str(calendar.as_str())
+        .map_err(|e| PyValueError::new_err(format!("Could not parse calendar: {}", e)))?;
+    let to_py_err = |e: crate::errors::Error| {
+        PyValueError::new_err(format!(
+            "Could not decode bounds {} into PyCFDatetime: {}",
+            numbers, e
+        ))
+    };
+    // Same order as num2date, bigger representations first
+    let bounds = if let Ok(bounds) = numbers.extract::<Vec<[i64; 2]>>() {
+        bounds.decode_bounds_cf(units.as_str(), calendar)
+    } else if let Ok(bounds) = numbers.extract::<Vec<[i32; 2]>>() {
+        bounds.decode_bounds_cf(units.as_str(), calendar)
+    } else if let Ok(bounds) = numbers.extract::<Vec<[f64; 2]>>() {
+        bounds.decode_bounds_cf(units.as_str(), calendar)
+    } else if let Ok(bounds) = numbers.extract::<Vec<[f32; 2]>>() {
+        bounds.decode_bounds_cf(units.as_str(), calendar)
+    } else {
+        return Err(PyValueError::new_err(
+            "Could not convert array to supported types. \
+            Needs a two-dimensional array of shape (n, 2) of one the following types: i64, i32, f64, f32",
+        ));
+    }
+    .map_err(to_py_err)?;
+    Ok(bounds
+        .into_iter()
+        .map(|(start, end)| {
+            (
+                PyCFDatetime { dt: start.into() },
+                PyCFDatetime { dt: end.into() },
+            )
+        })
+        .collect())
+}
+
+/// Returns a `datetime.timezone` with the fixed offset of `tz`
+fn fixed_offset_tzinfo<'a>(py: Python<'a>, tz: Tz) -> PyResult<&'a PyAny> {
+    let datetime = py.import("datetime")?;
+    let timezone = datetime.getattr("timezone")?;
+    match tz.offset_minutes() {
+        0 => timezone.getattr("utc"),
+        offset => {
+            let kwargs = PyDict::new(py);
+            kwargs.set_item("minutes", offset)?;
+            timezone.call1((datetime.getattr("timedelta")?.call((), Some(kwargs))?,))
+        }
+    }
+}
+
+/// Converts the year of a datetime to the `i32` of python, raising a `ValueError` instead of
+/// wrapping around if it does not fit
+fn pydatetime_year(year: i64) -> PyResult<i32> {
+    i32::try_from(year).map_err(|_| {
+        PyValueError::new_err(format!(
+            "Could not convert to datetime: year {} is out of range",
+            year
+        ))
+    })
+}
+
+/// Makes a `datetime.datetime`, with the datetime C API which is not part of the stable ABI
+#[cfg(not(feature = "abi3"))]
+#[allow(clippy::too_many_arguments)]
+fn new_pydatetime<'a>(
+    py: Python<'a>,
+    year: i32,
+    month: u8,
+    day: u8,
+    hour: u8,
+    minute: u8,
+    second: u8,
+    microsecond: u32,
+    tzinfo: Option<&PyAny>,
+) -> PyResult<&'a PyAny> {
+    let tzinfo = tzinfo.map(|tzinfo| tzinfo.downcast()).transpose()?;
+    PyDateTime::new(
+        py,
+        year,
+        month,
+        day,
+        hour,
+        minute,
+        second,
+        microsecond,
+        tzinfo,
+    )
+    .map(Into::into)
+}
+
+/// The `datetime.datetime` and `datetime.date` types, imported once instead of for every
+/// datetime as the datetime C API is not part of the stable ABI
+#[cfg(feature = "abi3")]
+struct PyDatetimeTypes {
+    datetime: PyObject,
+    date: PyObject,
+}
+
+#[cfg(feature = "abi3")]
+fn pydatetime_types(py: Python<'_>) -> PyResult<&PyDatetimeTypes> {
+    static TYPES: pyo3::sync::GILOnceCell<PyDatetimeTypes> = pyo3::sync::GILOnceCell::new();
+    TYPES.get_or_try_init(py, || {
+        let datetime = py.import("datetime")?;
+        Ok(PyDatetimeTypes {
+            datetime: datetime.getattr("datetime")?.into(),
+            date: datetime.getattr("date")?.into(),
+        })
+    })
+}
+
+/// Makes a `datetime.datetime` by calling the python constructor
+#[cfg(feature = "abi3")]
+#[allow(clippy::too_many_arguments)]
+fn new_pydatetime<'a>(
+    py: Python<'a>,
+    year: i32,
+    month: u8,
+    day: u8,
+    hour: u8,
+    minute: u8,
+    second: u8,
+    microsecond: u32,
+    tzinfo: Option<&PyAny>,
+) -> PyResult<&'a PyAny> {
+    pydatetime_types(py)?.datetime.as_ref(py).call1((
+        year,
+        month,
+        day,
+        hour,
+        minute,
+        second,
+        microsecond,
+        tzinfo,
+    ))
+}
+
+/// Makes a `datetime.datetime` from a unix timestamp, see [new_pydatetime]
+#[cfg(not(feature = "abi3"))]
+fn pydatetime_from_timestamp<'a>(
+    py: Python<'a>,
+    timestamp: f64,
+    tzinfo: Option<&PyAny>,
+) -> PyResult<&'a PyAny> {
+    let tzinfo = tzinfo.map(|tzinfo| tzinfo.downcast()).transpose()?;
+    PyDateTime::from_timestamp(py, timestamp, tzinfo).map(Into::into)
+}
+
+/// Makes a `datetime.datetime` from a unix timestamp, see [new_pydatetime]
+#[cfg(feature = "abi3")]
+fn pydatetime_from_timestamp<'a>(
+    py: Python<'a>,
+    timestamp: f64,
+    tzinfo: Option<&PyAny>,
+) -> PyResult<&'a PyAny> {
+    pydatetime_types(py)?
+        .datetime
+        .as_ref(py)
+        .call_method1("fromtimestamp", (timestamp, tzinfo))
+}
+
+/// Returns the wall clock time of `datetime` in `target` given its wall clock time in `source`
+fn shift_timezone(datetime: &CFDatetime, source: Tz, target: Tz) -> PyResult<CFDatetime> {
+    let minutes = target.offset_minutes() as i64 - source.offset_minutes() as i64;
+    (datetime + CFDuration::from_minutes(minutes, datetime.calendar()))
+        .map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+/// Returns the timezone asked with `tz`, where `"units"` is the timezone of the units or UTC if
+/// there is none
+fn target_tz(tz: &str, units: &str, absolute_time: bool) -> PyResult<Tz> {
+    let utc = Tz::new(0, 0).unwrap();
+    match tz {
+        "units" if absolute_time => Ok(utc),
+        "units" => Ok(parse_cf_time(units)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?
+            .datetime
+            .tz
+            .unwrap_or(utc)),
+        tz => Tz::from_str(tz).map_err(|e| PyValueError::new_err(e.to_string())),
+    }
+}
+
+#[pyfunction]
+#[pyo3(signature = (numbers, units, calendar, from_timestamp=false, absolute_time=false, tz=None))]
+fn num2pydate<'a>(
+    py: Python<'a>,
+    numbers: &'a PyAny,
+    units: String,
+    calendar: String,
+    from_timestamp: Option<bool>,
+    absolute_time: bool,
+    tz: Option<&str>,
+) -> PyResult<Vec<&'a PyAny>> {
+    let target = tz
+        .map(|tz| target_tz(tz, &units, absolute_time))
+        .transpose()?;
+    let tzinfo = match target {
+        Some(target) => Some(fixed_offset_tzinfo(py, target)?),
+        None => None,
+    };
+    // The decoded datetimes are in UTC
+    let datetimes = decode_cf_datetimes(numbers, units, calendar, absolute_time)?;
+    match from_timestamp {
+        Some(true) => datetimes
+            .iter()
+            .map(|dt| {
+                pydatetime_from_timestamp(
+                    py,
+                    dt.timestamp() as f64 + dt.nanoseconds() as f64 / 1e9,
+                    tzinfo,
+                )
+            })
+            .collect::<Result<Vec<_>, _>>(),
+        _ => {
+            let datetimes = match target {
+                Some(target) if target.offset_minutes() != 0 => datetimes
+                    .iter()
+                    .map(|datetime| shift_timezone(datetime, datetime.timezone(), target))
+                    .collect::<PyResult<Vec<_>>>()?,
+                _ => datetimes,
+            };
+            // The dates of a sorted time axis are computed incrementally
+            let dates = CFDatetime::ymd_hms_many(&datetimes).map_err(|e| {
+                PyValueError::new_err(format!("Could not convert to datetime: {}", e))
+            })?;
+            datetimes
+                .iter()
+                .zip(dates)
+                .map(|(datetime, (year, month, day, hour, minute, second))| {
+                    new_pydatetime(
+                        py,
+                        pydatetime_year(year)?,
+                        month,
+                        day,
+                        hour,
+                        minute,
+                        second,
+                        datetime.nanoseconds() / 1_000,
+                        tzinfo,
+                    )
+                })
+                .collect::<Result<Vec<_>, _>>()
+        }
+    }
+}
+/// Converts numbers encoded as `i64` to an unsigned type, a negative number, i.e. a date before
+/// the reference date of the units, is an error
+fn to_unsigned<T: TryFrom<i64>>(numbers: Vec<i64>, dtype: &str) -> PyResult<Vec<T>> {
+    numbers
+        .into_iter()
+        .enumerate()
+        .map(|(index, number)| {
+            T::try_from(number).map_err(|_| {
+                let reason = if number < 0 {
+                    "is negative because the date is before the reference date of the units"
+                } else {
+                    "is too large"
+                };
+                PyValueError::new_err(format!(
+                    "Could not encode datetimes: {number} at index {index} {reason} and does not fit in unsigned dtype `{dtype}`"
+                ))
+            })
+        })
+        .collect()
+}
+
+/// Encodes the datetimes into numbers of the given dtype
+fn encode_with_dtype<D: Borrow<CFDatetime>>(
+    py: Python,
+    converter: &CFConverter,
+    datetimes: &[D],
+    dtype: &str,
+) -> PyResult<PyObject> {
+    let dtype_enum = DType::from_str(dtype).map_err(|_| invalid_dtype_error(dtype))?;
+    let to_py_err = |e: crate::errors::Error| {
+        PyValueError::new_err(format!("Could not encode datetimes: {}", e))
+    };
+    match dtype_enum {
         DType::Int32 => {
-            let numbers: Vec<i32> = dts
-                .encode_cf(units.as_str(), calendar)
-                .map_err(|e| PyValueError::new_err(format!("Could not encode datetimes: {}", e)))?;
+            let numbers = converter.encode::<i32, _>(datetimes).map_err(to_py_err)?;
             Ok(numbers.into_py(py))
         }
         DType::Int64 => {
-            let numbers: Vec<i64> = dts
-                .encode_cf(units.as_str(), calendar)
-                .map_err(|e| PyValueError::new_err(format!("Could not encode datetimes: {}", e)))?;
+            let numbers = converter.encode::<i64, _>(datetimes).map_err(to_py_err)?;
             Ok(numbers.into_py(py))
         }
+        DType::UInt32 => {
+            let numbers = converter.encode::<i64, _>(datetimes).map_err(to_py_err)?;
+            Ok(to_unsigned::<u32>(numbers, dtype)?.into_py(py))
+        }
+        DType::UInt64 => {
+            let numbers = converter.encode::<i64, _>(datetimes).map_err(to_py_err)?;
+            Ok(to_unsigned::<u64>(numbers, dtype)?.into_py(py))
+        }
         DType::Float32 => {
-            let numbers: Vec<f32> = dts
-                .encode_cf(units.as_str(), calendar)
-                .map_err(|e| PyValueError::new_err(format!("Could not encode datetimes: {}", e)))?;
+            let numbers = converter.encode::<f32, _>(datetimes).map_err(to_py_err)?;
             Ok(numbers.into_py(py))
         }
         DType::Float64 => {
-            let numbers: Vec<f64> = dts
-                .encode_cf(units.as_str(), calendar)
-                .map_err(|e| PyValueError::new_err(format!("Could not encode datetimes: {}", e)))?;
+            let numbers = converter.encode::<f64, _>(datetimes).map_err(to_py_err)?;
             Ok(numbers.into_py(py))
         }
-        DType::Unknown => Err(PyValueError::new_err(format!(
-            "Invalid dtype `{}`. For i32 use {}. For i64 use {}. For f32 use {}. For f64 use {}.",
-            dtype,
-            INT_32_TYPES.join(", "),
-            INT_64_TYPES.join(", "),
-            FLOAT_32_TYPES.join(", "),
-            FLOAT_64_TYPES.join(", ")
-        ))),
     }
 }
+
+/// Parses the units of [encode_with_dtype] once for all the datetimes
+fn encoding_converter(units: &str, calendar: Calendar) -> PyResult<CFConverter> {
+    CFConverter::new(units, calendar)
+        .map_err(|e| PyValueError::new_err(format!("Could not encode datetimes: {}", e)))
+}
+
+fn invalid_dtype_error(dtype: &str) -> PyErr {
+    let names: Vec<String> = DType::ALL
+        .iter()
+        .map(|dtype| format!(" For {} use {}.", dtype, dtype.names().join(", ")))
+        .collect();
+    PyValueError::new_err(format!("Invalid dtype `{}`.{}", dtype, names.concat()))
+}
+
+/// PyCFConverter is a wrapper around Rust CFConverter, exposed as `Converter` in Python.
+/// It parses the units once to convert many batches of numbers or datetimes with the same units.
+#[pyclass(module = "cftime_rs", name = "Converter")]
+pub struct PyCFConverter {
+    pub converter: CFConverter,
+}
+
+#[pymethods]
+impl PyCFConverter {
+    /// Makes a new `Converter` with given units and calendar name, the year of the reference date
+    /// of the units being counted with or without a year zero
+    #[new]
+    #[pyo3(signature = (units, calendar, has_year_zero=true))]
+    pub fn new(units: String, calendar: String, has_year_zero: bool) -> PyResult<Self> {
+        let calendar = Calendar::from_str(calendar.as_str())
+            .map_err(|e| PyValueError::new_err(format!("Could not parse calendar: {}", e)))?;
+        let converter = CFConverter::new_with_year_zero(units.as_str(), calendar, has_year_zero)
+            .map_err(|e| PyValueError::new_err(format!("Could not parse units: {}", e)))?;
+        Ok(Self { converter })
+    }
+    /// Returns the units of the converter.
+    #[getter]
+    pub fn units(&self) -> &str {
+        self.converter.units()
+    }
+    /// Returns the calendar of the converter.
+    #[getter]
+    pub fn calendar(&self) -> PyCFCalendar {
+        PyCFCalendar {
+            calendar: self.converter.calendar(),
+        }
+    }
+    /// Decodes the numbers into PyCFDatetime, see `num2date`
+    pub fn num2date(&self, numbers: &PyAny) -> PyResult<Vec<PyCFDatetime>> {
+        let to_py_err = |e: crate::errors::Error| {
+            PyValueError::new_err(format!(
+                "Could not decode numbers {} into PyCFDatetime: {}",
+                numbers, e
+            ))
+        };
+        // Integers first to keep their precision, the floats accept the integers too
+        let datetimes = if let Ok(numbers) = numbers.extract::<Vec<i64>>() {
+            self.converter.decode(&numbers).map_err(to_py_err)?
+        } else if let Ok(numbers) = numbers.extract::<Vec<f64>>() {
+            self.converter.decode(&numbers).map_err(to_py_err)?
+        } else {
+            return Err(PyValueError::new_err(
+                "Could not convert array to supported types. \
+                Needs an one-dimensional array of one the following types: i64, f64",
+            ));
+        };
+        Ok(datetimes
+            .into_iter()
+            .map(|dt| PyCFDatetime { dt: dt.into() })
+            .collect())
+    }
+    /// Encodes the datetimes into numbers of the given dtype, see `date2num`
+    pub fn date2num(&self, py: Python, datetimes: &PyAny, dtype: &str) -> PyResult<PyObject> {
+        let datetimes = extract_datetimes(datetimes, self.converter.calendar())?;
+        let dts: Vec<&CFDatetime> = datetimes.iter().map(|datetime| &**datetime).collect();
+        encode_with_dtype(py, &self.converter, &dts, dtype)
+    }
+    fn __repr__(&self) -> String {
+        format!(
+            "Converter({}, {})",
+            self.converter.units(),
+            self.converter.calendar().cf_name()
+        )
+    }
+}
+
+/// PyTimeUnitsConverter is a wrapper around Rust TimeUnitsConverter, exposed as
+/// `TimeUnitsConverter` in Python. It gives the exact lengths of the days, months or years of a
+/// time axis to weight aggregations.
+#[pyclass(module = "cftime_rs", name = "TimeUnitsConverter")]
+pub struct PyTimeUnitsConverter {
+    pub converter: TimeUnitsConverter,
+}
+
+#[pymethods]
+impl PyTimeUnitsConverter {
+    /// Makes a new `TimeUnitsConverter` for the periods of the calendar
+    #[new]
+    pub fn new(period: String, calendar: String) -> PyResult<Self> {
+        let period = Period::from_str(period.as_str())
+            .map_err(|e| PyValueError::new_err(format!("Could not parse period: {}", e)))?;
+        let calendar = Calendar::from_str(calendar.as_str())
+            .map_err(|e| PyValueError::new_err(format!("Could not parse calendar: {}", e)))?;
+        Ok(Self {
+            converter: TimeUnitsConverter::new(period, calendar),
+        })
+    }
+    /// Returns the period of the converter
+    #[getter]
+    pub fn period(&self) -> String {
+        self.converter.period().to_string()
+    }
+    /// Returns the calendar of the converter
+    #[getter]
+    pub fn calendar(&self) -> PyCFCalendar {
+        PyCFCalendar {
+            calendar: self.converter.calendar(),
+        }
+    }
+    /// Returns the start of the period containing the datetime
+    pub fn period_start(&self, datetime: &PyCFDatetime) -> PyResult<PyCFDatetime> {
+        let start = self
+            .converter
+            .period_start(&datetime.dt)
+            .map_err(|e| PyValueError::new_err(format!("Could not find the period: {}", e)))?;
+        Ok(PyCFDatetime { dt: start.into() })
+    }
+    /// Returns the length in seconds of the period containing the datetime
+    pub fn period_seconds(&self, datetime: &PyCFDatetime) -> PyResult<i64> {
+        self.converter
+            .period_seconds(&datetime.dt)
+            .map_err(|e| PyValueError::new_err(format!("Could not find the period: {}", e)))
+    }
+    /// Returns the length in seconds of the period of every datetime
+    pub fn weights(&self, datetimes: Vec<PyCFDatetime>) -> PyResult<Vec<i64>> {
+        let dts: Vec<&CFDatetime> = datetimes.iter().map(|pydatetime| &*pydatetime.dt).collect();
+        self.converter
+            .weights(&dts)
+            .map_err(|e| PyValueError::new_err(format!("Could not compute the weights: {}", e)))
+    }
+    /// Returns the periods of a sorted time axis as tuples of the start and end of the period, the
+    /// slice of the datetimes within it and its length in seconds
+    #[allow(clippy::type_complexity)]
+    pub fn bins(
+        &self,
+        datetimes: Vec<PyCFDatetime>,
+    ) -> PyResult<Vec<(PyCFDatetime, PyCFDatetime, usize, usize, i64)>> {
+        let dts: Vec<&CFDatetime> = datetimes.iter().map(|pydatetime| &*pydatetime.dt).collect();
+        let bins = self
+            .converter
+            .bins(&dts)
+            .map_err(|e| PyValueError::new_err(format!("Could not compute the bins: {}", e)))?;
+        Ok(bins
+            .into_iter()
+            .map(|bin| {
+                (
+                    PyCFDatetime {
+                        dt: bin.start.into(),
+                    },
+                    PyCFDatetime { dt: bin.end.into() },
+                    bin.indices.start,
+                    bin.indices.end,
+                    bin.seconds,
+                )
+            })
+            .collect())
+    }
+    fn __repr__(&self) -> String {
+        format!(
+            "TimeUnitsConverter({}, {})",
+            self.converter.period(),
+            self.converter.calendar().cf_name()
+        )
+    }
+}
+
+#[pyfunction]
+fn date2num(
+    py: Python,
+    datetimes: &PyAny,
+    units: String,
+    calendar: String,
+    dtype: String,
+) -> PyResult<PyObject> {
+    let calendar = Calendar::from_str(calendar.as_str())
+        .map_err(|e| PyValueError::new_err(format!("Could not parse calendar: {}", e)))?;
+    let datetimes = extract_datetimes(datetimes, calendar)?;
+    let dts: Vec<&CFDatetime> = datetimes.iter().map(|datetime| &**datetime).collect();
+    let converter = encoding_converter(units.as_str(), calendar)?;
+    encode_with_dtype(py, &converter, &dts, dtype.as_str())
+}
+/// Either a python datetime or a python date, which is assumed to be at midnight
+pub enum PyDateLike<'a> {
+    DateTime(&'a PyAny),
+    Date(&'a PyAny),
+}
+
+impl<'a> pyo3::FromPyObject<'a> for PyDateLike<'a> {
+    // datetime.datetime is a subclass of datetime.date so it has to be checked first
+    #[cfg(not(feature = "abi3"))]
+    fn extract(obj: &'a PyAny) -> pyo3::PyResult<Self> {
+        if obj.downcast::<PyDateTime>().is_ok() {
+            return Ok(PyDateLike::DateTime(obj));
+        }
+        obj.downcast::<PyDate>()?;
+        Ok(PyDateLike::Date(obj))
+    }
+    // The datetime types are not part of the stable ABI
+    #[cfg(feature = "abi3")]
+    fn extract(obj: &'a PyAny) -> pyo3::PyResult<Self> {
+        let types = pydatetime_types(obj.py())?;
+        if obj.is_instance(types.datetime.as_ref(obj.py()))? {
+            return Ok(PyDateLike::DateTime(obj));
+        }
+        if obj.is_instance(types.date.as_ref(obj.py()))? {
+            return Ok(PyDateLike::Date(obj));
+        }
+        Err(pyo3::exceptions::PyTypeError::new_err(format!(
+            "'{}' object is not a date or a datetime",
+            obj.get_type().name()?
+        )))
+    }
+}
+
 // Create a newtype wrapper for Vec<PyDateTime>
 
 pub struct PyDateTimeList<'a> {
-    datetimes: Vec<&'a PyDateTime>,
+    datetimes: Vec<PyDateLike<'a>>,
 }
 
 impl<'a> pyo3::FromPyObject<'a> for PyDateTimeList<'a> {
     fn extract(obj: &'a PyAny) -> pyo3::PyResult<Self> {
-        let py_list = obj.downcast::<pyo3::types::PyList>()?;
+        let py_list = obj.downcast::<PyList>()?;
         let mut datetimes = Vec::with_capacity(py_list.len());
         for elem in py_list {
-            let py_dt = elem.extract::<&PyDateTime>()?;
-            datetimes.push(py_dt);
+            datetimes.push(elem.extract::<PyDateLike>()?);
         }
-        Ok(PyDateTimeList {
-            datetimes: datetimes,
-        })
+        Ok(PyDateTimeList { datetimes })
+    }
+}
+
+/// Convert a python datetime to a CFDatetime, keeping microseconds exactly.
+///
+/// Timezone aware datetimes are converted to UTC using their `utcoffset()`.
+fn pydatetime_to_cfdatetime(pydt: &PyAny, calendar: Calendar) -> PyResult<CFDatetime> {
+    let to_py_err = |e: crate::errors::Error| {
+        PyValueError::new_err(format!("Could not convert datetime to CFDatetime: {}", e))
+    };
+    let year = pydt.getattr("year")?.extract::<i64>()?;
+    let month = pydt.getattr("month")?.extract::<u8>()?;
+    let day = pydt.getattr("day")?.extract::<u8>()?;
+    let hour = pydt.getattr("hour")?.extract::<u8>()?;
+    let minute = pydt.getattr("minute")?.extract::<u8>()?;
+    let second = pydt.getattr("second")?.extract::<u8>()?;
+    let microsecond = pydt.getattr("microsecond")?.extract::<u32>()?;
+    let datetime =
+        CFDatetime::from_ymd_hms(year, month, day, hour, minute, second as f32, calendar)
+            .map_err(to_py_err)?;
+    let mut nanoseconds =
+        datetime.timestamp() as i128 * 1_000_000_000 + microsecond as i128 * 1_000;
+    let utcoffset = pydt.call_method0("utcoffset")?;
+    if !utcoffset.is_none() {
+        let days = utcoffset.getattr("days")?.extract::<i64>()?;
+        let seconds = utcoffset.getattr("seconds")?.extract::<i64>()?;
+        let microseconds = utcoffset.getattr("microseconds")?.extract::<i64>()?;
+        nanoseconds -=
+            (days * 86_400 + seconds) as i128 * 1_000_000_000 + microseconds as i128 * 1_000;
     }
+    CFDatetime::from_timestamp_nanos(nanoseconds, calendar).map_err(to_py_err)
+}
+
+/// Convert a python date to a CFDatetime at midnight.
+fn pydate_to_cfdatetime(pydate: &PyAny, calendar: Calendar) -> PyResult<CFDatetime> {
+    let year = pydate.getattr("year")?.extract::<i64>()?;
+    let month = pydate.getattr("month")?.extract::<u8>()?;
+    let day = pydate.getattr("day")?.extract::<u8>()?;
+    CFDatetime::from_ymd(year, month, day, calendar)
+        .map_err(|e| PyValueError::new_err(format!("Could not convert date to CFDatetime: {}", e)))
+}
+
+/// Converts the elements of any iterable, e.g. a list, a tuple, a generator or a numpy array of
+/// objects, to datetimes of the calendar. The elements are PyCFDatetime, kept as they are, or python
+/// datetimes and dates, converted as in `pydate2num`.
+///
+/// The errors give the index of the element that could not be converted.
+fn extract_datetimes(datetimes: &PyAny, calendar: Calendar) -> PyResult<Vec<Arc<CFDatetime>>> {
+    let py = datetimes.py();
+    let mut dts = Vec::with_capacity(datetimes.len().unwrap_or(0));
+    for (index, item) in datetimes.iter()?.enumerate() {
+        let item = item?;
+        if let Ok(datetime) = item.extract::<PyRef<PyCFDatetime>>() {
+            if datetime.dt.calendar() != calendar {
+                return Err(PyValueError::new_err(format!(
+                    "At index {index} : {}",
+                    crate::errors::Error::DifferentCalendars(
+                        datetime.dt.calendar().to_string(),
+                        calendar.to_string()
+                    )
+                )));
+            }
+            dts.push(datetime.dt.clone());
+            continue;
+        }
+        let datetime = match item.extract::<PyDateLike>() {
+            Ok(PyDateLike::DateTime(pydt)) => pydatetime_to_cfdatetime(pydt, calendar),
+            Ok(PyDateLike::Date(pydate)) => pydate_to_cfdatetime(pydate, calendar),
+            Err(_) => {
+                return Err(pyo3::exceptions::PyTypeError::new_err(format!(
+                    "At index {index} : '{}' object is not a PyCFDatetime, a datetime or a date",
+                    item.get_type().name()?
+                )))
+            }
+        }
+        .map_err(|e| PyValueError::new_err(format!("At index {index} : {}", e.value(py))))?;
+        dts.push(datetime.into());
+    }
+    Ok(dts)
 }
 
 #[pyfunction]
@@ -551,76 +1776,522 @@ fn pydate2num(
 ) -> PyResult<PyObject> {
     let calendar = Calendar::from_str(calendar.as_str())
         .map_err(|e| PyValueError::new_err(format!("Could not parse calendar: {}", e)))?;
-    let dtype_enum = DType::from_str(dtype.as_str())
-        .map_err(|e| PyValueError::new_err(format!("Could not parse dtype: {}", e)))?;
     let mut dts: Vec<CFDatetime> = Vec::with_capacity(datetimes.datetimes.len());
 
     for pydt in datetimes.datetimes.iter() {
-        let year = pydt.getattr("year")?.extract::<i64>()?;
-        let month = pydt.getattr("month")?.extract::<u8>()?;
-        let day = pydt.getattr("day")?.extract::<u8>()?;
-        let hour = pydt.getattr("hour")?.extract::<u8>()?;
-        let minute = pydt.getattr("minute")?.extract::<u8>()?;
-        let second = pydt.getattr("second")?.extract::<u8>()?;
-        let microsecond = pydt.getattr("microsecond")?.extract::<u32>()?;
-        let new_second = second as f32 + (microsecond / 1_000_000) as f32;
-        dts.push(
-            CFDatetime::from_ymd_hms(year, month, day, hour, minute, new_second, calendar)
-                .map_err(|e| {
-                    PyValueError::new_err(format!(
-                        "Could not convert datetime to CFDatetime: {}",
-                        e
-                    ))
-                })?,
-        );
+        let datetime = match pydt {
+            PyDateLike::DateTime(pydt) => pydatetime_to_cfdatetime(pydt, calendar)?,
+            PyDateLike::Date(pydate) => pydate_to_cfdatetime(pydate, calendar)?,
+        };
+        dts.push(datetime);
     }
 
-    match dtype_enum {
-        DType::Int32 => {
-            let numbers: Vec<i32> = dts
-                .encode_cf(units.as_str(), calendar)
-                .map_err(|e| PyValueError::new_err(format!("Could not encode datetimes: {}", e)))?;
-            Ok(numbers.into_py(py))
+    let converter = encoding_converter(units.as_str(), calendar)?;
+    encode_with_dtype(py, &converter, &dts, dtype.as_str())
+}
+
+enum Select {
+    Exact,
+    Before,
+    After,
+    Nearest,
+}
+
+impl FromStr for Select {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "exact" => Ok(Select::Exact),
+            "before" => Ok(Select::Before),
+            "after" => Ok(Select::After),
+            "nearest" => Ok(Select::Nearest),
+            _ => Err(format!(
+                "Invalid select `{}`. Should be one of exact, before, after, nearest",
+                s
+            )),
         }
-        DType::Int64 => {
-            let numbers: Vec<i64> = dts
-                .encode_cf(units.as_str(), calendar)
-                .map_err(|e| PyValueError::new_err(format!("Could not encode datetimes: {}", e)))?;
-            Ok(numbers.into_py(py))
+    }
+}
+
+impl std::fmt::Display for Select {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let name = match *self {
+            Select::Exact => "exact",
+            Select::Before => "before",
+            Select::After => "after",
+            Select::Nearest => "nearest",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Find the index of `target` in the time axis described by `numbers`, `reference` and `duration`.
+///
+/// Only the numbers needed are decoded. If the axis is sorted a binary search is used,
+/// otherwise only an exact match can be searched linearly.
+fn find_index(
+    target: &CFDatetime,
+    numbers: &[f64],
+    reference: &CFDatetime,
+    duration: &CFDuration,
+    select: &Select,
+    sorted: bool,
+) -> PyResult<usize> {
+    let decode_at = |index: usize| -> PyResult<i128> {
+        let datetime = numbers[index]
+            .scale(duration)
+            .and_then(|duration| reference + duration)
+            .map_err(|e| PyValueError::new_err(format!("Could not decode numbers: {}", e)))?;
+        Ok(datetime.timestamp_nanos())
+    };
+    let target_nanos = target.timestamp_nanos();
+    let not_found = || {
+        PyValueError::new_err(format!(
+            "Could not find {} in the time axis with select `{}`",
+            target, select
+        ))
+    };
+
+    if !sorted {
+        return match select {
+            Select::Exact => {
+                for index in 0..numbers.len() {
+                    if decode_at(index)? == target_nanos {
+                        return Ok(index);
+                    }
+                }
+                Err(not_found())
+            }
+            _ => Err(PyValueError::new_err(
+                "The time axis must be monotonically increasing for select other than `exact`",
+            )),
+        };
+    }
+
+    // First index whose datetime is greater or equal than the target
+    let (mut low, mut high) = (0, numbers.len());
+    while low < high {
+        let middle = low + (high - low) / 2;
+        if decode_at(middle)? < target_nanos {
+            low = middle + 1;
+        } else {
+            high = middle;
         }
-        DType::Float32 => {
-            let numbers: Vec<f32> = dts
-                .encode_cf(units.as_str(), calendar)
-                .map_err(|e| PyValueError::new_err(format!("Could not encode datetimes: {}", e)))?;
-            Ok(numbers.into_py(py))
+    }
+    let is_exact = low < numbers.len() && decode_at(low)? == target_nanos;
+    match select {
+        Select::Exact if is_exact => Ok(low),
+        Select::Exact => Err(not_found()),
+        Select::Before if is_exact => Ok(low),
+        Select::Before if low == 0 => Err(not_found()),
+        Select::Before => Ok(low - 1),
+        Select::After if low == numbers.len() => Err(not_found()),
+        Select::After => Ok(low),
+        Select::Nearest if numbers.is_empty() => Err(not_found()),
+        Select::Nearest if low == 0 => Ok(0),
+        Select::Nearest if low == numbers.len() => Ok(low - 1),
+        Select::Nearest => {
+            let before = target_nanos - decode_at(low - 1)?;
+            let after = decode_at(low)? - target_nanos;
+            Ok(if after < before { low } else { low - 1 })
         }
-        DType::Float64 => {
-            let numbers: Vec<f64> = dts
-                .encode_cf(units.as_str(), calendar)
-                .map_err(|e| PyValueError::new_err(format!("Could not encode datetimes: {}", e)))?;
-            Ok(numbers.into_py(py))
+    }
+}
+
+#[pyfunction]
+#[pyo3(signature = (dates, numbers, units, calendar, select="exact"))]
+fn date2index(
+    py: Python,
+    dates: &PyAny,
+    numbers: Vec<f64>,
+    units: String,
+    calendar: String,
+    select: &str,
+) -> PyResult<PyObject> {
+    let calendar = Calendar::from_str(calendar.as_str())
+        .map_err(|e| PyValueError::new_err(format!("Could not parse calendar: {}", e)))?;
+    let select = Select::from_str(select).map_err(PyValueError::new_err)?;
+    let (reference, unit, multiplier) = get_datetime_and_unit_from_units(units.as_str(), calendar)
+        .map_err(|e| PyValueError::new_err(format!("Could not parse units: {}", e)))?;
+    let duration = unit
+        .to_duration_with_multiplier(calendar, multiplier)
+        .map_err(|e| PyValueError::new_err(format!("Could not parse units: {}", e)))?;
+    let sorted = numbers.windows(2).all(|w| w[0] <= w[1]);
+
+    let find = |date: &PyCFDatetime| -> PyResult<usize> {
+        if date.dt.calendar() != calendar {
+            return Err(PyValueError::new_err(format!(
+                "{}",
+                crate::errors::Error::DifferentCalendars(
+                    date.dt.calendar().to_string(),
+                    calendar.to_string()
+                )
+            )));
         }
-        DType::Unknown => Err(PyValueError::new_err(format!(
-            "Invalid dtype `{}`. For i32 use {}. For i64 use {}. For f32 use {}. For f64 use {}.",
-            dtype,
-            INT_32_TYPES.join(", "),
-            INT_64_TYPES.join(", "),
-            FLOAT_32_TYPES.join(", "),
-            FLOAT_64_TYPES.join(", ")
-        ))),
+        find_index(&date.dt, &numbers, &reference, &duration, &select, sorted)
+    };
+    if let Ok(date) = dates.extract::<PyCFDatetime>() {
+        return Ok(find(&date)?.into_py(py));
     }
+    let dates = dates.extract::<Vec<PyCFDatetime>>()?;
+    let indices = dates.iter().map(find).collect::<PyResult<Vec<usize>>>()?;
+    Ok(indices.into_py(py))
 }
 
-/// cftime_rs is a python module that is implemented in Rust.
-#[pymodule]
-fn cftime_rs(_py: Python, m: &PyModule) -> PyResult<()> {
+/// Computes the valid times of forecasts from their reference times and lead times,
+/// see [crate::forecast::forecast_valid_times]
+#[pyfunction]
+fn forecast_valid_times(
+    reference_times: &PyAny,
+    reference_units: String,
+    lead_times: &PyAny,
+    lead_units: String,
+    calendar: String,
+) -> PyResult<Vec<Vec<PyCFDatetime>>> {
+    let calendar = Calendar::from_str(calendar.as_str())
+        .map_err(|e| PyValueError::new_err(format!("Could not parse calendar: {}", e)))?;
+    let reference_units = reference_units.as_str();
+    let lead_units = lead_units.as_str();
+    // Integers first to keep their precision, the floats accept the integers too
+    let valid_times = if let (Ok(reference_times), Ok(lead_times)) = (
+        reference_times.extract::<Vec<i64>>(),
+        lead_times.extract::<Vec<i64>>(),
+    ) {
+        crate::forecast::forecast_valid_times(
+            &reference_times,
+            reference_units,
+            &lead_times,
+            lead_units,
+            calendar,
+        )
+    } else if let (Ok(reference_times), Ok(lead_times)) = (
+        reference_times.extract::<Vec<f64>>(),
+        lead_times.extract::<Vec<f64>>(),
+    ) {
+        crate::forecast::forecast_valid_times(
+            &reference_times,
+            reference_units,
+            &lead_times,
+            lead_units,
+            calendar,
+        )
+    } else {
+        return Err(PyValueError::new_err(
+            "Could not convert arrays to supported types. \
+            Needs one-dimensional arrays of one the following types: i64, f64",
+        ));
+    }
+    .map_err(|e| PyValueError::new_err(format!("Could not compute valid times: {}", e)))?;
+    Ok(valid_times
+        .into_iter()
+        .map(|row| {
+            row.into_iter()
+                .map(|dt| PyCFDatetime { dt: dt.into() })
+                .collect()
+        })
+        .collect())
+}
+
+/// Parses CF units into the name of the unit, the reference date in UTC in the given calendar
+/// and the timezone if any
+#[pyfunction]
+#[pyo3(signature = (units, calendar="standard"))]
+fn parse_units(
+    units: &str,
+    calendar: &str,
+) -> PyResult<(&'static str, PyCFDatetime, Option<String>)> {
+    let calendar = Calendar::from_str(calendar)
+        .map_err(|e| PyValueError::new_err(format!("Could not parse calendar: {}", e)))?;
+    let to_py_err =
+        |e: crate::errors::Error| PyValueError::new_err(format!("Could not parse units: {}", e));
+    let parsed = parse_cf_time(units).map_err(to_py_err)?;
+    let origin = get_reference_datetime(units, &parsed, calendar).map_err(to_py_err)?;
+    Ok((
+        parsed.unit.cf_name(),
+        PyCFDatetime { dt: origin.into() },
+        parsed.datetime.tz.map(|tz| tz.to_string()),
+    ))
+}
+
+/// Infers the step of a time axis and the indices of the datetimes that do not follow it,
+/// see [crate::frequency::infer_freq]
+#[pyfunction]
+fn infer_freq(datetimes: Vec<PyCFDatetime>) -> PyResult<Option<(PyCFDuration, Vec<usize>)>> {
+    let dts: Vec<&CFDatetime> = datetimes.iter().map(|pydatetime| &*pydatetime.dt).collect();
+    let frequency = crate::frequency::infer_freq(&dts)
+        .map_err(|e| PyValueError::new_err(format!("Could not infer the frequency: {}", e)))?;
+    Ok(frequency.map(|frequency| {
+        let calendar = dts[0].calendar();
+        (
+            PyCFDuration {
+                duration: frequency.step.to_duration(calendar),
+            },
+            frequency.anomalies,
+        )
+    }))
+}
+
+/// Returns the components of many datetimes as one list per component, see
+/// [CFDatetime::components_many]
+#[pyfunction]
+fn datetime_components(py: Python<'_>, datetimes: Vec<PyCFDatetime>) -> PyResult<&PyDict> {
+    let dts: Vec<&CFDatetime> = datetimes.iter().map(|pydatetime| &*pydatetime.dt).collect();
+    let components = CFDatetime::components_many(&dts)
+        .map_err(|e| PyValueError::new_err(format!("Could not compute the components: {}", e)))?;
+    let dict = PyDict::new(py);
+    dict.set_item("year", components.years)?;
+    dict.set_item("month", components.months)?;
+    dict.set_item("day", components.days)?;
+    dict.set_item("hour", components.hours)?;
+    dict.set_item("minute", components.minutes)?;
+    dict.set_item("second", components.seconds)?;
+    dict.set_item("nanosecond", components.nanoseconds)?;
+    Ok(dict)
+}
+
+/// Merges the time axes of several files, see [crate::merge::merge_time_axes]
+#[pyfunction]
+#[pyo3(signature = (axes, duplicates="first", require_monotonic=false))]
+fn merge_time_axes<'py>(
+    py: Python<'py>,
+    axes: Vec<Vec<PyCFDatetime>>,
+    duplicates: &str,
+    require_monotonic: bool,
+) -> PyResult<&'py PyDict> {
+    let duplicates = DuplicatePolicy::from_str(duplicates)
+        .map_err(|e| PyValueError::new_err(format!("{}", e)))?;
+    let policy = MergePolicy::default()
+        .with_duplicates(duplicates)
+        .with_require_monotonic(require_monotonic);
+    // The datetimes are shared with the python objects instead of copied
+    let axes: Vec<Vec<Arc<CFDatetime>>> = axes
+        .into_iter()
+        .map(|axis| axis.into_iter().map(|datetime| datetime.dt).collect())
+        .collect();
+    let merged = crate::merge::merge_time_axes(axes, policy)
+        .map_err(|e| PyValueError::new_err(format!("Could not merge time axes: {}", e)))?;
+    let source = |source: SourceIndex| (source.axis, source.index);
+    let result = PyDict::new(py);
+    let datetimes: Vec<PyCFDatetime> = merged
+        .datetimes
+        .into_iter()
+        .map(|dt| PyCFDatetime { dt })
+        .collect();
+    result.set_item("datetimes", datetimes.into_py(py))?;
+    let sources: Vec<(usize, usize)> = merged.sources.into_iter().map(source).collect();
+    result.set_item("sources", sources)?;
+    let duplicates: Vec<((usize, usize), (usize, usize))> = merged
+        .report
+        .duplicates
+        .into_iter()
+        .map(|duplicate| (source(duplicate.kept), source(duplicate.dropped)))
+        .collect();
+    result.set_item("duplicates", duplicates)?;
+    result.set_item("overlaps", merged.report.overlaps)?;
+    Ok(result)
+}
+
+/// Validates a time coordinate against the CF conventions, see [crate::validation]
+#[pyfunction]
+fn validate_time_coordinate<'py>(
+    py: Python<'py>,
+    numbers: &PyAny,
+    units: String,
+    calendar: String,
+) -> PyResult<Vec<&'py PyDict>> {
+    let calendar = Calendar::from_str(calendar.as_str())
+        .map_err(|e| PyValueError::new_err(format!("Could not parse calendar: {}", e)))?;
+    let report = if let Ok(numbers) = numbers.extract::<Vec<i64>>() {
+        crate::validation::validate_time_coordinate(units.as_str(), calendar, &numbers)
+    } else if let Ok(numbers) = numbers.extract::<Vec<f64>>() {
+        crate::validation::validate_time_coordinate(units.as_str(), calendar, &numbers)
+    } else {
+        return Err(PyValueError::new_err(
+            "Could not convert array to supported types. \
+            Needs an one-dimensional array of one the following types: i64, f64",
+        ));
+    };
+    report
+        .issues
+        .into_iter()
+        .map(|issue| {
+            let py_issue = PyDict::new(py);
+            py_issue.set_item("kind", issue.kind.to_string())?;
+            py_issue.set_item("index", issue.index)?;
+            py_issue.set_item("message", issue.message)?;
+            Ok(py_issue)
+        })
+        .collect()
+}
+
+/// Converts numbers from some units to others without making the datetimes, see
+/// [crate::converter::rebase_cf]
+#[pyfunction]
+fn rebase_units(
+    py: Python<'_>,
+    numbers: &PyAny,
+    from_units: String,
+    to_units: String,
+    calendar: String,
+) -> PyResult<PyObject> {
+    let calendar = Calendar::from_str(calendar.as_str())
+        .map_err(|e| PyValueError::new_err(format!("Could not parse calendar: {}", e)))?;
+    let to_py_err =
+        |e: crate::errors::Error| PyValueError::new_err(format!("Could not rebase numbers: {}", e));
+    // The integers stay integers, the floats accept the integers too
+    if let Ok(numbers) = numbers.extract::<Vec<i64>>() {
+        let numbers: Vec<i64> =
+            rebase_cf(&numbers, &from_units, &to_units, calendar).map_err(to_py_err)?;
+        Ok(numbers.into_py(py))
+    } else if let Ok(numbers) = numbers.extract::<Vec<f64>>() {
+        let numbers: Vec<f64> =
+            rebase_cf(&numbers, &from_units, &to_units, calendar).map_err(to_py_err)?;
+        Ok(numbers.into_py(py))
+    } else {
+        Err(PyValueError::new_err(
+            "Could not convert array to supported types. \
+            Needs an one-dimensional array of one the following types: i64, f64",
+        ))
+    }
+}
+
+/// Encodes the datetimes with the units and type recommended to write them in a netCDF file,
+/// see [crate::encoder::encode_for_netcdf]
+#[pyfunction]
+#[pyo3(signature = (datetimes, policy="integer"))]
+fn encode_for_netcdf<'py>(
+    py: Python<'py>,
+    datetimes: Vec<PyCFDatetime>,
+    policy: &str,
+) -> PyResult<&'py PyDict> {
+    let policy = match policy {
+        "integer" => crate::encoder::EncodingPolicy::Integer,
+        "double_days" => crate::encoder::EncodingPolicy::DoubleDays,
+        _ => {
+            return Err(PyValueError::new_err(format!(
+                "Invalid policy `{policy}`, expected `integer` or `double_days`"
+            )))
+        }
+    };
+    let datetimes: Vec<&CFDatetime> = datetimes.iter().map(|datetime| &*datetime.dt).collect();
+    let encoded = crate::encoder::encode_for_netcdf(&datetimes, policy)
+        .map_err(|e| PyValueError::new_err(format!("Could not encode datetimes: {}", e)))?;
+    let result = PyDict::new(py);
+    match encoded.values {
+        crate::encoder::EncodedValues::Int64(values) => result.set_item("values", values)?,
+        crate::encoder::EncodedValues::Float64(values) => result.set_item("values", values)?,
+    }
+    result.set_item("units", encoded.units)?;
+    result.set_item("calendar", encoded.calendar_attr)?;
+    // Names of numpy and netCDF4 rather than the ones of the configurations
+    let dtype = match encoded.dtype {
+        DType::Int64 => "int64",
+        _ => "float64",
+    };
+    result.set_item("dtype", dtype)?;
+    Ok(result)
+}
+
+/// Returns `true`, the functions being implemented in Rust. Packages that fall back to a pure
+/// python implementation when cftime_rs is missing can check it the same way
+#[pyfunction]
+fn is_accelerated() -> bool {
+    true
+}
+
+/// Returns the information about how the module has been built
+#[pyfunction]
+fn build_info<'py>(py: Python<'py>) -> PyResult<&'py PyDict> {
+    let info = PyDict::new(py);
+    info.set_item("version", env!("CARGO_PKG_VERSION"))?;
+    info.set_item("rustc_version", env!("CFTIME_RS_RUSTC_VERSION"))?;
+    info.set_item("target", env!("CFTIME_RS_TARGET"))?;
+    info.set_item("profile", env!("CFTIME_RS_PROFILE"))?;
+    info.set_item("abi3", cfg!(feature = "abi3"))?;
+    let features: Vec<&str> = env!("CFTIME_RS_FEATURES")
+        .split(',')
+        .filter(|feature| !feature.is_empty())
+        .collect();
+    info.set_item("features", features)?;
+    Ok(info)
+}
+
+/// Makes the `cftime_rs.calendars` submodule with the calendar aware types
+fn calendars_module<'py>(py: Python<'py>) -> PyResult<&'py PyModule> {
+    let m = PyModule::new(py, "cftime_rs.calendars")?;
+    m.add_class::<PyCFCalendar>()?;
+    m.add_class::<PyCFDuration>()?;
+    m.add_class::<PyCFDatetime>()?;
+    m.add_class::<PyTimeUnitsConverter>()?;
+    m.add_function(wrap_pyfunction!(infer_freq, m)?)?;
+    m.add_function(wrap_pyfunction!(datetime_components, m)?)?;
+    m.add_function(wrap_pyfunction!(merge_time_axes, m)?)?;
+    Ok(m)
+}
+
+/// Makes the `cftime_rs.units` submodule that parses the units
+fn units_module<'py>(py: Python<'py>) -> PyResult<&'py PyModule> {
+    let m = PyModule::new(py, "cftime_rs.units")?;
+    m.add_function(wrap_pyfunction!(parse_units, m)?)?;
+    // Names of the units as returned by parse_units e.g. UNIT_HOUR = "hours"
+    for (name, unit) in [
+        ("UNIT_YEAR", Unit::Year),
+        ("UNIT_MONTH", Unit::Month),
+        ("UNIT_WEEK", Unit::Week),
+        ("UNIT_DAY", Unit::Day),
+        ("UNIT_HOUR", Unit::Hour),
+        ("UNIT_MINUTE", Unit::Minute),
+        ("UNIT_SECOND", Unit::Second),
+        ("UNIT_MILLISECOND", Unit::Millisecond),
+        ("UNIT_MICROSECOND", Unit::Microsecond),
+        ("UNIT_NANOSECOND", Unit::Nanosecond),
+    ] {
+        m.add(name, unit.cf_name())?;
+    }
+    Ok(m)
+}
+
+/// Makes the `cftime_rs.convert` submodule that decodes and encodes numbers
+fn convert_module<'py>(py: Python<'py>) -> PyResult<&'py PyModule> {
+    let m = PyModule::new(py, "cftime_rs.convert")?;
     m.add_function(wrap_pyfunction!(num2date, m)?)?;
     m.add_function(wrap_pyfunction!(date2num, m)?)?;
     m.add_function(wrap_pyfunction!(num2pydate, m)?)?;
+    m.add_function(wrap_pyfunction!(num2bounds, m)?)?;
     m.add_function(wrap_pyfunction!(pydate2num, m)?)?;
-    m.add_class::<PyCFCalendar>()?;
-    m.add_class::<PyCFDuration>()?;
-    m.add_class::<PyCFDatetime>()?;
+    m.add_function(wrap_pyfunction!(date2index, m)?)?;
+    m.add_function(wrap_pyfunction!(forecast_valid_times, m)?)?;
+    m.add_function(wrap_pyfunction!(validate_time_coordinate, m)?)?;
+    m.add_function(wrap_pyfunction!(encode_for_netcdf, m)?)?;
+    m.add_function(wrap_pyfunction!(rebase_units, m)?)?;
+    m.add_class::<PyCFConverter>()?;
+    Ok(m)
+}
+
+/// Adds the submodule as an attribute of `parent`, re-exporting its members at the top level,
+/// and registers it so `import cftime_rs.<name>` finds it
+fn add_submodule(py: Python, parent: &PyModule, name: &str, submodule: &PyModule) -> PyResult<()> {
+    for member in submodule.index()? {
+        let member: &str = member.extract()?;
+        parent.add(member, submodule.getattr(member)?)?;
+    }
+    // Not added with `PyModule::add_submodule` to keep the submodules out of `__all__`
+    parent.setattr(name, submodule)?;
+    py.import("sys")?
+        .getattr("modules")?
+        .set_item(format!("cftime_rs.{name}"), submodule)?;
+    Ok(())
+}
+
+/// cftime_rs is a python module that is implemented in Rust.
+#[pymodule]
+fn cftime_rs(py: Python, m: &PyModule) -> PyResult<()> {
+    add_submodule(py, m, "convert", convert_module(py)?)?;
+    add_submodule(py, m, "calendars", calendars_module(py)?)?;
+    add_submodule(py, m, "units", units_module(py)?)?;
+    m.add_function(wrap_pyfunction!(build_info, m)?)?;
+    m.add_function(wrap_pyfunction!(is_accelerated, m)?)?;
+    m.add("__version__", env!("CARGO_PKG_VERSION"))?;
 
     Ok(())
 }
@@ -0,0 +1,204 @@
+//! Module of the low level conversions between timestamps and dates.
+//!
+//! The functions of this module convert the seconds since 1970-01-01 of a calendar into the year,
+//! month, day, hour, minute and second of the date and back, without going through a
+//! [crate::datetime::CFDatetime]. They are meant for array libraries that store the times as raw
+//! `i64` buffers, e.g. arrow or numpy `datetime64[s]`.
+//!
+//! # Stability
+//!
+//! The functions of this module are part of the stable API of the crate: their signatures and
+//! results only change with a new major version. The helpers of [crate::utils] are public for the
+//! implementation of the calendars and may change in any version.
+//!
+//! The years are astronomical, see [crate::utils::to_astronomical_year], and the timestamps of
+//! the standard and proleptic gregorian calendars are unix timestamps.
+//!
+//! # Performance
+//!
+//! Converting the timestamps of a sorted time axis with [ymd_hms_from_timestamps] carries the
+//! days from one date to the next instead of converting every timestamp from 1970. Run
+//! `cargo bench --bench ymd_hms` for the timings of both conversions.
+
+use crate::calendars::Calendar;
+use crate::constants;
+use crate::datetime::YmdHms;
+use crate::utils::{get_hms_from_timestamp, get_timestamp_from_hms};
+use alloc::vec::Vec;
+
+/// Returns the year, month, day, hour, minute and second of the seconds since 1970-01-01 in the
+/// calendar
+///
+/// # Example
+///
+/// ```
+/// # use cftime_rs::{calendars::Calendar, raw::ymd_hms_from_timestamp};
+/// assert_eq!(ymd_hms_from_timestamp(86_400 + 3_661, Calendar::NoLeap), (1970, 1, 2, 1, 1, 1));
+/// ```
+pub fn ymd_hms_from_timestamp(timestamp: i64, calendar: Calendar) -> YmdHms {
+    let (year, month, day) =
+        calendar.days_to_ymd(timestamp.div_euclid(constants::SECS_PER_DAY as i64));
+    let (hour, minute, second) = get_hms_from_timestamp(timestamp);
+    (year, month, day, hour, minute, second)
+}
+
+/// Returns the seconds since 1970-01-01 of the date in the calendar, the inverse of
+/// [ymd_hms_from_timestamp]. As [crate::datetimes::traits::CalendarOps::ymd_to_days], the day is
+/// not checked against the length of the month, e.g. `2001-02-29` is `2001-03-01`.
+///
+/// # Returns
+///
+/// A Result containing the timestamp or an error of type `crate::errors::Error::InvalidDate` if
+/// the month is not between 1 and 12 or if the timestamp does not fit in an `i64`, or of type
+/// `crate::errors::Error::InvalidTime` if the time is not valid
+pub fn timestamp_from_ymd_hms(
+    (year, month, day, hour, minute, second): YmdHms,
+    calendar: Calendar,
+) -> crate::errors::Result<i64> {
+    let (seconds, _) = get_timestamp_from_hms(hour, minute, second as f32)?;
+    let days = calendar.ymd_to_days(year, month, day)?;
+    days.checked_mul(constants::SECS_PER_DAY as i64)
+        .and_then(|timestamp| timestamp.checked_add(seconds))
+        .ok_or_else(|| {
+            crate::errors::Error::InvalidDate(format!(
+                "{year}-{month:02}-{day:02} {hour:02}:{minute:02}:{second:02} is out of the range of the calendar"
+            ))
+        })
+}
+
+/// Returns the dates of many seconds since 1970-01-01 in the calendar, see [ymd_hms_from_timestamp].
+///
+/// The dates of a sorted time axis are carried from one timestamp to the next, an unsorted
+/// timestamp or a gap of more than a century falls back to the full conversion.
+pub fn ymd_hms_from_timestamps(timestamps: &[i64], calendar: Calendar) -> Vec<YmdHms> {
+    let mut cursor = YmdCursor::new(calendar);
+    timestamps
+        .iter()
+        .map(|&timestamp| {
+            let (year, month, day) =
+                cursor.ymd(timestamp.div_euclid(constants::SECS_PER_DAY as i64));
+            let (hour, minute, second) = get_hms_from_timestamp(timestamp);
+            (year, month, day, hour, minute, second)
+        })
+        .collect()
+}
+
+/// Returns the seconds since 1970-01-01 of many dates in the calendar, see [timestamp_from_ymd_hms]
+pub fn timestamps_from_ymd_hms(
+    dates: &[YmdHms],
+    calendar: Calendar,
+) -> crate::errors::Result<Vec<i64>> {
+    dates
+        .iter()
+        .map(|&date| timestamp_from_ymd_hms(date, calendar))
+        .collect()
+}
+
+/// Maximum number of days carried month by month before falling back to the full conversion
+const MAX_CARRIED_DAYS: i64 = 36_525;
+
+/// Month of the last date converted by [ymd_hms_from_timestamps] and
+/// [crate::datetime::CFDatetime::ymd_hms_many]
+pub(crate) struct YmdCursor {
+    calendar: Calendar,
+    /// Year, month and number of days since 1970-01-01 of the first day of the month
+    month_start: Option<(i64, u8, i64)>,
+}
+
+impl YmdCursor {
+    pub(crate) fn new(calendar: Calendar) -> Self {
+        Self {
+            calendar,
+            month_start: None,
+        }
+    }
+    /// Returns `true` for the month of the julian / gregorian break whose days are not contiguous
+    fn is_gregorian_break(&self, year: i64, month: u8) -> bool {
+        self.calendar == Calendar::Standard && (year, month) == (1582, 10)
+    }
+    pub(crate) fn ymd(&mut self, days: i64) -> (i64, u8, u8) {
+        if let Some((mut year, mut month, mut start)) = self.month_start {
+            if days >= start && days - start < MAX_CARRIED_DAYS {
+                while let Ok(days_in_month) = self.calendar.days_in_month(year, month) {
+                    if days < start + days_in_month as i64 {
+                        break;
+                    }
+                    start += days_in_month as i64;
+                    (year, month) = if month == 12 {
+                        (year + 1, 1)
+                    } else {
+                        (year, month + 1)
+                    };
+                }
+                if !self.is_gregorian_break(year, month) {
+                    self.month_start = Some((year, month, start));
+                    return (year, month, (days - start + 1) as u8);
+                }
+            }
+        }
+        let (year, month, day) = self.calendar.days_to_ymd(days);
+        // In october 1582 of the standard calendar the 15th follows the 4th
+        let offset = if self.is_gregorian_break(year, month) && day >= 15 {
+            day as i64 - 11
+        } else {
+            day as i64 - 1
+        };
+        self.month_start = Some((year, month, days - offset));
+        (year, month, day)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::datetime::CFDatetime;
+
+    const CALENDARS: [Calendar; 6] = [
+        Calendar::Standard,
+        Calendar::ProlepticGregorian,
+        Calendar::Julian,
+        Calendar::NoLeap,
+        Calendar::AllLeap,
+        Calendar::Day360,
+    ];
+
+    #[test]
+    fn test_matches_datetimes() {
+        // Sorted with a step of 5 days and 1 second, then unsorted, across the gregorian break
+        let mut timestamps: Vec<i64> = (0..2_000)
+            .map(|i| -12_219_292_800 - 5_000 * 86_400 + i * (5 * 86_400 + 1))
+            .collect();
+        timestamps.extend([0, -1, 1_000_000_000, -86_400 * 365 * 3_000]);
+        for calendar in CALENDARS {
+            let dates = ymd_hms_from_timestamps(&timestamps, calendar);
+            for (&timestamp, &date) in timestamps.iter().zip(&dates) {
+                let datetime = CFDatetime::from_timestamp(timestamp, 0, calendar).unwrap();
+                assert_eq!(date, datetime.ymd_hms().unwrap(), "{calendar} {timestamp}");
+                assert_eq!(ymd_hms_from_timestamp(timestamp, calendar), date);
+            }
+            assert_eq!(
+                timestamps_from_ymd_hms(&dates, calendar).unwrap(),
+                timestamps
+            );
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(
+            timestamp_from_ymd_hms((2000, 13, 1, 0, 0, 0), Calendar::Standard)
+                .is_err_and(|e| e.is_invalid_datetime())
+        );
+        assert!(
+            timestamp_from_ymd_hms((2000, 1, 1, 24, 0, 0), Calendar::Standard)
+                .is_err_and(|e| e.is_invalid_datetime())
+        );
+        assert!(
+            timestamp_from_ymd_hms((i64::MAX, 1, 1, 0, 0, 0), Calendar::Day360)
+                .is_err_and(|e| e.is_invalid_datetime())
+        );
+        assert!(timestamps_from_ymd_hms(&[], Calendar::Standard)
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -0,0 +1,298 @@
+//! Module that groups a time axis into calendar periods to aggregate it.
+//!
+//! A [TimeUnitsConverter] gives the exact length in seconds of the day, month or year containing a
+//! datetime in its calendar, e.g. 30 days for February in the 360_day calendar and 28 days in the
+//! noleap calendar. [TimeUnitsConverter::bins] splits a sorted time axis into the periods it
+//! covers, and [TimeUnitsConverter::weights] returns the length of the period of every datetime,
+//! so the weighted mean of monthly values over a year is `sum(w * x) / sum(w)`.
+//!
+//! ```
+//! use cftime_rs::calendars::Calendar;
+//! use cftime_rs::datetime::CFDatetime;
+//! use cftime_rs::resample::{Period, TimeUnitsConverter};
+//!
+//! // Monthly means of two years in the noleap calendar
+//! let axis = (0..24)
+//!     .map(|month| {
+//!         CFDatetime::from_ymd(2000 + month / 12, (month % 12) as u8 + 1, 15, Calendar::NoLeap)
+//!             .unwrap()
+//!     })
+//!     .collect::<Vec<_>>();
+//! let weights = TimeUnitsConverter::new(Period::Month, Calendar::NoLeap)
+//!     .weights(&axis)
+//!     .unwrap();
+//! assert_eq!(weights[1], 28 * 86_400);
+//! let years = TimeUnitsConverter::new(Period::Year, Calendar::NoLeap)
+//!     .bins(&axis)
+//!     .unwrap();
+//! assert_eq!(years.len(), 2);
+//! assert_eq!(years[1].indices, 12..24);
+//! let total: i64 = weights[years[1].indices.clone()].iter().sum();
+//! assert_eq!(total, years[1].seconds);
+//! ```
+
+use crate::calendars::Calendar;
+use crate::datetime::CFDatetime;
+use alloc::{format, string::ToString, vec::Vec};
+use core::borrow::Borrow;
+use core::ops::Range;
+
+/// Calendar period of the bins of a [TimeUnitsConverter]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Period {
+    Day,
+    Month,
+    Year,
+}
+
+impl core::fmt::Display for Period {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let name = match self {
+            Period::Day => "day",
+            Period::Month => "month",
+            Period::Year => "year",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl core::str::FromStr for Period {
+    type Err = crate::errors::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "d" | "day" | "days" | "daily" => Ok(Period::Day),
+            "m" | "month" | "months" | "monthly" => Ok(Period::Month),
+            "y" | "year" | "years" | "yearly" | "annual" => Ok(Period::Year),
+            _ => Err(crate::errors::Error::InvalidDuration(format!(
+                "Invalid period `{s}`, expected one of day, month or year"
+            ))),
+        }
+    }
+}
+
+/// Period of a time axis covered by at least one datetime, see [TimeUnitsConverter::bins]
+pub struct ResampleBin {
+    /// Start of the period, included
+    pub start: CFDatetime,
+    /// Start of the next period, excluded
+    pub end: CFDatetime,
+    /// Indices of the datetimes of the axis within the period
+    pub indices: Range<usize>,
+    /// Length of the period in seconds
+    pub seconds: i64,
+}
+
+/// Converts the datetimes of a calendar into the days, months or years containing them and their
+/// exact lengths in seconds
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct TimeUnitsConverter {
+    period: Period,
+    calendar: Calendar,
+}
+
+impl TimeUnitsConverter {
+    /// Makes a new converter for the periods of the calendar
+    pub fn new(period: Period, calendar: Calendar) -> Self {
+        Self { period, calendar }
+    }
+    /// Returns the period of the converter
+    pub fn period(&self) -> Period {
+        self.period
+    }
+    /// Returns the calendar of the converter
+    pub fn calendar(&self) -> Calendar {
+        self.calendar
+    }
+    fn check_calendar(&self, datetime: &CFDatetime) -> crate::errors::Result<()> {
+        if datetime.calendar() != self.calendar {
+            return Err(crate::errors::Error::DifferentCalendars(
+                self.calendar.to_string(),
+                datetime.calendar().to_string(),
+            ));
+        }
+        Ok(())
+    }
+    /// Returns the start of the period containing the datetime, at midnight.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the start of the period or an error of type
+    /// `crate::errors::Error::DifferentCalendars` if the datetime is not in the calendar of the
+    /// converter.
+    pub fn period_start(&self, datetime: &CFDatetime) -> crate::errors::Result<CFDatetime> {
+        self.check_calendar(datetime)?;
+        match self.period {
+            Period::Day => {
+                let (year, month, day) = datetime.ymd()?;
+                CFDatetime::from_ymd(year, month, day, self.calendar)
+            }
+            Period::Month => datetime.start_of_month(),
+            Period::Year => datetime.start_of_year(),
+        }
+    }
+    /// Returns the start of the period following the one starting at `start`
+    fn next_start(&self, start: &CFDatetime) -> crate::errors::Result<CFDatetime> {
+        match self.period {
+            Period::Day => start.succ_day(),
+            Period::Month => start.add_months(1),
+            Period::Year => start.add_months(12),
+        }
+    }
+    /// Returns the length in seconds of the period containing the datetime, e.g.
+    /// `30 * 86_400` for any month of the 360_day calendar.
+    pub fn period_seconds(&self, datetime: &CFDatetime) -> crate::errors::Result<i64> {
+        let start = self.period_start(datetime)?;
+        let end = self.next_start(&start)?;
+        Ok(end.timestamp() - start.timestamp())
+    }
+    /// Returns the length in seconds of the period containing every datetime, aligned with the
+    /// datetimes.
+    ///
+    /// The lengths are computed once per period, so the datetimes are expected to be sorted but
+    /// an unsorted axis gives the same weights.
+    pub fn weights<T: Borrow<CFDatetime>>(
+        &self,
+        datetimes: &[T],
+    ) -> crate::errors::Result<Vec<i64>> {
+        let mut weights = Vec::with_capacity(datetimes.len());
+        let mut current: Option<(i64, i64, i64)> = None;
+        for datetime in datetimes {
+            let datetime = datetime.borrow();
+            self.check_calendar(datetime)?;
+            let timestamp = datetime.timestamp();
+            let seconds = match current {
+                Some((start, end, seconds)) if start <= timestamp && timestamp < end => seconds,
+                _ => {
+                    let start = self.period_start(datetime)?;
+                    let end = self.next_start(&start)?;
+                    let seconds = end.timestamp() - start.timestamp();
+                    current = Some((start.timestamp(), end.timestamp(), seconds));
+                    seconds
+                }
+            };
+            weights.push(seconds);
+        }
+        Ok(weights)
+    }
+    /// Splits a sorted time axis into the periods containing at least one datetime.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the bins in increasing order, an error of type
+    /// `crate::errors::Error::UnsortedAxis` with the index of the first datetime before its
+    /// predecessor, or `crate::errors::Error::DifferentCalendars` if a datetime is not in the
+    /// calendar of the converter.
+    pub fn bins<T: Borrow<CFDatetime>>(
+        &self,
+        datetimes: &[T],
+    ) -> crate::errors::Result<Vec<ResampleBin>> {
+        let mut bins: Vec<ResampleBin> = Vec::new();
+        for (index, datetime) in datetimes.iter().enumerate() {
+            let datetime = datetime.borrow();
+            self.check_calendar(datetime)?;
+            let key = (datetime.timestamp(), datetime.nanoseconds());
+            if index > 0 {
+                let previous = datetimes[index - 1].borrow();
+                if key < (previous.timestamp(), previous.nanoseconds()) {
+                    return Err(crate::errors::Error::UnsortedAxis(index));
+                }
+            }
+            if let Some(bin) = bins.last_mut() {
+                if key.0 < bin.end.timestamp() {
+                    bin.indices.end = index + 1;
+                    continue;
+                }
+            }
+            let start = self.period_start(datetime)?;
+            let end = self.next_start(&start)?;
+            bins.push(ResampleBin {
+                seconds: end.timestamp() - start.timestamp(),
+                start,
+                end,
+                indices: index..index + 1,
+            });
+        }
+        Ok(bins)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::str::FromStr;
+
+    #[test]
+    fn test_period_seconds() {
+        let day = 86_400;
+        for (calendar, february, year) in [
+            (Calendar::Day360, 30 * day, 360 * day),
+            (Calendar::NoLeap, 28 * day, 365 * day),
+            (Calendar::AllLeap, 29 * day, 366 * day),
+            (Calendar::Standard, 29 * day, 366 * day),
+        ] {
+            let dt = CFDatetime::from_ymd_hms(2000, 2, 10, 12, 0, 0.0, calendar).unwrap();
+            let months = TimeUnitsConverter::new(Period::Month, calendar);
+            assert_eq!(months.period_seconds(&dt).unwrap(), february);
+            assert!(
+                months.period_start(&dt).unwrap()
+                    == CFDatetime::from_ymd(2000, 2, 1, calendar).unwrap()
+            );
+            let years = TimeUnitsConverter::new(Period::Year, calendar);
+            assert_eq!(years.period_seconds(&dt).unwrap(), year);
+            let days = TimeUnitsConverter::new(Period::Day, calendar);
+            assert_eq!(days.period_seconds(&dt).unwrap(), day);
+        }
+        // October 1582 lost 10 days in the standard calendar
+        let dt = CFDatetime::from_ymd(1582, 10, 20, Calendar::Standard).unwrap();
+        let months = TimeUnitsConverter::new(Period::Month, Calendar::Standard);
+        assert_eq!(months.period_seconds(&dt).unwrap(), 21 * day);
+        let other = CFDatetime::from_ymd(2000, 1, 1, Calendar::NoLeap).unwrap();
+        assert!(months
+            .period_seconds(&other)
+            .err()
+            .unwrap()
+            .is_calendar_error());
+    }
+
+    #[test]
+    fn test_bins_and_weights() {
+        // Daily values from 2000-01-30 to 2000-03-01 in the 360_day calendar
+        let calendar = Calendar::Day360;
+        let start = CFDatetime::from_ymd(2000, 1, 30, calendar).unwrap();
+        let mut axis = vec![start];
+        for _ in 0..31 {
+            let next = axis.last().unwrap().succ_day().unwrap();
+            axis.push(next);
+        }
+        let converter = TimeUnitsConverter::new(Period::Month, calendar);
+        let bins = converter.bins(&axis).unwrap();
+        assert_eq!(bins.len(), 3);
+        assert_eq!(bins[0].indices, 0..1);
+        assert_eq!(bins[1].indices, 1..31);
+        assert_eq!(bins[2].indices, 31..32);
+        assert!(bins.iter().all(|bin| bin.seconds == 30 * 86_400));
+        assert!(bins[1].start == CFDatetime::from_ymd(2000, 2, 1, calendar).unwrap());
+        assert!(bins[1].end == CFDatetime::from_ymd(2000, 3, 1, calendar).unwrap());
+        let weights = converter.weights(&axis).unwrap();
+        assert_eq!(weights.len(), axis.len());
+        assert!(weights.iter().all(|w| *w == 30 * 86_400));
+        let days = TimeUnitsConverter::new(Period::Day, calendar);
+        assert_eq!(days.bins(&axis).unwrap().len(), axis.len());
+        assert!(converter.bins::<CFDatetime>(&[]).unwrap().is_empty());
+        axis.swap(3, 4);
+        assert!(matches!(
+            converter.bins(&axis),
+            Err(crate::errors::Error::UnsortedAxis(4))
+        ));
+        assert_eq!(converter.weights(&axis).unwrap().len(), axis.len());
+    }
+
+    #[test]
+    fn test_period_from_str() {
+        assert_eq!(Period::from_str("Monthly").unwrap(), Period::Month);
+        assert_eq!(Period::from_str(" year ").unwrap(), Period::Year);
+        assert_eq!(Period::from_str("d").unwrap(), Period::Day);
+        assert_eq!(Period::Month.to_string(), "month");
+        assert!(Period::from_str("fortnight").is_err());
+    }
+}
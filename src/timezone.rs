@@ -1,11 +1,17 @@
+/// A fixed offset from UTC
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub struct Tz {
-    hour: i8,
-    minute: u8,
+    /// Signed offset in minutes
+    offset: i16,
 }
 
 impl Tz {
-    pub fn new(hour: i8, minute: u8) -> Result<Self, crate::errors::Error> {
+    /// Makes a new `Tz` from an hour and a minute offset.
+    ///
+    /// The minute offset takes the sign of the hour offset, i.e. `Tz::new(-3, 30)` is `-03:30`.
+    /// Use [Tz::from_str](core::str::FromStr) to express negative offsets lower than one hour
+    /// such as `-00:30`.
+    pub fn new(hour: i8, minute: u8) -> crate::errors::Result<Self> {
         if !(-23..=23).contains(&hour) {
             return Err(crate::errors::Error::InvalidTz(format!(
                 "Hour is out of bounds {}:{}",
@@ -18,6 +24,117 @@ impl Tz {
                 hour, minute
             )));
         }
-        Ok(Self { hour, minute })
+        let sign = if hour < 0 { -1 } else { 1 };
+        Ok(Self {
+            offset: hour as i16 * 60 + sign * minute as i16,
+        })
+    }
+    /// Returns the signed offset from UTC in minutes
+    pub fn offset_minutes(&self) -> i16 {
+        self.offset
+    }
+}
+
+/// Parse a timezone offset such as `+05:30`, `-0600`, `-6`, `-0:30` or `Z`
+impl core::str::FromStr for Tz {
+    type Err = crate::errors::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || crate::errors::Error::InvalidTz(format!("Invalid timezone '{s}'"));
+        let trimmed = s.trim();
+        if trimmed == "Z" || trimmed.eq_ignore_ascii_case("UTC") {
+            return Ok(Self { offset: 0 });
+        }
+        let (sign, offset) = match trimmed.as_bytes().first() {
+            Some(b'-') => (-1, &trimmed[1..]),
+            Some(b'+') => (1, &trimmed[1..]),
+            _ => (1, trimmed),
+        };
+        let (hour, minute) = match offset.split_once(':') {
+            Some((hour, minute)) => (hour, minute),
+            // Only digits are valid, checking the boundary avoids splitting a character
+            None if offset.len() == 4 && offset.is_char_boundary(2) => offset.split_at(2),
+            None => (offset, "0"),
+        };
+        if hour.is_empty()
+            || minute.is_empty()
+            || !hour
+                .bytes()
+                .chain(minute.bytes())
+                .all(|c| c.is_ascii_digit())
+        {
+            return Err(invalid());
+        }
+        let hour = hour.parse::<u8>().map_err(|_| invalid())?;
+        let minute = minute.parse::<u8>().map_err(|_| invalid())?;
+        if hour > 23 {
+            return Err(crate::errors::Error::InvalidTz(format!(
+                "Hour is out of bounds in '{s}'"
+            )));
+        }
+        if minute > 59 {
+            return Err(crate::errors::Error::InvalidTz(format!(
+                "Minute is out of bounds in '{s}'"
+            )));
+        }
+        Ok(Self {
+            offset: sign * (hour as i16 * 60 + minute as i16),
+        })
+    }
+}
+
+/// Display a Tz with the following format : `+HH:MM`
+impl core::fmt::Display for Tz {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let sign = if self.offset < 0 { '-' } else { '+' };
+        let offset = self.offset.abs();
+        write!(f, "{}{:02}:{:02}", sign, offset / 60, offset % 60)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::str::FromStr;
+
+    #[test]
+    fn test_from_str() {
+        let offsets = vec![
+            ("+05:30", 330),
+            ("-0600", -360),
+            ("Z", 0),
+            ("UTC", 0),
+            ("-6", -360),
+            ("2", 120),
+            ("-0:30", -30),
+            ("-03:30", -210),
+            ("+00:00", 0),
+        ];
+        for (input, expected) in offsets {
+            let tz = Tz::from_str(input).unwrap();
+            assert_eq!(tz.offset_minutes(), expected, "{input}");
+        }
+        for input in [
+            "",
+            "+",
+            "24:00",
+            "+05:60",
+            "-05:",
+            "05:30:00",
+            "+5a",
+            "--05",
+            "1\u{a0}1",
+            "\u{1650}0",
+        ] {
+            assert!(Tz::from_str(input).is_err(), "{input}");
+        }
+    }
+
+    #[test]
+    fn test_new_and_display() {
+        assert_eq!(Tz::new(-3, 30).unwrap(), Tz::from_str("-03:30").unwrap());
+        assert_eq!(Tz::from_str("-0:30").unwrap().to_string(), "-00:30");
+        assert_eq!(Tz::from_str("+0530").unwrap().to_string(), "+05:30");
+        assert!(Tz::new(24, 0).is_err());
+        assert!(Tz::new(0, 60).is_err());
     }
 }
@@ -0,0 +1,396 @@
+//! Module of the datetimes whose calendar is known at compile time.
+//!
+//! A [CFDatetime] stores a calendar datetime behind a `Box<dyn CalendarDatetime>`, or in an enum
+//! with the `inline-datetimes` feature, so every access goes through a dynamic dispatch.
+//! [TypedCFDatetime] is parameterized by the calendar datetime type instead, e.g.
+//! `TypedCFDatetime<NoLeapDatetime>`. It is `Copy`, never allocates, and its conversions and
+//! arithmetic are inlined, which suits the inner loops processing millions of datetimes of a
+//! single calendar. It converts from and to [CFDatetime] to use the rest of the crate.
+//!
+//! ```
+//! use cftime_rs::calendars::Calendar;
+//! use cftime_rs::datetime::CFDatetime;
+//! use cftime_rs::typed::NoLeapCFDatetime;
+//! use core::time::Duration;
+//!
+//! let start = NoLeapCFDatetime::from_ymd(2000, 2, 28).unwrap();
+//! let next = (start + Duration::from_secs(86_400)).unwrap();
+//! assert_eq!(next.ymd().unwrap(), (2000, 3, 1));
+//! let datetime = CFDatetime::from(next);
+//! assert_eq!(datetime.calendar(), Calendar::NoLeap);
+//! assert!(NoLeapCFDatetime::try_from(&datetime).unwrap() == next);
+//! ```
+
+use crate::calendars::Calendar;
+use crate::constants;
+use crate::datetime::CFDatetime;
+use crate::datetimes::all_leap::AllLeapDatetime;
+use crate::datetimes::day_360::Day360Datetime;
+use crate::datetimes::julian::JulianDatetime;
+use crate::datetimes::no_leap::NoLeapDatetime;
+use crate::datetimes::proleptic_gregorian::ProlepticGregorianDatetime;
+use crate::datetimes::standard::StandardDatetime;
+use crate::datetimes::traits::{CalendarDatetime, CalendarDatetimeCreator, CalendarOps};
+use crate::duration::CFDuration;
+use crate::utils::normalize_nanoseconds;
+use alloc::{format, string::ToString};
+use core::marker::PhantomData;
+
+/// Calendar datetime type that parameterizes a [TypedCFDatetime]
+pub trait TypedCalendar: CalendarOps + CalendarDatetime + CalendarDatetimeCreator {
+    /// The calendar of the datetimes
+    const CALENDAR: Calendar;
+}
+
+impl TypedCalendar for ProlepticGregorianDatetime {
+    const CALENDAR: Calendar = Calendar::ProlepticGregorian;
+}
+impl TypedCalendar for StandardDatetime {
+    const CALENDAR: Calendar = Calendar::Standard;
+}
+impl TypedCalendar for Day360Datetime {
+    const CALENDAR: Calendar = Calendar::Day360;
+}
+impl TypedCalendar for JulianDatetime {
+    const CALENDAR: Calendar = Calendar::Julian;
+}
+impl TypedCalendar for NoLeapDatetime {
+    const CALENDAR: Calendar = Calendar::NoLeap;
+}
+impl TypedCalendar for AllLeapDatetime {
+    const CALENDAR: Calendar = Calendar::AllLeap;
+}
+
+pub type ProlepticGregorianCFDatetime = TypedCFDatetime<ProlepticGregorianDatetime>;
+pub type StandardCFDatetime = TypedCFDatetime<StandardDatetime>;
+pub type Day360CFDatetime = TypedCFDatetime<Day360Datetime>;
+pub type JulianCFDatetime = TypedCFDatetime<JulianDatetime>;
+pub type NoLeapCFDatetime = TypedCFDatetime<NoLeapDatetime>;
+pub type AllLeapCFDatetime = TypedCFDatetime<AllLeapDatetime>;
+
+/// Datetime of the calendar `C`, stored as seconds and nanoseconds since the epoch as [CFDatetime].
+///
+/// The datetimes are ordered and hashed by their timestamps, which is only possible because they
+/// are all of the same calendar.
+pub struct TypedCFDatetime<C> {
+    timestamp: i64,
+    nanoseconds: u32,
+    // `fn() -> C` keeps the datetime `Send` and `Sync` whatever `C`
+    calendar: PhantomData<fn() -> C>,
+}
+
+impl<C: TypedCalendar> TypedCFDatetime<C> {
+    /// Creates a new datetime from a timestamp, as [CFDatetime::from_timestamp]
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the datetime or an error of type
+    /// `crate::errors::Error::OutOfBoundsCalendar` if the carried timestamp does not fit in an `i64`
+    #[inline]
+    pub fn from_timestamp(timestamp: i64, nanoseconds: u32) -> crate::errors::Result<Self> {
+        let (carry, nanoseconds) = normalize_nanoseconds(nanoseconds as i64);
+        let timestamp = timestamp.checked_add(carry).ok_or_else(|| {
+            crate::errors::Error::OutOfBoundsCalendar(
+                C::CALENDAR.to_string(),
+                format!("{timestamp} seconds and {nanoseconds} nanoseconds since epoch"),
+            )
+        })?;
+        Ok(Self::new(timestamp, nanoseconds))
+    }
+    /// Creates a new datetime from a number of nanoseconds since the epoch, as
+    /// [CFDatetime::from_timestamp_nanos]
+    #[inline]
+    pub fn from_timestamp_nanos(timestamp_nanos: i128) -> crate::errors::Result<Self> {
+        let ns_per_second = constants::MAX_NS as i128;
+        let timestamp = i64::try_from(timestamp_nanos.div_euclid(ns_per_second)).map_err(|_| {
+            crate::errors::Error::OutOfBoundsCalendar(
+                C::CALENDAR.to_string(),
+                format!("{timestamp_nanos} nanoseconds since epoch"),
+            )
+        })?;
+        Ok(Self::new(
+            timestamp,
+            timestamp_nanos.rem_euclid(ns_per_second) as u32,
+        ))
+    }
+    /// Creates a new datetime from the date and the time, as [CFDatetime::from_ymd_hms]
+    #[inline]
+    pub fn from_ymd_hms(
+        year: i64,
+        month: u8,
+        day: u8,
+        hour: u8,
+        minute: u8,
+        second: f32,
+    ) -> crate::errors::Result<Self> {
+        let datetime = C::from_ymd_hms(year, month, day, hour, minute, second)?;
+        Ok(Self::new(datetime.timestamp(), datetime.nanoseconds()))
+    }
+    /// Creates a new datetime at midnight, as [CFDatetime::from_ymd]
+    #[inline]
+    pub fn from_ymd(year: i64, month: u8, day: u8) -> crate::errors::Result<Self> {
+        Self::from_ymd_hms(year, month, day, 0, 0, 0.0)
+    }
+    #[inline]
+    fn new(timestamp: i64, nanoseconds: u32) -> Self {
+        Self {
+            timestamp,
+            nanoseconds,
+            calendar: PhantomData,
+        }
+    }
+    /// Returns the calendar
+    #[inline]
+    pub const fn calendar(&self) -> Calendar {
+        C::CALENDAR
+    }
+    /// Returns the number of seconds since the epoch
+    #[inline]
+    pub fn timestamp(&self) -> i64 {
+        self.timestamp
+    }
+    /// Returns the nanoseconds of the second
+    #[inline]
+    pub fn nanoseconds(&self) -> u32 {
+        self.nanoseconds
+    }
+    /// Returns the number of nanoseconds since the epoch
+    #[inline]
+    pub fn timestamp_nanos(&self) -> i128 {
+        self.timestamp as i128 * constants::MAX_NS as i128 + self.nanoseconds as i128
+    }
+    /// Returns the year, month, day, hour, minute and second, as [CFDatetime::ymd_hms]
+    #[inline]
+    pub fn ymd_hms(&self) -> crate::errors::Result<(i64, u8, u8, u8, u8, u8)> {
+        C::from_timestamp(self.timestamp, self.nanoseconds).ymd_hms()
+    }
+    /// Returns the year, month and day, as [CFDatetime::ymd]
+    #[inline]
+    pub fn ymd(&self) -> crate::errors::Result<(i64, u8, u8)> {
+        let (year, month, day, _, _, _) = self.ymd_hms()?;
+        Ok((year, month, day))
+    }
+    /// Adds a number of nanoseconds, negative to go back in time
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the datetime or an error of type
+    /// `crate::errors::Error::OutOfBoundsCalendar` if the timestamp does not fit in an `i64`
+    #[inline]
+    pub fn add_nanoseconds(&self, nanoseconds: i128) -> crate::errors::Result<Self> {
+        // A saturated sum is out of the range of the timestamps too
+        Self::from_timestamp_nanos(self.timestamp_nanos().saturating_add(nanoseconds))
+    }
+    /// Converts the datetime into a [CFDatetime] of the calendar
+    pub fn to_cfdatetime(&self) -> CFDatetime {
+        CFDatetime::from(*self)
+    }
+}
+
+impl<C> Clone for TypedCFDatetime<C> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<C> Copy for TypedCFDatetime<C> {}
+
+impl<C> PartialEq for TypedCFDatetime<C> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        (self.timestamp, self.nanoseconds) == (other.timestamp, other.nanoseconds)
+    }
+}
+impl<C> Eq for TypedCFDatetime<C> {}
+
+impl<C> PartialOrd for TypedCFDatetime<C> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl<C> Ord for TypedCFDatetime<C> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        (self.timestamp, self.nanoseconds).cmp(&(other.timestamp, other.nanoseconds))
+    }
+}
+
+impl<C> core::hash::Hash for TypedCFDatetime<C> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.timestamp.hash(state);
+        self.nanoseconds.hash(state);
+    }
+}
+
+impl<C: TypedCalendar> core::fmt::Debug for TypedCFDatetime<C> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "TypedCFDatetime({:#})", self.to_cfdatetime())
+    }
+}
+
+/// Displays the datetime as [CFDatetime]
+impl<C: TypedCalendar> core::fmt::Display for TypedCFDatetime<C> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        self.to_cfdatetime().fmt(f)
+    }
+}
+
+impl<C: TypedCalendar> From<TypedCFDatetime<C>> for CFDatetime {
+    fn from(datetime: TypedCFDatetime<C>) -> Self {
+        CFDatetime::from_timestamp(datetime.timestamp, datetime.nanoseconds, C::CALENDAR)
+            .expect("The nanoseconds of a typed datetime are below a second")
+    }
+}
+
+/// Converts a [CFDatetime] of the calendar `C`, or returns an error of type
+/// `crate::errors::Error::DifferentCalendars`
+impl<C: TypedCalendar> TryFrom<&CFDatetime> for TypedCFDatetime<C> {
+    type Error = crate::errors::Error;
+    fn try_from(datetime: &CFDatetime) -> Result<Self, Self::Error> {
+        if datetime.calendar() != C::CALENDAR {
+            return Err(crate::errors::Error::DifferentCalendars(
+                C::CALENDAR.to_string(),
+                datetime.calendar().to_string(),
+            ));
+        }
+        Ok(Self::new(datetime.timestamp(), datetime.nanoseconds()))
+    }
+}
+
+impl<C: TypedCalendar> TryFrom<CFDatetime> for TypedCFDatetime<C> {
+    type Error = crate::errors::Error;
+    fn try_from(datetime: CFDatetime) -> Result<Self, Self::Error> {
+        Self::try_from(&datetime)
+    }
+}
+
+impl<C: TypedCalendar> core::ops::Add<core::time::Duration> for TypedCFDatetime<C> {
+    type Output = crate::errors::Result<Self>;
+    #[inline]
+    fn add(self, rhs: core::time::Duration) -> Self::Output {
+        self.add_nanoseconds(rhs.as_nanos() as i128)
+    }
+}
+
+impl<C: TypedCalendar> core::ops::Sub<core::time::Duration> for TypedCFDatetime<C> {
+    type Output = crate::errors::Result<Self>;
+    #[inline]
+    fn sub(self, rhs: core::time::Duration) -> Self::Output {
+        self.add_nanoseconds(-(rhs.as_nanos() as i128))
+    }
+}
+
+/// Adds a CFDuration of the calendar. The durations of seconds are added inline, the durations
+/// with months go through [CFDatetime] to clamp the day to the length of the month.
+impl<C: TypedCalendar> core::ops::Add<&CFDuration> for TypedCFDatetime<C> {
+    type Output = crate::errors::Result<Self>;
+    #[inline]
+    fn add(self, rhs: &CFDuration) -> Self::Output {
+        if rhs.calendar() != C::CALENDAR {
+            return Err(crate::errors::Error::DifferentCalendars(
+                C::CALENDAR.to_string(),
+                rhs.calendar().to_string(),
+            ));
+        }
+        if rhs.months != 0 {
+            return Self::try_from((self.to_cfdatetime() + rhs)?);
+        }
+        self.add_nanoseconds(
+            rhs.seconds as i128 * constants::MAX_NS as i128 + rhs.nanoseconds as i128,
+        )
+    }
+}
+
+/// Subtracts a CFDuration of the calendar, see the addition
+impl<C: TypedCalendar> core::ops::Sub<&CFDuration> for TypedCFDatetime<C> {
+    type Output = crate::errors::Result<Self>;
+    #[inline]
+    fn sub(self, rhs: &CFDuration) -> Self::Output {
+        self + &(-rhs)
+    }
+}
+
+/// Returns the duration between two datetimes, which are always of the same calendar
+impl<C: TypedCalendar> core::ops::Sub for TypedCFDatetime<C> {
+    type Output = CFDuration;
+    #[inline]
+    fn sub(self, rhs: Self) -> Self::Output {
+        let nanoseconds = self.timestamp_nanos() - rhs.timestamp_nanos();
+        let ns_per_second = constants::MAX_NS as i128;
+        CFDuration::new(
+            nanoseconds.div_euclid(ns_per_second) as i64,
+            nanoseconds.rem_euclid(ns_per_second) as i64,
+            C::CALENDAR,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_matches_dynamic<C: TypedCalendar>() {
+        let typed = TypedCFDatetime::<C>::from_ymd_hms(1999, 12, 31, 23, 59, 30.25).unwrap();
+        let dynamic = CFDatetime::from_ymd_hms(1999, 12, 31, 23, 59, 30.25, C::CALENDAR).unwrap();
+        assert_eq!(typed.timestamp(), dynamic.timestamp());
+        assert_eq!(typed.nanoseconds(), dynamic.nanoseconds());
+        assert_eq!(typed.ymd_hms().unwrap(), dynamic.ymd_hms().unwrap());
+        assert_eq!(typed.to_string(), dynamic.to_string());
+        assert!(typed.to_cfdatetime() == dynamic);
+        assert!(TypedCFDatetime::<C>::try_from(&dynamic).unwrap() == typed);
+
+        let step = CFDuration::from_hours(36, C::CALENDAR);
+        let next = (typed + &step).unwrap();
+        let expected = (&dynamic + &step).unwrap();
+        assert!(next.to_cfdatetime() == expected);
+        assert!((next - &step).unwrap() == typed);
+        let month = CFDuration::from_months(2, C::CALENDAR);
+        let expected = (&dynamic + &month).unwrap();
+        assert!((typed + &month).unwrap().to_cfdatetime() == expected);
+        assert_eq!((next - typed).num_nanoseconds(), step.num_nanoseconds());
+    }
+
+    #[test]
+    fn test_typed_matches_dynamic() {
+        assert_matches_dynamic::<ProlepticGregorianDatetime>();
+        assert_matches_dynamic::<StandardDatetime>();
+        assert_matches_dynamic::<Day360Datetime>();
+        assert_matches_dynamic::<JulianDatetime>();
+        assert_matches_dynamic::<NoLeapDatetime>();
+        assert_matches_dynamic::<AllLeapDatetime>();
+    }
+
+    #[test]
+    fn test_typed_errors_and_ordering() {
+        assert!(Day360CFDatetime::from_ymd(2000, 13, 1).is_err());
+        assert!(StandardCFDatetime::from_ymd(1582, 10, 10).is_err());
+        let datetime = CFDatetime::from_ymd(2000, 1, 1, Calendar::NoLeap).unwrap();
+        assert!(Day360CFDatetime::try_from(&datetime)
+            .err()
+            .unwrap()
+            .is_calendar_error());
+        let typed = Day360CFDatetime::from_ymd(2000, 1, 1).unwrap();
+        let step = CFDuration::from_days(1, Calendar::NoLeap);
+        assert!((typed + &step).err().unwrap().is_calendar_error());
+        assert!(Day360CFDatetime::from_timestamp(i64::MAX, 1_000_000_000)
+            .err()
+            .unwrap()
+            .is_out_of_bounds());
+        assert!(typed.add_nanoseconds(i128::MAX).is_err());
+
+        let later = (typed + core::time::Duration::from_millis(1500)).unwrap();
+        assert_eq!(later.nanoseconds(), 500_000_000);
+        assert!(typed < later);
+        assert_eq!(
+            [later, typed].iter().min().unwrap().timestamp(),
+            typed.timestamp()
+        );
+        assert!((later - core::time::Duration::from_millis(1500)).unwrap() == typed);
+        assert_eq!(later.calendar(), Calendar::Day360);
+        assert_eq!(
+            format!("{later:?}"),
+            "TypedCFDatetime(2000-01-01 00:00:01.5 [360_day])"
+        );
+    }
+}
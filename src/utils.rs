@@ -1,40 +1,53 @@
 //! Utils crate where common behaviour for computing dates are shared
+//!
+//! The helpers are public for the implementation of the calendars and may change in any version,
+//! [crate::raw] has the stable conversions between timestamps and dates.
 
+use crate::float;
 use crate::{
     calendars::Calendar,
     constants,
     datetime::CFDatetime,
-    datetimes::traits::IsLeap,
+    datetimes::traits::CalendarOps,
     duration::CFDuration,
-    parser::{parse_cf_time, Unit},
+    parser::{parse_cf_time, ParsedCFTime, Unit},
 };
-use std::time::Duration;
+use alloc::{boxed::Box, string::ToString};
+use core::time::Duration;
 
-/// Calculates the timestamp from the given year, month, and day.
-///
-/// # Arguments
-///
-/// * `year` - The year.
-/// * `month` - The month.
-/// * `day` - The day.
-///
-/// # Returns
+/// Calculates the number of days between 1970-01-01 and the given year, month, and day
+/// with the leap years of the calendar `T`. The month must be between 1 and 12.
 ///
-/// The calculated timestamp.
+/// This is the default of [CalendarOps::ymd_to_days].
 ///
 /// # Errors
 ///
-/// Returns an error if there was an issue calculating the timestamp.
-pub fn get_timestamp_from_ymd<T: IsLeap>(
+/// Returns an error if the number of days does not fit in an `i64`.
+pub fn get_days_from_ymd<T: CalendarOps>(
     year: i64,
     month: u8,
     day: u8,
-) -> Result<i64, crate::errors::Error> {
-    let mut timestamp: i64 = 0;
+) -> crate::errors::Result<i64> {
+    let out_of_bounds = || {
+        crate::errors::Error::InvalidDate(format!(
+            "{year}-{month:02}-{day:02} is out of the range of the calendar"
+        ))
+    };
+    let mut days: i64 = 0;
+    let mut current_year: i64 = year;
 
-    // Calculate years
+    // Skip whole leap cycles, the remaining years are less than a cycle away from 1970
+    if let Some((cycle_years, cycle_days)) = T::LEAP_CYCLE {
+        let cycles = year
+            .checked_sub(constants::UNIX_DEFAULT_YEAR)
+            .ok_or_else(out_of_bounds)?
+            / cycle_years;
+        days = cycles.checked_mul(cycle_days).ok_or_else(out_of_bounds)?;
+        current_year -= cycles * cycle_years;
+    }
+    let remaining_year = current_year;
 
-    let mut current_year: i64 = year;
+    // Calculate years
     loop {
         if current_year == constants::UNIX_DEFAULT_YEAR {
             break;
@@ -42,41 +55,247 @@ pub fn get_timestamp_from_ymd<T: IsLeap>(
         // We have to look at the preceding year. For example if year == 1972
         // we have to look from 1971 to 1972
         let year_to_look_at = current_year - (current_year > constants::UNIX_DEFAULT_YEAR) as i64;
-        let seconds_in_year: i64 = if T::is_leap(year_to_look_at) {
-            constants::SECONDS_PER_YEAR_LEAP
+        let days_in_year: i64 = if T::is_leap(year_to_look_at) {
+            constants::DAYS_PER_YEAR_LEAP
         } else {
-            constants::SECONDS_PER_YEAR_NON_LEAP
+            constants::DAYS_PER_YEAR_NON_LEAP
         };
 
         if current_year > constants::UNIX_DEFAULT_YEAR {
-            timestamp += seconds_in_year;
+            days = days.checked_add(days_in_year).ok_or_else(out_of_bounds)?;
             current_year -= 1;
         } else {
-            timestamp -= seconds_in_year;
+            days = days.checked_sub(days_in_year).ok_or_else(out_of_bounds)?;
             current_year += 1;
         }
     }
 
     // Calculate months
-    let mut current_month = 0;
+    let cumulative_days = if T::is_leap(remaining_year) {
+        constants::CUM_DAYS_PER_MONTH_LEAP
+    } else {
+        constants::CUM_DAYS_PER_MONTH
+    };
+    let days_in_year = cumulative_days[(month - 1) as usize] as i64 + day as i64 - 1;
+
+    // Calculate days
+    days.checked_add(days_in_year).ok_or_else(out_of_bounds)
+}
+
+/// Converts a number of days since 1970-01-01 into the year, month, and day with the leap
+/// years of the calendar `T`.
+///
+/// This is the default of [CalendarOps::days_to_ymd].
+pub fn get_ymd_from_days<T: CalendarOps>(days: i64) -> (i64, u8, u8) {
+    let mut remaining_days = days;
+    let mut skipped_years = 0;
+
+    // Skip whole leap cycles, the remaining days are less than a cycle away from 1970
+    if let Some((cycle_years, cycle_days)) = T::LEAP_CYCLE {
+        let cycles = days / cycle_days;
+        remaining_days -= cycles * cycle_days;
+        skipped_years = cycles * cycle_years;
+    }
+    let mut current_year = constants::UNIX_DEFAULT_YEAR;
+
+    // Determine the direction (past or future)
+    let direction = if remaining_days >= 0 { 1 } else { -1 };
+
     loop {
-        if current_month + 1 == month {
+        let year_to_look_at = if current_year > constants::UNIX_DEFAULT_YEAR {
+            current_year
+        } else {
+            current_year - 1
+        };
+        let days_in_year: i64 = if T::is_leap(year_to_look_at) {
+            constants::DAYS_PER_YEAR_LEAP
+        } else {
+            constants::DAYS_PER_YEAR_NON_LEAP
+        };
+
+        let new_remaining = remaining_days - direction * days_in_year;
+
+        // After UNIX epoch we can stop
+        if direction == 1 && (new_remaining < 0) {
             break;
         }
-        if T::is_leap(year) {
-            timestamp += constants::DAYS_PER_MONTH_LEAP[(current_month) as usize] as i64
-                * constants::SECS_PER_DAY as i64;
-        } else {
-            timestamp += constants::DAYS_PER_MONTH[(current_month) as usize] as i64
-                * constants::SECS_PER_DAY as i64;
+        // Before UNIX epoch we substract one year if needed
+        // This ensure remaining_days is positive or equals 0
+        else if direction == -1 && (new_remaining >= 0) {
+            remaining_days = new_remaining;
+            current_year += direction;
+            break;
         }
-        current_month += 1;
+        remaining_days = new_remaining;
+        current_year += direction;
     }
 
-    // Calculate days
-    timestamp += (day as i64 - 1) * constants::SECS_PER_DAY as i64;
+    // Calculate months
+    // remaining_days is positive or equals 0
+    let days_per_month = if T::is_leap(current_year) {
+        constants::DAYS_PER_MONTH_LEAP
+    } else {
+        constants::DAYS_PER_MONTH
+    };
+    let mut month = 0;
+    while remaining_days >= days_per_month[month] as i64 {
+        remaining_days -= days_per_month[month] as i64;
+        month += 1;
+    }
+
+    (
+        current_year + skipped_years,
+        month as u8 + 1,
+        remaining_days as u8 + 1,
+    )
+}
+
+/// Leap rule of the calendars whose days are counted in closed form from a march epoch
+#[derive(Clone, Copy)]
+pub(crate) enum MarchEra {
+    /// 400 years of 146097 days
+    Gregorian,
+    /// 4 years of 1461 days
+    Julian,
+}
+
+impl MarchEra {
+    /// Returns the number of years and days of an era
+    fn cycle(self) -> (i128, i128) {
+        match self {
+            MarchEra::Gregorian => (400, 146_097),
+            MarchEra::Julian => (4, 1461),
+        }
+    }
+    /// Returns the number of days between 0000-03-01 and 1970-01-01
+    fn epoch_offset(self) -> i128 {
+        match self {
+            MarchEra::Gregorian => 719_468,
+            MarchEra::Julian => 719_483,
+        }
+    }
+    /// Returns the number of days between the start of the era and the start of the year of the
+    /// era, `year_of_era` being between 0 and the number of years of the era
+    fn days_before_year(self, year_of_era: i128) -> i128 {
+        match self {
+            MarchEra::Gregorian => year_of_era * 365 + year_of_era / 4 - year_of_era / 100,
+            MarchEra::Julian => year_of_era * 365,
+        }
+    }
+    /// Returns the year of the era of a day of the era
+    fn year_of_era(self, day_of_era: i128) -> i128 {
+        match self {
+            MarchEra::Gregorian => {
+                (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365
+            }
+            MarchEra::Julian => (day_of_era - day_of_era / 1460) / 365,
+        }
+    }
+}
+
+/// Calculates the number of days between 1970-01-01 and the given year, month and day of the
+/// gregorian or julian calendar in constant time.
+///
+/// The years start on the 1st of march so that the leap day is the last day of its year, and the
+/// eras of a whole leap cycle are counted from 0000-03-01, so the cost does not depend on the
+/// distance between the date and 1970. The month must be between 1 and 12.
+///
+/// # Errors
+///
+/// Returns an error if the number of days does not fit in an `i64`.
+pub(crate) fn get_days_from_ymd_in_era(
+    era: MarchEra,
+    year: i64,
+    month: u8,
+    day: u8,
+) -> crate::errors::Result<i64> {
+    let (cycle_years, cycle_days) = era.cycle();
+    let year_from_march = year as i128 - (month <= 2) as i128;
+    let eras = year_from_march.div_euclid(cycle_years);
+    let year_of_era = year_from_march - eras * cycle_years;
+    let month_from_march = (month as i128 + 9) % 12;
+    let day_of_year = (153 * month_from_march + 2) / 5 + day as i128 - 1;
+    let days =
+        eras * cycle_days + era.days_before_year(year_of_era) + day_of_year - era.epoch_offset();
+    i64::try_from(days).map_err(|_| {
+        crate::errors::Error::InvalidDate(format!(
+            "{year}-{month:02}-{day:02} is out of the range of the calendar"
+        ))
+    })
+}
+
+/// Converts a number of days since 1970-01-01 into the year, month and day of the gregorian or
+/// julian calendar in constant time, the inverse of [get_days_from_ymd_in_era].
+pub(crate) fn get_ymd_from_days_in_era(era: MarchEra, days: i64) -> (i64, u8, u8) {
+    let (cycle_years, cycle_days) = era.cycle();
+    let days_from_epoch = days as i128 + era.epoch_offset();
+    let eras = days_from_epoch.div_euclid(cycle_days);
+    let day_of_era = days_from_epoch - eras * cycle_days;
+    let year_of_era = era.year_of_era(day_of_era);
+    let day_of_year = day_of_era - era.days_before_year(year_of_era);
+    let month_from_march = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_from_march + 2) / 5 + 1;
+    let month = if month_from_march < 10 {
+        month_from_march + 3
+    } else {
+        month_from_march - 9
+    };
+    let year = eras * cycle_years + year_of_era + (month <= 2) as i128;
+    (year as i64, month as u8, day as u8)
+}
+
+/// Calculates the timestamp from the given year, month, and day.
+///
+/// # Arguments
+///
+/// * `year` - The year.
+/// * `month` - The month.
+/// * `day` - The day.
+///
+/// # Returns
+///
+/// The calculated timestamp.
+///
+/// # Errors
+///
+/// Returns an error if the month is not between 1 and 12 or if the timestamp does not fit in an `i64`.
+pub fn get_timestamp_from_ymd<T: CalendarOps>(
+    year: i64,
+    month: u8,
+    day: u8,
+) -> crate::errors::Result<i64> {
+    T::ymd_to_days(year, month, day)?
+        .checked_mul(constants::SECS_PER_DAY as i64)
+        .ok_or_else(|| {
+            crate::errors::Error::InvalidDate(format!(
+                "{year}-{month:02}-{day:02} is out of the range of the calendar"
+            ))
+        })
+}
 
-    Ok(timestamp)
+/// Calculates the timestamp and the nanoseconds from the given date and time.
+///
+/// # Errors
+///
+/// Returns an error if the time is not valid or if the timestamp does not fit in an `i64`.
+pub fn get_timestamp_from_ymd_hms<T: CalendarOps>(
+    year: i64,
+    month: u8,
+    day: u8,
+    hour: u8,
+    minute: u8,
+    second: f32,
+) -> crate::errors::Result<(i64, u32)> {
+    let (seconds, nanoseconds) = get_timestamp_from_hms(hour, minute, second)?;
+    // The first day of the range starts before the smallest timestamp
+    let days = T::ymd_to_days(year, month, day)?;
+    let timestamp = i64::try_from(days as i128 * constants::SECS_PER_DAY as i128 + seconds as i128)
+        .map_err(|_| {
+            crate::errors::Error::InvalidDate(format!(
+                "{year}-{month:02}-{day:02} {hour:02}:{minute:02}:{second:02} is out of the range of the calendar"
+            ))
+        })?;
+    Ok((timestamp, nanoseconds))
 }
 
 /// Converts a timestamp into hours, minutes, and seconds.
@@ -105,74 +324,23 @@ pub fn get_hms_from_timestamp(timestamp: i64) -> (u8, u8, u8) {
 ///
 /// # Generic Parameters
 ///
-/// * `T` - A type that implements the `IsLeap` trait, used to determine if a year is a leap year
+/// * `T` - A type that implements the `CalendarOps` trait, used to convert the days into a date
 ///
 /// # Returns
 ///
 /// A tuple containing the year, month, day, hour, minute, and second components of the timestamp.
-pub fn get_ymd_hms_from_timestamp<T: IsLeap>(timestamp: i64) -> (i64, u8, u8, u8, u8, u8) {
-    let mut remaining_timestamp = timestamp;
-    let mut current_year = constants::UNIX_DEFAULT_YEAR;
-
-    // Determine the direction (past or future)
-    let direction = if timestamp >= 0 { 1 } else { -1 };
-
-    loop {
-        let year_to_look_at = if current_year > constants::UNIX_DEFAULT_YEAR {
-            current_year
-        } else {
-            current_year - 1
-        };
-        let seconds_in_year: i64 = if T::is_leap(year_to_look_at) {
-            constants::SECONDS_PER_YEAR_LEAP
-        } else {
-            constants::SECONDS_PER_YEAR_NON_LEAP
-        };
-
-        let new_remaining = remaining_timestamp - direction * seconds_in_year;
-
-        // After UNIX epoch we can stop
-        if direction == 1 && (new_remaining < 0) {
-            break;
-        }
-        // Before UNIX epoch we substract one year if needed
-        // This ensure remaining_timestamp is positive or equals 0
-        else if direction == -1 && (new_remaining >= 0) {
-            remaining_timestamp = new_remaining;
-            current_year += direction;
-            break;
-        }
-        remaining_timestamp = new_remaining;
-        current_year += direction;
-    }
-
-    // Calculate months
-    // remaining_timestamp is positive or equals 0
-    let mut month: i64 = 0;
-    loop {
-        let days_in_month: i64 = if T::is_leap(current_year) {
-            constants::DAYS_PER_MONTH_LEAP[month as usize] as i64
-        } else {
-            constants::DAYS_PER_MONTH[month as usize] as i64
-        };
-        let seconds_in_month = days_in_month * constants::SECS_PER_DAY as i64;
-
-        if remaining_timestamp < seconds_in_month {
-            break;
-        }
-        remaining_timestamp -= seconds_in_month;
-        month += 1;
-    }
-
-    // Calculate days
-    let day = (remaining_timestamp / (constants::SECS_PER_DAY as i64)) as u8;
-
-    let (hour, min, sec) = get_hms_from_timestamp(remaining_timestamp);
-    (current_year, month as u8 + 1, day + 1, hour, min, sec)
+pub fn get_ymd_hms_from_timestamp<T: CalendarOps>(timestamp: i64) -> (i64, u8, u8, u8, u8, u8) {
+    let days = timestamp.div_euclid(constants::SECS_PER_DAY as i64);
+    let (year, month, day) = T::days_to_ymd(days);
+    let (hour, min, sec) = get_hms_from_timestamp(timestamp);
+    (year, month, day, hour, min, sec)
 }
 
 /// Determines if a given year is a leap year according to the Gregorian calendar.
 ///
+/// The years are astronomical, i.e. the year 0 is the year before the year 1 and is a leap year,
+/// see [to_astronomical_year].
+///
 /// # Arguments
 ///
 /// * `year` - The year to be checked.
@@ -181,14 +349,14 @@ pub fn get_ymd_hms_from_timestamp<T: IsLeap>(timestamp: i64) -> (i64, u8, u8, u8
 ///
 /// Returns `true` if the year is a leap year, `false` otherwise.
 pub fn is_leap_gregorian(year: i64) -> bool {
-    // Optimization : Adds 1 for negative years, 0 for non-negative years
-    // We extract the sign bit from the year i64 variable
-    let f_year = ((year >> 63) & 1) + year;
-    (f_year % 400 == 0) || ((f_year % 4 == 0) && (f_year % 100 != 0))
+    (year.rem_euclid(400) == 0) || ((year.rem_euclid(4) == 0) && (year.rem_euclid(100) != 0))
 }
 
 /// Determines if a given year is a leap year in the Julian calendar.
 ///
+/// The years are astronomical, i.e. the year 0 is the year before the year 1 and is a leap year,
+/// see [to_astronomical_year].
+///
 /// # Arguments
 ///
 /// * `year` - The year to check for leapness.
@@ -197,9 +365,46 @@ pub fn is_leap_gregorian(year: i64) -> bool {
 ///
 /// * `true` if the year is a leap year, `false` otherwise.
 pub fn is_leap_julian(year: i64) -> bool {
-    // Optimization : Adds 1 for negative years, 0 for non-negative years
-    // We extract the sign bit from the year i64 variable
-    (((year >> 63) & 1) + year) % 4 == 0
+    year.rem_euclid(4) == 0
+}
+
+/// Converts a year into the astronomical year used by the datetimes of the crate.
+///
+/// The datetimes count the years astronomically, i.e. with a year 0 before the year 1, which is
+/// `has_year_zero = true` in python-cftime. Without a year zero, the year -1 is the year before
+/// the year 1 and is the astronomical year 0.
+///
+/// # Returns
+///
+/// A Result containing the astronomical year or an error of type `crate::errors::Error::InvalidDate`
+/// if the year is 0 without a year zero
+///
+/// # Examples
+///
+/// ```
+/// # use cftime_rs::utils::to_astronomical_year;
+/// assert_eq!(to_astronomical_year(-1, false).unwrap(), 0);
+/// assert_eq!(to_astronomical_year(-1, true).unwrap(), -1);
+/// assert!(to_astronomical_year(0, false).is_err());
+/// ```
+pub fn to_astronomical_year(year: i64, has_year_zero: bool) -> crate::errors::Result<i64> {
+    match year {
+        0 if !has_year_zero => Err(crate::errors::Error::InvalidDate(
+            "Year 0 does not exist without a year zero".to_string(),
+        )),
+        year if year < 0 && !has_year_zero => Ok(year + 1),
+        year => Ok(year),
+    }
+}
+
+/// Converts an astronomical year of the crate into a year with or without a year zero, the
+/// inverse of [to_astronomical_year]
+pub fn from_astronomical_year(year: i64, has_year_zero: bool) -> i64 {
+    if year <= 0 && !has_year_zero {
+        year - 1
+    } else {
+        year
+    }
 }
 
 fn extract_seconds_and_nanoseconds(seconds: f32) -> (u64, u32) {
@@ -225,11 +430,7 @@ fn extract_seconds_and_nanoseconds(seconds: f32) -> (u64, u32) {
 /// # Errors
 ///
 /// Returns an error if any of the input values are out of bounds.
-pub fn get_timestamp_from_hms(
-    hour: u8,
-    min: u8,
-    sec: f32,
-) -> Result<(i64, u32), crate::errors::Error> {
+pub fn get_timestamp_from_hms(hour: u8, min: u8, sec: f32) -> crate::errors::Result<(i64, u32)> {
     if hour > 23 {
         return Err(crate::errors::Error::InvalidTime(
             format!("Hour {hour} is out of bounds").to_string(),
@@ -254,19 +455,115 @@ pub fn get_timestamp_from_hms(
     Ok((total_seconds as i64, nanoseconds))
 }
 
+/// Parses the units and returns the reference datetime, the unit and the multiplier of the unit.
+///
+/// # Arguments
+///
+/// * `units` - Valid CF units e.g. `3 hours since 2000-01-01`.
+/// * `calendar` - The calendar of the reference datetime.
+///
+/// # Returns
+///
+/// A tuple containing the reference datetime, the unit and the number of units in one increment.
 pub fn get_datetime_and_unit_from_units(
     units: &str,
     calendar: Calendar,
-) -> Result<(CFDatetime, Unit), crate::errors::Error> {
+) -> crate::errors::Result<(CFDatetime, Unit, i64)> {
     let parsed_cf_time = parse_cf_time(units)?;
+    let cf_datetime = get_reference_datetime(units, &parsed_cf_time, calendar)?;
+    let unit = parsed_cf_time.unit;
+    Ok((cf_datetime, unit, parsed_cf_time.multiplier))
+}
+
+/// Same as [get_datetime_and_unit_from_units] with the year of the reference date counted with or
+/// without a year zero, see [to_astronomical_year]
+pub fn get_datetime_and_unit_from_units_with_year_zero(
+    units: &str,
+    calendar: Calendar,
+    has_year_zero: bool,
+) -> crate::errors::Result<(CFDatetime, Unit, i64)> {
+    let mut parsed_cf_time = parse_cf_time(units)?;
+    parsed_cf_time.datetime.ymd.0 =
+        to_astronomical_year(parsed_cf_time.datetime.ymd.0, has_year_zero)?;
+    let cf_datetime = get_reference_datetime(units, &parsed_cf_time, calendar)?;
+    Ok((cf_datetime, parsed_cf_time.unit, parsed_cf_time.multiplier))
+}
+
+/// Returns the reference datetime of the parsed units, with the errors of an invalid date wrapped
+/// in `crate::errors::Error::InvalidReferenceDate` as they come from the units and not the data
+pub(crate) fn get_reference_datetime(
+    units: &str,
+    parsed_cf_time: &ParsedCFTime,
+    calendar: Calendar,
+) -> crate::errors::Result<CFDatetime> {
+    check_gregorian_gap(parsed_cf_time, calendar)
+        .and_then(|()| get_datetime_from_parsed_cf_time(parsed_cf_time, calendar))
+        .map_err(|error| {
+            crate::errors::Error::InvalidReferenceDate(units.to_string(), Box::new(error))
+        })
+}
+
+/// Checks that the reference date of the units is not one of the days from 1582-10-05 to
+/// 1582-10-14 skipped by the standard calendar.
+///
+/// Such an origin usually comes from gregorian dates written with the standard calendar. Without
+/// this check the error of the invalid date would not tell how to fix the units.
+fn check_gregorian_gap(
+    parsed_cf_time: &ParsedCFTime,
+    calendar: Calendar,
+) -> crate::errors::Result<()> {
+    let (year, month, day) = parsed_cf_time.datetime.ymd;
+    if calendar == Calendar::Standard && (year, month) == (1582, 10) && (5..=14).contains(&day) {
+        return Err(crate::errors::Error::InvalidDate(format!(
+            "{year}-{month:02}-{day:02} is one of the days from 1582-10-05 to 1582-10-14 that do \
+            not exist in the standard calendar, use the proleptic_gregorian calendar if the dates \
+            are gregorian"
+        )));
+    }
+    Ok(())
+}
+
+/// Returns the reference datetime of already parsed units, see [get_datetime_and_unit_from_units]
+pub fn get_datetime_from_parsed_cf_time(
+    parsed_cf_time: &ParsedCFTime,
+    calendar: Calendar,
+) -> crate::errors::Result<CFDatetime> {
     let (year, month, day) = parsed_cf_time.datetime.ymd;
     let (hour, minute, second) = match parsed_cf_time.datetime.hms {
         Some(hms) => (hms.0, hms.1, hms.2),
         None => (0, 0, 0.0),
     };
-    let cf_datetime = CFDatetime::from_ymd_hms(year, month, day, hour, minute, second, calendar)?;
-    let unit = parsed_cf_time.unit;
-    Ok((cf_datetime, unit))
+    let cf_datetime = match parsed_cf_time.datetime.nanosecond {
+        Some(nanosecond) => {
+            let datetime = CFDatetime::from_ymd_hms(
+                year,
+                month,
+                day,
+                hour,
+                minute,
+                float::trunc(second as f64) as f32,
+                calendar,
+            )?;
+            CFDatetime::from_timestamp(datetime.timestamp(), nanosecond as u32, calendar)?
+        }
+        None => CFDatetime::from_ymd_hms(year, month, day, hour, minute, second, calendar)?,
+    };
+    // The datetimes are in UTC, the reference date is moved back by the offset of its timezone
+    match parsed_cf_time.datetime.tz {
+        Some(tz) if tz.offset_minutes() != 0 => {
+            let timestamp = cf_datetime
+                .timestamp()
+                .checked_sub(tz.offset_minutes() as i64 * 60)
+                .ok_or_else(|| {
+                    crate::errors::Error::OutOfBoundsCalendar(
+                        calendar.to_string(),
+                        format!("{cf_datetime} {tz}"),
+                    )
+                })?;
+            CFDatetime::from_timestamp(timestamp, cf_datetime.nanoseconds(), calendar)
+        }
+        _ => Ok(cf_datetime),
+    }
 }
 /// Normalize the given number of nanoseconds into seconds and remaining nanoseconds.
 ///
@@ -296,18 +593,9 @@ pub fn get_datetime_and_unit_from_units(
 /// assert_eq!(remaining_nanoseconds, 500_000_000);
 /// ```
 pub fn normalize_nanoseconds(nanoseconds: i64) -> (i64, u32) {
-    // Calculate the number of remaining seconds
-    let mut remaining_seconds = nanoseconds / 1e9 as i64;
-
-    // Calculate the number of remaining nanoseconds
-    let remaining_nanoseconds: i64 = if remaining_seconds < 0 {
-        // If the remaining seconds is negative, subtract 1 and calculate the remaining nanoseconds accordingly
-        remaining_seconds -= 1;
-        (nanoseconds + (remaining_seconds.abs() * 1_000_000_000)) % 1_000_000_000
-    } else {
-        // If the remaining seconds is positive or zero, calculate the remaining nanoseconds directly
-        nanoseconds % 1e9 as i64
-    };
+    // Euclidean division keeps the remaining nanoseconds positive, i.e. -1 ns is -1 s + 999_999_999 ns
+    let remaining_seconds = nanoseconds.div_euclid(1_000_000_000);
+    let remaining_nanoseconds = nanoseconds.rem_euclid(1_000_000_000);
     (remaining_seconds, remaining_nanoseconds as u32)
 }
 
@@ -320,17 +608,359 @@ pub fn normalize_nanoseconds(nanoseconds: i64) -> (i64, u32) {
 ///
 /// # Returns
 ///
-/// The encoded value of the unit of time.
+/// The encoded value of the unit of time, see [encode_duration] for the rounding.
 pub fn unit_to_encode(unit: &Unit, duration: CFDuration) -> f64 {
+    encode_duration(unit, &duration, 1)
+}
+
+/// Returns the exact number of nanoseconds of one unit.
+/// Years and months follow the udunits definitions, see [Calendar::seconds_per_year]
+fn unit_nanoseconds(unit: &Unit, calendar: Calendar) -> i128 {
+    let ns_per_second = constants::MAX_NS as i128;
+    let ns_per_day = constants::SECS_PER_DAY as i128 * ns_per_second;
+    let ns_per_year = calendar.nanoseconds_per_year();
+    match unit {
+        Unit::Year => ns_per_year,
+        Unit::Month => ns_per_year / 12,
+        Unit::Week => 7 * ns_per_day,
+        Unit::Day => ns_per_day,
+        Unit::Hour => 3_600 * ns_per_second,
+        Unit::Minute => 60 * ns_per_second,
+        Unit::Second => ns_per_second,
+        Unit::Millisecond => 1_000_000,
+        Unit::Microsecond => 1_000,
+        Unit::Nanosecond => 1,
+    }
+}
+
+/// Converts a duration into a number of `multiplier` units.
+///
+/// The duration and the unit are converted to an exact number of nanoseconds and the quotient
+/// is rounded once to the nearest `f64`, ties to even. The computation uses integer arithmetic
+/// and a single rounding so the result is the same on every platform.
+///
+/// # Arguments
+///
+/// * `unit` - The unit of time to encode.
+/// * `duration` - The duration to encode.
+/// * `multiplier` - The number of units of one increment, e.g. 3 for `3 hours since ...`
+pub fn encode_duration(unit: &Unit, duration: &CFDuration, multiplier: i64) -> f64 {
+    let duration = duration.to_approximate_seconds();
+    let numerator =
+        duration.seconds as i128 * constants::MAX_NS as i128 + duration.nanoseconds as i128;
+    match unit_nanoseconds(unit, duration.calendar).checked_mul(multiplier as i128) {
+        Some(denominator) if denominator != 0 => div_round_half_even(numerator, denominator),
+        _ => unit_to_encode_approximate(unit, &duration) / multiplier as f64,
+    }
+}
+
+/// Converts a duration into a whole number of `multiplier` units.
+///
+/// The exact quotient is rounded toward negative infinity, so a duration of -1.5 days is -2 days.
+/// This keeps the decoded value at or before the encoded date whatever its sign, as the
+/// [CFDuration] whose seconds are floored.
+///
+/// # Arguments
+///
+/// * `unit` - The unit of time to encode.
+/// * `duration` - The duration to encode.
+/// * `multiplier` - The number of units of one increment, e.g. 3 for `3 hours since ...`
+pub fn encode_duration_floor(unit: &Unit, duration: &CFDuration, multiplier: i64) -> i128 {
+    let duration = duration.to_approximate_seconds();
+    let numerator =
+        duration.seconds as i128 * constants::MAX_NS as i128 + duration.nanoseconds as i128;
+    match unit_nanoseconds(unit, duration.calendar).checked_mul(multiplier as i128) {
+        Some(denominator) if denominator > 0 => numerator.div_euclid(denominator),
+        Some(denominator) if denominator < 0 => (-numerator).div_euclid(-denominator),
+        _ => float::floor(unit_to_encode_approximate(unit, &duration) / multiplier as f64) as i128,
+    }
+}
+
+/// Legacy float conversion used when the denominator overflows
+fn unit_to_encode_approximate(unit: &Unit, duration: &CFDuration) -> f64 {
     match unit {
-        Unit::Year => duration.num_years(),     // Convert to years
-        Unit::Month => duration.num_months(),   // Convert to months
-        Unit::Day => duration.num_days(),       // Convert to days
-        Unit::Hour => duration.num_hours(),     // Convert to hours
-        Unit::Minute => duration.num_minutes(), // Convert to minutes
-        Unit::Second => duration.num_seconds(), // Convert to seconds
-        Unit::Millisecond => duration.num_milliseconds(), // Convert to milliseconds
-        Unit::Microsecond => duration.num_microseconds(), // Convert to microseconds
-        Unit::Nanosecond => duration.num_nanoseconds(), // Convert to nanoseconds
+        Unit::Year => duration.num_years(),
+        Unit::Month => duration.num_months(),
+        Unit::Week => duration.num_weeks(),
+        Unit::Day => duration.num_days(),
+        Unit::Hour => duration.num_hours(),
+        Unit::Minute => duration.num_minutes(),
+        Unit::Second => duration.num_seconds(),
+        Unit::Millisecond => duration.num_milliseconds(),
+        Unit::Microsecond => duration.num_microseconds(),
+        Unit::Nanosecond => duration.num_nanoseconds(),
+    }
+}
+
+/// Divides two integers and rounds the exact quotient to the nearest `f64`, ties to even.
+fn div_round_half_even(numerator: i128, denominator: i128) -> f64 {
+    if numerator == 0 {
+        return 0.0;
+    }
+    let negative = (numerator < 0) != (denominator < 0);
+    let numerator = numerator.unsigned_abs();
+    let denominator = denominator.unsigned_abs();
+    let bits = |value: u128| 128 - value.leading_zeros() as i32;
+    // Scale the quotient by 2^shift to get 55 bits : 53 bits of mantissa, a rounding bit
+    // and a guard bit. The remainder acts as a sticky bit.
+    let mut shift = 55 - (bits(numerator) - bits(denominator));
+    let (quotient, sticky) = loop {
+        let (scaled_numerator, scaled_denominator) = if shift >= 0 {
+            if numerator.leading_zeros() < shift as u32 {
+                // Too large to be scaled, only happens for huge denominators
+                let value = numerator as f64 / denominator as f64;
+                return if negative { -value } else { value };
+            }
+            (numerator << shift, denominator)
+        } else {
+            if denominator.leading_zeros() < (-shift) as u32 {
+                let value = numerator as f64 / denominator as f64;
+                return if negative { -value } else { value };
+            }
+            (numerator, denominator << -shift)
+        };
+        let quotient = scaled_numerator / scaled_denominator;
+        if quotient >= 1 << 55 {
+            shift -= 1;
+        } else if quotient < 1 << 54 {
+            shift += 1;
+        } else {
+            break (quotient, scaled_numerator % scaled_denominator != 0);
+        }
+    };
+    let mut mantissa = quotient >> 2;
+    let rounding_bits = quotient & 3;
+    if rounding_bits == 3 || (rounding_bits == 2 && (sticky || mantissa & 1 == 1)) {
+        mantissa += 1;
+    }
+    // mantissa * 2^(2 - shift), the multiplication by a power of two is exact
+    let power_of_two = f64::from_bits(((2 - shift + 1023) as u64) << 52);
+    let value = mantissa as f64 * power_of_two;
+    if negative {
+        -value
+    } else {
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_div_round_half_even() {
+        assert_eq!(div_round_half_even(1, 3), 1.0 / 3.0);
+        assert_eq!(div_round_half_even(-1, 3), -1.0 / 3.0);
+        assert_eq!(div_round_half_even(7, -2), -3.5);
+        assert_eq!(div_round_half_even(0, 5), 0.0);
+        assert_eq!(div_round_half_even(17_532_001, 24), 17_532_001.0 / 24.0);
+        // 2^53 + 1 is a tie between 2^53 and 2^53 + 2, rounded to the even mantissa
+        assert_eq!(div_round_half_even((1 << 53) + 1, 1), 9007199254740992.0);
+        assert_eq!(div_round_half_even((1 << 53) + 3, 1), 9007199254740996.0);
+        // Slightly above the tie, the remainder breaks the tie upward
+        assert_eq!(
+            div_round_half_even(((1 << 53) + 1) * 3 + 1, 3),
+            9007199254740994.0
+        );
+        assert_eq!(div_round_half_even(i128::MAX, 1), i128::MAX as f64);
+    }
+
+    #[test]
+    fn test_encode_duration() {
+        let calendar = Calendar::Standard;
+        let duration = CFDuration::from_hours(1, calendar);
+        assert_eq!(encode_duration(&Unit::Day, &duration, 1), 1.0 / 24.0);
+        assert_eq!(encode_duration(&Unit::Hour, &duration, 3), 1.0 / 3.0);
+        let duration = CFDuration::from_seconds(31_556_925, calendar);
+        assert!((encode_duration(&Unit::Year, &duration, 1) - 1.0).abs() < 1e-7);
+        let duration = CFDuration::from_days(360, Calendar::Day360);
+        assert_eq!(encode_duration(&Unit::Month, &duration, 1), 12.0);
+    }
+
+    #[test]
+    fn test_years_and_months_round_trip() {
+        use crate::converter::CFConverter;
+        let values: Vec<i64> = vec![-25, -1, 0, 1, 7, 1000];
+        for calendar in [
+            Calendar::Standard,
+            Calendar::Julian,
+            Calendar::NoLeap,
+            Calendar::Day360,
+        ] {
+            for units in [
+                "common_years since 2000-01-01",
+                "months since 2000-01-01",
+                "3 common_years since 2000-01-01",
+            ] {
+                let converter = CFConverter::new(units, calendar).unwrap();
+                let datetimes = converter.decode(&values).unwrap();
+                assert_eq!(
+                    converter.encode::<i64, _>(&datetimes).unwrap(),
+                    values,
+                    "{units} {calendar}"
+                );
+                let encoded = converter.encode::<f64, _>(&datetimes).unwrap();
+                let expected: Vec<f64> = values.iter().map(|&value| value as f64).collect();
+                assert_eq!(encoded, expected, "{units} {calendar}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_leap_years_are_astronomical() {
+        for (year, gregorian, julian) in [
+            (0, true, true),
+            (-1, false, false),
+            (-4, true, true),
+            (-100, false, true),
+            (-400, true, true),
+            (1900, false, true),
+            (2000, true, true),
+        ] {
+            assert_eq!(is_leap_gregorian(year), gregorian, "{year}");
+            assert_eq!(is_leap_julian(year), julian, "{year}");
+        }
+    }
+
+    #[test]
+    fn test_astronomical_year() {
+        for (year, astronomical) in [(1, 1), (-1, 0), (-2, -1), (-45, -44)] {
+            assert_eq!(to_astronomical_year(year, false).unwrap(), astronomical);
+            assert_eq!(from_astronomical_year(astronomical, false), year);
+            assert_eq!(to_astronomical_year(year, true).unwrap(), year);
+            assert_eq!(from_astronomical_year(year, true), year);
+        }
+        assert!(to_astronomical_year(0, false).is_err_and(|e| e.is_invalid_datetime()));
+        assert_eq!(to_astronomical_year(0, true).unwrap(), 0);
+        let (origin, _, _) = get_datetime_and_unit_from_units_with_year_zero(
+            "days since -1-06-01",
+            Calendar::Julian,
+            false,
+        )
+        .unwrap();
+        assert_eq!(origin.ymd().unwrap(), (0, 6, 1));
+        assert!(get_datetime_and_unit_from_units_with_year_zero(
+            "days since 0000-06-01",
+            Calendar::Julian,
+            false
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_reference_date_in_gregorian_gap() {
+        use crate::decoder::VecCFDecoder;
+        let units = "days since 1582-10-10";
+        let error = vec![0_i64, 1]
+            .decode_cf(units, Calendar::Standard)
+            .err()
+            .unwrap();
+        assert!(error.is_invalid_datetime());
+        let message = error.to_string();
+        assert!(message.contains(units), "{message}");
+        assert!(message.contains("proleptic_gregorian"), "{message}");
+        let error =
+            get_datetime_and_unit_from_units_with_year_zero(units, Calendar::Standard, false)
+                .err()
+                .unwrap();
+        assert!(error.to_string().contains(units));
+        // The days around the gap and the other calendars are fine
+        for (units, calendar) in [
+            ("days since 1582-10-04", Calendar::Standard),
+            ("days since 1582-10-15 00:00:00", Calendar::Standard),
+            (units, Calendar::ProlepticGregorian),
+            (units, Calendar::Julian),
+        ] {
+            assert!(vec![0_i64, 1].decode_cf(units, calendar).is_ok(), "{units}");
+        }
+    }
+
+    #[test]
+    fn test_reference_date_timezone() {
+        use crate::decoder::VecCFDecoder;
+        for (units, expected) in [
+            (
+                "hours since 2000-01-01 00:00:00 -06:00",
+                (2000, 1, 1, 6, 0, 0),
+            ),
+            (
+                "hours since 2000-01-01 00:00:00 +05:30",
+                (1999, 12, 31, 18, 30, 0),
+            ),
+            ("hours since 2000-01-01 00:00:00 Z", (2000, 1, 1, 0, 0, 0)),
+        ] {
+            let datetimes = vec![0_i64].decode_cf(units, Calendar::NoLeap).unwrap();
+            assert_eq!(datetimes[0].ymd_hms().unwrap(), expected, "{units}");
+        }
+        // From CF conventions
+        let (origin, _, _) = get_datetime_and_unit_from_units(
+            "seconds since 1992-10-8 15:15:42.5 -6:00",
+            Calendar::Standard,
+        )
+        .unwrap();
+        assert_eq!(origin.ymd_hms().unwrap(), (1992, 10, 8, 21, 15, 42));
+        assert_eq!(origin.nanoseconds(), 500_000_000);
+        let (origin, _, _) = get_datetime_and_unit_from_units(
+            "seconds since 2000-03-01 00:30:00 +01:00",
+            Calendar::ProlepticGregorian,
+        )
+        .unwrap();
+        assert_eq!(origin.ymd_hms().unwrap(), (2000, 2, 29, 23, 30, 0));
+    }
+
+    #[test]
+    fn test_march_era_matches_year_scan() {
+        use crate::datetimes::{
+            julian::JulianDatetime, proleptic_gregorian::ProlepticGregorianDatetime,
+        };
+        // Around the year 0, 1970 and far dates, with every day of a few leap cycles
+        let days = (-1_000_000..1_000_000)
+            .step_by(13)
+            .chain(-719_600..-719_300)
+            .chain(-150_000..150_000)
+            .chain([i64::MAX / 86_400, i64::MIN / 86_400, 3_000_000_000_000]);
+        for days in days {
+            let date = get_ymd_from_days::<ProlepticGregorianDatetime>(days);
+            assert_eq!(get_ymd_from_days_in_era(MarchEra::Gregorian, days), date);
+            let (year, month, day) = date;
+            assert_eq!(
+                get_days_from_ymd_in_era(MarchEra::Gregorian, year, month, day).unwrap(),
+                days
+            );
+            let date = get_ymd_from_days::<JulianDatetime>(days);
+            assert_eq!(get_ymd_from_days_in_era(MarchEra::Julian, days), date);
+            let (year, month, day) = date;
+            assert_eq!(
+                get_days_from_ymd_in_era(MarchEra::Julian, year, month, day).unwrap(),
+                days
+            );
+        }
+        // The day is not checked against the length of the month
+        for (year, month, day) in [(2001, 2, 29), (2000, 1, 0), (-5, 12, 40), (1900, 2, 30)] {
+            assert_eq!(
+                get_days_from_ymd_in_era(MarchEra::Gregorian, year, month, day).unwrap(),
+                get_days_from_ymd::<ProlepticGregorianDatetime>(year, month, day).unwrap()
+            );
+            assert_eq!(
+                get_days_from_ymd_in_era(MarchEra::Julian, year, month, day).unwrap(),
+                get_days_from_ymd::<JulianDatetime>(year, month, day).unwrap()
+            );
+        }
+        assert!(
+            get_days_from_ymd_in_era(MarchEra::Gregorian, i64::MAX, 1, 1)
+                .is_err_and(|e| e.is_invalid_datetime())
+        );
+    }
+
+    #[test]
+    fn test_normalize_nanoseconds() {
+        assert_eq!(normalize_nanoseconds(-1), (-1, 999_999_999));
+        assert_eq!(normalize_nanoseconds(-1_000_000_000), (-1, 0));
+        assert_eq!(normalize_nanoseconds(-2_500_000_000), (-3, 500_000_000));
+        assert_eq!(normalize_nanoseconds(1_500_000_000), (1, 500_000_000));
+        assert_eq!(
+            normalize_nanoseconds(i64::MIN),
+            (-9_223_372_037, 145_224_192)
+        );
     }
 }
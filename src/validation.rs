@@ -0,0 +1,348 @@
+//! Module that checks the time coordinate metadata against the CF conventions
+//!
+//! [validate_time_coordinate] takes the units, the calendar and the values of a time coordinate and
+//! returns a [ValidationReport] listing the [Issue] found, similar to what a CF checker would report
+//! for the time coordinate.
+
+use crate::calendars::Calendar;
+use crate::datetime::CFDatetime;
+use crate::decoder::ScaleDuration;
+use crate::parser::{parse_cf_time_with_mode, ParseMode, Unit};
+use crate::utils::get_datetime_from_parsed_cf_time;
+use alloc::collections::BTreeSet;
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// Kind of [Issue] found by [validate_time_coordinate]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum IssueKind {
+    /// The units can not be parsed even in [ParseMode::Lenient]
+    InvalidUnits,
+    /// The units are parsed but are not written as the CF conventions recommend,
+    /// e.g. `secs` instead of `seconds` or `months` whose length is not a calendar month
+    NonstandardUnits,
+    /// A value is not finite or can not be decoded in the calendar
+    InvalidValue,
+    /// The values are not monotonic
+    NonMonotonic,
+    /// Several values decode to the same datetime
+    DuplicateTime,
+    /// A datetime is before the Gregorian reform of 1582-10-15 in the mixed Julian/Gregorian
+    /// `standard` calendar
+    BeforeCalendarRange,
+}
+
+impl core::fmt::Display for IssueKind {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let name = match self {
+            IssueKind::InvalidUnits => "invalid units",
+            IssueKind::NonstandardUnits => "nonstandard units",
+            IssueKind::InvalidValue => "invalid value",
+            IssueKind::NonMonotonic => "non monotonic",
+            IssueKind::DuplicateTime => "duplicate time",
+            IssueKind::BeforeCalendarRange => "before calendar range",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// An issue of the time coordinate
+#[derive(Debug, PartialEq, Clone)]
+pub struct Issue {
+    pub kind: IssueKind,
+    /// Index of the value in error, `None` if the issue is about the units
+    pub index: Option<usize>,
+    pub message: String,
+}
+
+impl core::fmt::Display for Issue {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self.index {
+            Some(index) => write!(f, "{} at index {}: {}", self.kind, index, self.message),
+            None => write!(f, "{}: {}", self.kind, self.message),
+        }
+    }
+}
+
+/// Result of [validate_time_coordinate]
+#[derive(Debug, Default, PartialEq, Clone)]
+pub struct ValidationReport {
+    pub issues: Vec<Issue>,
+}
+
+impl ValidationReport {
+    /// Returns `true` if no issue was found
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+    /// Returns the issues of the given kind
+    pub fn issues_of_kind(&self, kind: IssueKind) -> impl Iterator<Item = &Issue> {
+        self.issues.iter().filter(move |issue| issue.kind == kind)
+    }
+    fn push(&mut self, kind: IssueKind, index: Option<usize>, message: String) {
+        self.issues.push(Issue {
+            kind,
+            index,
+            message,
+        });
+    }
+}
+
+/// Checks the units spelling of the unit token against the CF name of the unit
+fn check_unit_spelling(units: &str, unit: &Unit, report: &mut ValidationReport) {
+    // The unit may be glued to the udunits @ separator e.g. seconds@2000-01-01
+    let mut tokens = units
+        .split_whitespace()
+        .flat_map(|token| token.split('@'))
+        .filter(|token| !token.is_empty());
+    let mut token = tokens.next();
+    if token.is_some_and(|token| token.parse::<i64>().is_ok()) {
+        token = tokens.next();
+    }
+    let cf_name = unit.cf_name();
+    if let Some(token) = token {
+        if token != cf_name && token != cf_name.trim_end_matches('s') {
+            report.push(
+                IssueKind::NonstandardUnits,
+                None,
+                format!("`{token}` is not a CF spelling, use `{cf_name}`"),
+            );
+        }
+    }
+    if matches!(unit, Unit::Year | Unit::Month) {
+        report.push(
+            IssueKind::NonstandardUnits,
+            None,
+            format!(
+                "`{cf_name}` has a fixed length that is not a calendar {}, \
+                the CF conventions recommend not to use it",
+                if *unit == Unit::Year { "year" } else { "month" }
+            ),
+        );
+    }
+}
+
+/// Validates a time coordinate given its units, calendar and values.
+///
+/// The following checks are done :
+/// - The units are parsed in [ParseMode::Lenient], the repairs and the spelling of the unit are reported
+/// - Every value is decoded, values that are not finite or out of the calendar are reported
+/// - The values have to be strictly monotonic, either increasing or decreasing
+/// - The same datetime must not appear several times
+/// - In the `standard` calendar, the reference date and the values should not be before 1582-10-15
+///
+/// # Arguments
+///
+/// * `units` - The units of the time coordinate e.g. `days since 2000-01-01`.
+/// * `calendar` - The calendar of the time coordinate.
+/// * `values` - The values of the time coordinate.
+///
+/// # Returns
+///
+/// A [ValidationReport] containing the issues in the order of the checks. If the units can not be
+/// parsed, the values are not checked.
+pub fn validate_time_coordinate<T>(
+    units: &str,
+    calendar: Calendar,
+    values: &[T],
+) -> ValidationReport
+where
+    T: ScaleDuration,
+{
+    let mut report = ValidationReport::default();
+    let parsed = match parse_cf_time_with_mode(units, ParseMode::Lenient) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            report.push(IssueKind::InvalidUnits, None, e.to_string());
+            return report;
+        }
+    };
+    for diagnostic in parsed.diagnostics {
+        report.push(IssueKind::NonstandardUnits, None, diagnostic.to_string());
+    }
+    check_unit_spelling(units, &parsed.parsed.unit, &mut report);
+    let reference = match get_datetime_from_parsed_cf_time(&parsed.parsed, calendar) {
+        Ok(reference) => reference,
+        Err(e) => {
+            report.push(IssueKind::InvalidUnits, None, e.to_string());
+            return report;
+        }
+    };
+    let gregorian_reform = match calendar {
+        Calendar::Standard => CFDatetime::from_ymd(1582, 10, 15, calendar)
+            .ok()
+            .map(|datetime| datetime.timestamp_nanos()),
+        _ => None,
+    };
+    if let Some(reform) = gregorian_reform {
+        if reference.timestamp_nanos() < reform {
+            report.push(
+                IssueKind::BeforeCalendarRange,
+                None,
+                format!(
+                    "The reference date {reference} is before 1582-10-15 in the standard calendar"
+                ),
+            );
+        }
+    }
+
+    let duration = match parsed
+        .parsed
+        .unit
+        .to_duration_with_multiplier(calendar, parsed.parsed.multiplier)
+    {
+        Ok(duration) => duration,
+        Err(e) => {
+            report.push(IssueKind::InvalidUnits, None, e.to_string());
+            return report;
+        }
+    };
+    let mut seen = BTreeSet::new();
+    // Previous decoded value and its index
+    let mut previous: Option<(usize, i128)> = None;
+    // Sign of the first change between two values
+    let mut direction: Option<bool> = None;
+    let mut before_reform: Vec<usize> = Vec::new();
+    for (index, value) in values.iter().enumerate() {
+        let timestamp = match value
+            .scale(&duration)
+            .and_then(|duration| &reference + duration)
+        {
+            Ok(datetime) => datetime.timestamp_nanos(),
+            Err(e) => {
+                report.push(
+                    IssueKind::InvalidValue,
+                    Some(index),
+                    format!("Could not decode {value}: {e}"),
+                );
+                continue;
+            }
+        };
+        if !seen.insert(timestamp) {
+            report.push(
+                IssueKind::DuplicateTime,
+                Some(index),
+                format!("{value} decodes to a datetime already present"),
+            );
+        }
+        if let Some((previous_index, previous_timestamp)) = previous {
+            if timestamp != previous_timestamp {
+                let increasing = timestamp > previous_timestamp;
+                match direction {
+                    None => direction = Some(increasing),
+                    Some(direction) if direction != increasing => report.push(
+                        IssueKind::NonMonotonic,
+                        Some(index),
+                        format!(
+                            "{value} is {} than the value at index {previous_index}",
+                            if increasing { "greater" } else { "lower" }
+                        ),
+                    ),
+                    _ => {}
+                }
+            }
+        }
+        if gregorian_reform.is_some_and(|reform| timestamp < reform) {
+            before_reform.push(index);
+        }
+        previous = Some((index, timestamp));
+    }
+    if let Some(first) = before_reform.first() {
+        report.push(
+            IssueKind::BeforeCalendarRange,
+            Some(*first),
+            format!(
+                "{} value(s) are before 1582-10-15 in the standard calendar, \
+                consider the proleptic_gregorian or julian calendar",
+                before_reform.len()
+            ),
+        );
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_time_coordinate() {
+        let report =
+            validate_time_coordinate("days since 2000-01-01", Calendar::Standard, &[0, 1, 2, 3]);
+        assert!(report.is_valid(), "{:?}", report);
+        let report =
+            validate_time_coordinate("hours since 2000-01-01", Calendar::NoLeap, &[3.0, 2.0, 1.5]);
+        assert!(report.is_valid(), "{:?}", report);
+    }
+
+    #[test]
+    fn test_units_issues() {
+        let report = validate_time_coordinate("foo since 2000-01-01", Calendar::Standard, &[0]);
+        assert_eq!(report.issues.len(), 1);
+        assert_eq!(report.issues[0].kind, IssueKind::InvalidUnits);
+        let units = "9223372036854775807 days since 2000-01-01";
+        let report = validate_time_coordinate(units, Calendar::Standard, &[0]);
+        assert_eq!(report.issues[0].kind, IssueKind::InvalidUnits);
+
+        let report = validate_time_coordinate("secs  since 2000-01-01", Calendar::Standard, &[0]);
+        let issues: Vec<_> = report.issues_of_kind(IssueKind::NonstandardUnits).collect();
+        assert_eq!(issues.len(), 2, "{:?}", report);
+
+        let report = validate_time_coordinate("months since 2000-01-01", Calendar::Day360, &[0]);
+        assert_eq!(
+            report.issues_of_kind(IssueKind::NonstandardUnits).count(),
+            1
+        );
+        let report = validate_time_coordinate("day since 2000-01-01", Calendar::Day360, &[0]);
+        assert!(report.is_valid(), "{:?}", report);
+        // The udunits separator is repaired but reported
+        for units in ["days @ 2000-01-01", "days@2000-01-01"] {
+            let report = validate_time_coordinate(units, Calendar::Day360, &[0, 1]);
+            let issues: Vec<_> = report.issues_of_kind(IssueKind::NonstandardUnits).collect();
+            assert!(!issues.is_empty(), "{units}");
+            assert!(issues
+                .iter()
+                .all(|issue| !issue.message.contains("CF spelling")));
+            assert_eq!(report.issues.len(), issues.len(), "{:?}", report);
+        }
+    }
+
+    #[test]
+    fn test_values_issues() {
+        let values = [0.0, 1.0, 1.0, 0.5, f64::NAN, 3.0];
+        let report = validate_time_coordinate("days since 2000-01-01", Calendar::Standard, &values);
+        let kinds: Vec<_> = report
+            .issues
+            .iter()
+            .map(|issue| (issue.kind, issue.index))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (IssueKind::DuplicateTime, Some(2)),
+                (IssueKind::NonMonotonic, Some(3)),
+                (IssueKind::InvalidValue, Some(4)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_before_calendar_range() {
+        let report =
+            validate_time_coordinate("days since 1582-10-01", Calendar::Standard, &[0, 10, 20]);
+        let issues: Vec<_> = report
+            .issues_of_kind(IssueKind::BeforeCalendarRange)
+            .map(|issue| issue.index)
+            .collect();
+        assert_eq!(issues, vec![None, Some(0)]);
+        assert!(report.issues[1].message.starts_with("1 value(s)"));
+        let report = validate_time_coordinate(
+            "days since 1582-10-01",
+            Calendar::ProlepticGregorian,
+            &[0, 10, 20],
+        );
+        assert!(report.is_valid(), "{:?}", report);
+    }
+}
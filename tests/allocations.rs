@@ -0,0 +1,45 @@
+//! Counts the allocations of the decoding with a global allocator, which is why the test has its
+//! own binary instead of being a unit test of the crate.
+
+use cftime_rs::calendars::Calendar;
+use cftime_rs::decoder::VecCFDecoder;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+/// System allocator counting the allocations of the current thread, the tests running in parallel
+/// threads
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+#[test]
+fn test_decode_allocations() {
+    let values: Vec<i64> = (0..10_000).collect();
+    let before = ALLOCATIONS.with(Cell::get);
+    let datetimes = values
+        .decode_cf("hours since 2000-01-01", Calendar::NoLeap)
+        .unwrap();
+    let allocations = ALLOCATIONS.with(Cell::get) - before;
+    assert_eq!(datetimes.len(), values.len());
+    if cfg!(feature = "inline-datetimes") {
+        // Only the parsing of the units and the growth of the vectors allocate
+        assert!(allocations < 100, "{allocations} allocations");
+    } else {
+        assert!(allocations >= values.len(), "{allocations} allocations");
+    }
+}